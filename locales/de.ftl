@@ -0,0 +1,276 @@
+# German texts of recall
+
+## Legend
+legend-previous-page = Vorherige Seite
+legend-next-page = Nächste Seite
+legend-close = Schließen
+legend-open-page = Seite öffnen
+legend-back = Zurück
+legend-change = Ändern
+legend-save = Speichern
+legend-cancel = Abbrechen
+legend-next-field = Nächstes Feld
+legend-copy = Kopieren
+legend-switch-pane = Bereich wechseln
+legend-switch = Wechseln
+legend-help = Hilfe
+legend-retry = Erneut versuchen
+page-counter = Seite { $current } von { $total }
+showing-hidden = Versteckte sichtbar
+wrapping-descriptions = Umbruch
+sorted-by-name = Nach Name sortiert
+sorted-by-shortcut = Nach Tastenkürzel sortiert
+updated-ago = Vor { $age } aktualisiert
+filtered-by = Gefiltert nach { $filter }
+
+## Pages
+recent-page = Zuletzt verwendet
+favorites-page = Favoriten
+search-page = Suche
+search-description = Treffer für { $query }, Enter öffnet die Seite eines Treffers
+all-pages-title = Alle Seiten
+keyboard-title = Tastatur
+image-fallback = Bild { $path }
+details-no-note = Keine Notiz, sie wird als Kommentar über dem Eintrag in der Konfiguration geschrieben
+details-section = Abschnitt: { $section }
+details-goto = Öffnet die Seite { $page }
+details-exec = Führt { $command } aus
+details-url = Webseite: { $url }
+details-image = Bild: { $path }
+details-hidden = Versteckt, bis versteckte Einträge gezeigt werden
+details-secret = Geheim, verdeckt bis zum Aufdecken
+details-encrypted = Mit age verschlüsselt in der Konfiguration gespeichert
+details-favorite = Als Favorit markiert
+page-index-title = Seiten
+page-index-entries = { $entries ->
+        [one] 1 Eintrag
+       *[other] { $entries } Einträge
+    }
+page-index-pages = { $pages ->
+        [one] 1 Seite
+       *[other] { $pages } Seiten
+    }
+help-title = Tasten
+help-previous-page = Vorherige Seite zeigen
+help-next-page = Nächste Seite zeigen
+help-first-page = Erste Seite zeigen
+help-last-page = Letzte Seite zeigen
+help-previous-entry = Vorherige Zeile auswählen
+help-next-entry = Nächste Zeile auswählen
+help-activate = Ausgewählte Zeile öffnen
+help-leave-subpage = Unterseite verlassen
+help-move-entry-up = Eintrag nach oben verschieben
+help-move-entry-down = Eintrag nach unten verschieben
+help-delete-entry = Eintrag löschen
+help-add-entry = Eintrag hinzufügen
+help-edit-entry = Eintrag ändern
+help-undo = Letzte Änderung rückgängig machen
+help-redo = Rückgängig gemachte Änderung wiederholen
+help-new-page = Seite anlegen
+help-move-entry = Eintrag auf eine andere Seite verschieben
+help-toggle-favorite = Eintrag als Favorit markieren oder nicht
+help-toggle-section = Abschnitt ein- oder ausklappen
+help-cycle-sort = Sortierung ändern
+help-toggle-hidden = Versteckte Einträge zeigen oder verbergen
+help-reveal-secret = Geheimen Eintrag anzeigen
+help-toggle-wrap = Beschreibungen umbrechen oder kürzen
+help-toggle-keyboard = Tastatur zeigen oder verbergen
+help-all-pages = Alle Seiten auf einmal zeigen
+help-page-index = Alle Seiten auflisten
+help-details = Details des Eintrags zeigen
+help-copy = Eintrag kopieren
+help-copy-description = Beschreibung des Eintrags kopieren
+help-open-url = Webseite des Eintrags öffnen
+help-stats = Am häufigsten nachgeschlagene Einträge zeigen
+help-settings = Einstellungen öffnen
+help-toggle-split = Ansicht teilen
+help-switch-pane = Bereich wechseln
+help-workspaces = Arbeitsbereich wechseln
+help-filter = Seite filtern
+help-search = Alle Seiten durchsuchen
+help-page-switcher = Zu einer Seite springen
+help-command-line = Zu einer Seite nach Namen springen
+help-clear = Filter oder Suche aufheben
+help-reload = Konfiguration erneut lesen
+help-help = Alle Tasten auflisten
+help-quit = Recall schließen
+stats-title = Am häufigsten nachgeschlagen
+stats-empty = Noch nichts nachgeschlagen
+workspaces-title = Arbeitsbereiche
+page-switcher-title = Gehe zu Seite
+page-switcher-empty = Keine Seite passt
+workspace-current = aktuell
+stats-lookups = { $views ->
+        [one] 1 Aufruf
+       *[other] { $views } Aufrufe
+    }, { $copies ->
+        [one] 1 Kopie
+       *[other] { $copies } Kopien
+    }
+subpage-summary = { $entries ->
+        [one] 1 Eintrag
+       *[other] { $entries } Einträge
+    }, { $subpages ->
+        [one] 1 Unterseite
+       *[other] { $subpages } Unterseiten
+    }
+section-collapsed = { $entries ->
+        [one] 1 Eintrag
+       *[other] { $entries } Einträge
+    }
+
+## Config conflict popup
+conflict-title = Konfiguration geändert
+conflict-message = Ein anderes Programm hat die Konfiguration geändert, seit recall sie zuletzt gespeichert hat. Was soll mit den Änderungen hier geschehen?
+conflict-reload = Neu laden: Änderungen verwerfen und die Konfiguration zeigen, wie sie jetzt ist
+conflict-merge = Zusammenführen: hier bearbeitete Seiten behalten, alle anderen aus der Konfiguration übernehmen
+conflict-overwrite = Überschreiben: die Konfiguration durch die Seiten ersetzen, wie sie hier sind
+config-error-title = Konfiguration nicht gelesen
+config-error-message = Die Konfiguration kann nicht gelesen werden, Seiten und Einstellungen bleiben wie sie waren:
+
+## New page prompt
+new-page-prompt = Neue Seite:
+page-name-empty = Name ist leer
+page-name-reserved = Name ist für Einstellungen reserviert
+page-name-taken = Name ist bereits vergeben
+
+## Filter prompt
+filter-prompt = Filter:
+filter-matches = { $entries ->
+        [one] 1 Eintrag
+       *[other] { $entries } Einträge
+    }
+
+## Search prompt
+search-prompt = Alle Seiten durchsuchen:
+search-results = { $results ->
+        [one] 1 Treffer
+       *[other] { $results } Treffer
+    }
+
+## Move prompt
+move-prompt = Verschieben auf Seite:
+move-target-empty = keine Seite angegeben
+move-target-unknown = keine eindeutig passende Seite
+move-target-same = Eintrag ist bereits auf dieser Seite
+move-target-generated = Einträge dieser Seite werden erzeugt
+move-target-taken = Seite hat bereits einen Eintrag mit diesem Namen
+command-line-hint = Namen einer Seite eingeben, Tab vervollständigt ihn
+command-line-unknown = keine einzelne Seite passt
+
+## Entry form
+entry-form-add-title = Eintrag hinzufügen
+entry-form-edit-title = Eintrag ändern
+entry-form-name = Name
+entry-form-keys = Tasten
+entry-form-description = Beschreibung
+entry-name-empty = Name ist leer
+entry-name-taken = Seite hat bereits einen Eintrag mit diesem Namen
+entry-keys-empty = keine Tasten oder Befehle angegeben, durch Kommas trennen
+
+## Placeholder form
+placeholder-title = Platzhalter ausfüllen
+notice-copied = In die Zwischenablage kopiert
+notice-moved = Auf eine andere Seite verschoben
+notice-reloaded = Geänderte Konfiguration neu geladen
+notice-merged = Änderungen in die geänderte Konfiguration übernommen
+notice-config-unreadable = Die geänderte Konfiguration ist nicht lesbar, korrigieren oder überschreiben
+notice-opened = Die Webseite wurde im Browser geöffnet
+notice-open-failed = Die Webseite kann nicht geöffnet werden
+notice-exec-failed = Der Befehl des Eintrags ist fehlgeschlagen
+notice-reload-failed = Die geänderte Konfiguration ist nicht lesbar, die Seiten bleiben wie sie waren
+
+## Settings popup
+settings-title = Einstellungen
+settings-theme = Farbschema
+settings-primary-color = Primärfarbe
+settings-highlight-color = Hervorhebung
+settings-no-theme = keines
+settings-default-color = Standard
+
+## Quit reasons
+quit-sigint = 'SIGINT'-Signal empfangen
+quit-close-key = 'Schließen'-Taste wurde gedrückt
+quit-init-completed = 'Init'-Befehl wurde abgeschlossen
+quit-check-completed = 'Check'-Befehl hat keine Probleme gefunden
+quit-edit-completed = 'Edit'-Befehl wurde abgeschlossen
+quit-idle-timeout = Vor Ablauf der Leerlaufzeit wurde keine Taste gedrückt
+quit-export-completed = 'Export'-Befehl wurde abgeschlossen
+quit-import-completed = 'Import'-Befehl wurde abgeschlossen
+quit-scrape-completed = 'Scrape'-Befehl wurde abgeschlossen
+quit-suggest-completed = 'Suggest'-Befehl wurde abgeschlossen
+quit-copy-completed = 'Copy'-Befehl wurde abgeschlossen
+quit-exec-completed = 'Exec'-Befehl wurde abgeschlossen
+quit-show-completed = 'Show'-Befehl wurde abgeschlossen
+quit-list-completed = 'List'-Befehl wurde abgeschlossen
+quit-stats-completed = 'Stats'-Befehl wurde abgeschlossen
+quit-add-completed = 'Add'-Befehl wurde abgeschlossen
+quit-remove-completed = 'Rm'-Befehl wurde abgeschlossen
+quit-rename-completed = 'Rename'-Befehl wurde abgeschlossen
+quit-move-completed = 'Mv'-Befehl wurde abgeschlossen
+quit-sed-completed = 'Sed'-Befehl wurde abgeschlossen
+quit-restore-completed = 'Restore'-Befehl wurde abgeschlossen
+
+## Plain mode
+plain-page = Seite { $page }, { $current } von { $total }
+plain-group = Gruppe { $group }
+plain-page-switcher-query = Eingabe: { $query }
+plain-workspace = Arbeitsbereich { $workspace }
+plain-split-left = Geteilte Ansicht, linker Bereich aktiv
+plain-split-right = Geteilte Ansicht, rechter Bereich aktiv
+plain-hidden-shown = Versteckte Einträge werden angezeigt
+plain-section = Seite { $page }
+plain-described-section = Seite { $page }, { $description }
+plain-section-expanded = Abschnitt { $name }, ausgeklappt
+plain-section-collapsed = Abschnitt { $name }, eingeklappt, { $entries ->
+        [one] 1 Eintrag
+       *[other] { $entries } Einträge
+    }
+plain-subpage = Unterseite { $name }, { $summary }
+plain-entry = { $shortcut }: { $description }
+plain-named-entry = { $name }, { $entry }
+plain-starred = favorisiert
+plain-hidden = versteckt
+plain-secret = Geheim, r zeigt es an
+plain-goto = öffnet Seite { $target }
+plain-exec = führt { $command } aus
+plain-setting = { $label }: { $value }
+plain-placeholder = Platzhalter { $name }: { $value }
+plain-entry-field = { $field }: { $value }
+plain-index-group-expanded = Gruppe { $name }, ausgeklappt
+plain-index-group-collapsed = Gruppe { $name }, eingeklappt, { $pages ->
+        [one] 1 Seite
+       *[other] { $pages } Seiten
+    }
+plain-index-page = Seite { $name }, { $entries ->
+        [one] 1 Eintrag
+       *[other] { $entries } Einträge
+    }
+plain-usage = { $path }, { $views ->
+        [one] 1 Aufruf
+       *[other] { $views } Aufrufe
+    }, { $copies ->
+        [one] 1 Kopie
+       *[other] { $copies } Kopien
+    }
+plain-command = Befehl: { $command }
+plain-note = Notiz mit { $lines ->
+        [one] 1 Zeile
+       *[other] { $lines } Zeilen
+    }
+plain-selection = Seite { $page }, Eintrag { $current } von { $total }: { $text }
+plain-position = Eintrag { $current } von { $total }: { $text }
+plain-empty = Nichts auszuwählen
+plain-keys-page = Tasten: Links und Rechts wechseln die Seite, Hoch und Runter wählen aus, Enter öffnet, y kopiert den Eintrag und Y seine Beschreibung, o öffnet seine Webseite, Leertaste klappt ein, Rücktaste geht zurück, / filtert, f durchsucht alle Seiten, Strg+P oder : springt zu einer Seite, p listet alle Seiten auf, ? listet alle Tasten auf, R liest die Konfiguration erneut, Escape hebt den Filter oder die Suche auf, s sortiert, A fügt einen Eintrag hinzu, e ändert ihn, m verschiebt ihn, r zeigt ein Geheimnis an, v teilt die Ansicht, Tab wechselt den Bereich, W wechselt den Arbeitsbereich, q schließt
+plain-keys-all-pages = Tasten: Hoch und Runter wählen aus, Enter öffnet die Seite, a geht zurück, q schließt
+plain-keys-page-index = Tasten: Hoch und Runter wählen aus, Enter öffnet die Seite oder klappt die Gruppe ein, p geht zurück, q schließt
+plain-keys-help = Tasten: Hoch und Runter wählen aus, Escape geht zurück, q schließt
+plain-keys-stats = Tasten: Hoch und Runter wählen aus, Enter öffnet den Eintrag, S geht zurück, q schließt
+plain-keys-details = Tasten: Escape geht zurück, r zeigt ein Geheimnis an, q schließt
+plain-keys-page-switcher = Tasten: Tippen grenzt die Seiten ein, Hoch und Runter wählen aus, Enter öffnet die Seite, Escape geht zurück
+plain-keys-workspaces = Tasten: Hoch und Runter wählen aus, Enter oder die Nummer eines Arbeitsbereichs wechselt dorthin, Escape geht zurück
+plain-keys-conflict = Tasten: r lädt neu, m führt zusammen, o überschreibt
+plain-keys-config-error = Tasten: R liest die Konfiguration erneut, Escape geht zurück, q schließt
+plain-keys-settings = Tasten: Hoch und Runter wählen aus, Links und Rechts ändern, Enter speichert, Escape bricht ab
+plain-keys-placeholders = Tasten: Wert eintippen, Tab und Umschalt+Tab wählen aus, Enter kopiert den Befehl, Escape bricht ab
+plain-keys-entry-form = Tasten: Wert eintippen, Tasten durch Kommas trennen, Tab und Umschalt+Tab wählen aus, Enter speichert den Eintrag, Escape bricht ab
@@ -0,0 +1,276 @@
+# English texts of recall, used as fallback for all other languages
+
+## Legend
+legend-previous-page = Previous Page
+legend-next-page = Next Page
+legend-close = Close
+legend-open-page = Open Page
+legend-back = Back
+legend-change = Change
+legend-save = Save
+legend-cancel = Cancel
+legend-next-field = Next field
+legend-copy = Copy
+legend-switch-pane = Switch Pane
+legend-switch = Switch
+legend-help = Help
+legend-retry = Retry
+page-counter = Page { $current } of { $total }
+showing-hidden = Showing hidden
+wrapping-descriptions = Wrapping
+sorted-by-name = Sorted by name
+sorted-by-shortcut = Sorted by shortcut
+updated-ago = Updated { $age } ago
+filtered-by = Filtered by { $filter }
+
+## Pages
+recent-page = Recent
+favorites-page = Favorites
+search-page = Search
+search-description = Results for { $query }, Enter opens the page of a result
+all-pages-title = All pages
+keyboard-title = Keyboard
+image-fallback = Image { $path }
+details-no-note = No note, write one as a comment above the entry in the config
+details-section = Section: { $section }
+details-goto = Opens page { $page }
+details-exec = Runs { $command }
+details-url = Web page: { $url }
+details-image = Image: { $path }
+details-hidden = Hidden until hidden entries are shown
+details-secret = Secret, masked until revealed
+details-encrypted = Stored encrypted with age in the config
+details-favorite = Starred as a favorite
+page-index-title = Pages
+page-index-entries = { $entries ->
+        [one] 1 entry
+       *[other] { $entries } entries
+    }
+page-index-pages = { $pages ->
+        [one] 1 page
+       *[other] { $pages } pages
+    }
+help-title = Keys
+help-previous-page = Show the previous page
+help-next-page = Show the next page
+help-first-page = Show the first page
+help-last-page = Show the last page
+help-previous-entry = Select the previous row
+help-next-entry = Select the next row
+help-activate = Open the selected row
+help-leave-subpage = Leave the subpage
+help-move-entry-up = Move the entry up
+help-move-entry-down = Move the entry down
+help-delete-entry = Delete the entry
+help-add-entry = Add an entry
+help-edit-entry = Change the entry
+help-undo = Undo the last edit
+help-redo = Redo the last undone edit
+help-new-page = Create a page
+help-move-entry = Move the entry to another page
+help-toggle-favorite = Star or unstar the entry
+help-toggle-section = Collapse or expand the section
+help-cycle-sort = Change the sort order
+help-toggle-hidden = Show or hide hidden entries
+help-reveal-secret = Reveal a secret entry
+help-toggle-wrap = Wrap or truncate descriptions
+help-toggle-keyboard = Show or hide the keyboard
+help-all-pages = Show all pages at once
+help-page-index = List all pages
+help-details = Show the details of the entry
+help-copy = Copy the entry
+help-copy-description = Copy the description of the entry
+help-open-url = Open the web page of the entry
+help-stats = Show the most looked up entries
+help-settings = Open the settings
+help-toggle-split = Split the view
+help-switch-pane = Switch panes
+help-workspaces = Switch workspaces
+help-filter = Filter the page
+help-search = Search all pages
+help-page-switcher = Go to a page
+help-command-line = Jump to a page by name
+help-clear = Clear the filter or search
+help-reload = Read the config again
+help-help = List all keys
+help-quit = Close recall
+stats-title = Most looked up
+stats-empty = Nothing was looked up yet
+workspaces-title = Workspaces
+page-switcher-title = Go to page
+page-switcher-empty = No page matches
+workspace-current = current
+stats-lookups = { $views ->
+        [one] 1 view
+       *[other] { $views } views
+    }, { $copies ->
+        [one] 1 copy
+       *[other] { $copies } copies
+    }
+subpage-summary = { $entries ->
+        [one] 1 entry
+       *[other] { $entries } entries
+    }, { $subpages ->
+        [one] 1 subpage
+       *[other] { $subpages } subpages
+    }
+section-collapsed = { $entries ->
+        [one] 1 entry
+       *[other] { $entries } entries
+    }
+
+## Config conflict popup
+conflict-title = Config changed
+conflict-message = Another program changed the config since recall last saved it, what should happen to the edits made here?
+conflict-reload = Reload: discard the edits and show the config as it is now
+conflict-merge = Merge: keep the pages edited here, take all other pages from the config
+conflict-overwrite = Overwrite: replace the config with the pages as they are here
+config-error-title = Config not read
+config-error-message = The config can't be read, keeping the pages and settings as they were:
+
+## New page prompt
+new-page-prompt = New page:
+page-name-empty = name is empty
+page-name-reserved = name is reserved for settings
+page-name-taken = name is already taken
+
+## Filter prompt
+filter-prompt = Filter:
+filter-matches = { $entries ->
+        [one] 1 entry
+       *[other] { $entries } entries
+    }
+
+## Search prompt
+search-prompt = Search all pages:
+search-results = { $results ->
+        [one] 1 result
+       *[other] { $results } results
+    }
+
+## Move prompt
+move-prompt = Move to page:
+move-target-empty = no page given
+move-target-unknown = no single page matches
+move-target-same = entry is already on this page
+move-target-generated = entries of this page are generated
+move-target-taken = page already has an entry with this name
+command-line-hint = type the name of a page, Tab completes it
+command-line-unknown = no single page matches
+
+## Entry form
+entry-form-add-title = Add entry
+entry-form-edit-title = Change entry
+entry-form-name = Name
+entry-form-keys = Keys
+entry-form-description = Description
+entry-name-empty = name is empty
+entry-name-taken = page already has an entry with this name
+entry-keys-empty = no keys or commands given, separate them by commas
+
+## Placeholder form
+placeholder-title = Fill in placeholders
+notice-copied = Copied to clipboard
+notice-moved = Moved to another page
+notice-reloaded = Reloaded the changed config
+notice-merged = Merged the edits into the changed config
+notice-config-unreadable = The changed config can't be read, fix it or overwrite it
+notice-opened = Opened the web page in the browser
+notice-open-failed = The web page can't be opened
+notice-exec-failed = The command of the entry failed
+notice-reload-failed = The changed config can't be read, keeping the pages as they were
+
+## Settings popup
+settings-title = Settings
+settings-theme = Theme
+settings-primary-color = Primary color
+settings-highlight-color = Highlight color
+settings-no-theme = none
+settings-default-color = default
+
+## Quit reasons
+quit-sigint = Received 'SIGINT' signal
+quit-close-key = 'Close' key was pressed
+quit-init-completed = 'Init' subcommand was completed
+quit-check-completed = 'Check' subcommand found no problems
+quit-edit-completed = 'Edit' subcommand was completed
+quit-idle-timeout = No key was pressed before the idle timeout
+quit-export-completed = 'Export' subcommand was completed
+quit-import-completed = 'Import' subcommand was completed
+quit-scrape-completed = 'Scrape' subcommand was completed
+quit-suggest-completed = 'Suggest' subcommand was completed
+quit-copy-completed = 'Copy' subcommand was completed
+quit-exec-completed = 'Exec' subcommand was completed
+quit-show-completed = 'Show' subcommand was completed
+quit-list-completed = 'List' subcommand was completed
+quit-stats-completed = 'Stats' subcommand was completed
+quit-add-completed = 'Add' subcommand was completed
+quit-remove-completed = 'Rm' subcommand was completed
+quit-rename-completed = 'Rename' subcommand was completed
+quit-move-completed = 'Mv' subcommand was completed
+quit-sed-completed = 'Sed' subcommand was completed
+quit-restore-completed = 'Restore' subcommand was completed
+
+## Plain mode
+plain-page = Page { $page }, { $current } of { $total }
+plain-group = Group { $group }
+plain-page-switcher-query = Typed: { $query }
+plain-workspace = Workspace { $workspace }
+plain-split-left = Split view, left pane focused
+plain-split-right = Split view, right pane focused
+plain-hidden-shown = Hidden entries are shown
+plain-section = Page { $page }
+plain-described-section = Page { $page }, { $description }
+plain-section-expanded = Section { $name }, expanded
+plain-section-collapsed = Section { $name }, collapsed, { $entries ->
+        [one] 1 entry
+       *[other] { $entries } entries
+    }
+plain-subpage = Subpage { $name }, { $summary }
+plain-entry = { $shortcut }: { $description }
+plain-named-entry = { $name }, { $entry }
+plain-starred = starred
+plain-hidden = hidden
+plain-secret = Secret, r reveals it
+plain-goto = opens page { $target }
+plain-exec = runs { $command }
+plain-setting = { $label }: { $value }
+plain-placeholder = Placeholder { $name }: { $value }
+plain-entry-field = { $field }: { $value }
+plain-index-group-expanded = Group { $name }, expanded
+plain-index-group-collapsed = Group { $name }, collapsed, { $pages ->
+        [one] 1 page
+       *[other] { $pages } pages
+    }
+plain-index-page = Page { $name }, { $entries ->
+        [one] 1 entry
+       *[other] { $entries } entries
+    }
+plain-usage = { $path }, { $views ->
+        [one] 1 view
+       *[other] { $views } views
+    }, { $copies ->
+        [one] 1 copy
+       *[other] { $copies } copies
+    }
+plain-command = Command: { $command }
+plain-note = Note with { $lines ->
+        [one] 1 line
+       *[other] { $lines } lines
+    }
+plain-selection = Page { $page }, entry { $current } of { $total }: { $text }
+plain-position = Entry { $current } of { $total }: { $text }
+plain-empty = Nothing to select
+plain-keys-page = Keys: Left and Right change the page, Up and Down select, Enter opens, y copies the entry and Y its description, o opens its web page, Space collapses, Backspace goes back, / filters, f searches all pages, Ctrl+P or : goes to a page, p lists all pages, ? lists all keys, R reads the config again, Escape clears the filter or search, s sorts, A adds an entry, e changes it, m moves it, r reveals a secret, v splits the view, Tab switches panes, W switches workspaces, q closes
+plain-keys-all-pages = Keys: Up and Down select, Enter opens the page, a goes back, q closes
+plain-keys-page-index = Keys: Up and Down select, Enter opens the page or collapses the group, p goes back, q closes
+plain-keys-help = Keys: Up and Down select, Escape goes back, q closes
+plain-keys-stats = Keys: Up and Down select, Enter opens the entry, S goes back, q closes
+plain-keys-details = Keys: Escape goes back, r reveals a secret, q closes
+plain-keys-page-switcher = Keys: type to narrow the pages, Up and Down select, Enter opens the page, Escape goes back
+plain-keys-workspaces = Keys: Up and Down select, Enter or the number of a workspace switches to it, Escape goes back
+plain-keys-conflict = Keys: r reloads, m merges, o overwrites
+plain-keys-config-error = Keys: R reads the config again, Escape goes back, q closes
+plain-keys-settings = Keys: Up and Down select, Left and Right change, Enter saves, Escape cancels
+plain-keys-placeholders = Keys: type the value, Tab and Shift+Tab select, Enter copies the command, Escape cancels
+plain-keys-entry-form = Keys: type the value, separating keys by commas, Tab and Shift+Tab select, Enter saves the entry, Escape cancels
@@ -5,8 +5,32 @@
 //! The application always has an associated state (Running/Quitting), the current page index, color configuration for the CLI and a list of pages which contain the shortcut entries.
 //! If the app quits, this change in state should always be accompanied by a reason.
 
-use anyhow::{anyhow, Result};
+use std::{
+    collections::{HashMap, HashSet},
+    slice,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, bail, Result};
 use log::debug;
+use ratatui::crossterm::event::KeyEvent;
+use serde::Serialize;
+
+use crate::{
+    config::{
+        count_entries, serialize_alignment, serialize_color, serialize_duration, serialize_pages,
+        RECALL_TABLE_NAME,
+    },
+    fuzzy,
+    graphics::Graphics,
+    hyperlink,
+    i18n::Localizer,
+    keymap::{Action, Keymap, PendingKeys},
+    keys::{normalize_content, Keyboard},
+    snippet,
+    state::{EntryKey, Usage, UserState},
+    theme::{ansi_index, THEMES},
+};
 
 /// Represents the main application, managing state, configuration, and navigation between pages.
 #[derive(Debug)]
@@ -14,11 +38,202 @@ pub struct App {
     /// Current state of the application (e.g. running or quitting)
     pub state: AppState,
 
-    /// Index of the current selected page.
-    page_number: usize,
+    /// Current interaction mode, deciding how key events are interpreted
+    pub mode: Mode,
+
+    /// Position of the focused pane, which keys act on.
+    pane: Pane,
+
+    /// The other pane and the side of the focused one while the split view is open.
+    split: Option<Split>,
 
     /// Configuration used to initialize and customize the application.
     config: Config,
+
+    /// Snapshots of previous and undone page states for undo and redo.
+    history: History,
+
+    /// Whether the pages have been edited since they were last written to disk.
+    unsaved_changes: bool,
+
+    /// Pages as they were last read from or written to disk, telling which pages were edited since.
+    saved_pages: Vec<Page>,
+
+    /// How to deal with changes another program made to the config, decided here and carried out outside of the app.
+    conflict_resolution: Option<ConflictResolution>,
+
+    /// Whether the config is about to be read again, which is done outside of the app.
+    reload_requested: bool,
+
+    /// Whether entries marked as hidden are revealed.
+    show_hidden: bool,
+
+    /// Whether the content of the selected entry is revealed if it is secret, until the next key.
+    reveal_secret: bool,
+
+    /// Whether long descriptions are wrapped onto further lines instead of being truncated.
+    wrap_descriptions: bool,
+
+    /// Whether a keyboard diagram with the keys of the selected entry is shown below the page.
+    show_keyboard: bool,
+
+    /// How the terminal shows images.
+    graphics: Graphics,
+
+    /// Data kept between sessions, like favorites.
+    user_state: UserState,
+
+    /// Whether the user state has changed since it was last written to disk.
+    unsaved_state: bool,
+
+    /// Whether lookups were counted since the user state was last written to disk.
+    ///
+    /// Lookups happen with nearly every key, so they are only written along with other changes or on exit.
+    unsaved_usage: bool,
+
+    /// Path of the page and key of the entry last counted as looked up, see [`App::track_usage`].
+    viewed: Option<(String, Option<EntryKey>)>,
+
+    /// Pages assembled from entries of other pages, shown before the pages of the config.
+    virtual_pages: Vec<VirtualPage>,
+
+    /// Texts of the configured language for the interface.
+    localizer: Localizer,
+
+    /// Whether the interface is shown as plain, linear text, either by configuration or for this session only.
+    plain: bool,
+
+    /// Whether colors are disabled for this session, leaving only bold and underlined text for emphasis.
+    no_color: bool,
+
+    /// Primary and highlight color for this session only, e.g. of a theme given on the command line.
+    ///
+    /// They take precedence over the configured colors until the settings are opened, which adjust the configured ones.
+    session_colors: Option<(Color, Color)>,
+
+    /// Whether the screen is cleared after being idle, until the next key is pressed.
+    blanked: bool,
+
+    /// Orders pages are shown in for this session, keyed by their path. Pages without one keep the order of the config.
+    sort_overrides: HashMap<String, SortOrder>,
+
+    /// Texts the entries of pages are filtered by in this session, keyed by the path of their page.
+    filters: HashMap<String, String>,
+
+    /// Text searched for across all pages, whose results are shown on a virtual page before all other pages.
+    search: Option<String>,
+
+    /// Path of the page the search was started on, returned to when the search is cleared there.
+    search_origin: Option<String>,
+
+    /// Sections that are collapsed in this session, identified by the path of their page and their name.
+    collapsed_sections: HashSet<(String, String)>,
+
+    /// Groups of pages that are collapsed in the page index in this session
+    collapsed_groups: HashSet<String>,
+
+    /// Text waiting to be copied to the clipboard, which is done outside of the app.
+    clipboard: Option<String>,
+
+    /// Page path and entry activated without a built-in action, handed to plugins outside of the app.
+    selection: Option<(String, Entry)>,
+
+    /// Command of an activated executable entry, run outside of the app.
+    execution: Option<String>,
+
+    /// Keys typed so far of a sequence of keys bound to an action, along with a count typed before them
+    pending_keys: PendingKeys,
+
+    /// URL waiting to be opened in the browser, which is done outside of the app.
+    browser_url: Option<String>,
+
+    /// Id of a message confirming the last action, shown until the next key is pressed.
+    notice: Option<&'static str>,
+
+    /// Names of all workspaces opened in this session, empty unless several configs are opened.
+    workspaces: Vec<String>,
+
+    /// Index of the workspace of this app among the workspaces.
+    workspace: usize,
+
+    /// Index of the workspace to switch to, which is done outside of the app.
+    workspace_switch: Option<usize>,
+}
+
+/// Position within the pages shown in a pane: the page, the subpages drilled into and the selected row.
+#[derive(Debug, Clone, Default)]
+pub struct Pane {
+    /// Index of the selected page.
+    page_number: usize,
+
+    /// Indices of the subpages that were drilled into, starting from the selected page.
+    subpage_path: Vec<usize>,
+
+    /// Index of the selected row on the page, subpages come before entries.
+    selected_entry: usize,
+
+    /// Number of rows scrolled past at the top of long pages, kept so the rows don't jump while the selection moves.
+    scroll_offset: usize,
+}
+
+/// The second pane of the split view, shown next to the focused pane.
+#[derive(Debug)]
+struct Split {
+    /// Position of the pane that isn't focused
+    other: Pane,
+
+    /// Whether the focused pane is the left one
+    focused_left: bool,
+}
+
+/// What the pane that isn't focused shows in the split view, see [`App::unfocused_pane`].
+pub struct PaneView<'a> {
+    /// Names of the pages from the top-level page down to the shown subpage
+    pub breadcrumbs: Vec<&'a str>,
+
+    /// Group of the top-level page, if it belongs to one
+    pub group: Option<&'a str>,
+
+    /// Description of the shown page, if it has one
+    pub description: Option<&'a str>,
+
+    /// Index of the shown page
+    pub page_number: usize,
+
+    /// Rows of the shown page, see [`App::rows`]
+    pub rows: Vec<PageRow<'a>>,
+
+    /// Indices of the starred entries of the shown page
+    pub starred: Vec<usize>,
+
+    /// The selected row, or `None` if the page has no rows
+    pub selected: Option<usize>,
+
+    /// Whether the names of entries are shown, see [`App::shows_names`]
+    pub show_names: bool,
+
+    /// Number of rows scrolled past at the top of the shown page
+    pub scroll_offset: usize,
+}
+
+/// A page assembled from entries of other pages, e.g. the favorites.
+#[derive(Debug)]
+struct VirtualPage {
+    /// The assembled page, its entries are copies of the original entries
+    page: Page,
+
+    /// The original entry for each entry of the page
+    sources: Vec<EntryKey>,
+}
+
+/// Undo and redo stacks of page snapshots, recorded before each edit.
+#[derive(Debug, Default)]
+struct History {
+    /// States to return to on undo, most recent last.
+    undo: Vec<Vec<Page>>,
+
+    /// States to return to on redo, most recent last.
+    redo: Vec<Vec<Page>>,
 }
 
 /// Represents the application state.
@@ -31,6 +246,225 @@ pub enum AppState {
     Quitting(QuitReason),
 }
 
+/// Represents the interaction mode of a running application.
+#[derive(Debug)]
+pub enum Mode {
+    /// Keys navigate and edit the pages
+    Normal,
+
+    /// Keys are typed into the name of a page that is about to be created
+    PageNameInput(String),
+
+    /// Keys are typed into the page the selected entry is about to be moved to
+    MoveEntry(String),
+
+    /// Keys are typed into the filter of the current page, narrowing its entries while typing
+    FilterInput,
+
+    /// Keys are typed into the search across all pages, updating the results page while typing
+    SearchInput,
+
+    /// Keys are typed into the page switcher, listing the pages matching fuzzily
+    PageSwitcher(PageSwitcher),
+
+    /// Keys are typed into the command line, jumping to the page with the typed name
+    CommandLine(CommandLine),
+
+    /// Keys adjust the theme and colors in the settings popup
+    Settings(Settings),
+
+    /// All pages are shown as sections of a single table, with the selected row
+    AllPages(usize),
+
+    /// Keys are typed into the placeholders of a command that is about to be copied
+    PlaceholderInput(PlaceholderForm),
+
+    /// Keys are typed into the fields of an entry that is about to be added or changed
+    EntryInput(EntryForm),
+
+    /// The most looked up entries are ranked in a heatmap, with the selected row
+    Stats(usize),
+
+    /// The selected entry is shown in full in a popup, including its note
+    Details,
+
+    /// The opened workspaces are listed to switch between them, with the selected one
+    Workspaces(usize),
+
+    /// All top-level pages are listed with their number of entries to open one, with the selected row
+    PageIndex(usize),
+
+    /// The keys of all actions are listed in a popup, with the selected row
+    Help(usize),
+
+    /// Another program changed the config while there are unsaved edits, asking how to resolve this
+    ConfigConflict,
+
+    /// The config couldn't be read again, showing why in a popup
+    ConfigError(String),
+}
+
+/// State of the form adding an entry to the current page or changing the selected entry.
+#[derive(Debug, Clone)]
+pub struct EntryForm {
+    /// Name of the entry that is changed, or `None` if a new entry is added
+    pub original: Option<String>,
+
+    /// Values typed into the fields, labeled by the message ids in [`ENTRY_FORM_FIELDS`]
+    pub values: [String; 3],
+
+    /// Index of the field that is typed into
+    pub field: usize,
+
+    /// Whether the keys are masked, as they belong to a secret entry
+    pub secret: bool,
+}
+
+/// Message ids of the labels of the name, keys and description fields of the entry form
+pub const ENTRY_FORM_FIELDS: [&str; 3] = [
+    "entry-form-name",
+    "entry-form-keys",
+    "entry-form-description",
+];
+
+/// Separates the keys or commands of an entry typed into the entry form, like for `recall add --keys`
+pub const ENTRY_KEY_SEPARATOR: char = ',';
+
+/// Ways of resolving edits made here to a config that another program changed in the meantime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Discard the edits and read the config again
+    Reload,
+
+    /// Read the config again, keeping the pages that were edited here
+    Merge,
+
+    /// Write the edits, replacing the changes of the other program
+    Overwrite,
+}
+
+/// State of the form filling in the placeholders of a command, e.g. `<branch>` in `git checkout <branch>`.
+#[derive(Debug, Clone)]
+pub struct PlaceholderForm {
+    /// The command containing the placeholders
+    pub command: String,
+
+    /// Names of the placeholders in order of appearance, along with the values typed so far
+    pub fields: Vec<(String, String)>,
+
+    /// Index of the field that is typed into
+    pub field: usize,
+
+    /// Whether the command is masked, as it belongs to a secret entry
+    pub secret: bool,
+}
+
+/// State of the page switcher, listing the paths of all pages that match what was typed.
+#[derive(Debug, Clone, Default)]
+pub struct PageSwitcher {
+    /// What was typed, matched fuzzily against the names and aliases of the pages
+    pub query: String,
+
+    /// Index of the selected page among the matching pages
+    pub selected: usize,
+}
+
+/// State of the command line at the bottom, completing the names of pages like a shell.
+#[derive(Debug, Clone, Default)]
+pub struct CommandLine {
+    /// What was typed, or completed by Tab
+    pub input: String,
+
+    /// What was typed before Tab cycled through the pages starting with it, if it did
+    cycled_prefix: Option<String>,
+}
+
+/// A row of the current page as it is shown and selected.
+#[derive(Debug)]
+pub enum PageRow<'a> {
+    /// A subpage, which can be drilled into
+    Subpage(&'a Page),
+
+    /// The header of a section of entries
+    Section {
+        /// Name of the section
+        name: &'a str,
+
+        /// Whether the entries of the section are hidden
+        collapsed: bool,
+
+        /// Number of visible entries in the section
+        entries: usize,
+    },
+
+    /// A visible entry, paired with its index on the page
+    Entry(usize, &'a Entry),
+}
+
+/// A row of the page index as it is shown and selected.
+#[derive(Debug)]
+pub enum IndexRow<'a> {
+    /// The header of pages sharing a group
+    Group {
+        /// Name of the group
+        name: &'a str,
+
+        /// Whether the pages of the group are hidden
+        collapsed: bool,
+
+        /// Number of pages in the group
+        pages: usize,
+    },
+
+    /// A top-level page
+    Page {
+        /// Number of the page, counted from zero
+        number: usize,
+
+        /// The page
+        page: &'a Page,
+
+        /// Number of entries of the page, including those of its subpages
+        entries: usize,
+    },
+}
+
+/// A section of the all-pages view, containing the visible entries of a single page.
+#[derive(Debug)]
+pub struct PageSection<'a> {
+    /// Names of the pages from the top-level page down to the page of this section
+    pub path: Vec<&'a str>,
+
+    /// Description of the page, if it has one
+    pub description: Option<&'a str>,
+
+    /// Visible entries of the page and whether they are starred
+    pub entries: Vec<(&'a Entry, bool)>,
+}
+
+/// State of the settings popup, which previews changes live until they are confirmed.
+#[derive(Debug)]
+pub struct Settings {
+    /// The setting that is currently being adjusted
+    pub field: SettingsField,
+
+    /// Theme and colors from before the popup was opened, restored on cancel
+    original: (Option<String>, Color, Color),
+}
+
+/// Enumerates the settings that can be adjusted in the settings popup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    /// The built-in theme
+    Theme,
+
+    /// The primary UI color
+    PrimaryColor,
+
+    /// The highlight UI color
+    HighlightColor,
+}
+
 /// Enumerates reasons for quitting the application.
 #[derive(Debug)]
 pub enum QuitReason {
@@ -42,36 +476,244 @@ pub enum QuitReason {
 
     /// A subcommand (e.g. `init`) completed and caused the app to exit.
     InitSubcommandCompleted,
+
+    /// The `check` subcommand found no problems in the config.
+    CheckSubcommandCompleted,
+
+    /// The `edit` subcommand left a valid config.
+    EditSubcommandCompleted,
+
+    /// No key was pressed for the configured idle timeout.
+    IdleTimeout,
+
+    /// The `export` subcommand wrote the exported config.
+    ExportSubcommandCompleted,
+
+    /// The `import` subcommand merged the imported entries into the config.
+    ImportSubcommandCompleted,
+
+    /// The `scrape` subcommand printed a drafted page.
+    ScrapeSubcommandCompleted,
+
+    /// The `suggest` subcommand finished reviewing suggestions.
+    SuggestSubcommandCompleted,
+
+    /// The `copy` subcommand copied an entry to the clipboard.
+    CopySubcommandCompleted,
+
+    /// The `exec` subcommand ran the command of an entry.
+    ExecSubcommandCompleted,
+
+    /// The `show` subcommand printed pages.
+    ShowSubcommandCompleted,
+
+    /// The `list` subcommand printed pages or entries.
+    ListSubcommandCompleted,
+
+    /// The `stats` subcommand printed statistics.
+    StatsSubcommandCompleted,
+
+    /// The `add` subcommand added an entry.
+    AddSubcommandCompleted,
+
+    /// The `rm` subcommand removed a page or entry.
+    RemoveSubcommandCompleted,
+
+    /// The `rename` subcommand renamed a page or entry.
+    RenameSubcommandCompleted,
+
+    /// The `mv` subcommand moved an entry to another page.
+    MoveSubcommandCompleted,
+
+    /// The `sed` subcommand replaced a pattern in entries.
+    SedSubcommandCompleted,
+
+    /// The `restore` subcommand restored or listed backups of the config.
+    RestoreSubcommandCompleted,
     //Other(String),
 }
 
 type Color = ratatui::style::Color;
 
+type Alignment = ratatui::layout::Alignment;
+
 /// Holds runtime configuration data including UI colors and pages.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Config {
+    /// Name of the selected built-in theme, if any
+    pub theme: Option<String>,
+
     /// Primary UI color
+    #[serde(serialize_with = "serialize_color")]
     pub primary_color: Color,
 
     /// Highlight color for specific UI elements
+    #[serde(serialize_with = "serialize_color")]
     pub highlight_color: Color,
 
+    /// Code of the language used for the interface
+    pub language: String,
+
+    /// Whether the interface is shown as plain, linear text for screen readers
+    pub plain: bool,
+
+    /// Whether the names of entries are shown in their own column, unless a page decides otherwise
+    pub show_names: bool,
+
+    /// Alignment of the text in the columns of entries
+    pub alignment: ColumnAlignment,
+
+    /// Number of columns tables are at most as wide as, centered in wider terminals
+    pub max_width: Option<u16>,
+
+    /// Minutes without input after which the idle action is taken, if any
+    pub idle_timeout: Option<u64>,
+
+    /// What happens once the idle timeout has passed
+    pub idle_action: IdleAction,
+
+    /// Milliseconds to wait for input before timers are checked again, if not the default
+    pub tick_rate: Option<u64>,
+
+    /// Whether the mouse wheel scrolls through entries and pages, which keeps the terminal from selecting text
+    pub mouse: bool,
+
+    /// Whether moving past the last or first page continues at the other end
+    pub wrap_pages: bool,
+
+    /// Keyboard deciding how the primary modifier is shown, detected from the operating system if not set
+    pub keyboard: Option<Keyboard>,
+
+    /// How images are shown, detected from the terminal if not set
+    pub graphics: Option<Graphics>,
+
+    /// Path of the age identity file decrypting encrypted entries, as written in the config
+    pub age_identity: Option<String>,
+
+    /// Keys of recall's own actions while browsing the pages
+    pub keybinds: Keymap,
+
     /// All pages that the application can display
     pub pages: Vec<Page>,
 }
 
+/// Alignment of the text in each column of a table of entries, all left-aligned by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ColumnAlignment {
+    /// Alignment of the names of entries, if they are shown
+    #[serde(serialize_with = "serialize_alignment")]
+    pub name: Alignment,
+
+    /// Alignment of the shortcuts
+    #[serde(serialize_with = "serialize_alignment")]
+    pub shortcut: Alignment,
+
+    /// Alignment of the descriptions
+    #[serde(serialize_with = "serialize_alignment")]
+    pub description: Alignment,
+}
+
+/// Enumerates what happens when recall wasn't used for the idle timeout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdleAction {
+    /// Recall quits
+    #[default]
+    Quit,
+
+    /// The screen is cleared until the next key is pressed
+    Blank,
+}
+
 /// Represents a logical page in the application, consisting of a name and content entries.
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Page {
     /// The name or title of the page
     pub name: String,
 
+    /// Name of the group of related pages this page belongs to, if any
+    pub group: Option<String>,
+
+    /// Line describing the page, shown under its title
+    pub description: Option<String>,
+
+    /// Path of an image illustrating the page, relative to the config file
+    pub image: Option<String>,
+
+    /// Other names the page can be opened by, e.g. from the command line
+    pub aliases: Vec<String>,
+
+    /// Order the entries are sorted in when the page is loaded
+    pub sort: SortOrder,
+
     /// Entries or items shown on the page
     pub entries: Vec<Entry>,
+
+    /// Nested pages that can be drilled into from this page
+    pub subpages: Vec<Page>,
+
+    /// Variables used in the commands of the page, e.g. imported from navi cheats
+    pub variables: Vec<Variable>,
+
+    /// Command generating the entries of the page when the config is loaded, if any
+    pub source: Option<PageSource>,
+
+    /// Name of a plugin function generating the entries of the page when the config is loaded, if any
+    pub generator: Option<String>,
+
+    /// Whether the names of entries are shown in their own column, if set for this page instead of globally
+    pub show_names: Option<bool>,
+}
+
+/// A command whose output is turned into the entries of a page when the config is loaded and, if set, on an interval.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PageSource {
+    /// Shell command printing one entry per line
+    pub command: String,
+
+    /// Regular expression matching a line of the output, capturing the groups `content` and `description` by name
+    pub pattern: Option<String>,
+
+    /// Interval the command is run again in while recall is open, if any
+    #[serde(serialize_with = "serialize_duration")]
+    pub refresh: Option<Duration>,
+
+    /// When the command was last run
+    #[serde(skip)]
+    pub ran: Option<Instant>,
+
+    /// When the entries were last generated successfully
+    #[serde(skip)]
+    pub updated: Option<Instant>,
+}
+
+/// A named value of a command, whose possible values are listed by another command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Variable {
+    /// Name of the variable, e.g. `branch` for the placeholder `<branch>`
+    pub name: String,
+
+    /// Shell command printing the possible values of the variable
+    pub command: String,
+}
+
+/// Enumerates the orders entries of a page can be sorted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Entries keep the order they were written in
+    #[default]
+    Manual,
+
+    /// Entries are sorted by their name, ignoring case
+    Alphabetical,
+
+    /// Entries are sorted by their content, ignoring case
+    Shortcut,
 }
 
 /// Represents a content entry on a page
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Entry {
     /// The name or label of the entry
     pub name: String,
@@ -83,6 +725,33 @@ pub struct Entry {
 
     /// A short description or tooltip for the entry
     pub description: String,
+
+    /// Whether the entry is only shown when hidden entries are revealed
+    pub hidden: bool,
+
+    /// Whether the content of the entry is masked until it is revealed, e.g. for tokens
+    pub secret: bool,
+
+    /// The content encrypted with age as written in the config, which is written back instead of the decrypted content
+    pub encrypted: Option<String>,
+
+    /// Path of a page that is opened when the entry is activated, if any
+    pub goto: Option<String>,
+
+    /// Shell command that is run when the entry is activated, if any
+    pub exec: Option<String>,
+
+    /// Web page about the entry, e.g. its upstream documentation, opened in the browser with `o`
+    pub url: Option<String>,
+
+    /// Name of the section within its page the entry is listed under, if any
+    pub section: Option<String>,
+
+    /// Path of an image illustrating the entry, relative to the config file
+    pub image: Option<String>,
+
+    /// Context written as comments directly above the entry in the config, if any
+    pub note: Option<String>,
 }
 
 /// The default primary UI color
@@ -91,14 +760,64 @@ pub const DEFAULT_PRIMARY_COLOR: Color = Color::White;
 /// The default secondary/highlight UI color
 pub const DEFAULT_SECONDARY_COLOR: Color = Color::Cyan;
 
+/// The maximum number of edits that can be undone
+const MAX_HISTORY_SIZE: usize = 100;
+
+/// Separates the names of nested pages when identifying a subpage by its path
+pub const PAGE_PATH_SEPARATOR: &str = "/";
+
+/// Shown instead of the content of secret entries, regardless of its length
+pub const SECRET_MASK: &str = "••••";
+
 impl App {
-    /// Creates a new application instance from a given configuration
-    pub fn new(config: Config) -> App {
-        App {
+    /// Creates a new application instance from a given configuration, the state of previous sessions and the texts of the interface
+    pub fn new(config: Config, user_state: UserState, localizer: Localizer) -> App {
+        let saved_pages = config.pages.clone();
+        let mut app = App {
             state: AppState::Running,
-            page_number: 0,
+            mode: Mode::Normal,
+            pane: Pane::default(),
+            split: None,
+            history: History::default(),
+            unsaved_changes: false,
+            saved_pages,
+            conflict_resolution: None,
+            reload_requested: false,
+            show_hidden: false,
+            reveal_secret: false,
+            wrap_descriptions: false,
+            show_keyboard: false,
+            graphics: Graphics::default(),
+            user_state,
+            unsaved_state: false,
+            unsaved_usage: false,
+            viewed: None,
+            virtual_pages: vec![],
+            localizer,
+            plain: config.plain,
+            no_color: false,
+            session_colors: None,
+            blanked: false,
+            sort_overrides: HashMap::new(),
+            filters: HashMap::new(),
+            search: None,
+            search_origin: None,
+            collapsed_sections: HashSet::new(),
+            collapsed_groups: HashSet::new(),
+            clipboard: None,
+            selection: None,
+            execution: None,
+            pending_keys: PendingKeys::default(),
+            browser_url: None,
+            notice: None,
+            workspaces: vec![],
+            workspace: 0,
+            workspace_switch: None,
             config,
-        }
+        };
+        app.refresh_virtual_pages();
+        app.pane.page_number = 0;
+        app
     }
 
     /// Returns `true` if the application is currently running
@@ -113,59 +832,2847 @@ impl App {
 
     /// Returns the current page number (zero-based index)
     pub fn current_page_number(&self) -> usize {
-        self.page_number
+        self.pane.page_number
     }
 
     /// Returns the total number of pages in the application.
     pub fn number_of_pages(&self) -> usize {
-        self.config.pages.len()
+        self.virtual_pages.len() + self.config.pages.len()
+    }
+
+    /// Returns the top-level pages in the order they are paged through, starting with the virtual pages
+    pub fn top_level_pages(&self) -> Vec<&Page> {
+        self.virtual_pages
+            .iter()
+            .map(|virtual_page| &virtual_page.page)
+            .chain(&self.config.pages)
+            .collect()
+    }
+
+    /// Shows the page with the given number, counted from zero, or the last page if there are fewer pages.
+    pub fn go_to_page(&mut self, number: usize) {
+        self.pane.page_number = number.min(self.number_of_pages().saturating_sub(1));
+        self.pane.subpage_path.clear();
+        self.pane.selected_entry = 0;
     }
 
-    /// Increments the current page number, unless already on the last page.
+    /// Returns the action completed by the pressed key along with the count typed before it, see [`Keymap::resolve`].
+    pub fn resolve_key(&mut self, key: &KeyEvent) -> Option<(Action, Option<usize>)> {
+        self.config.keybinds.resolve(&mut self.pending_keys, key)
+    }
+
+    /// Returns the action bound to the pressed key on its own, see [`Keymap::action`].
+    pub fn key_action(&self, key: &KeyEvent) -> Option<Action> {
+        self.config.keybinds.action(key)
+    }
+
+    /// Increments the current page number, unless already on the last page and `wrap_pages` is off, which starts over at the first page.
     pub fn increment_page(&mut self) {
-        if self.page_number == self.number_of_pages() - 1 {
-            debug!("Page counter is on last page, can't increment");
-            return;
+        if self.pane.page_number == self.number_of_pages() - 1 {
+            if !self.config.wrap_pages {
+                debug!("Page counter is on last page, can't increment");
+                return;
+            }
+            self.pane.page_number = 0;
+        } else {
+            self.pane.page_number += 1;
         }
-        self.page_number += 1;
+        self.pane.subpage_path.clear();
+        self.pane.selected_entry = 0;
     }
 
-    /// Decrements the current page number, unless already on the first page.
+    /// Decrements the current page number, unless already on the first page and `wrap_pages` is off, which continues at the last page.
     pub fn decrement_page(&mut self) {
-        if self.page_number == 0 {
-            debug!("Page counter is on first page, can't decrement");
+        if self.pane.page_number == 0 {
+            if !self.config.wrap_pages {
+                debug!("Page counter is on first page, can't decrement");
+                return;
+            }
+            self.pane.page_number = self.number_of_pages() - 1;
+        } else {
+            self.pane.page_number -= 1;
+        }
+        self.pane.subpage_path.clear();
+        self.pane.selected_entry = 0;
+    }
+
+    /// Advances to the next page, starting over after the last one.
+    ///
+    /// Used for cycling through the pages automatically, so nothing happens outside of normal mode to not interrupt prompts or popups.
+    pub fn cycle_page(&mut self) {
+        if !matches!(self.mode, Mode::Normal) || self.number_of_pages() == 0 {
             return;
         }
-        self.page_number -= 1;
+        self.pane.page_number = (self.pane.page_number + 1) % self.number_of_pages();
+        self.pane.subpage_path.clear();
+        self.pane.selected_entry = 0;
     }
 
     /// Returns a reference to the currently selected page, or an error if the index is out-of-bounds
     pub fn get_current_page(&self) -> Result<&Page> {
-        self.config
-            .pages
-            .get(self.page_number)
-            .ok_or(anyhow!("Can not get page {} from config", self.page_number))
+        self.page_in(&self.pane)
     }
 
-    /// Returns the primary UI color
-    pub fn primary_color(&self) -> Color {
-        self.config.primary_color
+    /// Returns the names of the pages from the top-level page down to the current subpage
+    pub fn breadcrumbs(&self) -> Vec<&str> {
+        self.breadcrumbs_in(&self.pane)
     }
 
-    /// Returns the highlight UI color
-    pub fn highlight_color(&self) -> Color {
-        self.config.highlight_color
+    /// Returns the group of the current top-level page, if it belongs to one
+    pub fn current_group(&self) -> Option<&str> {
+        self.top_level_page()?.group.as_deref()
     }
-}
 
-impl QuitReason {
-    /// Returns a human-readable description of the quit reason
-    pub fn text(&self) -> &str {
-        match self {
-            QuitReason::Sigint => "Received 'SIGINT' signal",
-            QuitReason::CloseKeyPressed => "'Close' key was pressed",
-            QuitReason::InitSubcommandCompleted => "'Init' subcommand was completed",
-            //QuitReason::Other(s) => s,
-        }
+    /// Returns the description of the current page, if it has one
+    pub fn current_description(&self) -> Option<&str> {
+        self.get_current_page().ok()?.description.as_deref()
+    }
+
+    /// Returns the subpages of the current page, which are listed before its entries
+    pub fn subpages(&self) -> &[Page] {
+        self.subpages_in(&self.pane)
+    }
+
+    /// Returns the time since the entries of the current page were last generated, if it is generated by a command
+    pub fn current_page_age(&self) -> Option<Duration> {
+        let source = self.get_current_page().ok()?.source.as_ref()?;
+        source.updated.map(|updated| updated.elapsed())
+    }
+
+    /// Returns the page path and entry activated without a built-in action, if any, and clears it.
+    ///
+    /// These are handed to plugins, which may act on them.
+    pub fn take_selection(&mut self) -> Option<(String, Entry)> {
+        self.selection.take()
+    }
+
+    /// Returns the command of the activated executable entry, if any, and clears it.
+    pub fn take_execution(&mut self) -> Option<String> {
+        self.execution.take()
+    }
+
+    /// Queues the web page of the selected entry to be opened in the browser.
+    ///
+    /// Entries without a `url` are opened if their content is a link.
+    pub fn open_selected_url(&mut self) {
+        let Some(url) = self
+            .selected_page_entry()
+            .and_then(|entry| entry.url.as_deref().or(entry.link_url()))
+        else {
+            debug!("Selected row has no URL to open");
+            return;
+        };
+        self.browser_url = Some(url.to_string());
+    }
+
+    /// Returns the URL waiting to be opened in the browser, if any, and clears it.
+    pub fn take_browser_url(&mut self) -> Option<String> {
+        self.browser_url.take()
+    }
+
+    /// Tells whether the web page of the selected entry was opened in the browser.
+    pub fn report_opened_url(&mut self, opened: bool) {
+        self.notice = Some(if opened {
+            "notice-opened"
+        } else {
+            "notice-open-failed"
+        });
+    }
+
+    /// Tells that the command of an executable entry failed.
+    pub fn report_failed_execution(&mut self) {
+        self.notice = Some("notice-exec-failed");
+    }
+
+    /// Queues the given text to be copied to the clipboard and confirms it with a notice.
+    pub fn copy_to_clipboard(&mut self, text: String) {
+        self.clipboard = Some(text);
+        self.notice = Some("notice-copied");
+
+        // Only entries are copied, which are still selected after their form or plugin is done
+        if let Some(key) = self
+            .selected_entry_index()
+            .and_then(|index| self.entry_key(index))
+        {
+            self.user_state
+                .record_usage(&key.page, Some(&key.entry), true);
+            self.unsaved_usage = true;
+        }
+    }
+
+    /// Returns the paths and sources of all pages whose command is due to run again
+    pub fn due_page_sources(&self) -> Vec<(String, PageSource)> {
+        let mut due = vec![];
+        for page in &self.config.pages {
+            collect_due_sources(page, &page.name, &mut due);
+        }
+        due
+    }
+
+    /// Replaces the entries of the page with the given path by freshly generated ones.
+    ///
+    /// `None` records a failed run, which keeps the previous entries.
+    /// Generated entries aren't part of the config, so this is neither undoable nor saved.
+    pub fn update_generated_entries(&mut self, path: &str, entries: Option<Vec<Entry>>) {
+        let Some((page_index, subpage_path)) = find_page(&self.config.pages, path) else {
+            return;
+        };
+
+        let mut page = &mut self.config.pages[page_index];
+        for index in subpage_path {
+            page = &mut page.subpages[index];
+        }
+        let Some(source) = &mut page.source else {
+            return;
+        };
+
+        source.ran = Some(Instant::now());
+        if let Some(mut entries) = entries {
+            source.updated = Some(Instant::now());
+            page.sort.sort(&mut entries);
+            page.entries = entries;
+        }
+
+        // Favorites may point to entries that were generated
+        self.refresh_virtual_pages();
+    }
+
+    /// Activates the selected row: drills into a selected subpage, toggles a selected section or follows the link of a selected entry.
+    ///
+    /// Selected executable entries have their command run. Selected entries with placeholders in their command open a form to fill them in instead.
+    pub fn activate_selection(&mut self) {
+        if self.pane.selected_entry < self.subpages().len() {
+            self.pane.subpage_path.push(self.pane.selected_entry);
+            self.pane.selected_entry = 0;
+            return;
+        }
+
+        if self.toggle_selected_section() {
+            return;
+        }
+
+        if self.is_search_page() {
+            let key = self
+                .virtual_pages
+                .first()
+                .zip(self.selected_entry_index())
+                .and_then(|(results, index)| results.sources.get(index).cloned());
+            if let Some(key) = key {
+                self.open_entry(&key);
+            }
+            return;
+        }
+
+        let Some(entry) = self
+            .selected_entry_index()
+            .and_then(|index| self.get_current_page().ok()?.entries.get(index))
+        else {
+            debug!("No entry is selected");
+            return;
+        };
+
+        if let Some(target) = entry.goto.clone() {
+            self.open_page(&target);
+            return;
+        }
+
+        let entry = entry.clone();
+        self.remember_selected_entry();
+
+        if let Some(command) = entry.exec {
+            debug!("Running command of selected entry {}", entry.name);
+            if let Some(key) = self
+                .selected_entry_index()
+                .and_then(|index| self.entry_key(index))
+            {
+                self.user_state
+                    .record_usage(&key.page, Some(&key.entry), false);
+                self.unsaved_usage = true;
+            }
+            self.execution = Some(command);
+            return;
+        }
+
+        let placeholders = snippet::placeholders(&entry.content);
+        if placeholders.is_empty() {
+            debug!("Selected entry neither links to a page nor has placeholders");
+            self.selection = Some((self.breadcrumbs().join(PAGE_PATH_SEPARATOR), entry));
+            return;
+        }
+
+        self.mode = Mode::PlaceholderInput(PlaceholderForm {
+            command: snippet::command(&entry.content),
+            fields: placeholders
+                .into_iter()
+                .map(|name| (name, String::new()))
+                .collect(),
+            field: 0,
+            secret: entry.secret,
+        });
+    }
+
+    /// Copies the content of the selected entry, or its description if asked for or if it has no content.
+    ///
+    /// Unlike activating the entry, this copies placeholders as they are and doesn't follow links.
+    pub fn copy_selected_entry(&mut self, description: bool) {
+        let Some(entry) = self.selected_page_entry() else {
+            debug!("No entry is selected to copy");
+            return;
+        };
+
+        let text = if description || entry.content.is_empty() {
+            entry.description.clone()
+        } else {
+            snippet::command(&entry.content)
+        };
+        self.remember_selected_entry();
+        self.copy_to_clipboard(text);
+    }
+
+    /// Copies the command of the placeholder form with the typed values filled in and closes the form.
+    pub fn confirm_placeholders(&mut self) {
+        let Mode::PlaceholderInput(form) = &self.mode else {
+            return;
+        };
+
+        self.copy_to_clipboard(form.filled());
+        self.mode = Mode::Normal;
+    }
+
+    /// Returns the text waiting to be copied to the clipboard, if any, and clears it.
+    pub fn take_clipboard(&mut self) -> Option<String> {
+        self.clipboard.take()
+    }
+
+    /// Returns the id of the message confirming the last action, if any.
+    pub fn notice(&self) -> Option<&'static str> {
+        self.notice
+    }
+
+    /// Clears the message confirming the last action.
+    pub fn clear_notice(&mut self) {
+        self.notice = None;
+    }
+
+    /// Switches to the page with the given path, returning `false` if there is no such page.
+    pub fn open_page(&mut self, path: &str) -> bool {
+        let Some((page_index, subpage_path)) = find_page(&self.config.pages, path) else {
+            debug!("Can't open unknown page {}", path);
+            return false;
+        };
+
+        self.pane.page_number = self.virtual_pages.len() + page_index;
+        self.pane.subpage_path = subpage_path;
+        self.pane.selected_entry = 0;
+        true
+    }
+
+    /// Opens the split view showing the current page in both panes, or closes it keeping only the focused pane.
+    pub fn toggle_split(&mut self) {
+        self.split = match self.split {
+            Some(_) => None,
+            None => Some(Split {
+                other: self.pane.clone(),
+                focused_left: true,
+            }),
+        };
+    }
+
+    /// Opens the split view showing the page with the given path next to the current page,
+    /// returning `false` if there is no such page.
+    pub fn open_split(&mut self, path: &str) -> bool {
+        let Some((page_index, subpage_path)) = find_page(&self.config.pages, path) else {
+            debug!("Can't split with unknown page {}", path);
+            return false;
+        };
+
+        self.split = Some(Split {
+            other: Pane {
+                page_number: self.virtual_pages.len() + page_index,
+                subpage_path,
+                selected_entry: 0,
+                scroll_offset: 0,
+            },
+            focused_left: true,
+        });
+        true
+    }
+
+    /// Returns `true` if the split view is open
+    pub fn is_split(&self) -> bool {
+        self.split.is_some()
+    }
+
+    /// Returns `true` if the focused pane is the left one, which is the only one outside of the split view
+    pub fn is_left_pane_focused(&self) -> bool {
+        self.split.as_ref().is_none_or(|split| split.focused_left)
+    }
+
+    /// Moves the focus to the other pane of the split view, keys then act on its page.
+    pub fn switch_pane(&mut self) {
+        let Some(split) = &mut self.split else {
+            debug!("Split view isn't open, no pane to switch to");
+            return;
+        };
+
+        std::mem::swap(&mut self.pane, &mut split.other);
+        split.focused_left = !split.focused_left;
+    }
+
+    /// Returns what the pane that isn't focused shows, or `None` if the split view isn't open
+    pub fn unfocused_pane(&self) -> Option<PaneView<'_>> {
+        let split = self.split.as_ref()?;
+        let pane = &split.other;
+
+        let rows = self.rows_in(pane);
+        let starred = rows
+            .iter()
+            .filter_map(|row| match row {
+                PageRow::Entry(index, _) => Some(*index),
+                _ => None,
+            })
+            .filter(|&index| self.is_favorite_in(pane, index))
+            .collect();
+
+        Some(PaneView {
+            breadcrumbs: self.breadcrumbs_in(pane),
+            group: self
+                .top_level_page_in(pane)
+                .and_then(|page| page.group.as_deref()),
+            description: self
+                .page_in(pane)
+                .ok()
+                .and_then(|page| page.description.as_deref()),
+            page_number: pane.page_number,
+            selected: (!rows.is_empty()).then_some(pane.selected_entry),
+            rows,
+            starred,
+            show_names: self.shows_names_in(pane),
+            scroll_offset: pane.scroll_offset,
+        })
+    }
+
+    /// Returns the number of rows scrolled past at the top of the current page
+    pub fn scroll_offset(&self) -> usize {
+        self.pane.scroll_offset
+    }
+
+    /// Keeps the number of rows scrolled past at the top of the current page as it was drawn.
+    pub fn set_scroll_offset(&mut self, offset: usize) {
+        self.pane.scroll_offset = offset;
+    }
+
+    /// Returns to the parent of the current subpage, selecting the subpage that was left.
+    pub fn leave_subpage(&mut self) {
+        let Some(index) = self.pane.subpage_path.pop() else {
+            debug!("Already on a top-level page, can't drill out");
+            return;
+        };
+
+        self.pane.selected_entry = index;
+    }
+
+    /// Returns the rows of the current page: its subpages, the visible entries without a section
+    /// and then each section followed by its entries, unless it is collapsed.
+    ///
+    /// Sections are listed in the order they first appear on the page.
+    pub fn rows(&self) -> Vec<PageRow<'_>> {
+        self.rows_in(&self.pane)
+    }
+
+    /// Collapses or expands the selected section, returning `false` if no section is selected.
+    pub fn toggle_selected_section(&mut self) -> bool {
+        let name = match self.rows().get(self.pane.selected_entry) {
+            Some(PageRow::Section { name, .. }) => name.to_string(),
+            _ => return false,
+        };
+
+        let key = (self.breadcrumbs().join(PAGE_PATH_SEPARATOR), name);
+        if !self.collapsed_sections.remove(&key) {
+            self.collapsed_sections.insert(key);
+        }
+        true
+    }
+
+    /// Returns the order the current page is shown in for this session, which doesn't change the config
+    ///
+    /// `Manual` is the order of the config, already sorted by the configured sort order of the page.
+    pub fn current_sort_order(&self) -> SortOrder {
+        self.sort_order_in(&self.pane)
+    }
+
+    /// Cycles the order the current page is shown in between the order of the config, by name and by shortcut.
+    pub fn cycle_sort_order(&mut self) {
+        let next = match self.current_sort_order() {
+            SortOrder::Manual => SortOrder::Alphabetical,
+            SortOrder::Alphabetical => SortOrder::Shortcut,
+            SortOrder::Shortcut => SortOrder::Manual,
+        };
+
+        let path = self.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+        if next == SortOrder::Manual {
+            self.sort_overrides.remove(&path);
+        } else {
+            self.sort_overrides.insert(path, next);
+        }
+        self.clamp_selection();
+    }
+
+    /// Returns the text the entries of the current page are filtered by, if any
+    pub fn current_filter(&self) -> Option<&str> {
+        self.filters
+            .get(&self.breadcrumbs().join(PAGE_PATH_SEPARATOR))
+            .map(String::as_str)
+    }
+
+    /// Starts typing into the filter of the current page, continuing an applied filter.
+    pub fn open_filter(&mut self) {
+        self.mode = Mode::FilterInput;
+    }
+
+    /// Adds a character to the filter of the current page, selecting the first remaining row.
+    pub fn push_filter_char(&mut self, c: char) {
+        let path = self.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+        self.filters.entry(path).or_default().push(c);
+        self.pane.selected_entry = 0;
+    }
+
+    /// Removes the last character of the filter of the current page, selecting the first row.
+    pub fn pop_filter_char(&mut self) {
+        let path = self.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+        if let Some(filter) = self.filters.get_mut(&path) {
+            filter.pop();
+        }
+        self.pane.selected_entry = 0;
+    }
+
+    /// Keeps the typed filter applied to the current page and stops typing, removing the filter if it is empty.
+    pub fn confirm_filter(&mut self) {
+        let path = self.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+        if self.filters.get(&path).is_some_and(String::is_empty) {
+            self.filters.remove(&path);
+        }
+        self.mode = Mode::Normal;
+    }
+
+    /// Shows all entries of the current page again and stops typing, returning `false` if no filter was applied.
+    pub fn clear_filter(&mut self) -> bool {
+        self.mode = Mode::Normal;
+        let path = self.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+        let cleared = self.filters.remove(&path).is_some();
+        self.clamp_selection();
+        cleared
+    }
+
+    /// Opens the page switcher, listing all pages.
+    pub fn open_page_switcher(&mut self) {
+        self.mode = Mode::PageSwitcher(PageSwitcher::default());
+    }
+
+    /// Returns the paths of the pages of the config and their subpages matching the query, the best match first.
+    ///
+    /// Without a query, all pages are listed in their order.
+    pub fn page_switcher_matches(&self, query: &str) -> Vec<String> {
+        if query.is_empty() {
+            let mut paths = vec![];
+            for page in &self.config.pages {
+                collect_page_paths(page, &page.name, &mut paths);
+            }
+            return paths;
+        }
+
+        let mut candidates = vec![];
+        for page in &self.config.pages {
+            collect_page_scores(page, &page.name, query, &mut candidates);
+        }
+        // Sorting is stable, so pages matching equally well keep their order
+        candidates.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        candidates.into_iter().map(|(path, _)| path).collect()
+    }
+
+    /// Adds a character to the query of the page switcher, selecting the best match.
+    pub fn push_page_switcher_char(&mut self, c: char) {
+        if let Mode::PageSwitcher(switcher) = &mut self.mode {
+            switcher.query.push(c);
+            switcher.selected = 0;
+        }
+    }
+
+    /// Removes the last character of the query of the page switcher, selecting the best match.
+    pub fn pop_page_switcher_char(&mut self) {
+        if let Mode::PageSwitcher(switcher) = &mut self.mode {
+            switcher.query.pop();
+            switcher.selected = 0;
+        }
+    }
+
+    /// Moves the selection of the page switcher to the next or previous match, stopping at the ends.
+    pub fn move_page_switcher_selection(&mut self, downwards: bool) {
+        let Mode::PageSwitcher(switcher) = &self.mode else {
+            return;
+        };
+        let number_of_matches = self.page_switcher_matches(&switcher.query).len();
+
+        if let Mode::PageSwitcher(switcher) = &mut self.mode {
+            switcher.selected = if downwards {
+                (switcher.selected + 1).min(number_of_matches.saturating_sub(1))
+            } else {
+                switcher.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Closes the page switcher and opens the selected page, if any page matches.
+    pub fn open_selected_switcher_page(&mut self) {
+        let Mode::PageSwitcher(switcher) = &self.mode else {
+            return;
+        };
+        let path = self
+            .page_switcher_matches(&switcher.query)
+            .into_iter()
+            .nth(switcher.selected);
+
+        self.mode = Mode::Normal;
+        if let Some(path) = path {
+            self.open_page(&path);
+        }
+    }
+
+    /// Opens the command line at the bottom, see [`Mode::CommandLine`].
+    pub fn open_command_line(&mut self) {
+        self.mode = Mode::CommandLine(CommandLine::default());
+    }
+
+    /// Returns the paths of the pages of the config and their subpages starting with the prefix, ignoring case.
+    pub fn command_line_completions(&self, prefix: &str) -> Vec<String> {
+        let mut paths = vec![];
+        for page in &self.config.pages {
+            collect_page_paths(page, &page.name, &mut paths);
+        }
+
+        let prefix = prefix.to_lowercase();
+        paths.retain(|path| path.to_lowercase().starts_with(&prefix));
+        paths
+    }
+
+    /// Adds a character to the command line.
+    pub fn push_command_line_char(&mut self, c: char) {
+        if let Mode::CommandLine(line) = &mut self.mode {
+            line.input.push(c);
+            line.cycled_prefix = None;
+        }
+    }
+
+    /// Removes the last character of the command line.
+    pub fn pop_command_line_char(&mut self) {
+        if let Mode::CommandLine(line) = &mut self.mode {
+            line.input.pop();
+            line.cycled_prefix = None;
+        }
+    }
+
+    /// Completes the command line to the pages starting with what was typed.
+    ///
+    /// A single page is completed in full, several pages as far as their paths are the same.
+    /// Once nothing is left to complete, each further Tab shows the next or previous of these pages.
+    pub fn complete_command_line(&mut self, forward: bool) {
+        let Mode::CommandLine(line) = &self.mode else {
+            return;
+        };
+        let prefix = line.cycled_prefix.as_ref().unwrap_or(&line.input).clone();
+        let completions = self.command_line_completions(&prefix);
+        if completions.is_empty() {
+            return;
+        }
+
+        let Mode::CommandLine(line) = &mut self.mode else {
+            return;
+        };
+        if line.cycled_prefix.is_none() {
+            let common = common_prefix(&completions);
+            if completions.len() == 1 || common.chars().count() > line.input.chars().count() {
+                line.input = common;
+                return;
+            }
+            line.cycled_prefix = Some(prefix);
+        }
+
+        let next = match completions.iter().position(|path| *path == line.input) {
+            Some(index) if forward => (index + 1) % completions.len(),
+            Some(index) => (index + completions.len() - 1) % completions.len(),
+            None if forward => 0,
+            None => completions.len() - 1,
+        };
+        line.input = completions[next].clone();
+    }
+
+    /// Returns the path of the page the command line jumps to, or `None` if no single page matches.
+    ///
+    /// A completed path is taken as it is, anything else is matched like `--page`.
+    pub fn command_line_target(&self, input: &str) -> Option<String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+        self.command_line_completions(input)
+            .into_iter()
+            .find(|path| path.eq_ignore_ascii_case(input))
+            .or_else(|| match_page(&self.config.pages, input).ok())
+    }
+
+    /// Closes the command line and jumps to the page with the typed name, unless no single page matches.
+    pub fn confirm_command_line(&mut self) {
+        let Mode::CommandLine(line) = &self.mode else {
+            return;
+        };
+        if let Some(path) = self.command_line_target(&line.input) {
+            self.mode = Mode::Normal;
+            self.open_page(&path);
+        }
+    }
+
+    /// Returns the text searched for across all pages, if any
+    pub fn current_search(&self) -> Option<&str> {
+        self.search.as_deref()
+    }
+
+    /// Returns the number of results of the search across all pages
+    pub fn number_of_search_results(&self) -> usize {
+        match (&self.search, self.virtual_pages.first()) {
+            (Some(_), Some(results)) => results.sources.len(),
+            _ => 0,
+        }
+    }
+
+    /// Starts typing into the search across all pages, continuing a previous search, and shows its results.
+    pub fn open_search(&mut self) {
+        if self.search.is_none() {
+            self.search_origin = Some(self.breadcrumbs().join(PAGE_PATH_SEPARATOR));
+            self.search = Some(String::new());
+        }
+        self.show_search_results();
+        self.mode = Mode::SearchInput;
+    }
+
+    /// Adds a character to the search across all pages, showing the updated results.
+    pub fn push_search_char(&mut self, c: char) {
+        if let Some(search) = &mut self.search {
+            search.push(c);
+        }
+        self.show_search_results();
+    }
+
+    /// Removes the last character of the search across all pages, showing the updated results.
+    pub fn pop_search_char(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.pop();
+        }
+        self.show_search_results();
+    }
+
+    /// Keeps the results page of the typed search and stops typing, removing the search if it is empty.
+    pub fn confirm_search(&mut self) {
+        self.mode = Mode::Normal;
+        if self.search.as_ref().is_some_and(String::is_empty) {
+            self.clear_search();
+        }
+    }
+
+    /// Removes the results page of the search across all pages and stops typing, returning `false` if there was no search.
+    ///
+    /// If the results page was shown, the page the search was started on is opened again.
+    pub fn clear_search(&mut self) -> bool {
+        self.mode = Mode::Normal;
+        if self.search.is_none() {
+            return false;
+        }
+
+        let on_results = self.is_search_page();
+        self.search = None;
+        self.refresh_virtual_pages();
+        if let (true, Some(origin)) = (on_results, self.search_origin.take()) {
+            self.open_page(&origin);
+        }
+        true
+    }
+
+    /// Returns `true` if the current page lists the results of the search across all pages
+    pub fn is_search_page(&self) -> bool {
+        // The results page is always the first page while searching
+        self.search.is_some() && self.pane.page_number == 0 && self.pane.subpage_path.is_empty()
+    }
+
+    /// Returns the selected row of the current page, or `None` if the page has no rows
+    ///
+    /// Rows are the subpages followed by the visible entries.
+    pub fn selected_entry(&self) -> Option<usize> {
+        (self.number_of_rows() > 0).then_some(self.pane.selected_entry)
+    }
+
+    /// Returns `true` if the names of entries on the current page are shown in their own column
+    ///
+    /// Pages can decide this for themselves, otherwise the global setting applies.
+    pub fn shows_names(&self) -> bool {
+        self.shows_names_in(&self.pane)
+    }
+
+    /// Returns `true` if hidden entries are currently revealed
+    pub fn shows_hidden_entries(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// Toggles whether hidden entries are revealed.
+    pub fn toggle_hidden_entries(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        self.clamp_selection();
+    }
+
+    /// Returns `true` if the content of the entry is shown, which secret entries only are while revealed
+    pub fn shows_content(&self, entry: &Entry) -> bool {
+        !entry.secret
+            || (self.reveal_secret
+                && self
+                    .selected_page_entry()
+                    .is_some_and(|selected| std::ptr::eq(selected, entry)))
+    }
+
+    /// Reveals the content of the selected entry if it is secret, until the next key is pressed.
+    pub fn reveal_selected_secret(&mut self) {
+        self.reveal_secret = self.selected_page_entry().is_some_and(|entry| entry.secret);
+    }
+
+    /// Masks the content of secret entries again.
+    pub fn hide_secrets(&mut self) {
+        self.reveal_secret = false;
+    }
+
+    /// Returns `true` if long descriptions are wrapped instead of truncated
+    pub fn wraps_descriptions(&self) -> bool {
+        self.wrap_descriptions
+    }
+
+    /// Toggles between wrapping and truncating long descriptions, for the rest of the session.
+    pub fn toggle_wrap_descriptions(&mut self) {
+        self.wrap_descriptions = !self.wrap_descriptions;
+    }
+
+    /// Returns `true` if the keyboard diagram is shown
+    pub fn shows_keyboard(&self) -> bool {
+        self.show_keyboard
+    }
+
+    /// Toggles the keyboard diagram below the page.
+    pub fn toggle_keyboard(&mut self) {
+        self.show_keyboard = !self.show_keyboard;
+    }
+
+    /// Returns the image of the selected entry, or of the current page if the entry has none
+    pub fn current_image(&self) -> Option<&str> {
+        self.selected_page_entry()
+            .and_then(|entry| entry.image.as_deref())
+            .or_else(|| self.get_current_page().ok()?.image.as_deref())
+    }
+
+    /// Returns how the terminal shows images
+    pub fn graphics(&self) -> Graphics {
+        self.graphics
+    }
+
+    /// Sets how the terminal shows images, which depends on the terminal recall runs in.
+    pub fn set_graphics(&mut self, graphics: Graphics) {
+        self.graphics = graphics;
+    }
+
+    /// Returns the selected entry of the current page, or `None` if a subpage or section is selected
+    pub fn selected_page_entry(&self) -> Option<&Entry> {
+        match self.rows().get(self.pane.selected_entry) {
+            Some(PageRow::Entry(_, entry)) => Some(entry),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries of the current page in the order they are shown, along with their index on the page
+    pub fn visible_entries(&self) -> Vec<(usize, &Entry)> {
+        self.visible_entries_in(&self.pane)
+    }
+
+    /// Moves the selection to the next entry, unless already on the last entry.
+    pub fn select_next_entry(&mut self) {
+        if self.pane.selected_entry + 1 >= self.number_of_rows() {
+            debug!("Selection is on last entry, can't move down");
+            return;
+        }
+        self.pane.selected_entry += 1;
+    }
+
+    /// Moves the selection to the previous entry, unless already on the first entry.
+    pub fn select_previous_entry(&mut self) {
+        if self.pane.selected_entry == 0 {
+            debug!("Selection is on first entry, can't move up");
+            return;
+        }
+        self.pane.selected_entry -= 1;
+    }
+
+    /// Deletes the selected entry from the current page.
+    pub fn delete_selected_entry(&mut self) {
+        let Some(index) = self.selected_entry_index() else {
+            debug!("No entry selected, nothing to delete");
+            return;
+        };
+
+        match self.edit_current_page(|page| page.entries.remove(index)) {
+            Some(entry) => debug!("Deleted entry {}", entry.name),
+            None => debug!("Entries of virtual pages can't be deleted"),
+        }
+    }
+
+    /// Opens the form adding an entry to the current page, unless its entries can't be edited.
+    pub fn open_add_entry_form(&mut self) {
+        if !self.is_current_page_editable() {
+            debug!("Entries can't be added to the current page");
+            return;
+        }
+        self.mode = Mode::EntryInput(EntryForm {
+            original: None,
+            values: Default::default(),
+            field: 0,
+            secret: false,
+        });
+    }
+
+    /// Opens the form changing the selected entry, unless no entry of an editable page is selected.
+    ///
+    /// Encrypted entries can't be changed, as their content is only decrypted for this session.
+    pub fn open_edit_entry_form(&mut self) {
+        if self.selected_entry_index().is_none() || !self.is_current_page_editable() {
+            debug!("No entry of an editable page selected, nothing to change");
+            return;
+        }
+        let Some(entry) = self.selected_page_entry() else {
+            return;
+        };
+        if entry.encrypted.is_some() {
+            debug!("Entry {} is encrypted, can't change it", entry.name);
+            return;
+        }
+
+        self.mode = Mode::EntryInput(EntryForm {
+            original: Some(entry.name.clone()),
+            values: [
+                entry.name.clone(),
+                entry.content.join(&ENTRY_KEY_SEPARATOR.to_string()),
+                entry.description.clone(),
+            ],
+            field: 0,
+            secret: entry.secret,
+        });
+    }
+
+    /// Returns the message id of the reason why the entry typed into the form can't be saved, if any.
+    pub fn entry_form_error(&self, form: &EntryForm) -> Option<&'static str> {
+        let name = form.name();
+        let taken = Some(name) != form.original.as_deref()
+            && self
+                .get_current_page()
+                .is_ok_and(|page| page.entries.iter().any(|entry| entry.name == name));
+
+        if name.is_empty() {
+            Some("entry-name-empty")
+        } else if form.keys().is_empty() {
+            Some("entry-keys-empty")
+        } else if taken {
+            Some("entry-name-taken")
+        } else {
+            None
+        }
+    }
+
+    /// Adds the entry typed into the form to the current page or changes the selected entry, and closes the form.
+    ///
+    /// Does nothing if the entry can't be saved, see [`App::entry_form_error`].
+    /// A changed entry keeps its star and usage, even if it is renamed.
+    pub fn confirm_entry_form(&mut self) {
+        let Mode::EntryInput(form) = &self.mode else {
+            return;
+        };
+        if let Some(error) = self.entry_form_error(form) {
+            debug!("Can't save entry {}: {}", form.name(), error);
+            return;
+        }
+        let form = form.clone();
+        let (name, content, description) = (
+            form.name().to_string(),
+            form.keys(),
+            form.values[2].trim().to_string(),
+        );
+
+        let saved = match &form.original {
+            Some(original) => {
+                let page = self.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+                let changed = self.edit_current_page(|page| {
+                    let entry = page
+                        .entries
+                        .iter_mut()
+                        .find(|entry| entry.name == *original)?;
+                    entry.name = name.clone();
+                    entry.content = content;
+                    entry.description = description;
+                    page.sort.sort(&mut page.entries);
+                    page.entries.iter().position(|entry| entry.name == name)
+                });
+                if name != *original {
+                    self.user_state.rename_entry(&page, original, &name);
+                    self.unsaved_state = true;
+                }
+                changed
+            }
+            None => self.edit_current_page(|page| {
+                page.entries.push(Entry {
+                    name: name.clone(),
+                    content,
+                    description,
+                    ..Entry::default()
+                });
+                page.sort.sort(&mut page.entries);
+                page.entries.iter().position(|entry| entry.name == name)
+            }),
+        };
+
+        self.mode = Mode::Normal;
+        match saved.flatten() {
+            Some(index) => {
+                debug!("Saved entry {}", name);
+                self.select_entry_row(index);
+            }
+            None => debug!("Entries of the current page can't be edited"),
+        }
+    }
+
+    /// Moves the selected entry above the visible one before it, keeping it selected.
+    pub fn move_selected_entry_up(&mut self) {
+        self.swap_selected_entry(false);
+    }
+
+    /// Moves the selected entry below the visible one after it, keeping it selected.
+    pub fn move_selected_entry_down(&mut self) {
+        self.swap_selected_entry(true);
+    }
+
+    /// Starts typing the page to move the selected entry to, unless no entry of an editable page is selected.
+    pub fn open_move_prompt(&mut self) {
+        if self.selected_entry_index().is_none() || self.config_page_index().is_none() {
+            debug!("No entry of a config page selected, nothing to move");
+            return;
+        }
+        if self.get_current_page().is_ok_and(Page::is_generated) {
+            debug!("Entries of the current page are generated, can't move them");
+            return;
+        }
+        self.mode = Mode::MoveEntry(String::new());
+    }
+
+    /// Returns the path of the page matching the query that the selected entry would be moved to,
+    /// or the message id of the reason why it can't be moved there.
+    pub fn move_target(&self, query: &str) -> Result<String, &'static str> {
+        if query.trim().is_empty() {
+            return Err("move-target-empty");
+        }
+        let Some((target, page)) = match_page(&self.config.pages, query)
+            .ok()
+            .and_then(|target| Some((target.clone(), page_at(&self.config.pages, &target)?)))
+        else {
+            return Err("move-target-unknown");
+        };
+
+        let name = self.selected_page_entry().map(|entry| entry.name.as_str());
+        if target == self.breadcrumbs().join(PAGE_PATH_SEPARATOR) {
+            Err("move-target-same")
+        } else if page.is_generated() {
+            Err("move-target-generated")
+        } else if page
+            .entries
+            .iter()
+            .any(|entry| Some(entry.name.as_str()) == name)
+        {
+            Err("move-target-taken")
+        } else {
+            Ok(target)
+        }
+    }
+
+    /// Moves the selected entry to the page matching the query, carrying its star and usage along.
+    ///
+    /// Does nothing if it can't be moved there, see [`App::move_target`].
+    pub fn move_selected_entry_to(&mut self, query: &str) {
+        let target = match self.move_target(query) {
+            Ok(target) => target,
+            Err(error) => {
+                debug!("Can't move entry to {}: {}", query, error);
+                return;
+            }
+        };
+        let Some((index, key)) = self
+            .selected_entry_index()
+            .and_then(|index| Some((index, self.entry_key(index)?)))
+        else {
+            debug!("No entry selected, nothing to move");
+            return;
+        };
+        let Some(entry) = self.edit_current_page(|page| page.entries.remove(index)) else {
+            debug!("Entries of virtual pages can't be moved");
+            return;
+        };
+        let Some((page_index, subpage_path)) = find_page(&self.config.pages, &target) else {
+            return;
+        };
+
+        debug!("Moving {} from page {} to {}", key.entry, key.page, target);
+        let mut page = &mut self.config.pages[page_index];
+        for index in subpage_path {
+            page = &mut page.subpages[index];
+        }
+        page.entries.push(entry);
+        page.sort.sort(&mut page.entries);
+
+        self.user_state.move_entry(&key.page, &key.entry, &target);
+        self.unsaved_state = true;
+        self.refresh_virtual_pages();
+        self.notice = Some("notice-moved");
+    }
+
+    /// Returns the key identifying the entry at the given index on the current page
+    ///
+    /// Entries on virtual pages are identified by their original entry.
+    pub fn entry_key(&self, index: usize) -> Option<EntryKey> {
+        self.entry_key_in(&self.pane, index)
+    }
+
+    /// Returns `true` if the entry at the given index on the current page is starred
+    pub fn is_favorite(&self, index: usize) -> bool {
+        self.is_favorite_in(&self.pane, index)
+    }
+
+    /// Stars the selected entry, or removes its star if it already has one.
+    pub fn toggle_favorite(&mut self) {
+        let Some(key) = self
+            .selected_entry_index()
+            .and_then(|index| self.entry_key(index))
+        else {
+            debug!("No entry selected, nothing to star");
+            return;
+        };
+
+        let favorites = &mut self.user_state.favorites;
+        if let Some(position) = favorites.iter().position(|favorite| *favorite == key) {
+            debug!("Removing star from {} on page {}", key.entry, key.page);
+            favorites.remove(position);
+        } else {
+            debug!("Starring {} on page {}", key.entry, key.page);
+            favorites.push(key);
+        }
+
+        self.unsaved_state = true;
+        self.refresh_virtual_pages();
+    }
+
+    /// Moves the selected entry to the front of the recent entries, keeping it selected if the recent page is shown.
+    fn remember_selected_entry(&mut self) {
+        let Some(key) = self
+            .selected_entry_index()
+            .and_then(|index| self.entry_key(index))
+        else {
+            return;
+        };
+
+        self.user_state.remember_recent(key.clone());
+        self.unsaved_state = true;
+        self.refresh_virtual_pages();
+
+        // Entries of virtual pages move when they are remembered, so the selection follows them
+        if let Some(virtual_page) = self.virtual_pages.get(self.pane.page_number) {
+            if let Some(position) = virtual_page
+                .sources
+                .iter()
+                .position(|source| *source == key)
+            {
+                self.pane.selected_entry = position;
+            }
+        }
+    }
+
+    /// Returns `true` if the user state has changed since it was last saved
+    pub fn has_unsaved_state(&self) -> bool {
+        self.unsaved_state
+    }
+
+    /// Returns `true` if lookups were counted since the user state was last saved
+    pub fn has_unsaved_usage(&self) -> bool {
+        self.unsaved_usage
+    }
+
+    /// Marks the current user state as written to disk
+    pub fn mark_state_saved(&mut self) {
+        self.unsaved_state = false;
+        self.unsaved_usage = false;
+    }
+
+    /// Counts a lookup of the current page when it was opened and of the selected entry when it was selected.
+    ///
+    /// The entry selected by opening a page wasn't chosen, so it isn't counted. Virtual pages are only counted
+    /// through their entries, which count for their original entry.
+    pub fn track_usage(&mut self) {
+        let path = self.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+        let entry = self
+            .selected_entry_index()
+            .and_then(|index| self.entry_key(index));
+
+        let (page_changed, entry_changed) = match &self.viewed {
+            Some((viewed_path, viewed_entry)) => (*viewed_path != path, *viewed_entry != entry),
+            None => (true, true),
+        };
+
+        if page_changed && self.pane.page_number >= self.virtual_pages.len() {
+            debug!("Counting lookup of page {}", path);
+            self.user_state.record_usage(&path, None, false);
+            self.unsaved_usage = true;
+        } else if !page_changed && entry_changed {
+            if let Some(key) = &entry {
+                debug!("Counting lookup of {} on page {}", key.entry, key.page);
+                self.user_state
+                    .record_usage(&key.page, Some(&key.entry), false);
+                self.unsaved_usage = true;
+            }
+        }
+
+        self.viewed = Some((path, entry));
+    }
+
+    /// Returns `true` if the selected row is an entry that is starred as a favorite
+    pub fn is_selected_favorite(&self) -> bool {
+        self.selected_entry_index()
+            .is_some_and(|index| self.is_favorite(index))
+    }
+
+    /// Shows the selected entry in full in a popup, unless a subpage or section is selected.
+    pub fn open_details(&mut self) {
+        if self.selected_page_entry().is_some() {
+            self.mode = Mode::Details;
+        }
+    }
+
+    /// Sets the names of all workspaces opened in this session and the index of the workspace of this app.
+    pub fn set_workspaces(&mut self, names: Vec<String>, index: usize) {
+        self.workspaces = names;
+        self.workspace = index;
+    }
+
+    /// Returns the names of all workspaces, empty unless several configs are opened.
+    pub fn workspaces(&self) -> &[String] {
+        &self.workspaces
+    }
+
+    /// Returns the name of the workspace of this app, or `None` unless several configs are opened.
+    pub fn workspace_name(&self) -> Option<&str> {
+        self.workspaces.get(self.workspace).map(String::as_str)
+    }
+
+    /// Opens the workspace switcher, selecting the workspace of this app, unless there are no other workspaces.
+    pub fn open_workspaces(&mut self) {
+        if self.workspaces.len() > 1 {
+            self.mode = Mode::Workspaces(self.workspace);
+        }
+    }
+
+    /// Moves the selection of the workspace switcher to the next or previous workspace, wrapping around at the ends.
+    pub fn move_workspace_selection(&mut self, downwards: bool) {
+        let number_of_workspaces = self.workspaces.len();
+
+        if let Mode::Workspaces(selected) = &mut self.mode {
+            *selected = if downwards {
+                (*selected + 1) % number_of_workspaces
+            } else {
+                (*selected + number_of_workspaces - 1) % number_of_workspaces
+            };
+        }
+    }
+
+    /// Closes the workspace switcher and switches to the workspace with the given index, see [`App::take_workspace_switch`].
+    pub fn switch_workspace(&mut self, index: usize) {
+        self.mode = Mode::Normal;
+        if index != self.workspace && index < self.workspaces.len() {
+            self.workspace_switch = Some(index);
+        }
+    }
+
+    /// Returns the index of the workspace to switch to, if any, and clears it.
+    pub fn take_workspace_switch(&mut self) -> Option<usize> {
+        self.workspace_switch.take()
+    }
+
+    /// Opens the page index, selecting the current page.
+    ///
+    /// The group of the current page is expanded, so it can be selected.
+    pub fn open_page_index(&mut self) {
+        let current = self.pane.page_number;
+        if let Some(group) = self
+            .top_level_pages()
+            .get(current)
+            .and_then(|page| page.group.clone())
+        {
+            self.collapsed_groups.remove(&group);
+        }
+
+        let selected = self
+            .page_index_rows()
+            .iter()
+            .position(|row| matches!(row, IndexRow::Page { number, .. } if *number == current))
+            .unwrap_or(0);
+        self.mode = Mode::PageIndex(selected);
+    }
+
+    /// Returns the rows of the page index, the top-level pages with a header before each run of pages sharing a group
+    pub fn page_index_rows(&self) -> Vec<IndexRow<'_>> {
+        let pages = self.top_level_pages();
+
+        let mut rows = vec![];
+        for (number, page) in pages.iter().enumerate() {
+            let group = page.group.as_deref();
+            let starts_group = number == 0 || pages[number - 1].group.as_deref() != group;
+            if let (Some(name), true) = (group, starts_group) {
+                rows.push(IndexRow::Group {
+                    name,
+                    collapsed: self.collapsed_groups.contains(name),
+                    pages: pages[number..]
+                        .iter()
+                        .take_while(|page| page.group.as_deref() == group)
+                        .count(),
+                });
+            }
+            if group.is_some_and(|group| self.collapsed_groups.contains(group)) {
+                continue;
+            }
+            rows.push(IndexRow::Page {
+                number,
+                page,
+                entries: count_entries(slice::from_ref(*page)),
+            });
+        }
+        rows
+    }
+
+    /// Moves the selection of the page index to the next or previous row, stopping at the ends.
+    pub fn move_page_index_selection(&mut self, downwards: bool) {
+        let number_of_rows = self.page_index_rows().len();
+
+        if let Mode::PageIndex(selected) = &mut self.mode {
+            *selected = if downwards {
+                (*selected + 1).min(number_of_rows.saturating_sub(1))
+            } else {
+                selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Activates the selected row of the page index: opens a selected page or collapses or expands a selected group.
+    pub fn activate_page_index_row(&mut self) {
+        let Mode::PageIndex(selected) = self.mode else {
+            return;
+        };
+
+        match self.page_index_rows().get(selected) {
+            Some(IndexRow::Group { name, .. }) => {
+                let name = name.to_string();
+                if !self.collapsed_groups.remove(&name) {
+                    self.collapsed_groups.insert(name);
+                }
+            }
+            Some(IndexRow::Page { number, .. }) => {
+                let number = *number;
+                self.go_to_page(number);
+                self.mode = Mode::Normal;
+            }
+            None => {}
+        }
+    }
+
+    /// Opens the help, listing the keys of all actions.
+    pub fn open_help(&mut self) {
+        self.mode = Mode::Help(0);
+    }
+
+    /// Moves the selection of the help to the next or previous action, stopping at the ends.
+    pub fn move_help_selection(&mut self, downwards: bool) {
+        let number_of_rows = self.config.keybinds.bindings().len();
+
+        if let Mode::Help(selected) = &mut self.mode {
+            *selected = if downwards {
+                (*selected + 1).min(number_of_rows.saturating_sub(1))
+            } else {
+                selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Switches to the stats view, selecting the most looked up entry.
+    pub fn open_stats_view(&mut self) {
+        self.mode = Mode::Stats(0);
+    }
+
+    /// Returns how often entries were looked up, the most looked up first
+    pub fn entry_usage(&self) -> Vec<&Usage> {
+        self.user_state.most_used(false)
+    }
+
+    /// Moves the selection of the stats view to the next or previous row, stopping at the ends.
+    pub fn move_stats_selection(&mut self, downwards: bool) {
+        let number_of_rows = self.entry_usage().len();
+
+        if let Mode::Stats(selected) = &mut self.mode {
+            *selected = if downwards {
+                (*selected + 1).min(number_of_rows.saturating_sub(1))
+            } else {
+                selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Leaves the stats view and opens the page of the selected entry, selecting it.
+    pub fn open_selected_usage(&mut self) {
+        let Mode::Stats(selected) = self.mode else {
+            return;
+        };
+
+        let target = self
+            .entry_usage()
+            .get(selected)
+            .map(|usage| (usage.page.clone(), usage.entry.clone()));
+
+        self.mode = Mode::Normal;
+        if let Some((path, Some(name))) = target {
+            if self.open_page(&path) {
+                let index = self
+                    .get_current_page()
+                    .ok()
+                    .and_then(|page| page.entries.iter().position(|entry| entry.name == name));
+                if let Some(index) = index {
+                    self.select_entry_row(index);
+                }
+            }
+        }
+    }
+
+    /// Returns the data kept between sessions
+    pub fn user_state(&self) -> &UserState {
+        &self.user_state
+    }
+
+    /// Switches to the all-pages view, selecting its first row.
+    pub fn open_all_pages_view(&mut self) {
+        self.mode = Mode::AllPages(0);
+    }
+
+    /// Returns all pages of the config including subpages as sections, in the order they appear in the config
+    pub fn all_pages_sections(&self) -> Vec<PageSection<'_>> {
+        let mut sections = vec![];
+        for page in &self.config.pages {
+            self.collect_sections(page, vec![], &mut sections);
+        }
+        sections
+    }
+
+    /// Moves the selection of the all-pages view to the next or previous row, stopping at the ends.
+    pub fn move_all_pages_selection(&mut self, downwards: bool) {
+        let number_of_rows: usize = self
+            .all_pages_sections()
+            .iter()
+            .map(|section| 1 + section.entries.len())
+            .sum();
+
+        if let Mode::AllPages(selected) = &mut self.mode {
+            *selected = if downwards {
+                (*selected + 1).min(number_of_rows.saturating_sub(1))
+            } else {
+                selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Leaves the all-pages view and opens the page the selected row belongs to.
+    pub fn open_selected_section(&mut self) {
+        let Mode::AllPages(selected) = self.mode else {
+            return;
+        };
+
+        let mut first_row = 0;
+        let mut target = None;
+        for section in self.all_pages_sections() {
+            let rows = 1 + section.entries.len();
+            if selected < first_row + rows {
+                // The header row selects the first entry as well
+                let position = (selected - first_row).saturating_sub(1);
+                target = Some((section.path.join(PAGE_PATH_SEPARATOR), position));
+                break;
+            }
+            first_row += rows;
+        }
+
+        self.mode = Mode::Normal;
+        if let Some((path, position)) = target {
+            if self.open_page(&path) {
+                // Sections are listed in the order of the page, regardless of sorting and sections
+                let index = self.get_current_page().ok().and_then(|page| {
+                    page.entries
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, entry)| self.show_hidden || !entry.hidden)
+                        .nth(position)
+                        .map(|(index, _)| index)
+                });
+                if let Some(index) = index {
+                    self.select_entry_row(index);
+                }
+            }
+        }
+    }
+
+    /// Returns the message id of the reason why the given name can't be used for a new page, or `None` if it can
+    pub fn page_name_error(&self, name: &str) -> Option<&'static str> {
+        if name.trim().is_empty() {
+            Some("page-name-empty")
+        } else if name == RECALL_TABLE_NAME {
+            Some("page-name-reserved")
+        } else if self.config.pages.iter().any(|page| page.name == name) {
+            Some("page-name-taken")
+        } else {
+            None
+        }
+    }
+
+    /// Appends a new, empty page with the given name and switches to it.
+    ///
+    /// Does nothing if the name can't be used, see [`App::page_name_error`].
+    pub fn create_page(&mut self, name: String) {
+        if let Some(error) = self.page_name_error(&name) {
+            debug!("Can't create page {}: {}", name, error);
+            return;
+        }
+
+        self.record_edit();
+        self.config.pages.push(Page {
+            name,
+            ..Page::default()
+        });
+        self.pane.page_number = self.number_of_pages() - 1;
+        self.pane.subpage_path.clear();
+        self.pane.selected_entry = 0;
+    }
+
+    /// Opens the settings popup, remembering the current theme and colors.
+    pub fn open_settings(&mut self) {
+        // The preview shows the configured colors that are adjusted and saved
+        self.session_colors = None;
+        self.mode = Mode::Settings(Settings {
+            field: SettingsField::Theme,
+            original: (
+                self.config.theme.clone(),
+                self.config.primary_color,
+                self.config.highlight_color,
+            ),
+        });
+    }
+
+    /// Selects the next setting in the settings popup, wrapping around at the end.
+    pub fn select_next_setting(&mut self) {
+        if let Mode::Settings(settings) = &mut self.mode {
+            settings.field = match settings.field {
+                SettingsField::Theme => SettingsField::PrimaryColor,
+                SettingsField::PrimaryColor => SettingsField::HighlightColor,
+                SettingsField::HighlightColor => SettingsField::Theme,
+            };
+        }
+    }
+
+    /// Selects the previous setting in the settings popup, wrapping around at the start.
+    pub fn select_previous_setting(&mut self) {
+        if let Mode::Settings(settings) = &mut self.mode {
+            settings.field = match settings.field {
+                SettingsField::Theme => SettingsField::HighlightColor,
+                SettingsField::PrimaryColor => SettingsField::Theme,
+                SettingsField::HighlightColor => SettingsField::PrimaryColor,
+            };
+        }
+    }
+
+    /// Cycles the selected setting forwards or backwards, applying it immediately as a preview.
+    ///
+    /// Selecting a theme replaces both colors with the ones of the theme.
+    pub fn adjust_setting(&mut self, forward: bool) {
+        let Mode::Settings(settings) = &self.mode else {
+            return;
+        };
+
+        match settings.field {
+            SettingsField::Theme => {
+                let current = self
+                    .config
+                    .theme
+                    .as_deref()
+                    .and_then(|name| THEMES.iter().position(|theme| theme.name == name));
+
+                let next = match (current, forward) {
+                    (None, true) => 0,
+                    (None, false) => THEMES.len() - 1,
+                    (Some(i), true) => (i + 1) % THEMES.len(),
+                    (Some(i), false) => (i + THEMES.len() - 1) % THEMES.len(),
+                };
+
+                let theme = &THEMES[next];
+                self.config.theme = Some(theme.name.to_string());
+                self.config.primary_color = Color::Indexed(theme.primary_color);
+                self.config.highlight_color = Color::Indexed(theme.highlight_color);
+            }
+            SettingsField::PrimaryColor => {
+                self.config.primary_color = cycle_color(self.config.primary_color, forward);
+            }
+            SettingsField::HighlightColor => {
+                self.config.highlight_color = cycle_color(self.config.highlight_color, forward);
+            }
+        }
+    }
+
+    /// Closes the settings popup and keeps the previewed settings, marking them to be saved.
+    pub fn confirm_settings(&mut self) {
+        self.mode = Mode::Normal;
+        self.unsaved_changes = true;
+    }
+
+    /// Closes the settings popup and restores the settings from before it was opened.
+    pub fn cancel_settings(&mut self) {
+        let mode = std::mem::replace(&mut self.mode, Mode::Normal);
+        if let Mode::Settings(Settings {
+            original: (theme, primary_color, highlight_color),
+            ..
+        }) = mode
+        {
+            self.config.theme = theme;
+            self.config.primary_color = primary_color;
+            self.config.highlight_color = highlight_color;
+        }
+    }
+
+    /// Reverts the most recent edit, if there is one.
+    pub fn undo(&mut self) {
+        let Some(pages) = self.history.undo.pop() else {
+            debug!("Nothing to undo");
+            return;
+        };
+
+        let current = std::mem::replace(&mut self.config.pages, pages);
+        self.history.redo.push(current);
+        self.after_history_change();
+    }
+
+    /// Reapplies the most recently undone edit, if there is one.
+    pub fn redo(&mut self) {
+        let Some(pages) = self.history.redo.pop() else {
+            debug!("Nothing to redo");
+            return;
+        };
+
+        let current = std::mem::replace(&mut self.config.pages, pages);
+        self.history.undo.push(current);
+        self.after_history_change();
+    }
+
+    /// Returns `true` if the pages have been edited since they were last saved
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved_changes
+    }
+
+    /// Marks the current state as written to disk
+    pub fn mark_saved(&mut self) {
+        self.unsaved_changes = false;
+        self.saved_pages = self.config.pages.clone();
+    }
+
+    /// Asks how to resolve the unsaved edits after another program changed the config, once no other prompt is open.
+    pub fn open_config_conflict(&mut self) {
+        if matches!(self.mode, Mode::Normal) {
+            self.mode = Mode::ConfigConflict;
+        }
+    }
+
+    /// Decides how to resolve the unsaved edits after another program changed the config, see [`App::take_conflict_resolution`].
+    pub fn resolve_config_conflict(&mut self, resolution: ConflictResolution) {
+        self.conflict_resolution = Some(resolution);
+        self.mode = Mode::Normal;
+    }
+
+    /// Returns the decided way of resolving the unsaved edits after another program changed the config, if any, and clears it.
+    pub fn take_conflict_resolution(&mut self) -> Option<ConflictResolution> {
+        self.conflict_resolution.take()
+    }
+
+    /// Asks for the config to be read again, see [`App::take_reload_request`].
+    pub fn request_reload(&mut self) {
+        self.reload_requested = true;
+    }
+
+    /// Returns `true` once if the config is about to be read again.
+    pub fn take_reload_request(&mut self) -> bool {
+        std::mem::take(&mut self.reload_requested)
+    }
+
+    /// Replaces the config by the one read again, including the settings, and the texts by those of its language.
+    ///
+    /// The pages are replaced like by [`App::reload_pages`], settings overridden for this session stay as they are.
+    pub fn reload_config(&mut self, mut config: Config, localizer: Localizer) {
+        let pages = std::mem::replace(&mut config.pages, std::mem::take(&mut self.config.pages));
+        self.plain |= config.plain;
+        self.config = config;
+        self.localizer = localizer;
+        self.pending_keys = PendingKeys::default();
+        self.reload_pages(pages);
+    }
+
+    /// Shows why the config couldn't be read again in a popup.
+    pub fn report_config_error(&mut self, error: String) {
+        self.mode = Mode::ConfigError(error);
+    }
+
+    /// Replaces the pages by those read from the config again, discarding unsaved edits.
+    ///
+    /// The discarded edits can be brought back by undoing the reload.
+    /// Both panes stay on the page they show if it still exists, even if pages were added, removed or moved in front of it.
+    pub fn reload_pages(&mut self, pages: Vec<Page>) {
+        let path = self.config_page_path(&self.pane);
+        let other_path = self
+            .split
+            .as_ref()
+            .and_then(|split| self.config_page_path(&split.other));
+
+        self.record_edit();
+        self.config.pages = pages;
+
+        let mut pane = std::mem::take(&mut self.pane);
+        self.move_pane_to(&mut pane, path.as_deref());
+        self.pane = pane;
+        if let Some(mut split) = self.split.take() {
+            self.move_pane_to(&mut split.other, other_path.as_deref());
+            self.split = Some(split);
+        }
+        self.after_history_change();
+        self.mark_saved();
+        self.notice = Some("notice-reloaded");
+    }
+
+    /// Merges the pages read from the config after another program changed it with the unsaved edits.
+    ///
+    /// Top-level pages edited here since the config was last saved replace their version read from the config,
+    /// pages created here are added and pages deleted here are left out. All other pages are taken as read.
+    pub fn merge_pages(&mut self, pages: Vec<Page>) {
+        let saved = |name: &str| self.saved_pages.iter().find(|page| page.name == name);
+        let edited: Vec<&Page> = self
+            .config
+            .pages
+            .iter()
+            .filter(|page| {
+                saved(&page.name).is_none_or(|saved| {
+                    serialize_pages(slice::from_ref(saved))
+                        != serialize_pages(slice::from_ref(page))
+                })
+            })
+            .collect();
+        let deleted = |name: &str| {
+            saved(name).is_some() && !self.config.pages.iter().any(|page| page.name == name)
+        };
+
+        let mut merged: Vec<Page> = pages
+            .iter()
+            .filter(|page| !deleted(&page.name))
+            .map(
+                |page| match edited.iter().find(|edited| edited.name == page.name) {
+                    Some(edited) => (*edited).clone(),
+                    None => page.clone(),
+                },
+            )
+            .collect();
+        for page in &edited {
+            if !merged.iter().any(|merged| merged.name == page.name) {
+                merged.push((*page).clone());
+            }
+        }
+        debug!(
+            "Merging {} edited pages into the changed config",
+            edited.len()
+        );
+
+        self.record_edit();
+        self.config.pages = merged;
+        self.saved_pages = pages;
+        self.after_history_change();
+        self.notice = Some("notice-merged");
+    }
+
+    /// Tells that the config changed by another program can't be read, so the pages stay as they were.
+    pub fn report_failed_reload(&mut self) {
+        self.notice = Some("notice-reload-failed");
+    }
+
+    /// Tells that the config changed by another program can't be read, so the edits can only overwrite it.
+    pub fn report_unreadable_config(&mut self) {
+        self.notice = Some("notice-config-unreadable");
+    }
+
+    /// Returns the URLs of all entries that are links, which are made clickable wherever they are shown
+    pub fn link_urls(&self) -> Vec<&str> {
+        let mut urls = vec![];
+        for page in &self.config.pages {
+            collect_link_urls(page, &mut urls);
+        }
+        urls
+    }
+
+    /// Returns the configuration in its current, possibly edited, state
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns `true` if the interface is shown as plain, linear text
+    pub fn is_plain(&self) -> bool {
+        self.plain
+    }
+
+    /// Shows the interface as plain, linear text for this session, without changing the configuration
+    pub fn enable_plain_mode(&mut self) {
+        self.plain = true;
+    }
+
+    /// Takes the configured idle action, either quitting or clearing the screen
+    pub fn idle(&mut self) {
+        match self.config.idle_action {
+            IdleAction::Quit => self.quit(QuitReason::IdleTimeout),
+            IdleAction::Blank => self.blanked = true,
+        }
+    }
+
+    /// Returns `true` if the screen is cleared after being idle
+    pub fn is_blanked(&self) -> bool {
+        self.blanked
+    }
+
+    /// Shows the interface again after it was cleared for being idle
+    pub fn wake(&mut self) {
+        self.blanked = false;
+    }
+
+    /// Returns the keyboard shortcuts are shown for
+    pub fn keyboard(&self) -> Keyboard {
+        self.config.keyboard.unwrap_or_else(Keyboard::current)
+    }
+
+    /// Returns `true` unless colors are disabled
+    pub fn has_colors(&self) -> bool {
+        !self.no_color
+    }
+
+    /// Disables colors for this session, without changing the configured colors
+    pub fn disable_colors(&mut self) {
+        self.no_color = true;
+    }
+
+    /// Uses the given colors for this session, without changing the configured colors
+    pub fn override_colors(&mut self, primary_color: Color, highlight_color: Color) {
+        self.session_colors = Some((primary_color, highlight_color));
+    }
+
+    /// Returns the texts of the interface in the configured language
+    pub fn localizer(&self) -> &Localizer {
+        &self.localizer
+    }
+
+    /// Returns the primary UI color, or the default color of the terminal if colors are disabled
+    pub fn primary_color(&self) -> Color {
+        if self.no_color {
+            Color::Reset
+        } else if let Some((primary_color, _)) = self.session_colors {
+            primary_color
+        } else {
+            self.config.primary_color
+        }
+    }
+
+    /// Returns the highlight UI color, or the default color of the terminal if colors are disabled
+    pub fn highlight_color(&self) -> Color {
+        if self.no_color {
+            Color::Reset
+        } else if let Some((_, highlight_color)) = self.session_colors {
+            highlight_color
+        } else {
+            self.config.highlight_color
+        }
+    }
+
+    /// Returns the number of rows on the current page, see [`App::rows`]
+    fn number_of_rows(&self) -> usize {
+        self.rows().len()
+    }
+
+    /// Returns the index of the selected entry on the current page, or `None` if no entry is selected
+    fn selected_entry_index(&self) -> Option<usize> {
+        match self.rows().get(self.pane.selected_entry) {
+            Some(PageRow::Entry(index, _)) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Selects the row of the entry with the given index on the current page, expanding its section if necessary.
+    fn select_entry_row(&mut self, index: usize) {
+        let section = self
+            .get_current_page()
+            .ok()
+            .and_then(|page| page.entries.get(index)?.section.clone());
+        if let Some(section) = section {
+            let path = self.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+            self.collapsed_sections.remove(&(path, section));
+        }
+
+        if let Some(row) = self
+            .rows()
+            .iter()
+            .position(|row| matches!(row, PageRow::Entry(i, _) if *i == index))
+        {
+            self.pane.selected_entry = row;
+        }
+    }
+
+    /// Moves the selected entry past the next or previous entry of its section, keeping it selected.
+    fn swap_selected_entry(&mut self, downwards: bool) {
+        if self.current_sort_order() != SortOrder::Manual {
+            debug!("Entries can't be moved while the page is sorted");
+            return;
+        }
+
+        let neighbour = if downwards {
+            self.pane.selected_entry + 1
+        } else {
+            self.pane.selected_entry.wrapping_sub(1)
+        };
+
+        let rows = self.rows();
+        let (Some(PageRow::Entry(index, entry)), Some(PageRow::Entry(other, other_entry))) =
+            (rows.get(self.pane.selected_entry), rows.get(neighbour))
+        else {
+            debug!("Selected entry can't be moved any further");
+            return;
+        };
+        if entry.section != other_entry.section {
+            debug!("Entries can't be moved out of their section");
+            return;
+        }
+        let (index, other) = (*index, *other);
+
+        let moved = self.edit_current_page(|page| {
+            let entry = page.entries.remove(index);
+            page.entries.insert(other, entry);
+        });
+
+        match moved {
+            Some(()) => self.pane.selected_entry = neighbour,
+            None => debug!("Entries of virtual pages can't be moved"),
+        }
+    }
+
+    /// Records an edit and applies it to the current page, or returns `None` if the page can't be edited.
+    ///
+    /// Pages are editable unless they are virtual or their entries are generated by a command or plugin.
+    fn edit_current_page<T>(&mut self, edit: impl FnOnce(&mut Page) -> T) -> Option<T> {
+        let page_index = self.config_page_index()?;
+        if self.get_current_page().ok()?.is_generated() {
+            debug!("Entries of the current page are generated, can't edit them");
+            return None;
+        }
+
+        self.record_edit();
+
+        let mut page = &mut self.config.pages[page_index];
+        for &index in &self.pane.subpage_path {
+            page = &mut page.subpages[index];
+        }
+
+        let result = edit(page);
+
+        self.clamp_selection();
+        self.refresh_virtual_pages();
+        Some(result)
+    }
+
+    /// Returns `true` if the current page is written in the config and its entries aren't generated.
+    fn is_current_page_editable(&self) -> bool {
+        self.config_page_index().is_some()
+            && self
+                .get_current_page()
+                .is_ok_and(|page| !page.is_generated())
+    }
+
+    /// Adds a section for the given page and its subpages, using the names of the enclosing pages as path.
+    fn collect_sections<'a>(
+        &'a self,
+        page: &'a Page,
+        mut path: Vec<&'a str>,
+        sections: &mut Vec<PageSection<'a>>,
+    ) {
+        path.push(&page.name);
+
+        let page_path = path.join(PAGE_PATH_SEPARATOR);
+        let entries = page
+            .entries
+            .iter()
+            .filter(|entry| self.show_hidden || !entry.hidden)
+            .map(|entry| {
+                let key = EntryKey {
+                    page: page_path.clone(),
+                    entry: entry.name.clone(),
+                };
+                (entry, self.user_state.favorites.contains(&key))
+            })
+            .collect();
+
+        sections.push(PageSection {
+            path: path.clone(),
+            description: page.description.as_deref(),
+            entries,
+        });
+
+        for subpage in &page.subpages {
+            self.collect_sections(subpage, path.clone(), sections);
+        }
+    }
+
+    /// Returns the top-level page that is currently selected or contains the current subpage
+    fn top_level_page(&self) -> Option<&Page> {
+        self.top_level_page_in(&self.pane)
+    }
+
+    /// Returns the index of the current page within the pages of the config, or `None` for virtual pages
+    fn config_page_index(&self) -> Option<usize> {
+        self.pane
+            .page_number
+            .checked_sub(self.virtual_pages.len())
+            .filter(|&index| index < self.config.pages.len())
+    }
+
+    /// Rebuilds the virtual pages from the config, keeping the current page selected.
+    ///
+    /// Must be called whenever the pages or favorites change.
+    fn refresh_virtual_pages(&mut self) {
+        let previous_names: Vec<String> = self
+            .virtual_pages
+            .iter()
+            .map(|virtual_page| virtual_page.page.name.clone())
+            .collect();
+
+        self.virtual_pages.clear();
+
+        if let Some(search) = &self.search {
+            // The results page is shown while searching even without results, so it doesn't disappear while typing
+            let keys = self.search_results(search);
+            let name = self.localizer.text("search-page");
+            let mut results = self
+                .build_virtual_page(&name, &keys)
+                .unwrap_or(VirtualPage {
+                    page: Page {
+                        name,
+                        ..Page::default()
+                    },
+                    sources: vec![],
+                });
+            results.page.description = Some(
+                self.localizer
+                    .text_with("search-description", &[("query", search.as_str().into())]),
+            );
+            self.virtual_pages.push(results);
+        }
+
+        let recent =
+            self.build_virtual_page(&self.localizer.text("recent-page"), &self.user_state.recent);
+        self.virtual_pages.extend(recent);
+
+        let favorites = self.build_virtual_page(
+            &self.localizer.text("favorites-page"),
+            &self.user_state.favorites,
+        );
+        self.virtual_pages.extend(favorites);
+
+        // Both panes of the split view are kept on their page and within bounds
+        let mut pane = std::mem::take(&mut self.pane);
+        self.restore_pane(&mut pane, &previous_names);
+        self.pane = pane;
+        if let Some(mut split) = self.split.take() {
+            self.restore_pane(&mut split.other, &previous_names);
+            self.split = Some(split);
+        }
+    }
+
+    /// Assembles a virtual page from the given entries, or `None` if none of them exist.
+    ///
+    /// Entries that no longer exist are skipped, descriptions are prefixed with the name of the original page.
+    fn build_virtual_page(&self, name: &str, keys: &[EntryKey]) -> Option<VirtualPage> {
+        let mut page = Page {
+            name: name.to_string(),
+            ..Page::default()
+        };
+        let mut sources = vec![];
+
+        for key in keys {
+            let Some(entry) = self.find_entry(key) else {
+                continue;
+            };
+
+            // Sections only make sense within the page of the entry
+            page.entries.push(Entry {
+                description: format!("[{}] {}", key.page, entry.description),
+                section: None,
+                ..entry.clone()
+            });
+            sources.push(key.clone());
+        }
+
+        (!page.entries.is_empty()).then_some(VirtualPage { page, sources })
+    }
+
+    /// Returns the keys of the entries on all pages of the config and their subpages matching the search, in their order.
+    ///
+    /// Hidden entries are only included while they are revealed, an empty search has no results.
+    fn search_results(&self, search: &str) -> Vec<EntryKey> {
+        let mut keys = vec![];
+        if search.trim().is_empty() {
+            return keys;
+        }
+
+        let mut pages: Vec<(String, &Page)> = self
+            .config
+            .pages
+            .iter()
+            .rev()
+            .map(|page| (page.name.clone(), page))
+            .collect();
+        while let Some((path, page)) = pages.pop() {
+            keys.extend(
+                page.entries
+                    .iter()
+                    .filter(|entry| self.show_hidden || !entry.hidden)
+                    .filter(|entry| matches_filter(entry, search))
+                    .map(|entry| EntryKey {
+                        page: path.clone(),
+                        entry: entry.name.clone(),
+                    }),
+            );
+            pages.extend(page.subpages.iter().rev().map(|subpage| {
+                (
+                    format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name),
+                    subpage,
+                )
+            }));
+        }
+        keys
+    }
+
+    /// Switches to the results page of the search, selecting the first result.
+    fn show_search_results(&mut self) {
+        self.refresh_virtual_pages();
+        self.pane = Pane::default();
+    }
+
+    /// Opens the page of the entry identified by the given key and selects the entry.
+    fn open_entry(&mut self, key: &EntryKey) {
+        if !self.open_page(&key.page) {
+            return;
+        }
+        let index = self.get_current_page().ok().and_then(|page| {
+            page.entries
+                .iter()
+                .position(|entry| entry.name == key.entry)
+        });
+        if let Some(index) = index {
+            self.select_entry_row(index);
+        }
+    }
+
+    /// Returns the entry of the config identified by the given key, if it exists
+    fn find_entry(&self, key: &EntryKey) -> Option<&Entry> {
+        page_at(&self.config.pages, &key.page)?
+            .entries
+            .iter()
+            .find(|entry| entry.name == key.entry)
+    }
+
+    /// Saves a snapshot of the pages before an edit, invalidating any undone edits.
+    fn record_edit(&mut self) {
+        if self.history.undo.len() == MAX_HISTORY_SIZE {
+            self.history.undo.remove(0);
+        }
+        self.history.undo.push(self.config.pages.clone());
+        self.history.redo.clear();
+        self.unsaved_changes = true;
+    }
+
+    /// Restores a valid page and entry selection after the pages were swapped out.
+    fn after_history_change(&mut self) {
+        self.pane.page_number = self
+            .pane
+            .page_number
+            .min(self.number_of_pages().saturating_sub(1));
+        self.refresh_virtual_pages();
+        self.unsaved_changes = true;
+    }
+
+    /// Keeps the entry selection within the bounds of the current page.
+    fn clamp_selection(&mut self) {
+        let number_of_rows = self.number_of_rows();
+        self.pane.selected_entry = self
+            .pane
+            .selected_entry
+            .min(number_of_rows.saturating_sub(1));
+    }
+
+    /// Moves a pane along with the pages of the config after virtual pages appeared or disappeared in front of them,
+    /// then keeps it within the bounds of the pages, subpages and rows.
+    ///
+    /// Panes on a virtual page stay on the virtual page with the same name, if it still exists.
+    fn restore_pane(&self, pane: &mut Pane, previous_names: &[String]) {
+        if pane.page_number >= previous_names.len() {
+            pane.page_number = pane.page_number - previous_names.len() + self.virtual_pages.len();
+        } else {
+            let name = &previous_names[pane.page_number];
+            if let Some(position) = self
+                .virtual_pages
+                .iter()
+                .position(|virtual_page| virtual_page.page.name == *name)
+            {
+                pane.page_number = position;
+            }
+            pane.subpage_path.clear();
+        }
+        pane.page_number = pane
+            .page_number
+            .min(self.number_of_pages().saturating_sub(1));
+        self.clamp_subpage_path(pane);
+
+        let number_of_rows = self.rows_in(pane).len();
+        pane.selected_entry = pane.selected_entry.min(number_of_rows.saturating_sub(1));
+    }
+
+    /// Drops the part of the subpage path of a pane that no longer leads to an existing subpage.
+    fn clamp_subpage_path(&self, pane: &mut Pane) {
+        let Some(mut page) = self.top_level_page_in(pane) else {
+            pane.subpage_path.clear();
+            return;
+        };
+
+        let mut valid_depth = 0;
+        for &index in &pane.subpage_path {
+            let Some(subpage) = page.subpages.get(index) else {
+                break;
+            };
+            page = subpage;
+            valid_depth += 1;
+        }
+
+        pane.subpage_path.truncate(valid_depth);
+    }
+
+    /// Returns the page shown in the given pane, or an error if its position is out-of-bounds
+    fn page_in(&self, pane: &Pane) -> Result<&Page> {
+        let mut page = match self.virtual_pages.get(pane.page_number) {
+            Some(virtual_page) => &virtual_page.page,
+            None => self
+                .config
+                .pages
+                .get(pane.page_number - self.virtual_pages.len())
+                .ok_or(anyhow!("Can not get page {} from config", pane.page_number))?,
+        };
+
+        for &index in &pane.subpage_path {
+            page = page.subpages.get(index).ok_or(anyhow!(
+                "Can not get subpage {} of {}",
+                index,
+                page.name
+            ))?;
+        }
+
+        Ok(page)
+    }
+
+    /// Returns the names of the pages from the top-level page down to the page shown in the given pane
+    fn breadcrumbs_in(&self, pane: &Pane) -> Vec<&str> {
+        let Some(mut page) = self.top_level_page_in(pane) else {
+            return vec![];
+        };
+
+        let mut names = vec![page.name.as_str()];
+        for &index in &pane.subpage_path {
+            let Some(subpage) = page.subpages.get(index) else {
+                break;
+            };
+            page = subpage;
+            names.push(page.name.as_str());
+        }
+
+        names
+    }
+
+    /// Returns the path of the page of the config shown in the given pane, or `None` on a virtual page
+    fn config_page_path(&self, pane: &Pane) -> Option<String> {
+        (pane.page_number >= self.virtual_pages.len())
+            .then(|| self.breadcrumbs_in(pane).join(PAGE_PATH_SEPARATOR))
+    }
+
+    /// Moves the given pane to the page of the config with the given path, or the closest of its parents that still exists.
+    ///
+    /// The selected row is kept, it is brought within the bounds of the page when the virtual pages are refreshed.
+    fn move_pane_to(&self, pane: &mut Pane, path: Option<&str>) {
+        let Some(mut path) = path else {
+            return;
+        };
+
+        loop {
+            if let Some((page_index, subpage_path)) = find_page(&self.config.pages, path) {
+                pane.page_number = self.virtual_pages.len() + page_index;
+                pane.subpage_path = subpage_path;
+                return;
+            }
+            match path.rsplit_once(PAGE_PATH_SEPARATOR) {
+                Some((parent, _)) => path = parent,
+                None => return,
+            }
+        }
+    }
+
+    /// Returns the subpages of the page shown in the given pane
+    fn subpages_in(&self, pane: &Pane) -> &[Page] {
+        self.page_in(pane)
+            .map(|page| page.subpages.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the entries of the page in the given pane that are shown, paired with their index on the page
+    ///
+    /// Hidden entries are only included while they are revealed.
+    fn visible_entries_in(&self, pane: &Pane) -> Vec<(usize, &Entry)> {
+        let Ok(page) = self.page_in(pane) else {
+            return vec![];
+        };
+
+        let mut entries: Vec<(usize, &Entry)> = page
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.show_hidden || !entry.hidden)
+            .collect();
+
+        if let Some(filter) = self
+            .filters
+            .get(&self.breadcrumbs_in(pane).join(PAGE_PATH_SEPARATOR))
+        {
+            entries.retain(|(_, entry)| matches_filter(entry, filter));
+        }
+
+        let order = self.sort_order_in(pane);
+        if order != SortOrder::Manual {
+            entries.sort_by_cached_key(|(_, entry)| order.key(entry));
+        }
+
+        entries
+    }
+
+    /// Returns the rows of the page shown in the given pane, see [`App::rows`]
+    fn rows_in(&self, pane: &Pane) -> Vec<PageRow<'_>> {
+        let mut rows: Vec<PageRow> = self
+            .subpages_in(pane)
+            .iter()
+            .map(PageRow::Subpage)
+            .collect();
+
+        let entries = self.visible_entries_in(pane);
+        rows.extend(
+            entries
+                .iter()
+                .filter(|(_, entry)| entry.section.is_none())
+                .map(|&(index, entry)| PageRow::Entry(index, entry)),
+        );
+
+        let mut sections: Vec<&str> = vec![];
+        for (_, entry) in &entries {
+            if let Some(section) = entry.section.as_deref() {
+                if !sections.contains(&section) {
+                    sections.push(section);
+                }
+            }
+        }
+
+        for name in sections {
+            let section_entries: Vec<PageRow> = entries
+                .iter()
+                .filter(|(_, entry)| entry.section.as_deref() == Some(name))
+                .map(|&(index, entry)| PageRow::Entry(index, entry))
+                .collect();
+            let collapsed = self.is_collapsed_in(pane, name);
+
+            rows.push(PageRow::Section {
+                name,
+                collapsed,
+                entries: section_entries.len(),
+            });
+            if !collapsed {
+                rows.extend(section_entries);
+            }
+        }
+
+        rows
+    }
+
+    /// Returns the order the page in the given pane is shown in for this session
+    fn sort_order_in(&self, pane: &Pane) -> SortOrder {
+        self.sort_overrides
+            .get(&self.breadcrumbs_in(pane).join(PAGE_PATH_SEPARATOR))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the key identifying the entry at the given index on the page in the given pane
+    fn entry_key_in(&self, pane: &Pane, index: usize) -> Option<EntryKey> {
+        if let Some(virtual_page) = self.virtual_pages.get(pane.page_number) {
+            return virtual_page.sources.get(index).cloned();
+        }
+
+        let entry = self.page_in(pane).ok()?.entries.get(index)?;
+
+        Some(EntryKey {
+            page: self.breadcrumbs_in(pane).join(PAGE_PATH_SEPARATOR),
+            entry: entry.name.clone(),
+        })
+    }
+
+    /// Returns `true` if the names of entries on the page in the given pane are shown, see [`App::shows_names`]
+    fn shows_names_in(&self, pane: &Pane) -> bool {
+        self.page_in(pane)
+            .ok()
+            .and_then(|page| page.show_names)
+            .unwrap_or(self.config.show_names)
+    }
+
+    /// Returns `true` if the entry at the given index on the page in the given pane is starred
+    fn is_favorite_in(&self, pane: &Pane, index: usize) -> bool {
+        self.entry_key_in(pane, index)
+            .is_some_and(|key| self.user_state.favorites.contains(&key))
+    }
+
+    /// Returns `true` if the section with the given name on the page in the given pane is collapsed
+    fn is_collapsed_in(&self, pane: &Pane, section: &str) -> bool {
+        self.collapsed_sections.contains(&(
+            self.breadcrumbs_in(pane).join(PAGE_PATH_SEPARATOR),
+            section.to_string(),
+        ))
+    }
+
+    /// Returns the top-level page shown in or containing the subpage shown in the given pane
+    fn top_level_page_in(&self, pane: &Pane) -> Option<&Page> {
+        match self.virtual_pages.get(pane.page_number) {
+            Some(virtual_page) => Some(&virtual_page.page),
+            None => self
+                .config
+                .pages
+                .get(pane.page_number - self.virtual_pages.len()),
+        }
+    }
+}
+
+/// Collects the sources of the page and its subpages whose refresh interval has passed since their command last ran.
+fn collect_due_sources(page: &Page, path: &str, due: &mut Vec<(String, PageSource)>) {
+    if let Some(source) = &page.source {
+        let elapsed = source.ran.map(|ran| ran.elapsed());
+        if let Some(refresh) = source.refresh {
+            if elapsed.is_none_or(|elapsed| elapsed >= refresh) {
+                due.push((path.to_string(), source.clone()));
+            }
+        }
+    }
+
+    for subpage in &page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        collect_due_sources(subpage, &subpage_path, due);
+    }
+}
+
+/// Returns the next or previous color in the ANSI color table, wrapping around at the ends.
+///
+/// Colors without an ANSI color code start over at the first color.
+fn cycle_color(color: Color, forward: bool) -> Color {
+    let index = ansi_index(color).unwrap_or(0);
+
+    if forward {
+        Color::Indexed(index.wrapping_add(1))
+    } else {
+        Color::Indexed(index.wrapping_sub(1))
+    }
+}
+
+/// Returns `true` if each word of the filter appears in the name, description or content of the entry, ignoring case.
+///
+/// The content of secret entries isn't searched, so filtering doesn't give it away.
+fn matches_filter(entry: &Entry, filter: &str) -> bool {
+    let mut text = format!("{} {}", entry.name, entry.description);
+    if !entry.secret {
+        text.push(' ');
+        text.push_str(&entry.content.join(" "));
+    }
+    let text = text.to_lowercase();
+
+    filter
+        .split_whitespace()
+        .all(|word| text.contains(&word.to_lowercase()))
+}
+
+/// Finds the page with the given path, the names of nested pages separated by [`PAGE_PATH_SEPARATOR`].
+///
+/// Returns the index of the top-level page and the indices of the subpages leading to the page.
+pub fn find_page(pages: &[Page], path: &str) -> Option<(usize, Vec<usize>)> {
+    let mut names = path.split(PAGE_PATH_SEPARATOR);
+
+    let top_level_name = names.next()?;
+    let page_index = pages.iter().position(|page| page.name == top_level_name)?;
+
+    let mut page = &pages[page_index];
+    let mut subpage_path = vec![];
+    for name in names {
+        let index = page
+            .subpages
+            .iter()
+            .position(|subpage| subpage.name == name)?;
+        page = &page.subpages[index];
+        subpage_path.push(index);
+    }
+
+    Some((page_index, subpage_path))
+}
+
+/// Returns the page with the given path, the names of nested pages separated by [`PAGE_PATH_SEPARATOR`].
+pub fn page_at<'a>(pages: &'a [Page], path: &str) -> Option<&'a Page> {
+    let (page_index, subpage_path) = find_page(pages, path)?;
+
+    let mut page = &pages[page_index];
+    for index in subpage_path {
+        page = &page.subpages[index];
+    }
+    Some(page)
+}
+
+/// Returns the path after the page at the old path was renamed to the new path,
+/// or `None` if the path is neither that page nor one of its subpages.
+pub fn rename_path(path: &str, old: &str, new: &str) -> Option<String> {
+    if path == old {
+        return Some(new.to_string());
+    }
+    let rest = path.strip_prefix(old)?.strip_prefix(PAGE_PATH_SEPARATOR)?;
+    Some(format!("{}{}{}", new, PAGE_PATH_SEPARATOR, rest))
+}
+
+/// Returns the entry of the page whose name matches the query best, fuzzily and ignoring case.
+///
+/// If no entry matches or several match equally well, the error lists the candidates.
+pub fn match_entry<'a>(page: &'a Page, query: &str) -> Result<&'a Entry> {
+    let scores: Vec<(&Entry, u32)> = page
+        .entries
+        .iter()
+        .filter_map(|entry| Some((entry, fuzzy::score(&entry.name, query)?)))
+        .collect();
+
+    let Some(best) = scores.iter().map(|(_, score)| *score).max() else {
+        let names: Vec<&str> = page.entries.iter().map(|e| e.name.as_str()).collect();
+        bail!(
+            "No entry of page {} matches {}, available entries are: {}",
+            page.name,
+            query,
+            names.join(", ")
+        );
+    };
+
+    let best_entries: Vec<&Entry> = scores
+        .into_iter()
+        .filter(|(_, score)| *score == best)
+        .map(|(entry, _)| entry)
+        .collect();
+    if let [entry] = best_entries[..] {
+        return Ok(entry);
+    }
+
+    let names: Vec<&str> = best_entries.iter().map(|e| e.name.as_str()).collect();
+    bail!(
+        "Entry {} of page {} is ambiguous, it matches: {}",
+        query,
+        page.name,
+        names.join(", ")
+    )
+}
+
+/// Returns the path of the page whose name or alias matches the query best, fuzzily and ignoring case.
+///
+/// Subpages are matched by their own name. If no page matches or several match equally well, the error lists the candidates.
+pub fn match_page(pages: &[Page], query: &str) -> Result<String> {
+    let mut candidates = vec![];
+    for page in pages {
+        collect_page_scores(page, &page.name, query, &mut candidates);
+    }
+
+    let Some(best) = candidates.iter().map(|(_, score)| *score).max() else {
+        let mut paths = vec![];
+        for page in pages {
+            collect_page_paths(page, &page.name, &mut paths);
+        }
+        bail!(
+            "No page matches {}, available pages are: {}",
+            query,
+            paths.join(", ")
+        );
+    };
+
+    let mut best_paths: Vec<String> = candidates
+        .into_iter()
+        .filter(|(_, score)| *score == best)
+        .map(|(path, _)| path)
+        .collect();
+    if best_paths.len() > 1 {
+        bail!(
+            "Page {} is ambiguous, it matches: {}",
+            query,
+            best_paths.join(", ")
+        );
+    }
+
+    Ok(best_paths.remove(0))
+}
+
+/// Collects the best score of the page's name and aliases along with its path, then does the same for its subpages.
+fn collect_page_scores(page: &Page, path: &str, query: &str, scores: &mut Vec<(String, u32)>) {
+    let score = [&page.name]
+        .into_iter()
+        .chain(&page.aliases)
+        .filter_map(|name| fuzzy::score(name, query))
+        .max();
+    if let Some(score) = score {
+        scores.push((path.to_string(), score));
+    }
+
+    for subpage in &page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        collect_page_scores(subpage, &subpage_path, query, scores);
+    }
+}
+
+/// Collects the paths of the page and its subpages.
+fn collect_page_paths(page: &Page, path: &str, paths: &mut Vec<String>) {
+    paths.push(path.to_string());
+    for subpage in &page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        collect_page_paths(subpage, &subpage_path, paths);
+    }
+}
+
+/// Returns the longest start that all paths share, comparing characters while ignoring case.
+///
+/// The start is taken as written in the first path.
+fn common_prefix(paths: &[String]) -> String {
+    let Some((first, rest)) = paths.split_first() else {
+        return String::new();
+    };
+
+    let mut length = first.len();
+    for path in rest {
+        let shared = first
+            .char_indices()
+            .zip(path.chars())
+            .take_while(|((_, a), b)| a.to_lowercase().eq(b.to_lowercase()))
+            .last()
+            .map_or(0, |((index, a), _)| index + a.len_utf8());
+        length = length.min(shared);
+    }
+    first[..length].to_string()
+}
+
+/// Collects the URLs of the entries of a page and its subpages that are links.
+fn collect_link_urls<'a>(page: &'a Page, urls: &mut Vec<&'a str>) {
+    urls.extend(page.entries.iter().filter_map(Entry::link_url));
+
+    for subpage in &page.subpages {
+        collect_link_urls(subpage, urls);
+    }
+}
+
+impl Page {
+    /// Returns `true` if the entries of the page are generated instead of written in the config
+    pub fn is_generated(&self) -> bool {
+        self.source.is_some() || self.generator.is_some()
+    }
+}
+
+impl Entry {
+    /// Returns the URL the entry links to if its content is a single URL
+    pub fn link_url(&self) -> Option<&str> {
+        hyperlink::url(&self.content)
+    }
+}
+
+impl SortOrder {
+    /// Returns the name of the sort order as used in the configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortOrder::Manual => "manual",
+            SortOrder::Alphabetical => "alphabetical",
+            SortOrder::Shortcut => "shortcut",
+        }
+    }
+
+    /// Returns the sort order with the given name, if there is one
+    pub fn from_name(name: &str) -> Option<SortOrder> {
+        [
+            SortOrder::Manual,
+            SortOrder::Alphabetical,
+            SortOrder::Shortcut,
+        ]
+        .into_iter()
+        .find(|order| order.name() == name)
+    }
+
+    /// Sorts the given entries in this order, keeping the written order for equal entries
+    pub fn sort(&self, entries: &mut [Entry]) {
+        if *self != SortOrder::Manual {
+            entries.sort_by_cached_key(|entry| self.key(entry));
+        }
+    }
+
+    /// Returns the key an entry is sorted by in this order, which is the same for all entries in manual order
+    ///
+    /// Shortcuts are compared in their canonical form, so different notations of a key are sorted together.
+    pub fn key(&self, entry: &Entry) -> String {
+        match self {
+            SortOrder::Manual => String::new(),
+            SortOrder::Alphabetical => entry.name.to_lowercase(),
+            SortOrder::Shortcut => normalize_content(&entry.content, Keyboard::current())
+                .join("+")
+                .to_lowercase(),
+        }
+    }
+}
+
+impl IdleAction {
+    /// Returns the name of the idle action as used in the configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            IdleAction::Quit => "quit",
+            IdleAction::Blank => "blank",
+        }
+    }
+
+    /// Returns the idle action with the given name, if there is one
+    pub fn from_name(name: &str) -> Option<IdleAction> {
+        [IdleAction::Quit, IdleAction::Blank]
+            .into_iter()
+            .find(|action| action.name() == name)
+    }
+}
+
+impl PlaceholderForm {
+    /// Returns the command with the values typed so far filled in.
+    pub fn filled(&self) -> String {
+        snippet::fill(&self.command, &self.fields)
+    }
+
+    /// Returns the command with the values typed so far filled in as it is shown, masked if it is a secret.
+    pub fn preview(&self) -> String {
+        if self.secret {
+            SECRET_MASK.to_string()
+        } else {
+            self.filled()
+        }
+    }
+
+    /// Returns the value of the field that is typed into.
+    pub fn value_mut(&mut self) -> &mut String {
+        &mut self.fields[self.field].1
+    }
+
+    /// Moves to the next field, starting over after the last one.
+    pub fn next_field(&mut self) {
+        self.field = (self.field + 1) % self.fields.len();
+    }
+
+    /// Moves to the previous field, continuing with the last one before the first.
+    pub fn previous_field(&mut self) {
+        self.field = (self.field + self.fields.len() - 1) % self.fields.len();
+    }
+}
+
+impl EntryForm {
+    /// Returns the typed name of the entry.
+    pub fn name(&self) -> &str {
+        self.values[0].trim()
+    }
+
+    /// Returns the typed keys or commands of the entry, leaving out empty ones.
+    pub fn keys(&self) -> Vec<String> {
+        self.values[1]
+            .split(ENTRY_KEY_SEPARATOR)
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Returns the value of the field that is typed into.
+    pub fn value_mut(&mut self) -> &mut String {
+        &mut self.values[self.field]
+    }
+
+    /// Moves to the next field, starting over after the last one.
+    pub fn next_field(&mut self) {
+        self.field = (self.field + 1) % self.values.len();
+    }
+
+    /// Moves to the previous field, continuing with the last one before the first.
+    pub fn previous_field(&mut self) {
+        self.field = (self.field + self.values.len() - 1) % self.values.len();
+    }
+}
+
+impl QuitReason {
+    /// Returns a human-readable description of the quit reason in the language of the given localizer
+    pub fn text(&self, localizer: &Localizer) -> String {
+        let id = match self {
+            QuitReason::Sigint => "quit-sigint",
+            QuitReason::CloseKeyPressed => "quit-close-key",
+            QuitReason::InitSubcommandCompleted => "quit-init-completed",
+            QuitReason::CheckSubcommandCompleted => "quit-check-completed",
+            QuitReason::EditSubcommandCompleted => "quit-edit-completed",
+            QuitReason::IdleTimeout => "quit-idle-timeout",
+            QuitReason::ExportSubcommandCompleted => "quit-export-completed",
+            QuitReason::ImportSubcommandCompleted => "quit-import-completed",
+            QuitReason::ScrapeSubcommandCompleted => "quit-scrape-completed",
+            QuitReason::SuggestSubcommandCompleted => "quit-suggest-completed",
+            QuitReason::CopySubcommandCompleted => "quit-copy-completed",
+            QuitReason::ExecSubcommandCompleted => "quit-exec-completed",
+            QuitReason::ShowSubcommandCompleted => "quit-show-completed",
+            QuitReason::ListSubcommandCompleted => "quit-list-completed",
+            QuitReason::StatsSubcommandCompleted => "quit-stats-completed",
+            QuitReason::AddSubcommandCompleted => "quit-add-completed",
+            QuitReason::RemoveSubcommandCompleted => "quit-remove-completed",
+            QuitReason::RenameSubcommandCompleted => "quit-rename-completed",
+            QuitReason::MoveSubcommandCompleted => "quit-move-completed",
+            QuitReason::SedSubcommandCompleted => "quit-sed-completed",
+            QuitReason::RestoreSubcommandCompleted => "quit-restore-completed",
+            //QuitReason::Other(s) => s,
+        };
+        localizer.text(id)
     }
 }
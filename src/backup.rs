@@ -0,0 +1,224 @@
+//! Backup module for Recall.
+//!
+//! Before the config is written, its previous content is copied into a backups directory next to it,
+//! named by the config and the time of the backup, so any change can be rolled back with `recall restore`.
+//! Only the most recent backups are kept, older ones are removed as new ones are made.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use log::{debug, info};
+
+/// Name of the directory next to the config that backups are kept in
+const BACKUP_DIRECTORY: &str = "backups";
+
+/// Number of backups kept of each config
+const MAX_BACKUPS: usize = 20;
+
+/// Extension of backups, so they can be opened like the config
+const BACKUP_EXTENSION: &str = "toml";
+
+/// Returns the directory backups of the config at the given path are kept in.
+pub fn backup_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(BACKUP_DIRECTORY)
+}
+
+/// Copies the config at the given path into the backups directory, removing the oldest backups beyond [`MAX_BACKUPS`].
+///
+/// Nothing is backed up if the config doesn't exist yet or is the same as its latest backup.
+pub fn back_up(config_path: &Path) -> Result<()> {
+    let Ok(content) = fs::read_to_string(config_path) else {
+        debug!("No config at {} to back up", config_path.display());
+        return Ok(());
+    };
+
+    let mut backups = list_backups(config_path)?;
+    if let Some(latest) = backups.last() {
+        if fs::read_to_string(latest).is_ok_and(|latest| latest == content) {
+            debug!("Config is unchanged since its latest backup");
+            return Ok(());
+        }
+    }
+
+    let dir = backup_dir(config_path);
+    fs::create_dir_all(&dir).context(format!("Failed to create directory {}", dir.display()))?;
+
+    let path = dir.join(format!(
+        "{}-{}.{}",
+        config_name(config_path),
+        timestamp(SystemTime::now()),
+        BACKUP_EXTENSION
+    ));
+    info!("Backing up config to {}", path.display());
+    fs::write(&path, content).context(format!("Failed to write backup {}", path.display()))?;
+    backups.push(path);
+
+    let excess = backups.len().saturating_sub(MAX_BACKUPS);
+    for old in &backups[..excess] {
+        debug!("Removing old backup {}", old.display());
+        fs::remove_file(old).context(format!("Failed to remove backup {}", old.display()))?;
+    }
+    Ok(())
+}
+
+/// Replaces the config at the given path by its latest backup and removes that backup, returning its path.
+///
+/// Restoring again rolls back the change before, and so on.
+pub fn restore(config_path: &Path) -> Result<PathBuf> {
+    let latest = list_backups(config_path)?.pop().ok_or(anyhow!(
+        "No backups of {} in {}",
+        config_path.display(),
+        backup_dir(config_path).display()
+    ))?;
+
+    info!("Restoring config from {}", latest.display());
+    fs::copy(&latest, config_path).context(format!(
+        "Failed to restore {} from {}",
+        config_path.display(),
+        latest.display()
+    ))?;
+    fs::remove_file(&latest).context(format!("Failed to remove backup {}", latest.display()))?;
+    Ok(latest)
+}
+
+/// Returns the paths of the backups of the config at the given path, the oldest first.
+pub fn list_backups(config_path: &Path) -> Result<Vec<PathBuf>> {
+    let dir = backup_dir(config_path);
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let name = config_name(config_path);
+    let mut backups: Vec<PathBuf> = fs::read_dir(&dir)
+        .context(format!("Failed to read directory {}", dir.display()))?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|file_name| file_name.to_str())
+                .is_some_and(|file_name| is_backup_of(file_name, &name))
+        })
+        .collect();
+
+    // Timestamps are written with a fixed width, so their names sort by time
+    backups.sort();
+    Ok(backups)
+}
+
+/// Returns the name of the config file without its extension, which backups are named after.
+fn config_name(config_path: &Path) -> String {
+    config_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or("config".to_string())
+}
+
+/// Returns whether the file name is that of a backup of the config with the given name, followed by a timestamp.
+///
+/// Checking the whole timestamp keeps backups of configs whose names start alike apart, e.g. `config` and `config-work`.
+fn is_backup_of(file_name: &str, config_name: &str) -> bool {
+    let Some(timestamp) = file_name
+        .strip_prefix(config_name)
+        .and_then(|rest| rest.strip_prefix('-'))
+        .and_then(|rest| rest.strip_suffix(BACKUP_EXTENSION))
+        .and_then(|rest| rest.strip_suffix('.'))
+    else {
+        return false;
+    };
+
+    let parts: Vec<&str> = timestamp.split('-').collect();
+    parts.iter().map(|part| part.len()).eq([8, 6, 3])
+        && parts
+            .iter()
+            .all(|part| part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Returns the time in UTC as `YYYYMMDD-HHMMSS-mmm`, which sorts in chronological order.
+fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (year, month, day) = civil_date((seconds / 86400) as i64);
+    let seconds_of_day = seconds % 86400;
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}-{:03}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Returns the year, month and day of the given number of days since 1970-01-01 in the Gregorian calendar.
+fn civil_date(days: i64) -> (i64, u32, u32) {
+    // Counts from 0000-03-01, so leap days fall at the end of a year, in eras of 400 years
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_backups_of_configs_with_similar_names_apart() {
+        let dir = std::env::temp_dir().join(format!("recall-backups-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("config.toml");
+        let work = dir.join("config-work.toml");
+        fs::write(&config, "[personal]\n").unwrap();
+        fs::write(&work, "[work]\n").unwrap();
+
+        back_up(&config).unwrap();
+        back_up(&work).unwrap();
+        let backups = list_backups(&config).unwrap();
+        let work_backups = list_backups(&work).unwrap();
+
+        assert_eq!(backups.len(), 1);
+        assert_eq!(work_backups.len(), 1);
+        fs::write(&config, "[changed]\n").unwrap();
+        restore(&config).unwrap();
+        assert_eq!(fs::read_to_string(&config).unwrap(), "[personal]\n");
+        assert_eq!(list_backups(&work).unwrap(), work_backups);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn matches_backups_by_their_whole_name() {
+        assert!(is_backup_of("config-20261016-124412-803.toml", "config"));
+        assert!(is_backup_of(
+            "config-work-20261016-124412-803.toml",
+            "config-work"
+        ));
+        assert!(!is_backup_of(
+            "config-work-20261016-124412-803.toml",
+            "config"
+        ));
+        assert!(!is_backup_of("config-20261016-124412.toml", "config"));
+        assert!(!is_backup_of("config-20261016-124412-803.json", "config"));
+    }
+}
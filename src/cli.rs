@@ -2,17 +2,76 @@
 //!
 //! This module defines the command-line interface using the [`clap`] crate.
 
-use clap::{Parser, Subcommand};
+use clap::{builder::PossibleValuesParser, Parser, Subcommand};
+use clap_complete::ArgValueCandidates;
 use std::path::PathBuf;
 
+use crate::{
+    completion::{entry_candidates, page_candidates},
+    export::ExportFormat,
+    import::ImportFormat,
+    rewrite::EntryField,
+    theme::THEMES,
+};
+
 /// Clap CLI Configuration
 #[derive(Parser)]
 #[command(about = "Recall Keybinds, shortcuts, commands and more",long_about=None)]
 #[command(version)]
 pub struct Cli {
-    /// Path to a different configuration file
+    /// Path to a different configuration file, given several times to open each config as a workspace switched with W
     #[arg(short, long, value_name = "FILE")]
-    pub config: Option<PathBuf>,
+    pub config: Vec<PathBuf>,
+
+    /// Show plain, linear text without borders and color cues, e.g. for screen readers
+    #[arg(long)]
+    pub plain: bool,
+
+    /// Disable colors, also done if the NO_COLOR environment variable is set
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Use a built-in theme instead of the configured colors, without changing the config
+    #[arg(
+        long,
+        global = true,
+        value_name = "THEME",
+        value_parser = PossibleValuesParser::new(THEMES.iter().map(|theme| theme.name))
+    )]
+    pub theme: Option<String>,
+
+    /// ANSI color code used for the interface and text instead of the configured one, without changing the config
+    #[arg(long, global = true, value_name = "COLOR")]
+    pub primary_color: Option<u8>,
+
+    /// ANSI color code used for highlighting instead of the configured one, without changing the config
+    #[arg(long, global = true, value_name = "COLOR")]
+    pub highlight_color: Option<u8>,
+
+    /// Print output directly instead of through $PAGER, even if it doesn't fit the terminal
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+
+    /// Advance to the next page every given number of seconds, starting over after the last page
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub cycle: Option<u64>,
+
+    /// Open the page best matching this name or alias, e.g. `recall git`
+    #[arg(value_name = "PAGE", add = ArgValueCandidates::new(page_candidates))]
+    pub page: Option<String>,
+
+    /// Open the page best matching this name or alias, the same as the PAGE argument
+    #[arg(
+        long = "page",
+        value_name = "PAGE",
+        conflicts_with = "page",
+        add = ArgValueCandidates::new(page_candidates)
+    )]
+    pub page_option: Option<String>,
+
+    /// Show the page best matching this name or alias next to the opened page, switching between them with Tab
+    #[arg(long, value_name = "PAGE", add = ArgValueCandidates::new(page_candidates))]
+    pub split: Option<String>,
 
     /// Subcommands for the CLI
     #[command(subcommand)]
@@ -22,6 +81,296 @@ pub struct Cli {
 /// Available subcommands
 #[derive(Subcommand)]
 pub enum Commands {
-    /// Initialize example config
-    Init,
+    /// Initialize a config, asking which templates and theme to use
+    Init {
+        /// Write the example config without asking, also done if the input is not a terminal
+        #[arg(long)]
+        example: bool,
+    },
+
+    /// Check the config for entries sharing a shortcut on the same page
+    Check {
+        /// Also report entries sharing a shortcut on different pages
+        #[arg(long)]
+        across_pages: bool,
+    },
+
+    /// Open the config in $VISUAL or $EDITOR and check it once the editor is closed
+    Edit,
+
+    /// Export the entries of the config for use in other tools
+    Export {
+        /// Format to export to, also given with --format
+        #[arg(value_enum, value_name = "FORMAT", conflicts_with_all = ["format", "plugin"])]
+        format_argument: Option<ExportFormat>,
+
+        /// Format to export to
+        #[arg(
+            short,
+            long,
+            value_enum,
+            required_unless_present_any = ["plugin", "format_argument"]
+        )]
+        format: Option<ExportFormat>,
+
+        /// Export only the page best matching this name or alias, with its subpages
+        #[arg(long, add = ArgValueCandidates::new(page_candidates))]
+        page: Option<String>,
+
+        /// Export with the converter `<NAME>.wasm` in the plugins directory instead of a built-in format
+        #[arg(long, value_name = "NAME", conflicts_with = "format")]
+        plugin: Option<String>,
+
+        /// File to write the export to instead of the standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Import entries from the file of another tool into the config
+    Import {
+        /// Format of the imported file, told by the extension of .json, .yaml and .cheat files if not given
+        #[arg(short, long, alias = "from", value_enum)]
+        format: Option<ImportFormat>,
+
+        /// Import with the converter `<NAME>.wasm` in the plugins directory instead of a built-in format
+        #[arg(long, value_name = "NAME", conflicts_with = "format")]
+        plugin: Option<String>,
+
+        /// Format to import, e.g. `recall import tmux`, or the file to import if it is the only argument
+        #[arg(value_name = "FORMAT")]
+        source: Option<String>,
+
+        /// File to import, or directory for formats like markdown-dir, found on its own for formats like tmux
+        #[arg(value_name = "FILE")]
+        file: Option<PathBuf>,
+
+        /// Only print the changes to the config
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Print the imported pages as TOML instead of adding them to the config
+        #[arg(long, conflicts_with = "dry_run")]
+        print: bool,
+    },
+
+    /// Draft a page from the --help output of a command and print it for review
+    Scrape {
+        /// Command to run with --help, including subcommands like `cargo build`
+        #[arg(
+            required = true,
+            value_name = "COMMAND",
+            trailing_var_arg = true,
+            allow_hyphen_values = true
+        )]
+        command: Vec<String>,
+    },
+
+    /// Suggest long commands from the shell history that are typed again and again as entries
+    Suggest {
+        /// Shell history to read instead of $HISTFILE or the history of zsh, bash or fish
+        #[arg(long, value_name = "FILE")]
+        history: Option<PathBuf>,
+
+        /// Minimum number of characters of a suggested command
+        #[arg(long, value_name = "CHARACTERS", default_value_t = 20)]
+        min_length: usize,
+
+        /// Minimum number of times a suggested command was typed
+        #[arg(long, value_name = "TIMES", default_value_t = 3)]
+        min_count: usize,
+
+        /// Maximum number of suggestions to review
+        #[arg(long, value_name = "SUGGESTIONS", default_value_t = 10)]
+        limit: usize,
+    },
+
+    /// Print pages as text, e.g. to grep or redirect them to a file
+    Show {
+        /// Page to print along with its subpages, matched like the page to open
+        #[arg(
+            required_unless_present = "all",
+            conflicts_with = "all",
+            add = ArgValueCandidates::new(page_candidates)
+        )]
+        page: Option<String>,
+
+        /// Print every page
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Print the paths of all pages, or the entries of a page, one per line for scripts
+    List {
+        /// Page whose entries to print, matched like the page to open
+        #[arg(add = ArgValueCandidates::new(page_candidates))]
+        page: Option<String>,
+
+        /// Print a JSON array instead, with all fields of the pages or entries
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print how many pages, entries and favorites there are and how often they were looked up
+    Stats {
+        /// Print a heatmap of the most looked up entries and pages instead
+        #[arg(long)]
+        usage: bool,
+    },
+
+    /// Add an entry to a page, creating the page if needed and keeping comments and formatting
+    Add {
+        /// Page to add the entry to, given by its path like `docker/compose`
+        #[arg(add = ArgValueCandidates::new(page_candidates))]
+        page: String,
+
+        /// Name of the entry
+        name: String,
+
+        /// Keys or commands of the entry, separated by commas like `Ctrl,Shift,P`
+        #[arg(
+            short,
+            long,
+            value_name = "KEYS",
+            value_delimiter = ',',
+            required = true
+        )]
+        keys: Vec<String>,
+
+        /// Description of the entry
+        #[arg(
+            short,
+            long = "desc",
+            visible_alias = "description",
+            value_name = "TEXT",
+            default_value = ""
+        )]
+        description: String,
+
+        /// Only print the changes to the config
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Remove an entry, or a page with all its entries and subpages, after showing the changes
+    Rm {
+        /// Page to remove or of the entry to remove, matched like the page to open
+        #[arg(add = ArgValueCandidates::new(page_candidates))]
+        page: String,
+
+        /// Name of the entry to remove, matched fuzzily
+        #[arg(add = ArgValueCandidates::new(entry_candidates))]
+        entry: Option<String>,
+
+        /// Remove without asking
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Only print the changes to the config
+        #[arg(long, conflicts_with = "yes")]
+        dry_run: bool,
+    },
+
+    /// Rename a page, or an entry if a new name is given for it, keeping comments and favorites
+    Rename {
+        /// Page to rename or of the entry to rename, matched like the page to open
+        #[arg(add = ArgValueCandidates::new(page_candidates))]
+        page: String,
+
+        /// New name of the page, or name of the entry to rename, matched fuzzily
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// New name of the entry
+        #[arg(value_name = "NEW_NAME")]
+        new_name: Option<String>,
+
+        /// Only print the changes to the config
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Move an entry to another page, keeping comments and favorites
+    Mv {
+        /// Page of the entry, matched like the page to open
+        #[arg(add = ArgValueCandidates::new(page_candidates))]
+        page: String,
+
+        /// Name of the entry, matched fuzzily
+        #[arg(add = ArgValueCandidates::new(entry_candidates))]
+        entry: String,
+
+        /// Page to move the entry to, matched like the page to open
+        #[arg(add = ArgValueCandidates::new(page_candidates))]
+        target: String,
+
+        /// Only print the changes to the config
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Replace a pattern in entries, printing the changes as a diff before writing them
+    Sed {
+        /// Regular expression to search for
+        pattern: String,
+
+        /// Text to replace matches with, `$1` or `${name}` insert groups of the pattern
+        replacement: String,
+
+        /// Only replace in entries of this page and its subpages, matched like the page to open
+        #[arg(long, add = ArgValueCandidates::new(page_candidates))]
+        page: Option<String>,
+
+        /// Field of the entries to replace in
+        #[arg(long, value_enum, default_value_t = EntryField::Content)]
+        field: EntryField,
+
+        /// Write the changes without asking
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Only print the changes
+        #[arg(long, conflicts_with = "yes")]
+        dry_run: bool,
+    },
+
+    /// Roll back the last change to the config by restoring its latest backup
+    Restore {
+        /// List the backups instead of restoring one, the latest last
+        #[arg(long)]
+        list: bool,
+    },
+
+    /// Copy the content of an entry to the clipboard without opening the interface
+    Copy {
+        /// Page of the entry, matched like the page to open
+        #[arg(add = ArgValueCandidates::new(page_candidates))]
+        page: String,
+
+        /// Name of the entry, matched fuzzily
+        #[arg(add = ArgValueCandidates::new(entry_candidates))]
+        entry: String,
+
+        /// Print the content instead of copying it, e.g. for scripts
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Run the `exec` command of an entry in the shell, or else its content, e.g. for aliases like `rx deploy prod`
+    Exec {
+        /// Page of the entry, matched like the page to open
+        #[arg(add = ArgValueCandidates::new(page_candidates))]
+        page: String,
+
+        /// Name of the entry, matched fuzzily
+        #[arg(add = ArgValueCandidates::new(entry_candidates))]
+        entry: String,
+
+        /// Values of the placeholders in the command, in order of their appearance
+        #[arg(value_name = "VALUES")]
+        values: Vec<String>,
+
+        /// Print the command instead of running it
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
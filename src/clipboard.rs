@@ -0,0 +1,141 @@
+//! Clipboard module for Recall.
+//!
+//! Text is copied to the system clipboard, e.g. of X11, Wayland, macOS or Windows. Without one, like in SSH sessions,
+//! the terminal is asked to copy it with the OSC 52 escape sequence, as long as recall runs in a terminal supporting it.
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use std::{
+    env,
+    io::{self, Read},
+    os::unix::process::CommandExt,
+    process::{Command, Stdio},
+};
+use std::{fs::OpenOptions, io::Write};
+
+use anyhow::{Context, Result};
+use log::debug;
+
+/// Characters of the Base64 alphabet, indexed by their value
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Terminal recall runs in, which is written to even if the output is redirected
+const TERMINAL_PATH: &str = "/dev/tty";
+
+/// Environment variable set for a copy of recall that holds copied text in the clipboard, see [`hold`]
+#[cfg(all(unix, not(target_os = "macos")))]
+pub const HOLDER_VARIABLE: &str = "RECALL_CLIPBOARD_HOLDER";
+
+/// Copies text to the system clipboard, or via the terminal if there is none.
+///
+/// Under X11 the copied text is only kept while the clipboard is open, unless a clipboard manager takes it over,
+/// so the clipboard should be kept open as long as recall runs.
+pub struct Clipboard {
+    system: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    /// Opens the system clipboard, if there is one.
+    pub fn open() -> Clipboard {
+        let system = arboard::Clipboard::new()
+            .inspect_err(|error| debug!("Found no system clipboard: {}", error))
+            .ok();
+        Clipboard { system }
+    }
+
+    /// Copies the given text to the system clipboard, or via the terminal if there is no system clipboard.
+    pub fn copy(&mut self, text: &str) -> Result<()> {
+        debug!("Copying {} bytes to the clipboard", text.len());
+
+        if let Some(system) = &mut self.system {
+            match system.set_text(text) {
+                Ok(()) => return Ok(()),
+                Err(error) => debug!("Failed to copy to the system clipboard: {}", error),
+            }
+        }
+
+        // Without a terminal, e.g. when run by a launcher, nobody would receive the escape sequence
+        let mut terminal = OpenOptions::new().write(true).open(TERMINAL_PATH).context(
+            "Failed to copy to the clipboard, found neither a system clipboard nor a terminal",
+        )?;
+        write!(terminal, "\x1b]52;c;{}\x07", base64(text.as_bytes()))
+            .and_then(|_| terminal.flush())
+            .context("Failed to copy to the clipboard")
+    }
+
+    /// Copies the given text like [`Clipboard::copy`] for a process that exits right after, like `recall copy`.
+    ///
+    /// Under X11 and Wayland the text is gone once the process that copied it exits, so a copy of recall is started
+    /// in the background that holds it until something else is copied.
+    pub fn copy_before_exit(&mut self, text: &str) -> Result<()> {
+        #[cfg(all(unix, not(target_os = "macos")))]
+        if self.system.is_some() {
+            match start_holder(text) {
+                Ok(()) => return Ok(()),
+                Err(error) => debug!("{:#}", error),
+            }
+        }
+        self.copy(text)
+    }
+}
+
+/// Starts a copy of recall in the background that holds the given text in the system clipboard, see [`hold`].
+#[cfg(all(unix, not(target_os = "macos")))]
+fn start_holder(text: &str) -> Result<()> {
+    let program = env::current_exe().context("Failed to find the program of recall")?;
+    let mut holder = Command::new(program)
+        .env(HOLDER_VARIABLE, "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        // Its own process group keeps it running when the shell interrupts the jobs it started
+        .process_group(0)
+        .spawn()
+        .context("Failed to start a process holding the clipboard")?;
+
+    // Closing stdin once the text is written tells the holder that the text is complete
+    holder
+        .stdin
+        .take()
+        .context("Failed to pass the text to the process holding the clipboard")?
+        .write_all(text.as_bytes())
+        .context("Failed to pass the text to the process holding the clipboard")
+}
+
+/// Copies the text read from stdin to the system clipboard and holds it until something else is copied.
+///
+/// Runs in the copy of recall started by [`Clipboard::copy_before_exit`] instead of the usual interface.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn hold() -> Result<()> {
+    use arboard::SetExtLinux;
+
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .context("Failed to read the text to copy")?;
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set().wait().text(text))
+        .context("Failed to copy to the clipboard")
+}
+
+/// Encodes bytes as Base64 with padding, as expected by OSC 52.
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (i, byte)| {
+            value | (*byte as u32) << (16 - 8 * i)
+        });
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (value >> (18 - 6 * i)) & 0x3f;
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
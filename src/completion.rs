@@ -0,0 +1,146 @@
+//! Shell completion module for Recall.
+//!
+//! Page and entry names are completed against the config, using the dynamic completion of [`clap_complete`].
+//! The shell runs recall with the words typed so far after `--`, which are also searched for the config to read
+//! and, when completing an entry, the page it belongs to.
+//! Errors can't be reported while completing, so a config that can't be read offers no candidates.
+
+use std::{env, iter, path::PathBuf};
+
+use clap::CommandFactory;
+use clap_complete::CompletionCandidate;
+
+use crate::{
+    app::{match_page, page_at, Page, PAGE_PATH_SEPARATOR},
+    cli::Cli,
+    config::{default_config_path, read_from_config, Entries},
+};
+
+/// Name of the argument of subcommands taking a page followed by an entry
+const ENTRY_ARGUMENT: &str = "entry";
+
+/// Returns the paths and aliases of all pages as candidates.
+pub fn page_candidates() -> Vec<CompletionCandidate> {
+    let mut candidates = vec![];
+    for page in read_pages(&typed_words()) {
+        collect_page_candidates(&page, &page.name, &mut candidates);
+    }
+    candidates
+}
+
+/// Returns the names of the entries on the page typed before the entry as candidates, described by their description.
+pub fn entry_candidates() -> Vec<CompletionCandidate> {
+    let words = typed_words();
+    let pages = read_pages(&words);
+
+    let Some(query) = typed_page(&words) else {
+        return vec![];
+    };
+    let Some(page) = match_page(&pages, query)
+        .ok()
+        .and_then(|path| page_at(&pages, &path))
+    else {
+        return vec![];
+    };
+
+    page.entries
+        .iter()
+        .map(|entry| {
+            CompletionCandidate::new(&entry.name).help(Some(entry.description.clone().into()))
+        })
+        .collect()
+}
+
+/// Returns the page typed before the entry, which is the first argument after a subcommand taking an entry.
+fn typed_page(words: &[String]) -> Option<&str> {
+    let subcommands = entry_subcommands();
+    words
+        .iter()
+        .skip_while(|word| !subcommands.contains(word))
+        .skip(1)
+        .find(|word| !word.starts_with('-'))
+        .map(String::as_str)
+}
+
+/// Returns the names and aliases of the subcommands taking a page followed by an entry.
+fn entry_subcommands() -> Vec<String> {
+    Cli::command()
+        .get_subcommands()
+        .filter(|subcommand| {
+            subcommand
+                .get_arguments()
+                .any(|argument| argument.get_id() == ENTRY_ARGUMENT)
+        })
+        .flat_map(|subcommand| {
+            iter::once(subcommand.get_name()).chain(subcommand.get_all_aliases())
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Adds the path and aliases of the page and its subpages to the candidates.
+fn collect_page_candidates(page: &Page, path: &str, candidates: &mut Vec<CompletionCandidate>) {
+    candidates.push(CompletionCandidate::new(path));
+    for alias in &page.aliases {
+        candidates.push(CompletionCandidate::new(alias).help(Some(path.to_string().into())));
+    }
+
+    for subpage in &page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        collect_page_candidates(subpage, &subpage_path, candidates);
+    }
+}
+
+/// Returns the words of the command line being completed, which the shell passes after `--`.
+fn typed_words() -> Vec<String> {
+    env::args().skip_while(|arg| arg != "--").skip(1).collect()
+}
+
+/// Reads the pages of the config given with `--config` on the command line, or of the default config.
+fn read_pages(words: &[String]) -> Vec<Page> {
+    let path = words
+        .iter()
+        .enumerate()
+        .find_map(|(i, word)| match word.as_str() {
+            "-c" | "--config" => words.get(i + 1).map(PathBuf::from),
+            _ => word.strip_prefix("--config=").map(PathBuf::from),
+        })
+        .or_else(|| default_config_path().ok());
+
+    path.and_then(|path| read_from_config(path, Entries::Written).ok())
+        .map(|config| config.pages)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the words of a command line split at spaces.
+    fn words(line: &str) -> Vec<String> {
+        line.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn completes_entries_of_subcommands_taking_one() {
+        let subcommands = entry_subcommands();
+        for subcommand in ["copy", "exec", "mv", "rm"] {
+            assert!(
+                subcommands.contains(&subcommand.to_string()),
+                "{}",
+                subcommand
+            );
+        }
+        assert!(!subcommands.contains(&"rename".to_string()));
+    }
+
+    #[test]
+    fn finds_the_page_before_the_entry() {
+        assert_eq!(typed_page(&words("recall mv tmux ")), Some("tmux"));
+        assert_eq!(
+            typed_page(&words("recall -c c.toml rm --yes git ")),
+            Some("git")
+        );
+        assert_eq!(typed_page(&words("recall show tmux ")), None);
+    }
+}
@@ -5,21 +5,45 @@
 //! The configuration is structured as subtables with each subtable presenting a new page in recall.
 //! Entries within a subtable correspond to entries within a page and are identified by their TOML-key. These entries contain content (keybinds, shortcuts, commands, etc.) and a description.
 //! The special subtable `[recall]` optionally defines global settings such as text-color and highlight-color.
+//! Comments written directly above an entry are its note, which is kept when the config is written back.
 
-use crate::app::{Config, Entry, Page, DEFAULT_PRIMARY_COLOR, DEFAULT_SECONDARY_COLOR};
+use crate::{
+    app::{
+        find_page, ColumnAlignment, Config, Entry, IdleAction, Page, PageSource, SortOrder,
+        Variable, DEFAULT_PRIMARY_COLOR, DEFAULT_SECONDARY_COLOR, PAGE_PATH_SEPARATOR,
+    },
+    encryption::decrypt_pages,
+    graphics::Graphics,
+    i18n::{is_supported, language_names, DEFAULT_LANGUAGE},
+    import::add_entry,
+    keymap::{Keymap, Preset},
+    keys::{normalize_content, Keyboard},
+    template::Template,
+    theme::{ansi_index, find_theme, theme_names, Theme},
+};
 
 use anyhow::{anyhow, bail, Context, Ok, Result};
 use directories::ProjectDirs;
 use indexmap::IndexMap;
-use log::{info, trace};
-use ratatui::style::Color;
-use serde::Deserialize;
-use std::{fs, path::PathBuf};
-use toml::Table;
+use log::{debug, info, trace, warn};
+use ratatui::{layout::Alignment, style::Color};
+use regex::Regex;
+use serde::{Deserialize, Serialize, Serializer};
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    hash::{DefaultHasher, Hash, Hasher},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+use toml::{Table, Value};
+use toml_edit::{DocumentMut, Item, RawString, TableLike};
 
 /// Represents the deserialized TOML structure for the app configuration.
 /// Includes optional global settings and a collection of pages.
-#[derive(Debug, Deserialize)]
+#[derive(Debug)]
 struct ConfigToml {
     /// Optional global settings for recall behaviour (e.g. colors).
     recall: Option<RecallToml>,
@@ -28,33 +52,249 @@ struct ConfigToml {
     pages: IndexMap<String, PageToml>,
 }
 
+/// Two entries that are triggered by the same shortcut.
+#[derive(Debug)]
+pub struct ShortcutConflict {
+    /// The shared shortcut, as written in the later entry
+    pub shortcut: String,
+
+    /// Name and page of the entry that uses the shortcut first
+    pub first: String,
+
+    /// Name and page of the entry that uses the shortcut again
+    pub second: String,
+}
+
+impl fmt::Display for ShortcutConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Shortcut {} of {} is already used by {}",
+            self.shortcut, self.second, self.first
+        )
+    }
+}
+
 /// Global options such as color themes for UI and text and highlights.
 #[derive(Debug, Deserialize)]
 struct RecallToml {
+    /// Name of a built-in color theme, overridden by explicitly set colors.
+    theme: Option<String>,
+
     /// ANSI color code used for the interface and text.
     primary_color: Option<u8>,
 
     /// ANSI color code used for highlighting.
     highlight_color: Option<u8>,
+
+    /// Code of the language used for the interface, e.g. `de`.
+    language: Option<String>,
+
+    /// Whether to show plain, linear text for screen readers instead of tables.
+    plain: Option<bool>,
+
+    /// Whether to show the names of entries in a column before their shortcuts.
+    show_names: Option<bool>,
+
+    /// Alignment of the columns of entries.
+    alignment: Option<AlignmentToml>,
+
+    /// Number of columns the table of entries is at most as wide as.
+    max_width: Option<u16>,
+
+    /// Minutes without input after which recall quits or blanks the screen.
+    idle_timeout: Option<u64>,
+
+    /// Whether to `quit` or `blank` after the idle timeout.
+    idle_action: Option<String>,
+
+    /// Milliseconds to wait for input before timers are checked again.
+    tick_rate: Option<u64>,
+
+    /// Whether the mouse wheel scrolls through entries and pages.
+    mouse: Option<bool>,
+
+    /// Whether moving past the last or first page continues at the other end.
+    wrap_pages: Option<bool>,
+
+    /// Either `pc` or `mac`, deciding whether the primary modifier is shown as Ctrl or Cmd.
+    keyboard: Option<String>,
+
+    /// Either `kitty`, `sixel` or `text`, deciding how images are shown.
+    graphics: Option<String>,
+
+    /// Path of the age identity file decrypting encrypted entries, relative to the config file.
+    age_identity: Option<String>,
+
+    /// Name of a preset of keys added to the defaults, e.g. `vim`.
+    keymap_preset: Option<String>,
+
+    /// Keys of remapped actions by the names of the actions.
+    keybinds: Option<IndexMap<String, KeybindToml>>,
 }
 
-/// A page contains a collection of entries
-/// Entries are keyed by name and preserve insertion order
-type PageToml = IndexMap<String, EntryToml>;
+/// Keys of a remapped action, which may also be a single key.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KeybindToml {
+    /// A single key
+    Single(String),
+
+    /// Any number of keys
+    Multiple(Vec<String>),
+}
+
+/// Alignment of each column of entries, either `left`, `center` or `right`.
+#[derive(Debug, Deserialize)]
+struct AlignmentToml {
+    /// Alignment of the names of entries.
+    name: Option<String>,
+
+    /// Alignment of the shortcuts.
+    shortcut: Option<String>,
+
+    /// Alignment of the descriptions.
+    description: Option<String>,
+}
+
+/// A page contains a collection of entries and nested subpages.
+/// Both are keyed by name and preserve insertion order
+#[derive(Debug, Default)]
+struct PageToml {
+    /// Optional name of a group of related pages.
+    group: Option<String>,
+
+    /// Optional line describing the page.
+    description: Option<String>,
+
+    /// Optional path of an image illustrating the page.
+    image: Option<String>,
+
+    /// Other names the page can be opened by.
+    aliases: Vec<String>,
+
+    /// Order the entries are sorted in when loading the page.
+    sort: SortOrder,
+
+    /// Entries of the page, these are tables with a `content` key.
+    entries: IndexMap<String, EntryToml>,
+
+    /// Nested pages, these are all other tables within the page.
+    subpages: IndexMap<String, PageToml>,
+
+    /// Variables used in the commands of the page.
+    variables: Vec<VariableToml>,
+
+    /// Command generating the entries of the page.
+    command: Option<String>,
+
+    /// Pattern matching the lines printed by the command.
+    pattern: Option<String>,
+
+    /// Interval the command is run again in.
+    refresh: Option<Duration>,
+
+    /// Name of the plugin function generating the entries of the page.
+    generator: Option<String>,
+
+    /// Whether to show the names of entries, overriding the global setting.
+    show_names: Option<bool>,
+}
+
+/// A variable of a page, listing the possible values of a placeholder.
+#[derive(Debug, Deserialize)]
+struct VariableToml {
+    /// Name of the variable.
+    name: String,
+
+    /// Shell command printing the possible values.
+    command: String,
+}
 
 /// A single entry within a page, containing some content (keybinds, shortcuts, commands, etc.) and a human-readable description.
 #[derive(Debug, Deserialize)]
 struct EntryToml {
     /// List of keybindings (e.g. ["Ctrl","Shift","C"])
     /// Might be changed to a Union of String and Vec<String> to account for more than keys
+    #[serde(default)]
     content: Vec<String>,
 
+    /// Content encrypted with age, one part per line, instead of the content.
+    content_encrypted: Option<String>,
+
     /// Description of what the entry does.
     description: String,
+
+    /// Whether the entry is hidden until hidden entries are revealed.
+    #[serde(default)]
+    hidden: bool,
+
+    /// Whether the content of the entry is masked until it is revealed.
+    #[serde(default)]
+    secret: bool,
+
+    /// Path of a page to open when the entry is activated.
+    goto: Option<String>,
+
+    /// Shell command to run when the entry is activated.
+    exec: Option<String>,
+
+    /// Web page about the entry, opened in the browser.
+    url: Option<String>,
+
+    /// Name of the section within the page the entry is listed under.
+    section: Option<String>,
+
+    /// Path of an image illustrating the entry, relative to the config file.
+    image: Option<String>,
 }
 
+/// The key of an entry table holding its content encrypted with age, which also makes the table an entry.
+pub const ENCRYPTED_CONTENT_KEY: &str = "content_encrypted";
+
+/// The key of a page table holding the name of the group the page belongs to.
+const PAGE_GROUP_KEY: &str = "group";
+
+/// The key of a page table holding a line describing the page.
+const PAGE_DESCRIPTION_KEY: &str = "description";
+
+/// The key of a page table holding the path of an image illustrating the page.
+const PAGE_IMAGE_KEY: &str = "image";
+
+/// The key of a page table holding other names the page can be opened by.
+const PAGE_ALIASES_KEY: &str = "aliases";
+
+/// The key of a page table holding the order its entries are sorted in.
+const PAGE_SORT_KEY: &str = "sort";
+
+/// The key of a page table holding the command generating its entries.
+const PAGE_COMMAND_KEY: &str = "command";
+
+/// The key of a page table holding the pattern matching the lines printed by its command.
+const PAGE_PATTERN_KEY: &str = "pattern";
+
+/// The key of a page table holding the interval its command is run again in.
+const PAGE_REFRESH_KEY: &str = "refresh";
+
+/// The key of a page table holding the name of the plugin function generating its entries.
+const PAGE_GENERATOR_KEY: &str = "generator";
+
+/// The key of a page table holding whether the names of its entries are shown.
+const PAGE_SHOW_NAMES_KEY: &str = "show_names";
+
+/// The name of the group capturing the content of a generated entry.
+const PATTERN_CONTENT_GROUP: &str = "content";
+
+/// The name of the group capturing the description of a generated entry.
+const PATTERN_DESCRIPTION_GROUP: &str = "description";
+
+/// The key of a page table holding its variables.
+///
+/// Variables are an array of tables, so they can't be mistaken for an entry or subpage.
+const PAGE_VARIABLES_KEY: &str = "variables";
+
 /// The TOML table name used for storing global recall settings (e.g. colors).
-const RECALL_TABLE_NAME: &str = "recall";
+pub const RECALL_TABLE_NAME: &str = "recall";
 
 /// Returns the default configuration file path
 ///
@@ -72,11 +312,26 @@ pub fn default_config_path() -> Result<PathBuf> {
     Ok(config_path)
 }
 
+/// How far the entries of a config are prepared when it is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Entries {
+    /// Entries as written, with encrypted entries left encrypted, e.g. for completion or to edit the config
+    Written,
+
+    /// Pages with a `command` are filled by running it, where their entries are listed
+    Generated,
+
+    /// Encrypted entries are decrypted with age as well, where their content is shown or used
+    Decrypted,
+}
+
 /// Reads and parses the configuration file into `Config` struct.
 ///
 /// Parses TOML from the given file path, extracting global settings and parsing all defined pages and entries.
+/// Commands of pages are only run and encrypted entries only decrypted if asked for, since commands may be slow and
+/// age may ask for a passphrase.
 /// Returns an Error if the file is unreadable, not valid TOML or contains invalid recall specific structure.
-pub fn read_from_config(path: PathBuf) -> Result<Config> {
+pub fn read_from_config(path: PathBuf, entries: Entries) -> Result<Config> {
     let path_str = path.to_str().unwrap_or("Non UTF-8 path");
     info!("Reading config from {}", path_str);
 
@@ -84,35 +339,206 @@ pub fn read_from_config(path: PathBuf) -> Result<Config> {
     let toml_table = parse_toml(&file)?;
 
     let config_toml = build_config_toml(toml_table)?;
-    let pages: Vec<Page> = config_toml
+    let mut pages: Vec<Page> = config_toml
         .pages
         .into_iter()
         .map(|(name, page)| build_page(name, page))
         .collect();
+    attach_notes(&mut pages, &file)?;
 
-    let primary_color = if let Some(recall_config) = &config_toml.recall {
-        if let Some(c) = recall_config.primary_color {
-            Color::Indexed(c)
+    for page in &mut pages {
+        let path = page.name.clone();
+        if entries >= Entries::Generated {
+            generate_entries(page, &path)?;
         } else {
-            DEFAULT_PRIMARY_COLOR
+            check_patterns(page, &path)?;
         }
+    }
+
+    let age_identity = config_toml
+        .recall
+        .as_ref()
+        .and_then(|r| r.age_identity.clone());
+    if entries >= Entries::Decrypted {
+        let config_dir = path.parent().unwrap_or(Path::new("."));
+        decrypt_pages(
+            &mut pages,
+            age_identity
+                .as_ref()
+                .map(|identity| config_dir.join(identity))
+                .as_deref(),
+        )?;
+    }
+
+    for page in &pages {
+        validate_links(&pages, page, &page.name)?;
+    }
+
+    let theme = match config_toml.recall.as_ref().and_then(|r| r.theme.as_deref()) {
+        Some(name) => Some(find_theme(name).ok_or(anyhow!(
+            "Unknown theme {}, available themes are: {}",
+            name,
+            theme_names()
+        ))?),
+        None => None,
+    };
+
+    let primary_color = if let Some(c) = config_toml.recall.as_ref().and_then(|r| r.primary_color) {
+        Color::Indexed(c)
+    } else if let Some(theme) = theme {
+        Color::Indexed(theme.primary_color)
     } else {
         DEFAULT_PRIMARY_COLOR
     };
 
-    let highlight_color = if let Some(recall_config) = &config_toml.recall {
-        if let Some(c) = recall_config.highlight_color {
+    let highlight_color =
+        if let Some(c) = config_toml.recall.as_ref().and_then(|r| r.highlight_color) {
             Color::Indexed(c)
+        } else if let Some(theme) = theme {
+            Color::Indexed(theme.highlight_color)
         } else {
             DEFAULT_SECONDARY_COLOR
-        }
-    } else {
-        DEFAULT_SECONDARY_COLOR
+        };
+
+    let language = match config_toml.recall.as_ref().and_then(|r| r.language.clone()) {
+        Some(language) if !is_supported(&language) => bail!(
+            "Unsupported language {}, available languages are: {}",
+            language,
+            language_names()
+        ),
+        Some(language) => language,
+        None => DEFAULT_LANGUAGE.to_string(),
     };
 
+    let idle_action = match config_toml
+        .recall
+        .as_ref()
+        .and_then(|r| r.idle_action.as_deref())
+    {
+        Some(name) => IdleAction::from_name(name).ok_or(anyhow!(
+            "Unknown idle action {}, expected quit or blank",
+            name
+        ))?,
+        None => IdleAction::default(),
+    };
+
+    let keyboard = match config_toml
+        .recall
+        .as_ref()
+        .and_then(|r| r.keyboard.as_deref())
+    {
+        Some(name) => Some(
+            Keyboard::from_name(name)
+                .ok_or(anyhow!("Unknown keyboard {}, expected pc or mac", name))?,
+        ),
+        None => None,
+    };
+
+    let graphics = match config_toml
+        .recall
+        .as_ref()
+        .and_then(|r| r.graphics.as_deref())
+    {
+        Some(name) => Some(Graphics::from_name(name).ok_or(anyhow!(
+            "Unknown graphics {}, expected kitty, sixel or text",
+            name
+        ))?),
+        None => None,
+    };
+
+    // Conflicts are likely mistakes, but don't keep recall from starting
+    for conflict in
+        find_shortcut_conflicts(&pages, false, keyboard.unwrap_or_else(Keyboard::current))
+    {
+        warn!("{}", conflict);
+    }
+
+    let alignment = match config_toml
+        .recall
+        .as_ref()
+        .and_then(|r| r.alignment.as_ref())
+    {
+        Some(alignment) => ColumnAlignment {
+            name: parse_alignment(alignment.name.as_deref(), "name")?,
+            shortcut: parse_alignment(alignment.shortcut.as_deref(), "shortcut")?,
+            description: parse_alignment(alignment.description.as_deref(), "description")?,
+        },
+        None => ColumnAlignment::default(),
+    };
+
+    let max_width = match config_toml.recall.as_ref().and_then(|r| r.max_width) {
+        Some(0) => bail!("The maximum width must be at least one column"),
+        max_width => max_width,
+    };
+
+    let idle_timeout = match config_toml.recall.as_ref().and_then(|r| r.idle_timeout) {
+        Some(0) => bail!("The idle timeout must be at least one minute"),
+        idle_timeout => idle_timeout,
+    };
+
+    let tick_rate = match config_toml.recall.as_ref().and_then(|r| r.tick_rate) {
+        Some(0) => bail!("The tick rate must be at least one millisecond"),
+        tick_rate => tick_rate,
+    };
+
+    let keybinds = config_toml
+        .recall
+        .as_ref()
+        .and_then(|r| r.keybinds.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|(action, keys)| match keys {
+            KeybindToml::Single(key) => (action.clone(), vec![key.clone()]),
+            KeybindToml::Multiple(keys) => (action.clone(), keys.clone()),
+        })
+        .collect();
+    let preset = match config_toml
+        .recall
+        .as_ref()
+        .and_then(|r| r.keymap_preset.as_deref())
+    {
+        Some(name) => Some(
+            Preset::from_name(name)
+                .ok_or(anyhow!("Unknown keymap preset {}, expected vim", name))?,
+        ),
+        None => None,
+    };
+    let keybinds = Keymap::new(preset, keybinds)?;
+
     let config = Config {
+        theme: theme.map(|theme| theme.name.to_string()),
         primary_color,
         highlight_color,
+        language,
+        plain: config_toml
+            .recall
+            .as_ref()
+            .and_then(|r| r.plain)
+            .unwrap_or(false),
+        show_names: config_toml
+            .recall
+            .as_ref()
+            .and_then(|r| r.show_names)
+            .unwrap_or(false),
+        alignment,
+        max_width,
+        idle_timeout,
+        idle_action,
+        tick_rate,
+        mouse: config_toml
+            .recall
+            .as_ref()
+            .and_then(|r| r.mouse)
+            .unwrap_or(true),
+        wrap_pages: config_toml
+            .recall
+            .as_ref()
+            .and_then(|r| r.wrap_pages)
+            .unwrap_or(false),
+        keyboard,
+        graphics,
+        age_identity,
+        keybinds,
         pages,
     };
 
@@ -121,11 +547,16 @@ pub fn read_from_config(path: PathBuf) -> Result<Config> {
     Ok(config)
 }
 
-/// Creates an example config file at the specified path.
+/// Creates a config file at the specified path, using the given theme if any.
 ///
-/// This function writes a TOML-formatted string with example content to disk.
+/// The config consists of the pages of the given templates, or of example content if no templates are given.
+/// Missing parent directories are created.
 /// Returns an Error if the file already exist, the path is invalid or the writing to disk fails.
-pub fn init_config(path: PathBuf) -> Result<String> {
+pub fn init_config(
+    path: PathBuf,
+    theme: Option<&Theme>,
+    templates: &[&Template],
+) -> Result<String> {
     let path_str = path.to_str().unwrap_or("Non UTF-8 path");
     info!("Reading config from {}", path_str);
 
@@ -135,11 +566,48 @@ pub fn init_config(path: PathBuf) -> Result<String> {
     {
         bail!("Path {} already exists!", path_str)
     }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .context(format!("Failed to create directory {}", parent.display()))?;
+    }
+
+    if !templates.is_empty() {
+        let mut config_toml_str = serialize_init_settings(theme);
+        for template in templates {
+            config_toml_str.push_str(template.pages.trim_start());
+            config_toml_str.push('\n');
+        }
+        parse_pages(&config_toml_str).context("Templates would make an invalid config")?;
+
+        fs::write(&path, config_toml_str)?;
+
+        let names: Vec<&str> = templates.iter().map(|template| template.name).collect();
+        return Ok(format!(
+            "Created config with {} in {}",
+            names.join(", "),
+            path_str
+        ));
+    }
 
     // Exemplary config that should cover all features
     let config = Config {
+        theme: theme.map(|theme| theme.name.to_string()),
         primary_color: DEFAULT_PRIMARY_COLOR,
         highlight_color: DEFAULT_SECONDARY_COLOR,
+        language: DEFAULT_LANGUAGE.to_string(),
+        plain: false,
+        show_names: false,
+        alignment: ColumnAlignment::default(),
+        max_width: None,
+        idle_timeout: None,
+        idle_action: IdleAction::default(),
+        tick_rate: None,
+        mouse: true,
+        wrap_pages: false,
+        keyboard: None,
+        graphics: None,
+        age_identity: None,
+        keybinds: Keymap::default(),
         pages: vec![
             Page {
                 name: String::from("General"),
@@ -148,17 +616,20 @@ pub fn init_config(path: PathBuf) -> Result<String> {
                         name: String::from("Copy"),
                         content: vec![String::from("Ctrl"), String::from("C")],
                         description: String::from("Copies the current selection."),
+                        ..Entry::default()
                     },
                     Entry {
                         name: String::from("RecallClose"),
                         content: vec![String::from("q")], // This should become just a string instead of a one-element vector of strings
                         description: String::from("Closes recall"),
+                        ..Entry::default()
                     },
                 ],
+                ..Page::default()
             },
             Page {
                 name: "EmptyPage".to_owned(),
-                entries: vec![],
+                ..Page::default()
             },
         ],
     };
@@ -170,6 +641,50 @@ pub fn init_config(path: PathBuf) -> Result<String> {
     Ok(format!("Created example config in {}", path_str))
 }
 
+/// Writes the content to the config at the given path while holding an exclusive advisory lock on it,
+/// so several instances of recall don't write the config at the same time.
+pub fn write_locked(path: &Path, content: &str) -> Result<()> {
+    let path_str = path.to_str().unwrap_or("Non UTF-8 path");
+
+    // Truncating only after the lock is taken keeps the config intact while another instance writes it
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .context(format!("Failed to open config {}", path_str))?;
+    file.lock()
+        .context(format!("Failed to lock config {}", path_str))?;
+
+    file.set_len(0)
+        .and_then(|_| file.write_all(content.as_bytes()))
+        .context(format!("Failed to write config to {}", path_str))
+}
+
+/// Returns a hash of the content of the config at the given path, or `None` if it can't be read.
+///
+/// Comparing it to the hash taken when the config was read tells whether another program changed it since.
+pub fn config_fingerprint(path: &Path) -> Option<u64> {
+    let content = fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Parses pages written in the format of the config, e.g. converted by a plugin.
+///
+/// A `recall` table with settings is ignored, commands of pages aren't run.
+pub fn parse_pages(content: &str) -> Result<Vec<Page>> {
+    let config_toml = build_config_toml(parse_toml(content)?)?;
+    let mut pages: Vec<Page> = config_toml
+        .pages
+        .into_iter()
+        .map(|(name, page)| build_page(name, page))
+        .collect();
+    attach_notes(&mut pages, content)?;
+    Ok(pages)
+}
+
 /// Reads the config file from disk and returns its contents as a string.
 fn read_file(path: &PathBuf, path_str: &str) -> Result<String> {
     fs::read_to_string(path).context(format!("Failed to read config from {}", path_str))
@@ -180,6 +695,73 @@ fn parse_toml(content: &str) -> Result<Table> {
     toml::from_str::<Table>(content).context("Failed to read toml".to_string())
 }
 
+/// Sets the notes of the entries on the given pages from the comments directly above them in the TOML string.
+///
+/// Comments are dropped when deserializing, so the string is parsed again as a document keeping them.
+fn attach_notes(pages: &mut [Page], content: &str) -> Result<()> {
+    let document: DocumentMut = content.parse().context("Failed to read toml")?;
+
+    for page in pages {
+        if let Some(table) = document.get(&page.name).and_then(Item::as_table_like) {
+            attach_page_notes(page, table);
+        }
+    }
+    Ok(())
+}
+
+/// Sets the notes of the entries on a page and its subpages from the comments in its table.
+fn attach_page_notes(page: &mut Page, table: &dyn TableLike) {
+    for (name, _) in table.iter() {
+        let Some((key, item)) = table.get_key_value(name) else {
+            continue;
+        };
+        let Some(entry_table) = item.as_table_like() else {
+            continue;
+        };
+
+        if !entry_table.contains_key("content") && !entry_table.contains_key(ENCRYPTED_CONTENT_KEY)
+        {
+            if let Some(subpage) = page.subpages.iter_mut().find(|s| s.name == name) {
+                attach_page_notes(subpage, entry_table);
+            }
+            continue;
+        }
+
+        // Entries written as tables have their comments above the header instead of the key
+        let prefix = match item {
+            Item::Table(entry_table) => entry_table.decor().prefix(),
+            _ => key.leaf_decor().prefix(),
+        };
+        if let Some(entry) = page.entries.iter_mut().find(|entry| entry.name == name) {
+            entry.note = note(prefix);
+        }
+    }
+}
+
+/// Returns the comment lines directly above an item without their `#`, or `None` if there are none.
+///
+/// Comments separated from the item by an empty line belong to something else, e.g. a group of entries.
+fn note(prefix: Option<&RawString>) -> Option<String> {
+    let mut lines: Vec<&str> = prefix?.as_str()?.split('\n').collect();
+    // The last line is the indentation in front of the item
+    lines.pop();
+
+    let mut comments: Vec<&str> = lines
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with('#'))
+        .map(|line| {
+            let comment = &line[1..];
+            comment.strip_prefix(' ').unwrap_or(comment)
+        })
+        .collect();
+    comments.reverse();
+
+    let note = comments.join("\n").trim_matches('\n').to_string();
+    (!note.is_empty()).then_some(note)
+}
+
 /// Constructs a `ConfigToml` from a parsed TOML table. Separates global settings from a "recall" table and pages from other tables.
 fn build_config_toml(toml_table: Table) -> Result<ConfigToml> {
     let mut config_toml = ConfigToml {
@@ -195,9 +777,10 @@ fn build_config_toml(toml_table: Table) -> Result<ConfigToml> {
                     .context("Failed to parse recall settings")?,
             );
         } else {
-            let page_toml = value
-                .try_into()
-                .context(format!("Failed to parse page {}", name))?;
+            let Value::Table(table) = value else {
+                bail!("Failed to parse page {}, expected a table", name);
+            };
+            let page_toml = build_page_toml(&name, table)?;
             config_toml.pages.insert(name, page_toml);
         }
     }
@@ -205,14 +788,534 @@ fn build_config_toml(toml_table: Table) -> Result<ConfigToml> {
     Ok(config_toml)
 }
 
-/// Converts a page definition from TOML into a `Page`
-fn build_page(name: String, page: IndexMap<String, EntryToml>) -> Page {
-    let entries = page
+/// Constructs a `PageToml` from a TOML table, separating entries from nested subpages.
+///
+/// Tables with a `content` key are entries, all other tables are subpages.
+/// Values that are not tables are settings of the page, like its group.
+fn build_page_toml(name: &str, table: Table) -> Result<PageToml> {
+    let mut page_toml = PageToml::default();
+
+    for (key, value) in table {
+        let table = match (key.as_str(), value) {
+            (_, Value::Table(table)) => table,
+            (PAGE_GROUP_KEY, Value::String(group)) => {
+                page_toml.group = Some(group);
+                continue;
+            }
+            (PAGE_GROUP_KEY, _) => {
+                bail!("Failed to parse group of page {}, expected a string", name)
+            }
+            (PAGE_DESCRIPTION_KEY, Value::String(description)) => {
+                page_toml.description = Some(description);
+                continue;
+            }
+            (PAGE_IMAGE_KEY, Value::String(image)) => {
+                page_toml.image = Some(image);
+                continue;
+            }
+            (PAGE_IMAGE_KEY, _) => {
+                bail!("Failed to parse image of page {}, expected a path", name)
+            }
+            (PAGE_DESCRIPTION_KEY, _) => {
+                bail!(
+                    "Failed to parse description of page {}, expected a string",
+                    name
+                )
+            }
+            (PAGE_SORT_KEY, Value::String(sort)) => {
+                page_toml.sort = SortOrder::from_name(&sort).ok_or(anyhow!(
+                    "Unknown sort order {} of page {}, expected manual, alphabetical or shortcut",
+                    sort,
+                    name
+                ))?;
+                continue;
+            }
+            (PAGE_SORT_KEY, _) => {
+                bail!(
+                    "Failed to parse sort order of page {}, expected a string",
+                    name
+                )
+            }
+            (PAGE_ALIASES_KEY, aliases @ Value::Array(_)) => {
+                page_toml.aliases = aliases.try_into().context(format!(
+                    "Failed to parse aliases of page {}, expected strings",
+                    name
+                ))?;
+                continue;
+            }
+            (PAGE_ALIASES_KEY, _) => {
+                bail!(
+                    "Failed to parse aliases of page {}, expected an array",
+                    name
+                )
+            }
+            (PAGE_VARIABLES_KEY, variables @ Value::Array(_)) => {
+                page_toml.variables = variables.try_into().context(format!(
+                    "Failed to parse variables of page {}, expected tables with a name and command",
+                    name
+                ))?;
+                continue;
+            }
+            (PAGE_VARIABLES_KEY, _) => {
+                bail!(
+                    "Failed to parse variables of page {}, expected an array",
+                    name
+                )
+            }
+            (PAGE_COMMAND_KEY, Value::String(command)) => {
+                page_toml.command = Some(command);
+                continue;
+            }
+            (PAGE_COMMAND_KEY, _) => {
+                bail!(
+                    "Failed to parse command of page {}, expected a string",
+                    name
+                )
+            }
+            (PAGE_PATTERN_KEY, Value::String(pattern)) => {
+                page_toml.pattern = Some(pattern);
+                continue;
+            }
+            (PAGE_PATTERN_KEY, _) => {
+                bail!(
+                    "Failed to parse pattern of page {}, expected a string",
+                    name
+                )
+            }
+            (PAGE_REFRESH_KEY, Value::String(refresh)) => {
+                let refresh = parse_duration(&refresh)
+                    .filter(|refresh| !refresh.is_zero())
+                    .ok_or(anyhow!(
+                        "Invalid refresh interval {} of page {}, expected e.g. 30s, 5m or 1h",
+                        refresh,
+                        name
+                    ))?;
+                page_toml.refresh = Some(refresh);
+                continue;
+            }
+            (PAGE_REFRESH_KEY, _) => {
+                bail!(
+                    "Failed to parse refresh interval of page {}, expected a string",
+                    name
+                )
+            }
+            (PAGE_GENERATOR_KEY, Value::String(generator)) => {
+                page_toml.generator = Some(generator);
+                continue;
+            }
+            (PAGE_GENERATOR_KEY, _) => {
+                bail!(
+                    "Failed to parse generator of page {}, expected a string",
+                    name
+                )
+            }
+            (PAGE_SHOW_NAMES_KEY, Value::Boolean(show_names)) => {
+                page_toml.show_names = Some(show_names);
+                continue;
+            }
+            (PAGE_SHOW_NAMES_KEY, _) => {
+                bail!(
+                    "Failed to parse show_names of page {}, expected true or false",
+                    name
+                )
+            }
+            _ => bail!(
+                "Failed to parse entry {} of page {}, expected a table",
+                key,
+                name
+            ),
+        };
+
+        if table.contains_key("content") || table.contains_key(ENCRYPTED_CONTENT_KEY) {
+            let entry: EntryToml = Value::Table(table)
+                .try_into()
+                .context(format!("Failed to parse entry {} of page {}", key, name))?;
+            if !entry.content.is_empty() && entry.content_encrypted.is_some() {
+                bail!(
+                    "Entry {} of page {} has both content and encrypted content, only one is used",
+                    key,
+                    name
+                );
+            }
+            page_toml.entries.insert(key, entry);
+        } else {
+            let subpage = build_page_toml(&format!("{}.{}", name, key), table)?;
+            page_toml.subpages.insert(key, subpage);
+        }
+    }
+
+    if page_toml.command.is_some() && !page_toml.entries.is_empty() {
+        bail!(
+            "Page {} has both a command and entries, its entries are generated by the command",
+            name
+        );
+    }
+    if page_toml.pattern.is_some() && page_toml.command.is_none() {
+        bail!(
+            "Page {} has a pattern but no command to match it against",
+            name
+        );
+    }
+    if page_toml.refresh.is_some() && page_toml.command.is_none() {
+        bail!("Page {} has a refresh interval but no command to run", name);
+    }
+    if page_toml.generator.is_some() && !page_toml.entries.is_empty() {
+        bail!(
+            "Page {} has both a generator and entries, its entries are generated by the plugin",
+            name
+        );
+    }
+    if page_toml.generator.is_some() && page_toml.command.is_some() {
+        bail!(
+            "Page {} has both a command and a generator, only one can generate its entries",
+            name
+        );
+    }
+
+    Ok(page_toml)
+}
+
+/// Converts a page definition from TOML into a `Page`, including its subpages
+///
+/// Entries are sorted according to the sort order of the page.
+fn build_page(name: String, page: PageToml) -> Page {
+    let mut entries: Vec<Entry> = page
+        .entries
         .into_iter()
         .map(|(entry_name, entry)| build_entry(entry_name.to_string(), entry))
         .collect();
 
-    Page { name, entries }
+    page.sort.sort(&mut entries);
+
+    let subpages = page
+        .subpages
+        .into_iter()
+        .map(|(subpage_name, subpage)| build_page(subpage_name, subpage))
+        .collect();
+
+    let variables = page
+        .variables
+        .into_iter()
+        .map(|variable| Variable {
+            name: variable.name,
+            command: variable.command,
+        })
+        .collect();
+
+    let source = page.command.map(|command| PageSource {
+        command,
+        pattern: page.pattern,
+        refresh: page.refresh,
+        ran: None,
+        updated: None,
+    });
+
+    Page {
+        name,
+        group: page.group,
+        description: page.description,
+        image: page.image,
+        aliases: page.aliases,
+        sort: page.sort,
+        entries,
+        subpages,
+        variables,
+        source,
+        generator: page.generator,
+        show_names: page.show_names,
+    }
+}
+
+/// Parses the alignment of the given column, which is left-aligned if none is set.
+fn parse_alignment(name: Option<&str>, column: &str) -> Result<Alignment> {
+    match name {
+        None | Some("left") => Ok(Alignment::Left),
+        Some("center") => Ok(Alignment::Center),
+        Some("right") => Ok(Alignment::Right),
+        Some(name) => bail!(
+            "Unknown alignment {} of the {} column, expected left, center or right",
+            name,
+            column
+        ),
+    }
+}
+
+/// Returns the name of an alignment as written in the config.
+fn alignment_name(alignment: Alignment) -> &'static str {
+    match alignment {
+        Alignment::Left => "left",
+        Alignment::Center => "center",
+        Alignment::Right => "right",
+    }
+}
+
+/// Serializes an alignment by its name as written in the config, e.g. for `recall export json`.
+pub fn serialize_alignment<S: Serializer>(
+    alignment: &Alignment,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(alignment_name(*alignment))
+}
+
+/// Serializes a color by its ANSI color code as written in the config, or as `null` if it has none.
+pub fn serialize_color<S: Serializer>(
+    color: &Color,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    ansi_index(*color).serialize(serializer)
+}
+
+/// Serializes an optional duration as written in the config, e.g. `5m`.
+pub fn serialize_duration<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    duration.map(format_duration).serialize(serializer)
+}
+
+/// Runs the commands of a page and its subpages, filling them with the generated entries.
+///
+/// The path of the page is used for messages.
+/// A failing command leaves its page empty instead of keeping recall from starting, but an invalid pattern is an error.
+fn generate_entries(page: &mut Page, path: &str) -> Result<()> {
+    for subpage in &mut page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        generate_entries(subpage, &subpage_path)?;
+    }
+
+    let Some(source) = &mut page.source else {
+        return Ok(());
+    };
+
+    // Checked up front, so a mistake in the pattern is reported even if the command fails
+    compile_pattern(source, path)?;
+
+    match run_page_source(source, path) {
+        Result::Ok(entries) => {
+            page.entries = entries;
+            page.sort.sort(&mut page.entries);
+            source.updated = Some(Instant::now());
+        }
+        Err(error) => warn!("{:#}", error),
+    }
+    source.ran = Some(Instant::now());
+
+    Ok(())
+}
+
+/// Checks the patterns of the commands of a page and its subpages without running them, see [`generate_entries`].
+fn check_patterns(page: &Page, path: &str) -> Result<()> {
+    for subpage in &page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        check_patterns(subpage, &subpage_path)?;
+    }
+
+    if let Some(source) = &page.source {
+        compile_pattern(source, path)?;
+    }
+    Ok(())
+}
+
+/// Runs the command of a page and returns the entries generated from the lines it printed.
+///
+/// The path of the page is used for messages.
+pub fn run_page_source(source: &PageSource, path: &str) -> Result<Vec<Entry>> {
+    let pattern = compile_pattern(source, path)?;
+
+    info!("Running command of page {}: {}", path, source.command);
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", &source.command]).output()
+    } else {
+        Command::new("sh").args(["-c", &source.command]).output()
+    }
+    .context(format!("Failed to run command of page {}", path))?;
+
+    if !output.status.success() {
+        bail!(
+            "Command of page {} failed with {}: {}",
+            path,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let mut page = Page::default();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        // Without a pattern, the first column is the content and the rest of the line its description
+        let (content, description) = match &pattern {
+            Some(regex) => {
+                let Some(captures) = regex.captures(line) else {
+                    debug!("Skipping line not matching the pattern: {}", line);
+                    continue;
+                };
+                let group = |name| captures.name(name).map_or("", |m| m.as_str()).trim();
+                (
+                    group(PATTERN_CONTENT_GROUP),
+                    group(PATTERN_DESCRIPTION_GROUP),
+                )
+            }
+            None => {
+                let line = line.trim();
+                line.split_once(char::is_whitespace)
+                    .map_or((line, ""), |(content, description)| {
+                        (content, description.trim())
+                    })
+            }
+        };
+
+        let entry = Entry {
+            name: content.to_string(),
+            content: vec![content.to_string()],
+            description: description.to_string(),
+            ..Entry::default()
+        };
+        add_entry(&mut page, entry);
+    }
+
+    Ok(page.entries)
+}
+
+/// Compiles the pattern of a page's command, checking that it captures the content of entries.
+fn compile_pattern(source: &PageSource, path: &str) -> Result<Option<Regex>> {
+    let Some(pattern) = &source.pattern else {
+        return Ok(None);
+    };
+
+    let regex = Regex::new(pattern).context(format!("Invalid pattern of page {}", path))?;
+    if !regex
+        .capture_names()
+        .any(|name| name == Some(PATTERN_CONTENT_GROUP))
+    {
+        bail!(
+            "Pattern of page {} has no group named {}, e.g. (?P<{}>...)",
+            path,
+            PATTERN_CONTENT_GROUP,
+            PATTERN_CONTENT_GROUP
+        );
+    }
+
+    Ok(Some(regex))
+}
+
+/// Parses a duration like `30s`, `5m` or `1h`, a number without unit is in seconds.
+fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let (number, seconds_per_unit) = match text.char_indices().last()? {
+        (i, 's') => (&text[..i], 1),
+        (i, 'm') => (&text[..i], 60),
+        (i, 'h') => (&text[..i], 60 * 60),
+        _ => (text, 1),
+    };
+
+    let number: u64 = number.trim().parse().ok()?;
+    number
+        .checked_mul(seconds_per_unit)
+        .map(Duration::from_secs)
+}
+
+/// Formats a duration in the largest unit it is a whole multiple of, e.g. `5m` for 300 seconds.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds.is_multiple_of(60 * 60) {
+        format!("{}h", seconds / (60 * 60))
+    } else if seconds.is_multiple_of(60) {
+        format!("{}m", seconds / 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Checks that all entries of a page and its subpages only link to existing pages.
+///
+/// The path of the page is used for error messages.
+fn validate_links(pages: &[Page], page: &Page, path: &str) -> Result<()> {
+    for entry in &page.entries {
+        if let Some(target) = &entry.goto {
+            if find_page(pages, target).is_none() {
+                bail!(
+                    "Entry {} of page {} links to unknown page {}",
+                    entry.name,
+                    path,
+                    target
+                );
+            }
+        }
+    }
+
+    for subpage in &page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        validate_links(pages, subpage, &subpage_path)?;
+    }
+
+    Ok(())
+}
+
+/// Returns all pairs of entries that share a shortcut, either on the same page or anywhere if `across_pages` is set.
+///
+/// Shortcuts are compared in their canonical form, so `C-x` and `Ctrl+X` conflict.
+/// Each further use of a shortcut is reported together with its first use. Entries without content are ignored.
+pub fn find_shortcut_conflicts(
+    pages: &[Page],
+    across_pages: bool,
+    keyboard: Keyboard,
+) -> Vec<ShortcutConflict> {
+    let mut entries = vec![];
+    for page in pages {
+        collect_entries(page, page.name.clone(), &mut entries);
+    }
+
+    let mut first_uses: IndexMap<(String, String), String> = IndexMap::new();
+    let mut conflicts = vec![];
+
+    for (path, entry) in entries {
+        if entry.content.is_empty() {
+            continue;
+        }
+
+        let shortcut = normalize_content(&entry.content, keyboard).join("+");
+        let scope = if across_pages {
+            String::new()
+        } else {
+            path.clone()
+        };
+        let location = format!("{} on page {}", entry.name, path);
+
+        match first_uses.get(&(scope.clone(), shortcut.to_lowercase())) {
+            Some(first) => conflicts.push(ShortcutConflict {
+                shortcut,
+                first: first.clone(),
+                second: location,
+            }),
+            None => {
+                first_uses.insert((scope, shortcut.to_lowercase()), location);
+            }
+        }
+    }
+
+    conflicts
+}
+
+/// Returns the number of entries on the given pages and their subpages.
+pub fn count_entries(pages: &[Page]) -> usize {
+    let mut entries = vec![];
+    for page in pages {
+        collect_entries(page, page.name.clone(), &mut entries);
+    }
+    entries.len()
+}
+
+/// Collects the entries of a page and its subpages along with the path of the page they are on.
+pub fn collect_entries<'a>(page: &'a Page, path: String, entries: &mut Vec<(String, &'a Entry)>) {
+    entries.extend(page.entries.iter().map(|entry| (path.clone(), entry)));
+
+    for subpage in &page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        collect_entries(subpage, subpage_path, entries);
+    }
 }
 
 /// Converts an entry definition from TOML into an `Entry`
@@ -221,9 +1324,208 @@ fn build_entry(name: String, entry: EntryToml) -> Entry {
         name,
         content: entry.content,
         description: entry.description,
+        hidden: entry.hidden,
+        secret: entry.secret,
+        encrypted: entry.content_encrypted,
+        goto: entry.goto,
+        exec: entry.exec,
+        url: entry.url,
+        section: entry.section,
+        image: entry.image,
+        note: None,
+    }
+}
+
+/// Serializes pages as TOML tables, e.g. to print a drafted page.
+pub fn serialize_pages(pages: &[Page]) -> String {
+    let mut str = String::new();
+    for page in pages {
+        serialize_page(&mut str, &[], page);
+    }
+    str
+}
+
+/// Serializes a page as a TOML table into the given string, followed by its subpages as nested tables.
+///
+/// The names of the enclosing pages are used to build the dotted table header.
+fn serialize_page(str: &mut String, parents: &[&str], page: &Page) {
+    let path: Vec<&str> = parents
+        .iter()
+        .copied()
+        .chain([page.name.as_str()])
+        .collect();
+
+    if !str.is_empty() {
+        str.push('\n');
+    }
+    let header = path
+        .iter()
+        .map(|name| toml_key(name))
+        .collect::<Vec<_>>()
+        .join(".");
+    str.push_str(&format!("[{}]\n", header));
+
+    if let Some(group) = &page.group {
+        str.push_str(&format!("{} = {}\n", PAGE_GROUP_KEY, toml_string(group)));
+    }
+    if let Some(description) = &page.description {
+        str.push_str(&format!(
+            "{} = {}\n",
+            PAGE_DESCRIPTION_KEY,
+            toml_string(description)
+        ));
+    }
+    if let Some(image) = &page.image {
+        str.push_str(&format!("{} = {}\n", PAGE_IMAGE_KEY, toml_string(image)));
+    }
+    if !page.aliases.is_empty() {
+        let aliases = page
+            .aliases
+            .iter()
+            .map(|alias| toml_string(alias))
+            .collect::<Vec<_>>()
+            .join(", ");
+        str.push_str(&format!("{} = [{}]\n", PAGE_ALIASES_KEY, aliases));
+    }
+    if page.sort != SortOrder::Manual {
+        str.push_str(&format!(
+            "{} = {}\n",
+            PAGE_SORT_KEY,
+            toml_string(page.sort.name())
+        ));
+    }
+    if let Some(show_names) = page.show_names {
+        str.push_str(&format!("{} = {}\n", PAGE_SHOW_NAMES_KEY, show_names));
+    }
+    if let Some(source) = &page.source {
+        str.push_str(&format!(
+            "{} = {}\n",
+            PAGE_COMMAND_KEY,
+            toml_string(&source.command)
+        ));
+        if let Some(pattern) = &source.pattern {
+            str.push_str(&format!(
+                "{} = {}\n",
+                PAGE_PATTERN_KEY,
+                toml_string(pattern)
+            ));
+        }
+        if let Some(refresh) = source.refresh {
+            str.push_str(&format!(
+                "{} = {}\n",
+                PAGE_REFRESH_KEY,
+                toml_string(&format_duration(refresh))
+            ));
+        }
+    }
+
+    if let Some(generator) = &page.generator {
+        str.push_str(&format!(
+            "{} = {}\n",
+            PAGE_GENERATOR_KEY,
+            toml_string(generator)
+        ));
+    }
+
+    // Generated entries are replaced on the next load, so only their command or generator is written
+    let entries = if page.is_generated() {
+        &[][..]
+    } else {
+        &page.entries[..]
+    };
+    for entry in entries {
+        // Encrypted content is written as it was read, the decrypted content never goes into the config
+        let content = match &entry.encrypted {
+            Some(encrypted) => format!("{} = {}", ENCRYPTED_CONTENT_KEY, toml_string(encrypted)),
+            None => format!(
+                "content = [{}]",
+                entry
+                    .content
+                    .iter()
+                    .map(|key| toml_string(key))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        let mut options = String::new();
+        if entry.hidden {
+            options.push_str(", hidden = true");
+        }
+        if entry.secret {
+            options.push_str(", secret = true");
+        }
+        if let Some(goto) = &entry.goto {
+            options.push_str(&format!(", goto = {}", toml_string(goto)));
+        }
+        if let Some(exec) = &entry.exec {
+            options.push_str(&format!(", exec = {}", toml_string(exec)));
+        }
+        if let Some(url) = &entry.url {
+            options.push_str(&format!(", url = {}", toml_string(url)));
+        }
+        if let Some(section) = &entry.section {
+            options.push_str(&format!(", section = {}", toml_string(section)));
+        }
+        if let Some(image) = &entry.image {
+            options.push_str(&format!(", image = {}", toml_string(image)));
+        }
+
+        if let Some(note) = &entry.note {
+            for line in note.lines() {
+                str.push_str(format!("# {}", line).trim_end());
+                str.push('\n');
+            }
+        }
+        str.push_str(&format!(
+            "{} = {{ {}, description = {}{} }}\n",
+            toml_key(&entry.name),
+            content,
+            toml_string(&entry.description),
+            options
+        ));
+    }
+
+    if !page.variables.is_empty() {
+        let variables = page
+            .variables
+            .iter()
+            .map(|variable| {
+                format!(
+                    "{{ name = {}, command = {} }}",
+                    toml_string(&variable.name),
+                    toml_string(&variable.command)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        str.push_str(&format!("{} = [{}]\n", PAGE_VARIABLES_KEY, variables));
+    }
+
+    for subpage in &page.subpages {
+        serialize_page(str, &path, subpage);
+    }
+}
+
+/// Formats a string as a TOML key, quoting it if it is not a valid bare key.
+fn toml_key(key: &str) -> String {
+    let is_bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if is_bare {
+        key.to_string()
+    } else {
+        toml_string(key)
     }
 }
 
+/// Formats a string as a quoted and escaped TOML string.
+fn toml_string(value: &str) -> String {
+    toml::Value::from(value).to_string()
+}
+
 /// Tracks whether a specific Hint has already been given or not.
 ///
 /// This enum is used to prevent duplicate comments when generating an exemplary config output.
@@ -235,6 +1537,33 @@ enum Hint {
     NotYetOutput,
 }
 
+/// Serializes the settings of a new config, using the given theme or the default colors, annotated with usage hints.
+fn serialize_init_settings(theme: Option<&Theme>) -> String {
+    let mut str = String::new();
+
+    str.push_str("# Global settings for recall\n");
+    str.push_str("[recall]\n");
+    match theme {
+        Some(theme) => {
+            str.push_str(&format!("# Built-in themes are {}\n", theme_names()));
+            str.push_str("# Colors can be adjusted in the settings opened with t\n");
+            str.push_str(&format!("theme = {}\n", toml_string(theme.name)));
+        }
+        None => {
+            str.push_str("# Colors are u8-encoded numbers as per the ANSI Color Table\n");
+
+            // Ratatui colors support more than u8-encoded numbers, therefore we cannot convert between them.
+            // We substitute default colors here and ignore the config supplied ones.
+            // In the future, we could try to find a ANSI-color that closely matches the supplied one.
+            str.push_str(&format!("primary_color = {}\n", 15));
+            str.push_str(&format!("highlight_color = {}\n", 14));
+        }
+    }
+    str.push('\n');
+
+    str
+}
+
 /// Serializes a given config into a TOML-formatted string annotated with usage hints.
 fn serialize_init_config(config: &Config) -> String {
     let mut str = String::new();
@@ -245,16 +1574,9 @@ fn serialize_init_config(config: &Config) -> String {
     let mut description_hint = Hint::NotYetOutput;
     let mut empty_table_hint = Hint::NotYetOutput;
 
-    str.push_str("# Global settings for recall\n");
-    str.push_str("[recall]\n");
-    str.push_str("# Colors are u8-encoded numbers as per the ANSI Color Table\n");
-
-    // Ratatui colors support more than u8-encoded numbers, therefore we cannot convert between them.
-    // We substitute default colors here and ignore the config supplied ones.
-    // In the future, we could try to find a ANSI-color that closely matches the supplied one.
-    str.push_str(&format!("primary_color = {}\n", 15));
-    str.push_str(&format!("highlight_color = {}\n", 14));
-    str.push('\n');
+    str.push_str(&serialize_init_settings(
+        config.theme.as_deref().and_then(find_theme),
+    ));
 
     for page in &config.pages {
         if matches!(subtable_hint, Hint::NotYetOutput) {
@@ -0,0 +1,186 @@
+//! Keyboard diagram module for Recall.
+//!
+//! Draws a keyboard in the US layout with the keys of shortcuts highlighted, so it can be seen where chords lie
+//! on the board. Symbols typed with Shift, like `?`, highlight the key they are on together with Shift.
+
+use ratatui::{
+    style::{Color, Stylize},
+    text::{Line, Span},
+};
+
+use crate::keys::Keyboard;
+
+/// A key of the diagram, named by its label and the canonical names of the keys it types.
+type Key = (&'static str, &'static [&'static str]);
+
+/// Rows of the diagram above the row of modifiers
+const ROWS: [&[Key]; 5] = [
+    &[
+        ("Esc", &["Esc"]),
+        ("F1", &["F1"]),
+        ("F2", &["F2"]),
+        ("F3", &["F3"]),
+        ("F4", &["F4"]),
+        ("F5", &["F5"]),
+        ("F6", &["F6"]),
+        ("F7", &["F7"]),
+        ("F8", &["F8"]),
+        ("F9", &["F9"]),
+        ("F10", &["F10"]),
+        ("F11", &["F11"]),
+        ("F12", &["F12"]),
+    ],
+    &[
+        ("`", &["`", "~"]),
+        ("1", &["1", "!"]),
+        ("2", &["2", "@"]),
+        ("3", &["3", "#"]),
+        ("4", &["4", "$"]),
+        ("5", &["5", "%"]),
+        ("6", &["6", "^"]),
+        ("7", &["7", "&"]),
+        ("8", &["8", "*"]),
+        ("9", &["9", "("]),
+        ("0", &["0", ")"]),
+        ("-", &["-", "_"]),
+        ("=", &["=", "+"]),
+        ("Backspace", &["Backspace"]),
+        ("Ins", &["Ins"]),
+        ("Home", &["Home"]),
+        ("PgUp", &["PageUp"]),
+    ],
+    &[
+        ("Tab", &["Tab"]),
+        ("Q", &["Q"]),
+        ("W", &["W"]),
+        ("E", &["E"]),
+        ("R", &["R"]),
+        ("T", &["T"]),
+        ("Y", &["Y"]),
+        ("U", &["U"]),
+        ("I", &["I"]),
+        ("O", &["O"]),
+        ("P", &["P"]),
+        ("[", &["[", "{"]),
+        ("]", &["]", "}"]),
+        ("\\", &["\\", "|"]),
+        ("Del", &["Del"]),
+        ("End", &["End"]),
+        ("PgDn", &["PageDown"]),
+    ],
+    &[
+        ("Caps", &[]),
+        ("A", &["A"]),
+        ("S", &["S"]),
+        ("D", &["D"]),
+        ("F", &["F"]),
+        ("G", &["G"]),
+        ("H", &["H"]),
+        ("J", &["J"]),
+        ("K", &["K"]),
+        ("L", &["L"]),
+        (";", &[";", ":"]),
+        ("'", &["'", "\""]),
+        ("Enter", &["Enter"]),
+    ],
+    &[
+        ("Shift", &["Shift"]),
+        ("Z", &["Z"]),
+        ("X", &["X"]),
+        ("C", &["C"]),
+        ("V", &["V"]),
+        ("B", &["B"]),
+        ("N", &["N"]),
+        ("M", &["M"]),
+        (",", &[",", "<"]),
+        (".", &[".", ">"]),
+        ("/", &["/", "?"]),
+        ("Shift", &["Shift"]),
+        ("↑", &["Up"]),
+    ],
+];
+
+/// Row of modifiers of PC keyboards
+const PC_MODIFIERS: &[Key] = &[
+    ("Ctrl", &["Ctrl"]),
+    ("Super", &["Super"]),
+    ("Alt", &["Alt"]),
+    ("     Space     ", &["Space"]),
+    ("Alt", &["Alt"]),
+    ("Super", &["Super"]),
+    ("Ctrl", &["Ctrl"]),
+    ("←", &["Left"]),
+    ("↓", &["Down"]),
+    ("→", &["Right"]),
+];
+
+/// Row of modifiers of Apple keyboards
+const MAC_MODIFIERS: &[Key] = &[
+    ("Ctrl", &["Ctrl"]),
+    ("Alt", &["Alt"]),
+    ("Cmd", &["Cmd"]),
+    ("     Space     ", &["Space"]),
+    ("Cmd", &["Cmd"]),
+    ("Alt", &["Alt"]),
+    ("←", &["Left"]),
+    ("↓", &["Down"]),
+    ("→", &["Right"]),
+];
+
+/// Symbols typed by holding Shift
+const SHIFTED_SYMBOLS: &str = "~!@#$%^&*()_+{}|:\"<>?";
+
+/// Number of lines of the diagram
+pub const HEIGHT: u16 = 6;
+
+/// Returns the lines of the diagram for the given keyboard.
+///
+/// The keys in `pressed` are shown in the highlight color, reversed. Keys in `bound`, e.g. those of the other
+/// shortcuts on a page, are shown bold in the highlight color and all other keys dimmed in the primary color.
+pub fn diagram(
+    pressed: &[String],
+    bound: &[String],
+    keyboard: Keyboard,
+    primary_color: Color,
+    highlight_color: Color,
+) -> Vec<Line<'static>> {
+    let modifiers = match keyboard {
+        Keyboard::Pc => PC_MODIFIERS,
+        Keyboard::Mac => MAC_MODIFIERS,
+    };
+
+    ROWS.iter()
+        .copied()
+        .chain([modifiers])
+        .map(|row| {
+            let mut line = Line::default();
+            for (i, (label, names)) in row.iter().enumerate() {
+                if i > 0 {
+                    line.push_span(" ");
+                }
+                let key = format!(" {} ", label);
+                let span = if types_any(names, pressed) {
+                    Span::from(key).fg(highlight_color).bold().reversed()
+                } else if types_any(names, bound) {
+                    Span::from(key).fg(highlight_color).bold()
+                } else {
+                    Span::from(key).fg(primary_color).dim()
+                };
+                line.push_span(span);
+            }
+            line
+        })
+        .collect()
+}
+
+/// Returns `true` if the key types any of the given keys.
+///
+/// Shift is typed along with symbols that need it.
+fn types_any(names: &[&str], keys: &[String]) -> bool {
+    keys.iter().any(|key| {
+        names.contains(&key.as_str())
+            || (names.contains(&"Shift")
+                && key.chars().count() == 1
+                && key.chars().all(|c| SHIFTED_SYMBOLS.contains(c)))
+    })
+}
@@ -0,0 +1,38 @@
+//! Editor module for Recall.
+//!
+//! `recall edit` opens the config in the editor named by `$VISUAL` or `$EDITOR` like git and crontab do,
+//! falling back to `vi`. The variables may hold arguments, e.g. `code --wait`.
+
+use std::{env, path::Path, process::Command};
+
+use anyhow::{bail, Context, Result};
+use log::debug;
+
+/// Environment variables naming the editor, in order of preference
+const EDITOR_VARIABLES: [&str; 2] = ["VISUAL", "EDITOR"];
+
+/// Editor used if neither `$VISUAL` nor `$EDITOR` is set
+const DEFAULT_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
+
+/// Opens the file in the editor and waits until it is closed.
+pub fn edit(path: &Path) -> Result<()> {
+    let editor = EDITOR_VARIABLES
+        .iter()
+        .filter_map(|variable| env::var(variable).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR);
+
+    debug!("Opening {} in editor {}", path.display(), editor);
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .context(format!("Failed to start editor {}", editor))?;
+    if !status.success() {
+        bail!("Editor {} exited with {}", editor, status);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//! Encryption module for Recall.
+//!
+//! The content of entries can be stored encrypted with [age](https://age-encryption.org) as `content_encrypted`,
+//! so a config synced between machines can contain a few sensitive snippets.
+//! It is only decrypted where it is shown or used, i.e. in the interface and by `show`, `copy` and `exec`, by running the
+//! `age` command with the identity file set as `age_identity`, or by age asking for the passphrase on the terminal if
+//! the content was encrypted with one.
+//! The config keeps the encrypted content, recall never writes it back decrypted.
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+    sync::{LazyLock, Mutex},
+};
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info};
+
+use crate::app::{Page, PAGE_PATH_SEPARATOR};
+
+/// Program decrypting the content, looked up in `PATH`
+const AGE_PROGRAM: &str = "age";
+
+/// Content decrypted during this session by its encrypted form, so reloading the config doesn't ask for passphrases again
+static DECRYPTED: LazyLock<Mutex<HashMap<String, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Decrypts the content of the encrypted entries of the pages and their subpages, using the identity file if one is given.
+pub fn decrypt_pages(pages: &mut [Page], identity: Option<&Path>) -> Result<()> {
+    for page in pages {
+        decrypt_page(page, &page.name.clone(), identity)?;
+    }
+    Ok(())
+}
+
+/// Decrypts the content of the encrypted entries of the page at the given path and its subpages.
+fn decrypt_page(page: &mut Page, path: &str, identity: Option<&Path>) -> Result<()> {
+    for entry in &mut page.entries {
+        let Some(encrypted) = &entry.encrypted else {
+            continue;
+        };
+
+        let decrypted = decrypt(encrypted, identity).context(format!(
+            "Failed to decrypt entry {} of page {}",
+            entry.name, path
+        ))?;
+        // Each line is a part of the content, like the strings of `content`
+        entry.content = decrypted.lines().map(str::to_string).collect();
+    }
+
+    for subpage in &mut page.subpages {
+        let subpath = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        decrypt_page(subpage, &subpath, identity)?;
+    }
+    Ok(())
+}
+
+/// Returns the given content encrypted with age decrypted, using the identity file if one is given.
+///
+/// Without an identity file, age asks for the passphrase on the terminal.
+fn decrypt(encrypted: &str, identity: Option<&Path>) -> Result<String> {
+    if let Some(decrypted) = DECRYPTED
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(encrypted).cloned())
+    {
+        debug!("Content was already decrypted in this session");
+        return Ok(decrypted);
+    }
+
+    let mut command = Command::new(AGE_PROGRAM);
+    command.arg("--decrypt");
+    if let Some(identity) = identity {
+        command.arg("--identity").arg(identity);
+    }
+    info!("Decrypting content with {}", AGE_PROGRAM);
+
+    // Errors and the passphrase prompt of age go to the terminal
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .context(format!("Failed to run {}, is it installed?", AGE_PROGRAM))?;
+    child
+        .stdin
+        .take()
+        .context(format!("Failed to pass content to {}", AGE_PROGRAM))?
+        .write_all(format!("{}\n", encrypted.trim()).as_bytes())
+        .context(format!("Failed to pass content to {}", AGE_PROGRAM))?;
+
+    let output = child
+        .wait_with_output()
+        .context(format!("Failed to run {}", AGE_PROGRAM))?;
+    if !output.status.success() {
+        bail!("{} exited with {}", AGE_PROGRAM, output.status);
+    }
+    let decrypted = String::from_utf8(output.stdout).context("Decrypted content is not UTF-8")?;
+
+    if let Ok(mut cache) = DECRYPTED.lock() {
+        cache.insert(encrypted.to_string(), decrypted.clone());
+    }
+    Ok(decrypted)
+}
@@ -0,0 +1,361 @@
+//! Export module for Recall.
+//!
+//! Converts the pages of a configuration into formats of other tools,
+//! so they are generated from the same configuration that is shown by recall.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+
+use crate::{
+    app::{Config, Entry, Page, PAGE_PATH_SEPARATOR, SECRET_MASK},
+    config::collect_entries,
+    html,
+    keys::{normalize_content, Keyboard},
+    show,
+};
+
+/// Formats the configuration can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Tab-separated notes for the text importer of Anki, one card per entry
+    Anki,
+
+    /// Cheat file of navi, with the page paths as tags
+    Navi,
+
+    /// Colored text as printed by `recall show --all`, with clickable links
+    Ansi,
+
+    /// Markdown with a table of shortcuts and descriptions per page, e.g. for wikis
+    #[value(alias = "md")]
+    Markdown,
+
+    /// Standalone HTML cheatsheet in the configured colors, to host or print
+    Html,
+
+    /// The parsed config with its settings, pages and entries as JSON, e.g. for scripts and dashboards
+    Json,
+
+    /// The parsed config with its settings, pages and entries as YAML
+    Yaml,
+}
+
+/// Tag prefixed to the page path of every exported Anki note
+const ANKI_ROOT_TAG: &str = "recall";
+
+/// Returns the given pages of the config in the given format, with shortcuts written for the given keyboard.
+///
+/// The pages are exported with their path below the given parent pages, e.g. when a single subpage is exported.
+pub fn export(
+    config: &Config,
+    pages: &[Page],
+    parents: &[&str],
+    format: ExportFormat,
+    keyboard: Keyboard,
+) -> Result<String> {
+    Ok(match format {
+        ExportFormat::Anki => export_anki(pages, parents, keyboard),
+        ExportFormat::Navi => export_navi(pages, parents),
+        ExportFormat::Ansi => show::show(
+            pages,
+            parents,
+            config.show_names,
+            keyboard,
+            Some((config.primary_color, config.highlight_color)),
+        ),
+        ExportFormat::Markdown => export_markdown(pages, parents, config.show_names, keyboard),
+        ExportFormat::Html => html::cheatsheet(
+            pages,
+            parents,
+            config.show_names,
+            keyboard,
+            (config.primary_color, config.highlight_color),
+        ),
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(&exported_config(config, pages))
+                .context("Failed to write JSON")?
+                + "\n"
+        }
+        ExportFormat::Yaml => serde_yaml_ng::to_string(&exported_config(config, pages))
+            .context("Failed to write YAML")?,
+    })
+}
+
+/// Returns the config with only the given pages, whose secret entries are masked like in `recall show`.
+///
+/// Encrypted entries are decrypted when the config is read, so they are masked too, keeping only their ciphertext.
+fn exported_config(config: &Config, pages: &[Page]) -> Config {
+    let mut pages = pages.to_vec();
+    for page in &mut pages {
+        mask_secrets(page);
+    }
+    Config {
+        pages,
+        ..config.clone()
+    }
+}
+
+/// Replaces the content of the secret and encrypted entries of the page and its subpages by [`SECRET_MASK`].
+fn mask_secrets(page: &mut Page) {
+    for entry in &mut page.entries {
+        if entry.secret || entry.encrypted.is_some() {
+            entry.content = vec![SECRET_MASK.to_string()];
+        }
+    }
+    for subpage in &mut page.subpages {
+        mask_secrets(subpage);
+    }
+}
+
+/// Returns the entries of the given pages as notes importable by Anki.
+///
+/// The description is asked on the front and the shortcut is shown on the back of each card.
+/// Pages become hierarchical tags like `recall::git::branches`, so decks can be filtered by page.
+/// Entries without content, e.g. plain links to other pages, have nothing to drill and are left out.
+fn export_anki(pages: &[Page], parents: &[&str], keyboard: Keyboard) -> String {
+    let mut str = String::new();
+
+    // File headers understood by Anki since version 2.1.54
+    str.push_str("#separator:tab\n");
+    str.push_str("#html:false\n");
+    str.push_str("#notetype:Basic\n");
+    str.push_str("#columns:Front\tBack\tTags\n");
+    str.push_str("#tags column:3\n");
+
+    let mut entries = vec![];
+    for page in pages {
+        let path = parents
+            .iter()
+            .copied()
+            .chain([page.name.as_str()])
+            .collect::<Vec<_>>()
+            .join(PAGE_PATH_SEPARATOR);
+        collect_entries(page, path, &mut entries);
+    }
+
+    for (path, entry) in entries {
+        if entry.content.is_empty() {
+            continue;
+        }
+
+        let front = if entry.description.is_empty() {
+            &entry.name
+        } else {
+            &entry.description
+        };
+        let back = normalize_content(&entry.content, keyboard).join(" + ");
+        let tag = std::iter::once(ANKI_ROOT_TAG)
+            .chain(path.split(PAGE_PATH_SEPARATOR))
+            .map(anki_tag_component)
+            .collect::<Vec<_>>()
+            .join("::");
+
+        str.push_str(&format!(
+            "{}\t{}\t{}\n",
+            anki_field(front),
+            anki_field(&back),
+            tag
+        ));
+    }
+
+    str
+}
+
+/// Returns the pages as navi cheats, one section per page tagged with its path like `% git, branches`.
+///
+/// Commands are written as they are, with multiple parts joined by spaces.
+/// Entries without content are left out, as there is nothing to run.
+fn export_navi(pages: &[Page], parents: &[&str]) -> String {
+    let mut str = String::new();
+    for page in pages {
+        export_navi_page(&mut str, parents, page);
+    }
+    str
+}
+
+/// Writes a page as a navi section into the given string, followed by its subpages.
+fn export_navi_page(str: &mut String, parents: &[&str], page: &Page) {
+    let path: Vec<&str> = parents
+        .iter()
+        .copied()
+        .chain([page.name.as_str()])
+        .collect();
+
+    let entries: Vec<_> = page
+        .entries
+        .iter()
+        .filter(|entry| !entry.content.is_empty())
+        .collect();
+
+    if !entries.is_empty() || !page.variables.is_empty() {
+        if !str.is_empty() {
+            str.push('\n');
+        }
+        str.push_str(&format!("% {}\n", path.join(", ")));
+
+        for entry in entries {
+            str.push('\n');
+            if !entry.description.is_empty() {
+                str.push_str(&format!("# {}\n", single_line(&entry.description)));
+            }
+            str.push_str(&format!("{}\n", entry.content.join(" ")));
+        }
+
+        if !page.variables.is_empty() {
+            str.push('\n');
+        }
+        for variable in &page.variables {
+            str.push_str(&format!("$ {}: {}\n", variable.name, variable.command));
+        }
+    }
+
+    for subpage in &page.subpages {
+        export_navi_page(str, &path, subpage);
+    }
+}
+
+/// Returns the pages as Markdown, one table of shortcuts and descriptions per page headed by its path.
+///
+/// Entries without a section come first, followed by a table per section. Like in `recall show`, hidden entries are
+/// left out, secrets are masked and names are shown on pages that ask for them or if `show_names` is set.
+fn export_markdown(
+    pages: &[Page],
+    parents: &[&str],
+    show_names: bool,
+    keyboard: Keyboard,
+) -> String {
+    let mut str = String::new();
+    for page in pages {
+        export_markdown_page(&mut str, parents, page, show_names, keyboard);
+    }
+    str
+}
+
+/// Writes a page as a Markdown section into the given string, followed by its subpages.
+fn export_markdown_page(
+    str: &mut String,
+    parents: &[&str],
+    page: &Page,
+    show_names: bool,
+    keyboard: Keyboard,
+) {
+    let path: Vec<&str> = parents
+        .iter()
+        .copied()
+        .chain([page.name.as_str()])
+        .collect();
+
+    let entries: Vec<&Entry> = page.entries.iter().filter(|entry| !entry.hidden).collect();
+    if !entries.is_empty() {
+        if !str.is_empty() {
+            str.push('\n');
+        }
+        str.push_str(&format!(
+            "## {}\n",
+            markdown_text(&path.join(show::PATH_SEPARATOR))
+        ));
+        if let Some(description) = &page.description {
+            str.push_str(&format!("\n{}\n", markdown_text(description)));
+        }
+
+        let mut sections: Vec<Option<&str>> = vec![None];
+        for entry in &entries {
+            if !sections.contains(&entry.section.as_deref()) {
+                sections.push(entry.section.as_deref());
+            }
+        }
+
+        let show_names = page.show_names.unwrap_or(show_names);
+        for section in sections {
+            let section_entries: Vec<&Entry> = entries
+                .iter()
+                .copied()
+                .filter(|entry| entry.section.as_deref() == section)
+                .collect();
+            if section_entries.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = section {
+                str.push_str(&format!("\n### {}\n", markdown_text(name)));
+            }
+            str.push('\n');
+            str.push_str(match show_names {
+                true => "| Name | Shortcut | Description |\n| --- | --- | --- |\n",
+                false => "| Shortcut | Description |\n| --- | --- |\n",
+            });
+            for entry in section_entries {
+                str.push_str("| ");
+                if show_names {
+                    str.push_str(&format!("{} | ", markdown_text(&entry.name)));
+                }
+                str.push_str(&markdown_shortcut(entry, keyboard));
+                str.push_str(" | ");
+                str.push_str(&markdown_text(&entry.description));
+                if let Some(target) = &entry.goto {
+                    str.push_str(&format!(" → {}", markdown_text(target)));
+                }
+                str.push_str(" |\n");
+            }
+        }
+    }
+
+    for subpage in &page.subpages {
+        export_markdown_page(str, &path, subpage, show_names, keyboard);
+    }
+}
+
+/// Returns the shortcut of an entry for a Markdown table, its keys as code joined by `+`, or a link if it is one.
+fn markdown_shortcut(entry: &Entry, keyboard: Keyboard) -> String {
+    if entry.secret {
+        return markdown_code(SECRET_MASK);
+    }
+    if let Some(url) = entry.link_url() {
+        return format!("<{}>", url.replace('|', "%7C"));
+    }
+    normalize_content(&entry.content, keyboard)
+        .iter()
+        .map(|key| markdown_code(key))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Returns text as inline code for a Markdown table, fenced by enough backticks for those in the text.
+fn markdown_code(text: &str) -> String {
+    let text = single_line(text).replace('|', "\\|");
+    if text.contains('`') {
+        format!("`` {} ``", text)
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+/// Returns text for a single line or table cell of Markdown, escaping characters that would format it.
+fn markdown_text(text: &str) -> String {
+    let mut escaped = String::new();
+    for char in single_line(text).chars() {
+        if matches!(
+            char,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+    }
+    escaped
+}
+
+/// Returns text on a single line, replacing line breaks by spaces.
+fn single_line(text: &str) -> String {
+    text.replace(['\n', '\r'], " ")
+}
+
+/// Returns text usable as a single field of a tab-separated note, replacing tabs and line breaks by spaces.
+fn anki_field(text: &str) -> String {
+    single_line(text).replace('\t', " ")
+}
+
+/// Returns a page name usable as part of an Anki tag, which mustn't contain spaces.
+fn anki_tag_component(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
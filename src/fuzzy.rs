@@ -0,0 +1,58 @@
+//! Fuzzy matching module for Recall.
+//!
+//! Matches what was typed against names like those of pages, so `gt` finds `git` and `k8s` finds `k8s-pods`.
+//! The characters typed have to appear in the name in order, ignoring case. Matches at the start of the name,
+//! at the start of words and of consecutive characters score higher, so the closest names come first.
+
+/// Score of a name equal to the query, ignoring case
+const EXACT_SCORE: u32 = u32::MAX;
+
+/// Score of each matched character
+const MATCH_SCORE: u32 = 10;
+
+/// Additional score of a name starting with the query
+const PREFIX_BONUS: u32 = 50;
+
+/// Additional score of a character matched right after the previous one
+const CONSECUTIVE_BONUS: u32 = 15;
+
+/// Additional score of a character matched at the start of a word
+const WORD_START_BONUS: u32 = 20;
+
+/// Returns how well the query matches the name, or `None` if it doesn't match at all.
+///
+/// Higher scores are better matches, an exact match scores highest.
+pub fn score(name: &str, query: &str) -> Option<u32> {
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+
+    if name == query {
+        return Some(EXACT_SCORE);
+    }
+
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for c in &query {
+        let index = position + name[position..].iter().position(|n| n == c)?;
+
+        score += MATCH_SCORE;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += CONSECUTIVE_BONUS;
+        }
+        if index == 0 || !name[index - 1].is_alphanumeric() {
+            score += WORD_START_BONUS;
+        }
+
+        previous = Some(index);
+        position = index + 1;
+    }
+
+    if name.starts_with(&query) {
+        score += PREFIX_BONUS;
+    }
+
+    // Among otherwise equal matches, the name with the fewest extra characters is closest
+    let extra = (name.len() - query.len()) as u32;
+    Some(score.saturating_sub(extra).max(1))
+}
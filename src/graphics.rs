@@ -0,0 +1,192 @@
+//! Graphics module for Recall.
+//!
+//! Entries and pages can be illustrated by an image, e.g. a mode map or a layered keyboard layout.
+//! Terminals supporting the kitty graphics protocol show PNG images scaled to fit the space reserved for them,
+//! terminals supporting sixel show images already converted to sixel (`.six` or `.sixel`), e.g. by `img2sixel`,
+//! at their own size. Other terminals and images show the path of the image instead.
+//! Like links, images are written over the frame drawn by ratatui, into the blank area reserved for them.
+
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use anyhow::{Context, Result};
+use ratatui::{
+    crossterm::{cursor::MoveTo, queue, style::Print},
+    layout::Rect,
+};
+use serde::Serialize;
+
+/// Ways of showing images in the terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Graphics {
+    /// The kitty graphics protocol, also supported by e.g. Ghostty and WezTerm
+    Kitty,
+
+    /// Sixel graphics, supported by e.g. foot, mlterm and xterm
+    Sixel,
+
+    /// No graphics, images are named by their path
+    #[default]
+    Text,
+}
+
+/// Escape sequence deleting all images shown with the kitty graphics protocol
+const KITTY_DELETE: &str = "\x1b_Ga=d,q=2\x1b\\";
+
+/// Number of base64 characters sent in a single escape sequence of the kitty graphics protocol
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// Signature every PNG file starts with
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Extensions of images written in sixel
+const SIXEL_EXTENSIONS: [&str; 2] = ["six", "sixel"];
+
+/// Number of pixels a cell is assumed to be higher than wide, used to keep the aspect ratio of images
+const CELL_ASPECT_RATIO: u32 = 2;
+
+/// Characters of the base64 encoding
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl Graphics {
+    /// Returns the graphics supported by the terminal, judging by the environment it sets.
+    pub fn detect() -> Graphics {
+        let term = env::var("TERM").unwrap_or_default();
+        let program = env::var("TERM_PROGRAM").unwrap_or_default();
+
+        if env::var_os("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || program == "WezTerm"
+        {
+            Graphics::Kitty
+        } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+            Graphics::Sixel
+        } else {
+            Graphics::Text
+        }
+    }
+
+    /// Returns the name of the graphics as used in the configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            Graphics::Kitty => "kitty",
+            Graphics::Sixel => "sixel",
+            Graphics::Text => "text",
+        }
+    }
+
+    /// Returns the graphics with the given name, if there are any
+    pub fn from_name(name: &str) -> Option<Graphics> {
+        [Graphics::Kitty, Graphics::Sixel, Graphics::Text]
+            .into_iter()
+            .find(|graphics| graphics.name() == name)
+    }
+
+    /// Returns `true` if the image at the given path can be shown, judging by its extension.
+    pub fn can_show(&self, path: &str) -> bool {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+
+        match self {
+            Graphics::Kitty => extension == "png",
+            Graphics::Sixel => SIXEL_EXTENSIONS.contains(&extension.as_str()),
+            Graphics::Text => false,
+        }
+    }
+
+    /// Shows the image at the given path in the given area of the terminal.
+    pub fn show(&self, path: &Path, area: Rect) -> Result<()> {
+        let image = fs::read(path).context(format!("Failed to read image {}", path.display()))?;
+
+        let mut stdout = io::stdout();
+        queue!(stdout, MoveTo(area.x, area.y))?;
+        match self {
+            Graphics::Kitty => {
+                let size = match png_size(&image) {
+                    // Only one dimension is given, so kitty keeps the aspect ratio
+                    Some((width, height))
+                        if width * area.height as u32 * CELL_ASPECT_RATIO
+                            > height * area.width as u32 =>
+                    {
+                        format!("c={}", area.width)
+                    }
+                    _ => format!("r={}", area.height),
+                };
+
+                let encoded = base64(&image);
+                let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = if i + 1 < chunks.len() { 1 } else { 0 };
+                    let control = if i == 0 {
+                        format!("a=T,f=100,{},C=1,q=2,m={}", size, more)
+                    } else {
+                        format!("m={}", more)
+                    };
+                    queue!(
+                        stdout,
+                        Print(format!(
+                            "\x1b_G{};{}\x1b\\",
+                            control,
+                            String::from_utf8_lossy(chunk)
+                        ))
+                    )?;
+                }
+            }
+            Graphics::Sixel => queue!(stdout, Print(String::from_utf8_lossy(&image)))?,
+            Graphics::Text => {}
+        }
+
+        stdout.flush().context("Failed to write image")
+    }
+
+    /// Removes images shown before, as far as the terminal doesn't do so when their cells are redrawn.
+    pub fn clear(&self) -> Result<()> {
+        if *self == Graphics::Kitty {
+            let mut stdout = io::stdout();
+            queue!(stdout, Print(KITTY_DELETE))?;
+            stdout.flush().context("Failed to remove images")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the width and height of a PNG image in pixels, read from its header.
+fn png_size(image: &[u8]) -> Option<(u32, u32)> {
+    if !image.starts_with(PNG_SIGNATURE) || image.len() < 24 {
+        return None;
+    }
+    let width = u32::from_be_bytes(image[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(image[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+/// Returns the data encoded in base64, as the kitty graphics protocol expects it.
+fn base64(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let triple = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (triple >> (18 - 6 * i)) & 0x3f;
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,128 @@
+//! Shell history module for Recall.
+//!
+//! Reads the history of bash, zsh or fish to find long commands that are typed again and again,
+//! which are then suggested as entries.
+
+use std::{cmp::Reverse, collections::HashSet, env, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use directories::BaseDirs;
+use log::{debug, info};
+
+/// A command found in the shell history along with how often it was typed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The command as it was typed
+    pub command: String,
+
+    /// Number of times the command occurs in the history
+    pub count: usize,
+}
+
+/// Returns the path of the shell history to read.
+///
+/// `$HISTFILE` is used if set, otherwise the first existing history of zsh, bash or fish.
+pub fn default_history_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("HISTFILE").filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+
+    let dirs = BaseDirs::new()?;
+    [
+        dirs.home_dir().join(".zsh_history"),
+        dirs.home_dir().join(".bash_history"),
+        dirs.data_dir().join("fish").join("fish_history"),
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+}
+
+/// Reads all commands from the shell history at the given path, oldest first.
+pub fn read_history(path: &PathBuf) -> Result<Vec<String>> {
+    info!("Reading shell history from {}", path.display());
+
+    // Histories may contain bytes that aren't valid UTF-8, e.g. zsh stores some characters escaped
+    let content = fs::read(path).context(format!(
+        "Failed to read shell history from {}",
+        path.display()
+    ))?;
+    Ok(parse_history(&String::from_utf8_lossy(&content)))
+}
+
+/// Returns the commands that are at least `min_length` characters long and occur at least `min_count` times,
+/// most frequent first.
+///
+/// Commands that are already known, e.g. as entries of the config, are left out.
+pub fn suggest(
+    commands: &[String],
+    min_length: usize,
+    min_count: usize,
+    known: &HashSet<String>,
+) -> Vec<Suggestion> {
+    let mut suggestions: Vec<Suggestion> = vec![];
+
+    for command in commands {
+        if command.chars().count() < min_length || known.contains(command) {
+            continue;
+        }
+        match suggestions.iter_mut().find(|s| s.command == *command) {
+            Some(suggestion) => suggestion.count += 1,
+            None => suggestions.push(Suggestion {
+                command: command.clone(),
+                count: 1,
+            }),
+        }
+    }
+
+    suggestions.retain(|suggestion| suggestion.count >= min_count);
+    // The sort is stable, so commands typed equally often keep the order they were first typed in
+    suggestions.sort_by_key(|suggestion| Reverse(suggestion.count));
+
+    debug!("Found {} frequently typed commands", suggestions.len());
+    suggestions
+}
+
+/// Parses the history of bash, zsh or fish into its commands.
+///
+/// - zsh prefixes commands with their timestamp like `: 1700000000:0;git status`
+/// - fish stores commands as `- cmd: git status`, followed by indented metadata
+/// - bash stores plain commands, optionally preceded by timestamp comments like `#1700000000`
+///
+/// Commands spanning multiple lines end each but the last line with a backslash, they are joined into a single line.
+fn parse_history(content: &str) -> Vec<String> {
+    let mut commands = vec![];
+    let mut pending = String::new();
+    let is_fish = content.lines().any(|line| line.starts_with("- cmd: "));
+
+    for line in content.lines() {
+        let command = if !pending.is_empty() {
+            line.trim_start()
+        } else if let Some(command) = line.strip_prefix("- cmd: ") {
+            command
+        } else if is_fish {
+            // Metadata of fish like `when:` or `paths:`
+            continue;
+        } else if let Some(rest) = line.strip_prefix(": ") {
+            rest.split_once(';').map_or(rest, |(_, command)| command)
+        } else if line.starts_with('#') && line[1..].chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        } else {
+            line
+        };
+
+        if let Some(continued) = command.strip_suffix('\\') {
+            pending.push_str(continued.trim_end());
+            pending.push(' ');
+            continue;
+        }
+        pending.push_str(command);
+
+        let command = pending.trim();
+        if !command.is_empty() {
+            commands.push(command.to_string());
+        }
+        pending.clear();
+    }
+
+    commands
+}
@@ -0,0 +1,266 @@
+//! HTML module for Recall.
+//!
+//! Writes pages as a standalone HTML cheatsheet that can be hosted or printed, styled with the configured colors.
+//! The document needs no other files: the style is embedded and an index at the top links to every page.
+//! Like in `recall show`, hidden entries are left out, secrets are masked and names are shown on pages that ask for them.
+
+use ratatui::style::Color;
+
+use crate::{
+    app::{Entry, Page, SECRET_MASK},
+    keys::{normalize_content, Keyboard},
+    show::{Colors, PATH_SEPARATOR},
+    theme::ansi_index,
+};
+
+/// Title of the cheatsheet
+const TITLE: &str = "Recall";
+
+/// Background behind a light primary color, like a dark terminal
+const DARK_BACKGROUND: &str = "#1d1f21";
+
+/// Background behind a dark primary color, like a light terminal
+const LIGHT_BACKGROUND: &str = "#ffffff";
+
+/// Intensities of the 6×6×6 color cube of the 256 ANSI colors, as used by xterm
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 standard ANSI colors, as used by xterm
+const STANDARD_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Returns the pages and their subpages as an HTML document, headed by their path below the given parent pages.
+pub fn cheatsheet(
+    pages: &[Page],
+    parents: &[&str],
+    show_names: bool,
+    keyboard: Keyboard,
+    colors: Colors,
+) -> String {
+    let mut sections = vec![];
+    for page in pages {
+        collect_sections(&mut sections, parents, page, show_names, keyboard);
+    }
+
+    let (primary, highlight) = colors;
+    let primary = rgb(primary).unwrap_or(STANDARD_COLORS[15]);
+    let highlight = rgb(highlight).unwrap_or(STANDARD_COLORS[6]);
+    let background = if luminance(primary) > 0.5 {
+        DARK_BACKGROUND
+    } else {
+        LIGHT_BACKGROUND
+    };
+
+    let mut str = String::new();
+    str.push_str("<!DOCTYPE html>\n");
+    str.push_str("<html>\n<head>\n");
+    str.push_str("<meta charset=\"utf-8\">\n");
+    str.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    str.push_str(&format!("<title>{}</title>\n", TITLE));
+    str.push_str("<style>\n");
+    str.push_str(&format!(
+        ":root {{ --primary: {}; --highlight: {}; --background: {}; }}\n",
+        css(primary),
+        css(highlight),
+        background
+    ));
+    str.push_str(STYLE);
+    str.push_str("</style>\n</head>\n<body>\n");
+    str.push_str(&format!("<h1>{}</h1>\n", TITLE));
+
+    str.push_str("<nav>\n<ul>\n");
+    for (index, (path, _)) in sections.iter().enumerate() {
+        str.push_str(&format!(
+            "<li><a href=\"#page-{}\">{}</a></li>\n",
+            index + 1,
+            escape(path)
+        ));
+    }
+    str.push_str("</ul>\n</nav>\n");
+
+    for (index, (path, section)) in sections.iter().enumerate() {
+        str.push_str(&format!(
+            "<section id=\"page-{}\">\n<h2>{}</h2>\n",
+            index + 1,
+            escape(path)
+        ));
+        str.push_str(section);
+        str.push_str("</section>\n");
+    }
+
+    str.push_str("</body>\n</html>\n");
+    str
+}
+
+/// Embedded style of the cheatsheet, using the colors set as variables before it
+const STYLE: &str = "\
+body { margin: 2rem auto; max-width: 60rem; padding: 0 1rem; font-family: system-ui, sans-serif; color: var(--primary); background: var(--background); }
+h1, h2, h3 { color: var(--highlight); }
+h2 { border-bottom: 1px solid var(--highlight); }
+a { color: var(--highlight); }
+nav ul { columns: 3 12rem; padding-left: 1rem; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1rem; }
+th, td { text-align: left; padding: 0.25rem 0.5rem; vertical-align: top; }
+tr + tr td { border-top: 1px solid color-mix(in srgb, var(--primary) 20%, transparent); }
+td.shortcut { white-space: nowrap; }
+kbd { font-family: ui-monospace, monospace; font-weight: bold; color: var(--highlight); }
+.description { opacity: 0.8; }
+@media print {
+  body { margin: 0; max-width: none; color: black; background: white; }
+  nav { display: none; }
+  section { break-inside: avoid; }
+}
+";
+
+/// Collects the path and HTML of the page if it has entries to show, followed by those of its subpages.
+fn collect_sections(
+    sections: &mut Vec<(String, String)>,
+    parents: &[&str],
+    page: &Page,
+    show_names: bool,
+    keyboard: Keyboard,
+) {
+    let path: Vec<&str> = parents
+        .iter()
+        .copied()
+        .chain([page.name.as_str()])
+        .collect();
+
+    let entries: Vec<&Entry> = page.entries.iter().filter(|entry| !entry.hidden).collect();
+    if !entries.is_empty() {
+        let mut str = String::new();
+        if let Some(description) = &page.description {
+            str.push_str(&format!(
+                "<p class=\"description\">{}</p>\n",
+                escape(description)
+            ));
+        }
+
+        let mut groups: Vec<Option<&str>> = vec![None];
+        for entry in &entries {
+            if !groups.contains(&entry.section.as_deref()) {
+                groups.push(entry.section.as_deref());
+            }
+        }
+
+        let show_names = page.show_names.unwrap_or(show_names);
+        for group in groups {
+            let group_entries: Vec<&Entry> = entries
+                .iter()
+                .copied()
+                .filter(|entry| entry.section.as_deref() == group)
+                .collect();
+            if group_entries.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = group {
+                str.push_str(&format!("<h3>{}</h3>\n", escape(name)));
+            }
+            str.push_str("<table>\n");
+            for entry in group_entries {
+                str.push_str("<tr>");
+                if show_names {
+                    str.push_str(&format!("<td>{}</td>", escape(&entry.name)));
+                }
+                str.push_str(&format!(
+                    "<td class=\"shortcut\">{}</td>",
+                    shortcut(entry, keyboard)
+                ));
+                str.push_str(&format!("<td>{}", escape(&entry.description)));
+                if let Some(target) = &entry.goto {
+                    str.push_str(&format!(" → {}", escape(target)));
+                }
+                str.push_str("</td></tr>\n");
+            }
+            str.push_str("</table>\n");
+        }
+
+        sections.push((path.join(PATH_SEPARATOR), str));
+    }
+
+    for subpage in &page.subpages {
+        collect_sections(sections, &path, subpage, show_names, keyboard);
+    }
+}
+
+/// Returns the shortcut of an entry, its keys joined by `+`, or a link if the entry is one.
+fn shortcut(entry: &Entry, keyboard: Keyboard) -> String {
+    if entry.secret {
+        return format!("<kbd>{}</kbd>", SECRET_MASK);
+    }
+    if let Some(url) = entry.link_url() {
+        return format!("<a href=\"{}\">{}</a>", escape(url), escape(url));
+    }
+    normalize_content(&entry.content, keyboard)
+        .iter()
+        .map(|key| format!("<kbd>{}</kbd>", escape(key)))
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Returns text with the characters escaped that HTML would read as markup.
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+/// Returns the red, green and blue parts of a color as xterm shows it, or `None` for the terminal's default color.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    if let Color::Rgb(red, green, blue) = color {
+        return Some((red, green, blue));
+    }
+
+    let index = ansi_index(color)?;
+    Some(match index {
+        0..=15 => STANDARD_COLORS[index as usize],
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+/// Returns the relative luminance of a color, from 0 for black to 1 for white.
+fn luminance((red, green, blue): (u8, u8, u8)) -> f64 {
+    (0.2126 * red as f64 + 0.7152 * green as f64 + 0.0722 * blue as f64) / 255.0
+}
+
+/// Returns a color as a CSS hex color.
+fn css((red, green, blue): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", red, green, blue)
+}
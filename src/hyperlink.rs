@@ -0,0 +1,108 @@
+//! Hyperlink module for Recall.
+//!
+//! Entries whose content is a single URL are links. Printed output and the interface make them clickable
+//! with OSC 8 escape sequences, which terminals without support for them ignore.
+//! The interface is drawn by ratatui, whose cells can't carry such sequences, so the links are written
+//! over the drawn frame again wherever their URL is shown, looking the same but clickable.
+
+use std::io::{self, Write};
+
+use anyhow::{Context, Result};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::{
+        cursor::MoveTo,
+        queue,
+        style::{
+            Attribute, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+        },
+    },
+    style::Modifier,
+};
+
+/// Schemes of URLs that entries can link to
+const URL_SCHEMES: [&str; 3] = ["http://", "https://", "mailto:"];
+
+/// Escape sequence ending a link
+const LINK_END: &str = "\x1b]8;;\x07";
+
+/// Attributes of the styles of cells that are kept when writing links over them
+const ATTRIBUTES: [(Modifier, Attribute); 6] = [
+    (Modifier::BOLD, Attribute::Bold),
+    (Modifier::DIM, Attribute::Dim),
+    (Modifier::ITALIC, Attribute::Italic),
+    (Modifier::UNDERLINED, Attribute::Underlined),
+    (Modifier::REVERSED, Attribute::Reverse),
+    (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+];
+
+/// Returns the URL if the content of an entry is a single URL.
+pub fn url(content: &[String]) -> Option<&str> {
+    match content {
+        [url] if URL_SCHEMES.iter().any(|scheme| url.starts_with(scheme)) => Some(url),
+        _ => None,
+    }
+}
+
+/// Returns the text as a link to the URL.
+pub fn link(text: &str, url: &str) -> String {
+    format!("{}{}{}", link_start(url), text, LINK_END)
+}
+
+/// Writes the given URLs over the frame in the buffer as links wherever they are shown in full.
+pub fn write_links(buffer: &Buffer, urls: &[&str]) -> Result<()> {
+    let mut stdout = io::stdout();
+
+    let area = buffer.area;
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            for url in urls {
+                if !shows_at(buffer, x, y, url) {
+                    continue;
+                }
+
+                queue!(stdout, MoveTo(x, y), Print(link_start(url)))?;
+                for (offset, _) in url.chars().enumerate() {
+                    let cell = &buffer[(x + offset as u16, y)];
+                    queue!(
+                        stdout,
+                        SetAttribute(Attribute::Reset),
+                        SetForegroundColor(cell.fg.into()),
+                        SetBackgroundColor(cell.bg.into()),
+                    )?;
+                    for (modifier, attribute) in ATTRIBUTES {
+                        if cell.modifier.contains(modifier) {
+                            queue!(stdout, SetAttribute(attribute))?;
+                        }
+                    }
+                    queue!(stdout, Print(cell.symbol()))?;
+                }
+                queue!(
+                    stdout,
+                    Print(LINK_END),
+                    SetAttribute(Attribute::Reset),
+                    ResetColor
+                )?;
+            }
+        }
+    }
+
+    stdout.flush().context("Failed to write links")
+}
+
+/// Returns `true` if the cells of the buffer starting at the given position show the URL.
+fn shows_at(buffer: &Buffer, x: u16, y: u16, url: &str) -> bool {
+    let length = url.chars().count() as u16;
+    if x + length > buffer.area.right() {
+        return false;
+    }
+
+    url.chars()
+        .enumerate()
+        .all(|(offset, c)| buffer[(x + offset as u16, y)].symbol().chars().eq([c]))
+}
+
+/// Returns the escape sequence starting a link to the URL.
+fn link_start(url: &str) -> String {
+    format!("\x1b]8;;{}\x07", url)
+}
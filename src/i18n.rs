@@ -0,0 +1,127 @@
+//! Localization module for Recall.
+//!
+//! User-facing texts are looked up by their message id in [Fluent](https://projectfluent.org) resources.
+//! Each supported language has a resource in the `locales` directory, which is compiled into the binary.
+//! Messages missing in a translation fall back to English.
+
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use log::{debug, warn};
+use unic_langid::LanguageIdentifier;
+
+/// The language used if none is configured, it also serves as fallback for missing messages.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// All supported languages and their Fluent resources.
+const LANGUAGES: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+];
+
+/// Looks up localized texts of a single language.
+pub struct Localizer {
+    /// Code of the selected language, e.g. `de`
+    language: &'static str,
+
+    /// Messages of the selected language
+    bundle: FluentBundle<FluentResource>,
+
+    /// Messages of the default language, used for messages missing in the selected language
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl Localizer {
+    /// Creates a localizer for the given language code.
+    ///
+    /// Returns an error if the language is not supported.
+    pub fn new(language: &str) -> Result<Localizer> {
+        let (language, _) = LANGUAGES
+            .iter()
+            .find(|(code, _)| *code == language)
+            .ok_or(anyhow!(
+                "Unsupported language {}, available languages are: {}",
+                language,
+                language_names()
+            ))?;
+
+        Ok(Localizer {
+            language,
+            bundle: build_bundle(language)?,
+            fallback: build_bundle(DEFAULT_LANGUAGE)?,
+        })
+    }
+
+    /// Returns the text of the message with the given id.
+    pub fn text(&self, id: &str) -> String {
+        self.text_with(id, &[])
+    }
+
+    /// Returns the text of the message with the given id, filling in the given arguments.
+    ///
+    /// If the message doesn't exist in any language, its id is returned instead.
+    pub fn text_with(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let args: FluentArgs = args.iter().cloned().collect();
+
+        for bundle in [&self.bundle, &self.fallback] {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+
+            let mut errors = vec![];
+            let text = bundle.format_pattern(pattern, Some(&args), &mut errors);
+            if !errors.is_empty() {
+                warn!("Failed to format message {}: {:?}", id, errors);
+            }
+            return text.into_owned();
+        }
+
+        debug!("Missing message {} for language {}", id, self.language);
+        id.to_string()
+    }
+}
+
+impl fmt::Debug for Localizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Localizer")
+            .field("language", &self.language)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns `true` if there are texts for the given language code.
+pub fn is_supported(language: &str) -> bool {
+    LANGUAGES.iter().any(|(code, _)| *code == language)
+}
+
+/// Returns the codes of all supported languages, separated by commas.
+pub fn language_names() -> String {
+    LANGUAGES
+        .iter()
+        .map(|(code, _)| *code)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses the Fluent resource of a supported language into a bundle.
+fn build_bundle(language: &str) -> Result<FluentBundle<FluentResource>> {
+    let (_, source) = LANGUAGES
+        .iter()
+        .find(|(code, _)| *code == language)
+        .ok_or(anyhow!("Unsupported language {}", language))?;
+
+    let language_id: LanguageIdentifier = language.parse()?;
+
+    let resource = FluentResource::try_new(source.to_string())
+        .map_err(|(_, errors)| anyhow!("Invalid texts for language {}: {:?}", language, errors))?;
+
+    let mut bundle = FluentBundle::new(vec![language_id]);
+    // Isolation marks around arguments are only useful for right-to-left scripts and garble terminal output
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .map_err(|errors| anyhow!("Duplicate texts for language {}: {:?}", language, errors))?;
+
+    Ok(bundle)
+}
@@ -0,0 +1,1006 @@
+//! Import module for Recall.
+//!
+//! Converts the files of other tools into pages, which are merged into the pages of a configuration.
+//! Entries need unique names within a page, so imported entries are named after their description.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use directories::BaseDirs;
+use log::{debug, warn};
+use serde::Deserialize;
+
+use crate::{
+    app::{Entry, Page, SortOrder, Variable},
+    importers::{alacritty, aliases, git, hyprland, i3, kitty, tmux, vim, vscode},
+};
+
+/// Formats pages can be imported from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// Cheat files of navi, with tags turning into pages
+    Navi,
+
+    /// The `snippet.toml` of pet, with snippets listed on a single page
+    Pet,
+
+    /// A directory of Markdown notes, with each file turning into a page
+    MarkdownDir,
+
+    /// A JSON list of pages, or an object with a list of pages like `recall export json` writes
+    Json,
+
+    /// A YAML list of pages, or a mapping with a list of pages like `recall export yaml` writes
+    Yaml,
+
+    /// The key bindings of a tmux config, read from `~/.tmux.conf` if no file is given
+    Tmux,
+
+    /// The key bindings of an i3 config with a page per mode, read from `~/.config/i3/config` if no file is given
+    I3,
+
+    /// The key bindings of a sway config with a page per mode, read from `~/.config/sway/config` if no file is given
+    Sway,
+
+    /// The mappings of a vimrc or init.vim with a page per mode, read from `~/.vimrc` if no file is given
+    Vim,
+
+    /// The shortcuts of a kitty.conf, read from `~/.config/kitty/kitty.conf` if no file is given
+    Kitty,
+
+    /// The key bindings of an Alacritty config in TOML, or YAML for `.yml` files, read from
+    /// `~/.config/alacritty/alacritty.toml` if no file is given
+    Alacritty,
+
+    /// The `keybindings.json` of VS Code with a page per command prefix, read from the user settings of VS Code if no
+    /// file is given
+    Vscode,
+
+    /// The key bindings of a hyprland.conf with a page per submap, read from `~/.config/hypr/hyprland.conf` if no file
+    /// is given
+    Hyprland,
+
+    /// The aliases and functions of a shell startup file or of the output of `alias`, read from the `.zshrc` or
+    /// `.bashrc` of the current shell if no file is given
+    Aliases,
+
+    /// The aliases of a git config, read by git from `~/.gitconfig` if no file is given
+    Git,
+}
+
+/// Name of imported entries without any description
+const UNNAMED_ENTRY: &str = "entry";
+
+/// Name of the page imported pet snippets are listed on
+const PET_PAGE_NAME: &str = "pet";
+
+/// The snippet file of pet.
+#[derive(Debug, Deserialize)]
+struct PetSnippets {
+    /// All snippets, written as `[[snippets]]` tables
+    #[serde(default)]
+    snippets: Vec<PetSnippet>,
+}
+
+/// A single snippet of pet.
+#[derive(Debug, Deserialize)]
+struct PetSnippet {
+    /// Description of what the command does
+    #[serde(default)]
+    description: String,
+
+    /// The command itself, possibly with `<param>` placeholders
+    command: String,
+
+    /// Tags for filtering snippets
+    #[serde(default)]
+    tag: Vec<String>,
+}
+
+/// A JSON or YAML document of pages, either a list of them or the pages of an exported config.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PagesDocument {
+    /// The pages as written by `recall export json`, whose other settings are ignored
+    Config { pages: Vec<DocumentPage> },
+
+    /// A list of pages
+    Pages(Vec<DocumentPage>),
+}
+
+/// A page of a JSON or YAML document, with the fields of the pages written by `recall export json`.
+#[derive(Debug, Deserialize)]
+struct DocumentPage {
+    /// Name of the page
+    name: String,
+
+    /// Group of the page, if it is a top-level page belonging to one
+    group: Option<String>,
+
+    /// Line describing the page
+    description: Option<String>,
+
+    /// Path of an image illustrating the page
+    image: Option<String>,
+
+    /// Other names the page can be opened by
+    #[serde(default)]
+    aliases: Vec<String>,
+
+    /// Name of the order the entries are sorted in, e.g. `alphabetical`
+    sort: Option<String>,
+
+    /// Entries of the page
+    #[serde(default)]
+    entries: Vec<DocumentEntry>,
+
+    /// Nested pages
+    #[serde(default)]
+    subpages: Vec<DocumentPage>,
+
+    /// Variables used in the commands of the page
+    #[serde(default)]
+    variables: Vec<DocumentVariable>,
+
+    /// Whether the names of entries are shown in their own column
+    show_names: Option<bool>,
+}
+
+/// An entry of a JSON or YAML document, named after its description if it has no name.
+#[derive(Debug, Deserialize)]
+struct DocumentEntry {
+    /// Name of the entry
+    name: Option<String>,
+
+    /// Keys or command of the entry, a single string or a list of them
+    content: Option<DocumentContent>,
+
+    /// Description of the entry
+    #[serde(default)]
+    description: String,
+
+    /// Whether the entry is only shown when hidden entries are revealed
+    #[serde(default)]
+    hidden: bool,
+
+    /// Whether the content of the entry is masked until it is revealed
+    #[serde(default)]
+    secret: bool,
+
+    /// Path of a page opened by the entry
+    goto: Option<String>,
+
+    /// Shell command run by the entry
+    exec: Option<String>,
+
+    /// Web page about the entry
+    url: Option<String>,
+
+    /// Section within its page the entry is listed under
+    section: Option<String>,
+
+    /// Path of an image illustrating the entry
+    image: Option<String>,
+
+    /// Context about the entry, written as comments above it
+    note: Option<String>,
+}
+
+/// Content of an entry in a JSON or YAML document, which may be given as a single string.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DocumentContent {
+    /// A single key or command
+    Single(String),
+
+    /// Keys or parts of a command
+    List(Vec<String>),
+}
+
+/// A variable of a page in a JSON or YAML document.
+#[derive(Debug, Deserialize)]
+struct DocumentVariable {
+    /// Name of the variable
+    name: String,
+
+    /// Shell command printing the possible values of the variable
+    command: String,
+}
+
+/// Extension of Markdown files
+const MARKDOWN_EXTENSION: &str = "md";
+
+/// Characters that separate the command from its description in items of Markdown lists, e.g. `` `ls` - list files ``
+const MARKDOWN_SEPARATORS: [&str; 4] = [" - ", " – ", " — ", ": "];
+
+/// Returns the pages described by the file or directory at the given path in the given format.
+pub fn import(path: &Path, format: ImportFormat) -> Result<Vec<Page>> {
+    match format {
+        ImportFormat::Navi => Ok(import_navi(&read_import(path)?)),
+        ImportFormat::Pet => import_pet(&read_import(path)?),
+        ImportFormat::MarkdownDir => import_markdown_dir(path),
+        ImportFormat::Json => {
+            let document = serde_json::from_str(&read_import(path)?).context(format!(
+                "Failed to parse {}, expected a list of pages or an object with pages",
+                path.display()
+            ))?;
+            Ok(import_document(document))
+        }
+        ImportFormat::Yaml => {
+            let document = serde_yaml_ng::from_str(&read_import(path)?).context(format!(
+                "Failed to parse {}, expected a list of pages or a mapping with pages",
+                path.display()
+            ))?;
+            Ok(import_document(document))
+        }
+        ImportFormat::Tmux => Ok(tmux::import(&read_import(path)?).into_iter().collect()),
+        ImportFormat::I3 => Ok(i3::import(&read_import(path)?, i3::I3_PAGE_NAME)
+            .into_iter()
+            .collect()),
+        ImportFormat::Sway => Ok(i3::import(&read_import(path)?, i3::SWAY_PAGE_NAME)
+            .into_iter()
+            .collect()),
+        ImportFormat::Vim => Ok(vim::import(&read_import(path)?).into_iter().collect()),
+        ImportFormat::Kitty => Ok(kitty::import(&read_import(path)?).into_iter().collect()),
+        ImportFormat::Alacritty => {
+            let yaml = ImportFormat::from_path(path) == Some(ImportFormat::Yaml);
+            let page = alacritty::import(&read_import(path)?, yaml)
+                .context(format!("Failed to import {}", path.display()))?;
+            Ok(page.into_iter().collect())
+        }
+        ImportFormat::Hyprland => Ok(hyprland::import(&read_import(path)?).into_iter().collect()),
+        ImportFormat::Aliases => Ok(aliases::import(&read_import(path)?).into_iter().collect()),
+        ImportFormat::Git => Ok(git::import(&read_git_aliases(path)?).into_iter().collect()),
+        ImportFormat::Vscode => {
+            let page = vscode::import(&read_import(path)?)
+                .context(format!("Failed to import {}", path.display()))?;
+            Ok(page.into_iter().collect())
+        }
+    }
+}
+
+/// Returns the format and the path to import from the arguments of `recall import`.
+///
+/// The format is given with `--format` or as the first of two arguments, e.g. `recall import tmux ~/.tmux.conf`.
+/// A single argument naming a format, e.g. `recall import tmux`, imports the config of that tool from where it is
+/// usually kept, unless a file of that name exists. Otherwise the format is told by the extension of the file.
+pub fn import_source(
+    format: Option<ImportFormat>,
+    first: Option<String>,
+    second: Option<PathBuf>,
+) -> Result<(Option<ImportFormat>, PathBuf)> {
+    let named_format = first
+        .as_deref()
+        .and_then(|name| ImportFormat::from_str(name, true).ok());
+
+    let (format, path) = match (first, second) {
+        (Some(first), Some(path)) => match (format, named_format) {
+            (None, Some(named_format)) => (Some(named_format), path),
+            (Some(_), _) => bail!(
+                "The format is given with --format, so {} can not be the format",
+                first
+            ),
+            (None, None) => bail!("Unknown format {}", first),
+        },
+        (Some(first), None) if format.is_none() && !Path::new(&first).exists() => {
+            match named_format {
+                Some(named_format) => (Some(named_format), default_import_path(named_format)?),
+                None => (None, PathBuf::from(first)),
+            }
+        }
+        (Some(first), None) => (format, PathBuf::from(first)),
+        (None, _) => match format {
+            Some(format) => (Some(format), default_import_path(format)?),
+            None => bail!("Give the file to import, and its format unless its extension tells it"),
+        },
+    };
+
+    Ok((format.or_else(|| ImportFormat::from_path(&path)), path))
+}
+
+/// Returns where the config of the tool of the given format is usually kept, if the format has one.
+///
+/// tmux reads `~/.tmux.conf`, or `tmux/tmux.conf` in the XDG config directory if that doesn't exist.
+/// i3 and sway read `i3/config` and `sway/config` in the XDG config directory, or `~/.i3/config` and `~/.sway/config`.
+/// Vim reads `~/.vimrc` or `~/.vim/vimrc`, falling back to the `nvim/init.vim` of Neovim in the XDG config directory.
+/// kitty reads `kitty/kitty.conf` in the XDG config directory, or in `~/Library/Preferences` on macOS.
+/// Alacritty reads `alacritty/alacritty.toml` in the XDG config directory or `~/.alacritty.toml`, falling back to the
+/// legacy `alacritty.yml` in the same places.
+/// VS Code reads `Code/User/keybindings.json` in the config directory of the platform, e.g. `~/.config` on Linux.
+/// Hyprland reads `hypr/hyprland.conf` in the XDG config directory.
+/// Aliases are read from `~/.zshrc` if the shell is zsh, otherwise from `~/.bashrc` or `~/.bash_aliases`.
+/// git reads `~/.gitconfig`, or `git/config` in the XDG config directory if that doesn't exist.
+fn default_import_path(format: ImportFormat) -> Result<PathBuf> {
+    let dirs = BaseDirs::new().context("Failed to find the home directory")?;
+    let home = dirs.home_dir();
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+
+    let candidates = match format {
+        ImportFormat::Tmux => vec![
+            home.join(".tmux.conf"),
+            config_dir.join("tmux").join("tmux.conf"),
+        ],
+        ImportFormat::I3 => vec![
+            config_dir.join("i3").join("config"),
+            home.join(".i3").join("config"),
+        ],
+        ImportFormat::Sway => vec![
+            config_dir.join("sway").join("config"),
+            home.join(".sway").join("config"),
+        ],
+        ImportFormat::Vim => vec![
+            home.join(".vimrc"),
+            home.join(".vim").join("vimrc"),
+            config_dir.join("nvim").join("init.vim"),
+        ],
+        ImportFormat::Kitty => vec![
+            config_dir.join("kitty").join("kitty.conf"),
+            home.join("Library")
+                .join("Preferences")
+                .join("kitty")
+                .join("kitty.conf"),
+        ],
+        ImportFormat::Alacritty => vec![
+            config_dir.join("alacritty").join("alacritty.toml"),
+            home.join(".alacritty.toml"),
+            config_dir.join("alacritty").join("alacritty.yml"),
+            home.join(".alacritty.yml"),
+        ],
+        ImportFormat::Vscode => vec![
+            config_dir
+                .join("Code")
+                .join("User")
+                .join("keybindings.json"),
+            dirs.config_dir()
+                .join("Code")
+                .join("User")
+                .join("keybindings.json"),
+        ],
+        ImportFormat::Hyprland => vec![config_dir.join("hypr").join("hyprland.conf")],
+        ImportFormat::Aliases => {
+            let shell = env::var_os("SHELL").map(PathBuf::from).unwrap_or_default();
+            match shell.file_name().and_then(|name| name.to_str()) {
+                Some("zsh") => vec![home.join(".zshrc")],
+                _ => vec![home.join(".bashrc"), home.join(".bash_aliases")],
+            }
+        }
+        ImportFormat::Git => vec![
+            home.join(".gitconfig"),
+            config_dir.join("git").join("config"),
+        ],
+        _ => bail!(
+            "Give the file to import in the {} format",
+            format
+                .to_possible_value()
+                .map_or(String::new(), |value| value.get_name().to_string())
+        ),
+    };
+
+    let first = candidates[0].clone();
+    Ok(candidates
+        .into_iter()
+        .find(|path| path.exists())
+        .unwrap_or(first))
+}
+
+impl ImportFormat {
+    /// Returns the format of a file with a known extension, e.g. `.json`, `.yaml` or `.cheat`.
+    ///
+    /// The `keybindings.json` of VS Code is told apart from other JSON files by its name.
+    pub fn from_path(path: &Path) -> Option<ImportFormat> {
+        if path.file_name()? == "keybindings.json" {
+            return Some(ImportFormat::Vscode);
+        }
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "json" => Some(ImportFormat::Json),
+            "yaml" | "yml" => Some(ImportFormat::Yaml),
+            "cheat" => Some(ImportFormat::Navi),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the file to import.
+pub fn read_import(path: &Path) -> Result<String> {
+    fs::read_to_string(path).context(format!("Failed to read import from {}", path.display()))
+}
+
+/// Returns the aliases of the git config at the given path as printed by `git config --null --get-regexp`.
+///
+/// git reads the config, so its quoting and included files are understood like git itself does.
+fn read_git_aliases(path: &Path) -> Result<String> {
+    // git config prints nothing for missing files, so they would be mistaken for configs without aliases
+    if !path.is_file() {
+        bail!(
+            "Failed to read import from {}, no such file",
+            path.display()
+        );
+    }
+
+    let output = Command::new("git")
+        .args(["config", "--includes", "--null", "--file"])
+        .arg(path)
+        .args(["--get-regexp", git::GIT_ALIAS_PATTERN])
+        .output()
+        .context("Failed to run git config")?;
+    // git config exits with 1 if no key matches
+    if !output.status.success() && output.status.code() != Some(1) {
+        bail!(
+            "Failed to read the aliases of {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Merges the given pages into the existing ones.
+///
+/// Pages with the same name are merged recursively, other pages are appended.
+/// Entries already on the page with the same content and description are skipped,
+/// so importing a file again doesn't duplicate them.
+/// Imported entries are renamed if their name is already taken on the page.
+pub fn merge_pages(pages: &mut Vec<Page>, imported: Vec<Page>) {
+    for page in imported {
+        let Some(existing) = pages.iter_mut().find(|p| p.name == page.name) else {
+            pages.push(page);
+            continue;
+        };
+
+        for entry in page.entries {
+            let is_duplicate = existing
+                .entries
+                .iter()
+                .any(|e| e.content == entry.content && e.description == entry.description);
+            if is_duplicate {
+                debug!(
+                    "Skipping already existing entry {} of page {}",
+                    entry.name, existing.name
+                );
+                continue;
+            }
+            add_entry(existing, entry);
+        }
+        for variable in page.variables {
+            if existing.variables.iter().any(|v| v.name == variable.name) {
+                debug!(
+                    "Keeping existing variable {} of page {}",
+                    variable.name, existing.name
+                );
+                continue;
+            }
+            existing.variables.push(variable);
+        }
+        merge_pages(&mut existing.subpages, page.subpages);
+    }
+}
+
+/// Parses the cheats of navi.
+///
+/// A line starting with `%` begins a new section, whose comma-separated tags are the path of the page it fills:
+/// `% git, branches` fills the subpage `branches` of page `git`.
+/// Within a section, `#` lines describe the command on the following lines and `$` lines define variables.
+/// Commands spanning multiple lines are joined with `;`, unless a line ends with a backslash.
+/// Comments starting with `;` and `@` lines extending other cheats are skipped.
+fn import_navi(content: &str) -> Vec<Page> {
+    let mut pages = vec![];
+    let mut path: Vec<String> = vec![];
+    let mut description = String::new();
+    let mut command = String::new();
+
+    for line in content.lines().map(str::trim_end) {
+        let finishes_command = line.is_empty()
+            || line.starts_with(['%', '#', '$', ';', '@'])
+                && !command.ends_with('\\')
+                && !command.is_empty();
+        if finishes_command {
+            add_navi_entry(&mut pages, &path, &mut description, &mut command);
+        }
+
+        if let Some(tags) = line.strip_prefix('%') {
+            path = tags
+                .split(',')
+                .map(str::trim)
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect();
+            description.clear();
+        } else if let Some(text) = line.strip_prefix('#') {
+            if !description.is_empty() {
+                description.push(' ');
+            }
+            description.push_str(text.trim());
+        } else if let Some(definition) = line.strip_prefix('$') {
+            let Some((name, command)) = definition.split_once(':') else {
+                warn!("Skipping navi variable without command: {}", line);
+                continue;
+            };
+            let variable = Variable {
+                name: name.trim().to_string(),
+                command: command.trim().to_string(),
+            };
+            page_at(&mut pages, &path).variables.push(variable);
+        } else if line.starts_with(';') {
+            continue;
+        } else if line.starts_with('@') {
+            debug!("Skipping navi extension: {}", line);
+        } else if !line.is_empty() {
+            if let Some(continued) = command.strip_suffix('\\') {
+                command = format!("{} {}", continued.trim_end(), line.trim());
+            } else if command.is_empty() {
+                command = line.trim().to_string();
+            } else {
+                command = format!("{}; {}", command, line.trim());
+            }
+        }
+    }
+    add_navi_entry(&mut pages, &path, &mut description, &mut command);
+
+    pages
+}
+
+/// Parses the snippets of pet into entries of a single page.
+///
+/// The first tag of a snippet becomes the section it is listed under, further tags have no counterpart in recall.
+fn import_pet(content: &str) -> Result<Vec<Page>> {
+    let pet: PetSnippets = toml::from_str(content).context("Failed to parse pet snippets")?;
+
+    let mut page = Page {
+        name: PET_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+
+    for snippet in pet.snippets {
+        if snippet.tag.len() > 1 {
+            debug!(
+                "Keeping only the first tag of snippet {}: {:?}",
+                snippet.command, snippet.tag
+            );
+        }
+
+        let entry = Entry {
+            name: entry_name(&snippet.description),
+            content: vec![snippet.command],
+            description: snippet.description,
+            section: snippet.tag.into_iter().next(),
+            ..Entry::default()
+        };
+        add_entry(&mut page, entry);
+    }
+
+    Ok(vec![page])
+}
+
+/// Returns the pages of a JSON or YAML document.
+fn import_document(document: PagesDocument) -> Vec<Page> {
+    let pages = match document {
+        PagesDocument::Config { pages } | PagesDocument::Pages(pages) => pages,
+    };
+    pages.into_iter().map(document_page).collect()
+}
+
+/// Converts a page of a JSON or YAML document and its subpages.
+///
+/// Entries without a name are named after their description, unknown sort orders are ignored.
+fn document_page(page: DocumentPage) -> Page {
+    let sort = page.sort.as_deref().and_then(|name| {
+        let sort = SortOrder::from_name(name);
+        if sort.is_none() {
+            warn!("Ignoring unknown sort order {} of page {}", name, page.name);
+        }
+        sort
+    });
+
+    let mut imported = Page {
+        name: page.name,
+        group: page.group,
+        description: page.description,
+        image: page.image,
+        aliases: page.aliases,
+        sort: sort.unwrap_or_default(),
+        subpages: page.subpages.into_iter().map(document_page).collect(),
+        variables: page
+            .variables
+            .into_iter()
+            .map(|variable| Variable {
+                name: variable.name,
+                command: variable.command,
+            })
+            .collect(),
+        show_names: page.show_names,
+        ..Page::default()
+    };
+
+    for entry in page.entries {
+        let entry = Entry {
+            name: entry.name.unwrap_or_else(|| entry_name(&entry.description)),
+            content: match entry.content {
+                Some(DocumentContent::Single(content)) => vec![content],
+                Some(DocumentContent::List(content)) => content,
+                None => vec![],
+            },
+            description: entry.description,
+            hidden: entry.hidden,
+            secret: entry.secret,
+            goto: entry.goto,
+            exec: entry.exec,
+            url: entry.url,
+            section: entry.section,
+            image: entry.image,
+            note: entry.note,
+            ..Entry::default()
+        };
+        add_entry(&mut imported, entry);
+    }
+
+    imported
+}
+
+/// Turns each Markdown file in the directory into a page named after the file, in alphabetical order.
+///
+/// Subdirectories and other files are skipped.
+fn import_markdown_dir(dir: &Path) -> Result<Vec<Page>> {
+    let mut paths = vec![];
+    for dir_entry in
+        fs::read_dir(dir).context(format!("Failed to read directory {}", dir.display()))?
+    {
+        let path = dir_entry
+            .context(format!("Failed to read directory {}", dir.display()))?
+            .path();
+        if path.is_file() && path.extension().is_some_and(|e| e == MARKDOWN_EXTENSION) {
+            paths.push(path);
+        } else {
+            debug!("Skipping {}, it is no Markdown file", path.display());
+        }
+    }
+    paths.sort();
+
+    let mut pages = vec![];
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            warn!("Skipping {}, its name is not valid UTF-8", path.display());
+            continue;
+        };
+        let page = import_markdown(name, &read_import(&path)?);
+        if page.entries.is_empty() {
+            debug!("Skipping {}, it has no tables or lists", path.display());
+            continue;
+        }
+        pages.push(page);
+    }
+    Ok(pages)
+}
+
+/// Parses a Markdown note into a page with the given name.
+///
+/// Headings start a section, rows of tables and items of lists are entries.
+/// The first column of a table is the content of an entry and the second its description, header rows are skipped.
+/// Items of lists are split into content and description after a leading code span, e.g. `` `git status` shows changes ``,
+/// or at a separator like ` - ` or `: `. Items that can't be split are prose and skipped, as is code in fenced blocks.
+fn import_markdown(name: &str, content: &str) -> Page {
+    let mut page = Page {
+        name: name.to_string(),
+        ..Page::default()
+    };
+    let mut section: Option<String> = None;
+    let mut in_code_block = false;
+
+    let lines: Vec<&str> = content.lines().map(str::trim).collect();
+    for (index, line) in lines.iter().enumerate() {
+        if line.starts_with("```") || line.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        let entry = if line.starts_with('#') {
+            let heading = line.trim_start_matches('#').trim();
+            section = (!heading.is_empty()).then(|| markdown_text(heading));
+            continue;
+        } else if line.starts_with('|') {
+            let is_header = lines
+                .get(index + 1)
+                .is_some_and(|next| is_table_separator(next));
+            if is_header || is_table_separator(line) {
+                continue;
+            }
+            let cells = table_cells(line);
+            match &cells[..] {
+                [content, description, ..] => (content.clone(), description.clone()),
+                _ => continue,
+            }
+        } else if let Some(item) = list_item(line) {
+            match split_list_item(item) {
+                Some(entry) => entry,
+                None => {
+                    debug!("Skipping list item without a command: {}", item);
+                    continue;
+                }
+            }
+        } else {
+            continue;
+        };
+
+        let (content, description) = entry;
+        if content.is_empty() {
+            continue;
+        }
+        let name = if description.is_empty() {
+            entry_name(&content)
+        } else {
+            entry_name(&description)
+        };
+        add_entry(
+            &mut page,
+            Entry {
+                name,
+                content: vec![content],
+                description,
+                section: section.clone(),
+                ..Entry::default()
+            },
+        );
+    }
+
+    page
+}
+
+/// Returns `true` if the line separates the header of a Markdown table from its rows, e.g. `|---|:--:|`.
+fn is_table_separator(line: &str) -> bool {
+    line.starts_with('|')
+        && line.contains('-')
+        && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// Returns the cells of a row of a Markdown table as plain text, keeping escaped pipes within cells.
+fn table_cells(line: &str) -> Vec<String> {
+    let mut cells = vec![];
+    let mut cell = String::new();
+    let mut chars = line.trim_matches('|').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => cell.push(chars.next().unwrap()),
+            '|' => cells.push(markdown_text(&std::mem::take(&mut cell))),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(markdown_text(&cell));
+    cells
+}
+
+/// Returns the text of an item of a Markdown list, or `None` if the line isn't one.
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(item) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return Some(item.trim());
+    }
+
+    // Ordered lists are numbered like `1.` or `1)`
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let rest = &line[digits..];
+    (digits > 0)
+        .then(|| rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")))
+        .flatten()
+        .map(str::trim)
+}
+
+/// Splits an item of a Markdown list into content and description, or returns `None` if it is prose.
+fn split_list_item(item: &str) -> Option<(String, String)> {
+    if let Some(code) = item.strip_prefix('`') {
+        let (content, rest) = code.split_once('`')?;
+        let description = rest
+            .trim_start()
+            .trim_start_matches(['-', '–', '—', ':'])
+            .trim();
+        return Some((content.trim().to_string(), markdown_text(description)));
+    }
+
+    MARKDOWN_SEPARATORS.iter().find_map(|separator| {
+        let (content, description) = item.split_once(separator)?;
+        Some((markdown_text(content), markdown_text(description)))
+    })
+}
+
+/// Returns Markdown as plain text, removing code spans, emphasis and `<kbd>` tags.
+fn markdown_text(markdown: &str) -> String {
+    markdown
+        .replace("<kbd>", "")
+        .replace("</kbd>", "")
+        .replace(['`', '*'], "")
+        .replace("__", "")
+        .trim()
+        .to_string()
+}
+
+/// Drafts a page from the `--help` output of a command, listing its options and subcommands.
+///
+/// The heuristic fits the help of clap, argparse and getopts alike:
+/// indented lines starting with a dash are options, their description follows after a gap of at least two spaces
+/// or on the following, further indented lines.
+/// Unindented lines ending with a colon are headings like `Options:`, which become sections.
+/// Indented lines under a heading mentioning commands are subcommands.
+pub fn import_help(command: &str, help: &str) -> Page {
+    let mut page = Page {
+        name: command.to_string(),
+        ..Page::default()
+    };
+    let mut section: Option<String> = None;
+    let mut pending: Option<(Entry, usize)> = None;
+
+    for line in help.lines() {
+        let text = line.trim();
+        let indent = line.len() - line.trim_start().len();
+
+        // Descriptions continue on further indented lines
+        if let Some((entry, option_indent)) = &mut pending {
+            if !text.is_empty() && indent > *option_indent && !text.starts_with('-') {
+                if !entry.description.is_empty() {
+                    entry.description.push(' ');
+                }
+                entry.description.push_str(text);
+                continue;
+            }
+        }
+        if let Some((entry, _)) = pending.take() {
+            add_entry(&mut page, entry);
+        }
+
+        if text.is_empty() {
+            continue;
+        }
+
+        if indent == 0 {
+            section = text.strip_suffix(':').map(str::to_string);
+            continue;
+        }
+
+        let (name, description) = match text.split_once("  ") {
+            Some((name, description)) => (name.trim(), description.trim()),
+            None => (text, ""),
+        };
+
+        let is_command_list = section
+            .as_deref()
+            .is_some_and(|section| section.to_lowercase().contains("command"));
+
+        let entry = if name.starts_with('-') {
+            Entry {
+                name: option_name(name),
+                content: vec![name.to_string()],
+                description: description.to_string(),
+                section: section.clone(),
+                ..Entry::default()
+            }
+        } else if is_command_list && !name.contains(char::is_whitespace) {
+            Entry {
+                name: entry_name(name),
+                content: vec![format!("{} {}", command, name)],
+                description: description.to_string(),
+                section: section.clone(),
+                ..Entry::default()
+            }
+        } else {
+            continue;
+        };
+        pending = Some((entry, indent));
+    }
+    if let Some((entry, _)) = pending {
+        add_entry(&mut page, entry);
+    }
+
+    page
+}
+
+/// Returns a name for an entry of an option, preferring its long form, e.g. `config` for `-c, --config <FILE>`.
+fn option_name(spec: &str) -> String {
+    let flags: Vec<&str> = spec
+        .split([',', ' ', '=', '['])
+        .filter(|flag| flag.starts_with('-'))
+        .collect();
+
+    let flag = flags
+        .iter()
+        .find(|flag| flag.starts_with("--"))
+        .or(flags.first())
+        .copied()
+        .unwrap_or(spec);
+    entry_name(flag)
+}
+
+/// Adds the pending command of a navi cheat as an entry of the page at the given path and resets it.
+fn add_navi_entry(
+    pages: &mut Vec<Page>,
+    path: &[String],
+    description: &mut String,
+    command: &mut String,
+) {
+    if command.is_empty() {
+        description.clear();
+        return;
+    }
+
+    let entry = Entry {
+        name: entry_name(description),
+        content: vec![command.trim_end_matches('\\').trim_end().to_string()],
+        description: description.clone(),
+        ..Entry::default()
+    };
+    add_entry(page_at(pages, path), entry);
+
+    description.clear();
+    command.clear();
+}
+
+/// Returns the page at the given path, creating it and its parents as needed.
+///
+/// Cheats without any tags end up on a page named `navi`.
+fn page_at<'a>(pages: &'a mut Vec<Page>, path: &[String]) -> &'a mut Page {
+    let (name, rest) = match path.split_first() {
+        Some((name, rest)) => (name.as_str(), rest),
+        None => ("navi", &[][..]),
+    };
+
+    let index = match pages.iter().position(|page| page.name == name) {
+        Some(index) => index,
+        None => {
+            pages.push(Page {
+                name: name.to_string(),
+                ..Page::default()
+            });
+            pages.len() - 1
+        }
+    };
+
+    if rest.is_empty() {
+        &mut pages[index]
+    } else {
+        page_at(&mut pages[index].subpages, rest)
+    }
+}
+
+/// Adds an entry to a page, appending a number to its name if it is already taken.
+pub fn add_entry(page: &mut Page, mut entry: Entry) {
+    let is_taken = |page: &Page, name: &str| page.entries.iter().any(|e| e.name == name);
+
+    if is_taken(page, &entry.name) {
+        let base = entry.name.clone();
+        let mut number = 2;
+        while is_taken(page, &format!("{}_{}", base, number)) {
+            number += 1;
+        }
+        entry.name = format!("{}_{}", base, number);
+    }
+
+    page.entries.push(entry);
+}
+
+/// Returns a name for an entry derived from its description, e.g. `change_branch` for "Change branch".
+pub fn entry_name(description: &str) -> String {
+    let name = description
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+
+    if name.is_empty() {
+        UNNAMED_ENTRY.to_string()
+    } else {
+        name
+    }
+}
@@ -0,0 +1,51 @@
+//! Importers module for Recall.
+//!
+//! Reads the key bindings out of the configs of other tools, so pages stay in sync with what is actually bound.
+//! Unlike the formats of cheatsheet tools in [`crate::import`], these configs aren't meant to be read by others,
+//! so each importer understands only the lines binding keys and skips everything else.
+
+#[cfg(test)]
+use crate::app::Page;
+
+pub mod alacritty;
+pub mod aliases;
+pub mod git;
+pub mod hyprland;
+pub mod i3;
+pub mod kitty;
+pub mod tmux;
+pub mod vim;
+pub mod vscode;
+
+/// Splits the first word off the text, returning it and the rest without leading whitespace.
+pub fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (text, ""),
+    }
+}
+
+/// Returns the content and description of the entries of an imported page.
+#[cfg(test)]
+fn bindings(page: &Page) -> Vec<(Vec<&str>, &str)> {
+    sectioned_bindings(page)
+        .into_iter()
+        .map(|(content, description, _)| (content, description))
+        .collect()
+}
+
+/// Returns the content, description and section of the entries of an imported page.
+#[cfg(test)]
+fn sectioned_bindings(page: &Page) -> Vec<(Vec<&str>, &str, Option<&str>)> {
+    page.entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.content.iter().map(String::as_str).collect(),
+                entry.description.as_str(),
+                entry.section.as_deref(),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,335 @@
+//! Alacritty importer for Recall.
+//!
+//! Reads the key bindings of an Alacritty config into a page, from the `keyboard.bindings` of the TOML config or the
+//! `key_bindings` of the legacy YAML config. The modifiers and key of a binding become a single chord as its content,
+//! e.g. `Control+Shift+N` for `mods = "Control|Shift"` and `key = "N"`, like the shortcuts of the other importers.
+//! Its action, the characters it sends in caret notation like `^L`, or its command describe it.
+//! Bindings restricted to a mode like `Vi` are put in a section named after the mode.
+
+use anyhow::{Context, Result};
+use log::debug;
+use serde::Deserialize;
+
+use crate::{
+    app::{Entry, Page},
+    import::{add_entry, entry_name},
+};
+
+/// Name of the page imported Alacritty bindings are listed on
+pub const ALACRITTY_PAGE_NAME: &str = "alacritty";
+
+/// Actions that unbind a key instead of doing something
+const UNBINDING_ACTIONS: [&str; 2] = ["None", "ReceiveChar"];
+
+/// The parts of an Alacritty config holding key bindings.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AlacrittyConfig {
+    /// Keyboard settings, holding the bindings since the TOML config
+    keyboard: AlacrittyKeyboard,
+
+    /// Key bindings of the legacy YAML config
+    key_bindings: Vec<AlacrittyBinding>,
+}
+
+/// The `keyboard` table of an Alacritty config.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AlacrittyKeyboard {
+    /// Key bindings
+    bindings: Vec<AlacrittyBinding>,
+}
+
+/// A key binding of Alacritty.
+#[derive(Debug, Deserialize)]
+struct AlacrittyBinding {
+    /// Bound key, named like `N` or `PageUp` or given as a scancode
+    key: Option<AlacrittyKey>,
+
+    /// Modifiers joined by `|`, e.g. `Control|Shift`
+    mods: Option<String>,
+
+    /// Modes the binding is restricted to, e.g. `Vi|~Search`
+    mode: Option<String>,
+
+    /// Bound action, e.g. `CreateNewWindow`
+    action: Option<String>,
+
+    /// Characters sent to the terminal
+    chars: Option<String>,
+
+    /// Program run by the binding
+    command: Option<AlacrittyCommand>,
+}
+
+/// A key of an Alacritty binding.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AlacrittyKey {
+    /// A key name like `N` or `PageUp`
+    Name(String),
+
+    /// A scancode of the keyboard
+    Scancode(u32),
+}
+
+/// A program run by an Alacritty binding.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AlacrittyCommand {
+    /// A program without arguments
+    Program(String),
+
+    /// A program with arguments
+    WithArguments {
+        /// Program to run
+        program: String,
+
+        /// Arguments of the program
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+/// Parses the key bindings of an Alacritty config, written in TOML or in YAML for the legacy config, into a page.
+///
+/// Returns `None` if nothing is bound.
+pub fn import(content: &str, yaml: bool) -> Result<Option<Page>> {
+    let config: AlacrittyConfig = if yaml {
+        serde_yaml_ng::from_str(content).context("Failed to parse the YAML config of Alacritty")?
+    } else {
+        toml::from_str(content).context("Failed to parse the TOML config of Alacritty")?
+    };
+
+    let mut page = Page {
+        name: ALACRITTY_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+    let bindings = config
+        .keyboard
+        .bindings
+        .into_iter()
+        .chain(config.key_bindings);
+    for binding in bindings {
+        match entry(binding) {
+            Some(entry) => add_entry(&mut page, entry),
+            None => debug!("Skipping binding without a key or an action"),
+        }
+    }
+
+    Ok((!page.entries.is_empty()).then_some(page))
+}
+
+/// Returns the entry of a binding, or `None` if it binds no key or unbinds it.
+fn entry(binding: AlacrittyBinding) -> Option<Entry> {
+    let key = match binding.key? {
+        AlacrittyKey::Name(name) => key_name(&name).to_string(),
+        AlacrittyKey::Scancode(scancode) => format!("Scancode {}", scancode),
+    };
+
+    let description = if let Some(action) = binding.action {
+        if UNBINDING_ACTIONS.contains(&action.as_str()) {
+            return None;
+        }
+        words(&action)
+    } else if let Some(chars) = binding.chars {
+        format!("Send {}", caret_notation(&chars))
+    } else {
+        match binding.command? {
+            AlacrittyCommand::Program(program) => format!("Run {}", program),
+            AlacrittyCommand::WithArguments { program, args } => {
+                format!("Run {} {}", program, args.join(" "))
+                    .trim_end()
+                    .to_string()
+            }
+        }
+    };
+
+    let mut chord: Vec<&str> = binding
+        .mods
+        .as_deref()
+        .unwrap_or_default()
+        .split('|')
+        .map(str::trim)
+        .filter(|modifier| !modifier.is_empty() && *modifier != "None")
+        .collect();
+    chord.push(&key);
+
+    Some(Entry {
+        name: entry_name(&description),
+        content: vec![chord.join("+")],
+        description,
+        section: binding.mode.filter(|mode| !mode.is_empty()),
+        ..Entry::default()
+    })
+}
+
+/// Returns the name recall uses for a key named by Alacritty, e.g. `1` for `Key1` or `Backspace` for `Back`.
+///
+/// Other names are kept, as recall reads them as they are, e.g. `PageUp` or `F5`.
+fn key_name(key: &str) -> &str {
+    if let Some(digit) = key.strip_prefix("Key").filter(|digit| digit.len() == 1) {
+        return digit;
+    }
+    match key {
+        "Back" => "Backspace",
+        "Plus" | "NumpadAdd" => "+",
+        "Minus" | "NumpadSubtract" => "-",
+        "Equals" => "=",
+        "Comma" => ",",
+        "Period" => ".",
+        "Slash" => "/",
+        "Backslash" => "\\",
+        "Semicolon" => ";",
+        "Apostrophe" => "'",
+        "Grave" => "`",
+        "LBracket" => "[",
+        "RBracket" => "]",
+        key => key,
+    }
+}
+
+/// Returns the characters with control characters written in caret notation, e.g. `^L` for form feed or `^[` for
+/// escape, as terminals show them.
+fn caret_notation(chars: &str) -> String {
+    let mut text = String::new();
+    for char in chars.chars() {
+        match char as u32 {
+            code @ 0..=0x1f => {
+                text.push('^');
+                text.extend(char::from_u32(code + 0x40));
+            }
+            0x7f => text.push_str("^?"),
+            _ => text.push(char),
+        }
+    }
+    text
+}
+
+/// Returns the words of an action written in camel case, e.g. `Create new window` for `CreateNewWindow`.
+fn words(action: &str) -> String {
+    let mut words = String::new();
+    for (index, char) in action.chars().enumerate() {
+        if index > 0 && char.is_uppercase() {
+            words.push(' ');
+            words.extend(char.to_lowercase());
+        } else {
+            words.push(char);
+        }
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::sectioned_bindings;
+
+    #[test]
+    fn reads_toml_bindings() {
+        let config = r#"
+[font]
+size = 12
+
+[keyboard]
+bindings = [
+    { key = "N", mods = "Control|Shift", action = "CreateNewWindow" },
+    { key = "L", mods = "Control", chars = "\u000c" },
+    { key = "Return", mods = "Command", command = { program = "open", args = ["-n", "/Applications/Alacritty.app"] } },
+    { key = "F", mods = "Control|Shift", mode = "~Search", action = "SearchForward" },
+    { key = "Key1", command = "htop" },
+]
+"#;
+        let page = import(config, false).unwrap().unwrap();
+
+        assert_eq!(page.name, "alacritty");
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![
+                (vec!["Control+Shift+N"], "Create new window", None),
+                (vec!["Control+L"], "Send ^L", None),
+                (
+                    vec!["Command+Return"],
+                    "Run open -n /Applications/Alacritty.app",
+                    None
+                ),
+                (vec!["Control+Shift+F"], "Search forward", Some("~Search")),
+                (vec!["1"], "Run htop", None),
+            ]
+        );
+        assert_eq!(page.entries[0].name, "create_new_window");
+        assert_eq!(page.entries[1].name, "send_l");
+    }
+
+    #[test]
+    fn reads_toml_binding_tables() {
+        let config = r#"
+[[keyboard.bindings]]
+key = "Back"
+mods = "Alt"
+chars = "\u001b\u007f"
+
+[[keyboard.bindings]]
+key = "V"
+mods = "Control|Shift"
+mode = "Vi"
+action = "ToggleNormalSelection"
+"#;
+        let page = import(config, false).unwrap().unwrap();
+
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![
+                (vec!["Alt+Backspace"], "Send ^[^?", None),
+                (
+                    vec!["Control+Shift+V"],
+                    "Toggle normal selection",
+                    Some("Vi")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_legacy_yaml_bindings() {
+        let config = "
+font:
+  size: 12
+key_bindings:
+  - { key: V, mods: Control|Shift, action: Paste }
+  - { key: Plus, mods: Control, action: IncreaseFontSize }
+  - { key: 29, mods: Control, chars: \"\\x1b\" }
+  - key: Q
+    mods: Command
+    action: Quit
+";
+        let page = import(config, true).unwrap().unwrap();
+
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![
+                (vec!["Control+Shift+V"], "Paste", None),
+                (vec!["Control++"], "Increase font size", None),
+                (vec!["Control+Scancode 29"], "Send ^[", None),
+                (vec!["Command+Q"], "Quit", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_unbound_keys() {
+        let config = r#"
+[keyboard]
+bindings = [
+    { key = "Q", mods = "Control", action = "None" },
+    { key = "W", mods = "Control", action = "ReceiveChar" },
+    { mods = "Control", action = "Copy" },
+]
+"#;
+        assert!(import(config, false).unwrap().is_none());
+        assert!(import("[window]\nopacity = 0.9\n", false)
+            .unwrap()
+            .is_none());
+        assert!(import("[keyboard\n", false).is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! Shell alias importer for Recall.
+//!
+//! Reads the aliases and functions of a shell into a page, either from a startup file like `.bashrc` or `.zshrc` or
+//! from the output of `alias`, e.g. with `alias | recall import aliases /dev/stdin`. The name of an alias becomes the
+//! content of its entry and its expansion the description, while functions are described by their body if it is a
+//! single line. A `# recall:` comment after an alias or function, or on the line above it, describes it instead.
+
+use crate::{
+    app::{Entry, Page},
+    import::{add_entry, entry_name},
+};
+
+/// Name of the page imported aliases are listed on
+pub const ALIASES_PAGE_NAME: &str = "aliases";
+
+/// Start of comments describing the following or preceding alias or function
+const DESCRIPTION_COMMENT: &str = "recall:";
+
+/// Parses the aliases and functions of a shell startup file, or the aliases printed by `alias`, into a page.
+///
+/// Returns `None` if nothing is defined.
+pub fn import(content: &str) -> Option<Page> {
+    // Startup files define aliases with `alias`, but zsh prints them as `name=value`, like variables are assigned
+    let is_startup_file = content
+        .lines()
+        .any(|line| line.trim_start().starts_with("alias "));
+
+    let mut page = Page {
+        name: ALIASES_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+    let mut description = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(comment) = line.strip_prefix('#') {
+            description = described(comment);
+            continue;
+        }
+
+        let (words, comment) = shell_words(line);
+        let description = comment.and_then(described).or(description.take());
+        let definitions = match words.first().map(String::as_str) {
+            Some("alias") => aliases(&words[1..]),
+            Some(_) if !is_startup_file => aliases(&words),
+            _ => function(line).into_iter().collect(),
+        };
+
+        for (name, expansion) in definitions {
+            let description = description.clone().unwrap_or(expansion);
+            add_entry(
+                &mut page,
+                Entry {
+                    name: entry_name(&description),
+                    content: vec![name],
+                    description,
+                    ..Entry::default()
+                },
+            );
+        }
+    }
+
+    (!page.entries.is_empty()).then_some(page)
+}
+
+/// Returns the description written in a `# recall:` comment, or `None` for other comments.
+fn described(comment: &str) -> Option<String> {
+    let description = comment.trim().strip_prefix(DESCRIPTION_COMMENT)?.trim();
+    (!description.is_empty()).then(|| description.to_string())
+}
+
+/// Returns the names and expansions of the aliases defined by the arguments of `alias`.
+///
+/// Aliases are written as `name=value`, or as `name value` like fish prints them. Options like `-g` are skipped.
+fn aliases(arguments: &[String]) -> Vec<(String, String)> {
+    let mut aliases = vec![];
+    let mut arguments = arguments
+        .iter()
+        .skip_while(|argument| argument.starts_with('-'));
+    while let Some(argument) = arguments.next() {
+        let (name, expansion) = match argument.split_once('=') {
+            Some((name, expansion)) => (name, expansion.to_string()),
+            None => match arguments.next() {
+                Some(expansion) => (argument.as_str(), expansion.clone()),
+                None => continue,
+            },
+        };
+        if !name.is_empty() && !expansion.is_empty() {
+            aliases.push((name.to_string(), expansion));
+        }
+    }
+    aliases
+}
+
+/// Returns the name and body of a function defined on the line as `name() {` or `function name {`.
+///
+/// The body describes a function defined on a single line like `mkcd() { mkdir -p "$1" && cd "$1"; }`, otherwise the
+/// name followed by parentheses does.
+fn function(line: &str) -> Option<(String, String)> {
+    let (head, body) = line.split_once('{')?;
+    let head = head.trim();
+    let head = head.strip_prefix("function ").unwrap_or(head).trim();
+    let name = head.strip_suffix("()").unwrap_or(head).trim_end();
+    let is_name = name
+        .chars()
+        .all(|c| c.is_alphanumeric() || "_-:.".contains(c));
+    if name.is_empty() || !is_name || name == head && !line.trim_start().starts_with("function ") {
+        return None;
+    }
+
+    let body = body
+        .trim()
+        .strip_suffix('}')
+        .map(|body| body.trim().trim_end_matches(';').trim_end())
+        .filter(|body| !body.is_empty());
+    let description = body.map_or_else(|| format!("{}()", name), str::to_string);
+    Some((name.to_string(), description))
+}
+
+/// Splits a line into words like a shell, removing quotes and escapes, and returns them with a trailing comment.
+///
+/// Quoted parts next to each other form a single word, so aliases printed by bash like `'echo '\''hi'\'''` are read.
+fn shell_words(line: &str) -> (Vec<String>, Option<&str>) {
+    let mut words = vec![];
+    let mut word: Option<String> = None;
+    let mut chars = line.char_indices();
+
+    while let Some((index, char)) = chars.next() {
+        match char {
+            '#' if word.is_none() => return (words, Some(&line[index + 1..])),
+            char if char.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                word.extend(
+                    chars
+                        .by_ref()
+                        .map(|(_, char)| char)
+                        .take_while(|&c| c != '\''),
+                );
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                while let Some((_, char)) = chars.next() {
+                    match char {
+                        '"' => break,
+                        '\\' => word.extend(chars.next().map(|(_, char)| char)),
+                        char => word.push(char),
+                    }
+                }
+            }
+            '\\' => word
+                .get_or_insert_with(String::new)
+                .extend(chars.next().map(|(_, char)| char)),
+            char => word.get_or_insert_with(String::new).push(char),
+        }
+    }
+    words.extend(word);
+    (words, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::bindings;
+
+    #[test]
+    fn reads_startup_files() {
+        let bashrc = "\
+export EDITOR=vim
+HISTSIZE=1000
+alias ll='ls -alF'
+alias gs=\"git status\" gd='git diff'
+alias -g G='| grep'
+# Make a directory and enter it
+mkcd() { mkdir -p \"$1\" && cd \"$1\"; }
+function extract {
+    tar xf \"$1\"
+}
+if [ -f ~/.bash_aliases ]; then
+fi
+";
+        let page = import(bashrc).unwrap();
+
+        assert_eq!(page.name, "aliases");
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["ll"], "ls -alF"),
+                (vec!["gs"], "git status"),
+                (vec!["gd"], "git diff"),
+                (vec!["G"], "| grep"),
+                (vec!["mkcd"], "mkdir -p \"$1\" && cd \"$1\""),
+                (vec!["extract"], "extract()"),
+            ]
+        );
+        assert_eq!(page.entries[0].name, "ls_alf");
+    }
+
+    #[test]
+    fn reads_alias_output() {
+        let bash = "alias hi='echo '\\''hi'\\'''\nalias ..='cd ..'\n";
+        let page = import(bash).unwrap();
+        assert_eq!(
+            bindings(&page),
+            vec![(vec!["hi"], "echo 'hi'"), (vec![".."], "cd ..")]
+        );
+
+        let zsh = "l=ls\nll='ls -l'\nrun-help=man\n";
+        let page = import(zsh).unwrap();
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["l"], "ls"),
+                (vec!["ll"], "ls -l"),
+                (vec!["run-help"], "man")
+            ]
+        );
+
+        let fish = "alias gco 'git checkout'\n";
+        let page = import(fish).unwrap();
+        assert_eq!(bindings(&page), vec![(vec!["gco"], "git checkout")]);
+    }
+
+    #[test]
+    fn describes_by_recall_comments() {
+        let zshrc = "\
+alias gs='git status' # recall: Show the working tree status
+# recall: Open the config of recall
+alias rc='recall edit'
+# Not a description
+alias k=kubectl
+# recall: Extract any archive
+extract() {
+";
+        let page = import(zshrc).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["gs"], "Show the working tree status"),
+                (vec!["rc"], "Open the config of recall"),
+                (vec!["k"], "kubectl"),
+                (vec!["extract"], "Extract any archive"),
+            ]
+        );
+        assert_eq!(page.entries[0].name, "show_the_working_tree_status");
+    }
+
+    #[test]
+    fn skips_files_without_aliases() {
+        assert!(import("").is_none());
+        assert!(import("alias\nalias -p\n").is_none());
+        assert!(
+            import("alias ll='ls -l'\nexport PATH=\"$HOME/bin:$PATH\"\n")
+                .is_some_and(|page| page.entries.len() == 1)
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! git alias importer for Recall.
+//!
+//! Reads the aliases of a git config into a page, as printed by `git config --null --get-regexp`, so git itself reads
+//! the config with its quoting, continued lines and included files. An alias is typed as `git name`, which becomes the
+//! content of its entry. Long aliases, often shell commands starting with `!`, are shortened to fit the description,
+//! while the whole alias is kept as the note of the entry.
+
+use crate::{
+    app::{Entry, Page},
+    import::{add_entry, entry_name},
+};
+
+/// Name of the page imported git aliases are listed on
+pub const GIT_PAGE_NAME: &str = "Git Aliases";
+
+/// Pattern of the keys of aliases given to `git config --get-regexp`
+pub const GIT_ALIAS_PATTERN: &str = r"^alias\.";
+
+/// Characters of an alias shown in the description of its entry
+const MAX_DESCRIPTION_LENGTH: usize = 60;
+
+/// Marks a description shortened to the maximum length
+const ELLIPSIS: char = '…';
+
+/// Parses the aliases printed by `git config --null --get-regexp`, each key followed by a newline, its value and a
+/// null character, into a page.
+///
+/// Returns `None` if there are no aliases.
+pub fn import(output: &str) -> Option<Page> {
+    let mut page = Page {
+        name: GIT_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+
+    for record in output.split('\0') {
+        let Some((key, value)) = record.split_once('\n') else {
+            continue;
+        };
+        let Some(name) = key.trim().strip_prefix("alias.") else {
+            continue;
+        };
+        if name.is_empty() || value.trim().is_empty() {
+            continue;
+        }
+
+        let (description, shortened) = describe(value);
+        add_entry(
+            &mut page,
+            Entry {
+                name: entry_name(name),
+                content: vec![format!("git {}", name)],
+                description,
+                note: shortened.then(|| value.trim().to_string()),
+                ..Entry::default()
+            },
+        );
+    }
+
+    (!page.entries.is_empty()).then_some(page)
+}
+
+/// Returns an alias on a single line as the description of its entry, shortened if it is too long, and whether it
+/// was shortened.
+fn describe(alias: &str) -> (String, bool) {
+    let line = alias.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= MAX_DESCRIPTION_LENGTH {
+        return (line, false);
+    }
+
+    let mut description: String = line.chars().take(MAX_DESCRIPTION_LENGTH - 1).collect();
+    description.truncate(description.trim_end().len());
+    description.push(ELLIPSIS);
+    (description, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_aliases() {
+        let output = "alias.co\ncheckout\0alias.st\nstatus -sb\0alias.empty\n\0";
+        let page = import(output).unwrap();
+
+        assert_eq!(page.name, "Git Aliases");
+        let entries: Vec<(&str, &str, &str)> = page
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.name.as_str(),
+                    entry.content[0].as_str(),
+                    entry.description.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![("co", "git co", "checkout"), ("st", "git st", "status -sb")]
+        );
+        assert_eq!(page.entries[0].note, None);
+    }
+
+    #[test]
+    fn shortens_long_aliases() {
+        let alias = "!f() { git status;\n  git log --graph --pretty=format:'%h %s' --abbrev-commit --all; }; f";
+        let page = import(&format!("alias.overview\n{}\0", alias)).unwrap();
+        let entry = &page.entries[0];
+
+        assert_eq!(
+            entry.description,
+            "!f() { git status; git log --graph --pretty=format:'%h %s'…"
+        );
+        assert!(entry.description.chars().count() <= MAX_DESCRIPTION_LENGTH);
+        assert_eq!(entry.note.as_deref(), Some(alias));
+    }
+
+    #[test]
+    fn skips_other_keys() {
+        assert!(import("").is_none());
+        assert!(import("user.name\nJane\0alias.\nstatus\0").is_none());
+    }
+}
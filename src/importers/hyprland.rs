@@ -0,0 +1,310 @@
+//! Hyprland importer for Recall.
+//!
+//! Reads the `bind` lines of a hyprland.conf into a page, including their variants with flags like `binde` or `bindm`
+//! for mouse bindings. Variables like `$mainMod = SUPER` are substituted first, and modifiers and key names are
+//! translated into those of recall, e.g. `SUPER` into `Super` and `Return` into `Enter`. The dispatcher and its
+//! arguments describe a binding, unless `bindd` gives a description. Bindings of a submap are listed on a subpage.
+
+use std::cmp::Reverse;
+
+use log::debug;
+
+use crate::{
+    app::{Entry, Page, PAGE_PATH_SEPARATOR},
+    import::{add_entry, entry_name},
+    importers::i3,
+};
+
+/// Name of the page imported Hyprland bindings are listed on
+pub const HYPRLAND_PAGE_NAME: &str = "Hyprland";
+
+/// Submap that ends the bindings of a submap, returning to the global bindings
+const RESET_SUBMAP: &str = "reset";
+
+/// Dispatcher entering a submap
+const SUBMAP_DISPATCHER: &str = "submap";
+
+/// Flags of `bind`, e.g. `e` to repeat while held in `binde`
+const BIND_FLAGS: &str = "lrenmtisdpocgu";
+
+/// Parses the bindings of a hyprland.conf into a page, or `None` if nothing is bound.
+///
+/// Global bindings are listed on the page itself, bindings of submaps on a subpage per submap.
+/// Bindings entering a submap go to its subpage.
+pub fn import(content: &str) -> Option<Page> {
+    let mut variables: Vec<(String, String)> = vec![];
+    let mut submap: Option<String> = None;
+    let mut bindings: Vec<(Option<String>, Entry)> = vec![];
+
+    for line in content.lines() {
+        let line = uncommented(line);
+        let Some((keyword, value)) = line.split_once('=') else {
+            continue;
+        };
+        let keyword = keyword.trim();
+
+        // Variables can be set again, so only their values are substituted
+        if let Some(name) = keyword.strip_prefix('$') {
+            variables.push((format!("${}", name), substitute(value.trim(), &variables)));
+            // Longer names are substituted first, so `$mod` doesn't replace the start of `$mainMod`
+            variables.sort_by_key(|(name, _)| Reverse(name.len()));
+            continue;
+        }
+
+        if keyword == SUBMAP_DISPATCHER {
+            let name = value.trim();
+            submap = (name != RESET_SUBMAP).then(|| name.to_string());
+            continue;
+        }
+
+        let Some(flags) = keyword.strip_prefix("bind") else {
+            continue;
+        };
+        if !flags.chars().all(|flag| BIND_FLAGS.contains(flag)) {
+            continue;
+        }
+        match binding(&substitute(value, &variables), flags.contains('d')) {
+            Some(entry) => bindings.push((submap.clone(), entry)),
+            None => debug!("Skipping binding without a dispatcher: {}", line.trim()),
+        }
+    }
+
+    if bindings.is_empty() {
+        return None;
+    }
+
+    let mut page = Page {
+        name: HYPRLAND_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+    for (submap, entry) in bindings {
+        let Some(submap) = submap else {
+            add_entry(&mut page, entry);
+            continue;
+        };
+        let index = match page.subpages.iter().position(|page| page.name == submap) {
+            Some(index) => index,
+            None => {
+                page.subpages.push(Page {
+                    name: submap,
+                    ..Page::default()
+                });
+                page.subpages.len() - 1
+            }
+        };
+        add_entry(&mut page.subpages[index], entry);
+    }
+
+    // Bindings entering a submap open its subpage, so the keys of the submap are a key press away
+    let submaps: Vec<String> = page.subpages.iter().map(|page| page.name.clone()).collect();
+    let entries = page
+        .entries
+        .iter_mut()
+        .chain(page.subpages.iter_mut().flat_map(|page| &mut page.entries));
+    for entry in entries {
+        if let Some(submap) = entry.goto.take() {
+            entry.goto = submaps
+                .contains(&submap)
+                .then(|| format!("{}{}{}", HYPRLAND_PAGE_NAME, PAGE_PATH_SEPARATOR, submap));
+        }
+    }
+
+    Some(page)
+}
+
+/// Parses a binding written as `MODS, key, dispatcher, arguments` into an entry, or as
+/// `MODS, key, description, dispatcher, arguments` if it is `described`.
+///
+/// The keys are kept as a single chord like `Super+Shift+1`. Bindings entering a submap get the name of the submap as
+/// `goto`, which is resolved once all submaps are known.
+/// Returns `None` if there is no dispatcher.
+fn binding(value: &str, described: bool) -> Option<Entry> {
+    let fields = if described { 5 } else { 4 };
+    let mut parts = value.splitn(fields, ',').map(str::trim);
+    let modifiers = parts.next()?;
+    let key = parts.next()?;
+    let description = described.then(|| parts.next()).flatten();
+    let dispatcher = parts.next().filter(|dispatcher| !dispatcher.is_empty())?;
+    let arguments = parts.next().unwrap_or_default();
+
+    let mut chord: Vec<String> = modifiers
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '+')
+        .filter(|modifier| !modifier.is_empty())
+        .map(|name| modifier(name).to_string())
+        .collect();
+    if !key.is_empty() {
+        chord.push(key_name(key));
+    }
+    if chord.is_empty() {
+        return None;
+    }
+
+    let command = format!("{} {}", dispatcher, arguments)
+        .trim_end()
+        .to_string();
+    let description = description
+        .filter(|description| !description.is_empty())
+        .map_or(command, str::to_string);
+    Some(Entry {
+        name: entry_name(&description),
+        content: vec![chord.join("+")],
+        description,
+        goto: (dispatcher == SUBMAP_DISPATCHER && arguments != RESET_SUBMAP)
+            .then(|| arguments.to_string()),
+        ..Entry::default()
+    })
+}
+
+/// Returns the name recall gives a modifier of Hyprland, e.g. `Super` for `SUPER` or `MOD4`.
+fn modifier(name: &str) -> &str {
+    match name.to_uppercase().as_str() {
+        "SUPER" | "WIN" | "LOGO" | "MOD4" => "Super",
+        "CTRL" | "CONTROL" => "Ctrl",
+        "ALT" | "MOD1" => "Alt",
+        "SHIFT" => "Shift",
+        "CAPS" => "CapsLock",
+        "MOD2" => "NumLock",
+        "MOD5" => "AltGr",
+        _ => name,
+    }
+}
+
+/// Returns the name recall gives a key of Hyprland, e.g. `Enter` for `Return` or `MouseLeft` for `mouse:272`.
+///
+/// Keys are named by their X key symbols, as in i3, by keycodes like `code:10` or by mouse buttons.
+fn key_name(key: &str) -> String {
+    if let Some(code) = key.strip_prefix("code:") {
+        return format!("Keycode {}", code);
+    }
+    let name = match key {
+        "mouse:272" => "MouseLeft",
+        "mouse:273" => "MouseRight",
+        "mouse:274" => "MouseMiddle",
+        "mouse_up" => "ScrollUp",
+        "mouse_down" => "ScrollDown",
+        "mouse_left" => "ScrollLeft",
+        "mouse_right" => "ScrollRight",
+        key => i3::key(key),
+    };
+    name.to_string()
+}
+
+/// Returns the line without its comment, where `##` stands for a literal `#`.
+fn uncommented(line: &str) -> String {
+    let mut uncommented = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char == '#' && chars.next_if_eq(&'#').is_none() {
+            break;
+        }
+        uncommented.push(char);
+    }
+    uncommented
+}
+
+/// Returns the text with the given variables replaced by their values.
+fn substitute(text: &str, variables: &[(String, String)]) -> String {
+    let mut text = text.to_string();
+    for (name, value) in variables {
+        text = text.replace(name.as_str(), value);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::bindings;
+
+    #[test]
+    fn expands_variables_and_dispatchers() {
+        let config = "\
+$mainMod = SUPER
+$terminal = kitty --single-instance
+monitor = ,preferred,auto,1
+bind = $mainMod, Q, exec, $terminal
+bind = $mainMod SHIFT, 1, movetoworkspace, 1
+bind = $mainMod_CTRL, Return, fullscreen
+binde = , XF86AudioRaiseVolume, exec, wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+
+bind = ALT, code:10, workspace, 1 # first workspace
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(page.name, "Hyprland");
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["Super+Q"], "exec kitty --single-instance"),
+                (vec!["Super+Shift+1"], "movetoworkspace 1"),
+                (vec!["Super+Ctrl+Enter"], "fullscreen"),
+                (
+                    vec!["XF86AudioRaiseVolume"],
+                    "exec wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+"
+                ),
+                (vec!["Alt+Keycode 10"], "workspace 1"),
+            ]
+        );
+        assert_eq!(page.entries[1].name, "movetoworkspace_1");
+    }
+
+    #[test]
+    fn reads_mouse_and_described_bindings() {
+        let config = "\
+bindm = SUPER, mouse:272, movewindow
+bind = SUPER, mouse_down, workspace, e+1
+bindd = SUPER, B, Open the browser, exec, firefox, --new-window
+bind = SUPER, N, exec, echo ## not a comment
+bindx = SUPER, X, exec, ignored
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["Super+MouseLeft"], "movewindow"),
+                (vec!["Super+ScrollDown"], "workspace e+1"),
+                (vec!["Super+B"], "Open the browser"),
+                (vec!["Super+N"], "exec echo # not a comment"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_submaps_on_subpages() {
+        let config = "\
+bind = ALT, R, submap, resize
+bind = ALT, M, submap, missing
+submap = resize
+binde = , right, resizeactive, 10 0
+bind = , Escape, submap, reset
+submap = reset
+bind = SUPER, C, killactive,
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["Alt+R"], "submap resize"),
+                (vec!["Alt+M"], "submap missing"),
+                (vec!["Super+C"], "killactive"),
+            ]
+        );
+        assert_eq!(page.entries[0].goto.as_deref(), Some("Hyprland/resize"));
+        assert_eq!(page.entries[1].goto, None);
+        assert_eq!(page.subpages[0].name, "resize");
+        assert_eq!(
+            bindings(&page.subpages[0]),
+            vec![
+                (vec!["right"], "resizeactive 10 0"),
+                (vec!["Esc"], "submap reset"),
+            ]
+        );
+        assert_eq!(page.subpages[0].entries[1].goto, None);
+    }
+
+    #[test]
+    fn skips_bindings_without_a_dispatcher() {
+        assert!(import("bind = SUPER, Q\nbind = , ,exec, x\n").is_none());
+    }
+}
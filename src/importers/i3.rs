@@ -0,0 +1,416 @@
+//! i3 and sway importer for Recall.
+//!
+//! Reads the `bindsym` and `bindcode` lines of an i3 or sway config into a page, with a subpage per binding mode.
+//! Variables set with `set $name value` are substituted first, so `$mod+Return` is read as `Mod4+Return` if `$mod` is
+//! `Mod4`. Modifiers and key names of X are translated into those of recall, e.g. `Mod4` into `Super` and `Return`
+//! into `Enter`, while bindings in `bar` blocks, which bind mouse buttons of the bar, are skipped.
+
+use std::cmp::Reverse;
+
+use log::debug;
+
+use crate::{
+    app::{Entry, Page, PAGE_PATH_SEPARATOR},
+    import::{add_entry, entry_name},
+    importers::split_word,
+};
+
+/// Name of the page imported i3 bindings are listed on
+pub const I3_PAGE_NAME: &str = "i3";
+
+/// Name of the page imported sway bindings are listed on
+pub const SWAY_PAGE_NAME: &str = "sway";
+
+/// Mode the bindings outside of any `mode` block belong to
+const DEFAULT_MODE: &str = "default";
+
+/// A block of an i3 config, opened by a line ending with `{` and closed by a line of `}`.
+#[derive(Debug, PartialEq)]
+enum Block {
+    /// A binding mode, whose bindings are listed on a subpage named after it
+    Mode(String),
+
+    /// Bindings written as `key command` lines, as sway allows with `bindsym {`
+    Bindings {
+        /// Whether the keys are keycodes, as in `bindcode {`
+        keycodes: bool,
+    },
+
+    /// Any other block, e.g. `bar` or `input`, whose lines are skipped
+    Other,
+}
+
+/// Parses the bindings of an i3 or sway config into a page with the given name, or `None` if nothing is bound.
+///
+/// Bindings of the default mode are listed on the page itself, bindings of other modes on a subpage per mode.
+/// The bound command describes a binding, bindings entering a mode go to its subpage.
+pub fn import(content: &str, name: &str) -> Option<Page> {
+    let mut variables: Vec<(String, String)> = vec![];
+    let mut blocks: Vec<Block> = vec![];
+    let mut bindings: Vec<(String, Entry)> = vec![];
+
+    for line in logical_lines(content) {
+        // Variables can be set again, so only their values are substituted
+        if let ("set", rest) = split_word(&line) {
+            let (variable, value) = split_word(rest);
+            if variable.starts_with('$') {
+                variables.push((variable.to_string(), substitute(value.trim(), &variables)));
+                // Longer names are substituted first, so `$mod` doesn't replace the start of `$mod_alt`
+                variables.sort_by_key(|(name, _)| Reverse(name.len()));
+            }
+            continue;
+        }
+
+        let line = substitute(line.trim(), &variables);
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line == "}" {
+            blocks.pop();
+            continue;
+        }
+
+        let mode = blocks.iter().rev().find_map(|block| match block {
+            Block::Mode(name) => Some(name.as_str()),
+            _ => None,
+        });
+        let mode = mode.unwrap_or(DEFAULT_MODE).to_string();
+        let in_other_block = blocks.contains(&Block::Other);
+
+        if let Some(opening) = line.strip_suffix('{') {
+            let (word, rest) = split_word(opening);
+            let block = match word {
+                "mode" => Block::Mode(mode_name(rest)),
+                "bindsym" | "bindcode" if !in_other_block => Block::Bindings {
+                    keycodes: word == "bindcode",
+                },
+                _ => Block::Other,
+            };
+            blocks.push(block);
+            continue;
+        }
+
+        if in_other_block {
+            continue;
+        }
+
+        let binding = match blocks.last() {
+            Some(Block::Bindings { keycodes }) => binding(&line, *keycodes),
+            _ => match split_word(&line) {
+                ("bindsym", rest) => binding(rest, false),
+                ("bindcode", rest) => binding(rest, true),
+                _ => continue,
+            },
+        };
+        match binding {
+            Some(entry) => bindings.push((mode, entry)),
+            None => debug!("Skipping binding without a command: {}", line),
+        }
+    }
+
+    if bindings.is_empty() {
+        return None;
+    }
+
+    let mut page = Page {
+        name: name.to_string(),
+        ..Page::default()
+    };
+    for (mode, entry) in bindings {
+        if mode == DEFAULT_MODE {
+            add_entry(&mut page, entry);
+            continue;
+        }
+
+        let index = match page.subpages.iter().position(|page| page.name == mode) {
+            Some(index) => index,
+            None => {
+                page.subpages.push(Page {
+                    name: mode,
+                    ..Page::default()
+                });
+                page.subpages.len() - 1
+            }
+        };
+        add_entry(&mut page.subpages[index], entry);
+    }
+
+    // Bindings entering a mode open its subpage, so the keys of the mode are a key press away
+    let modes: Vec<String> = page.subpages.iter().map(|page| page.name.clone()).collect();
+    let entries = page
+        .entries
+        .iter_mut()
+        .chain(page.subpages.iter_mut().flat_map(|page| &mut page.entries));
+    for entry in entries {
+        if let ("mode", mode) = split_word(&entry.description) {
+            let mode = mode_name(mode);
+            if modes.contains(&mode) {
+                entry.goto = Some(format!("{}{}{}", name, PAGE_PATH_SEPARATOR, mode));
+            }
+        }
+    }
+
+    Some(page)
+}
+
+/// Returns the lines of the config, joining lines ending with a backslash with the following line.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for physical_line in content.lines() {
+        match physical_line.strip_suffix('\\') {
+            Some(continued) => {
+                line.push_str(continued);
+                line.push(' ');
+            }
+            None => {
+                line.push_str(physical_line);
+                lines.push(std::mem::take(&mut line));
+            }
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Returns the line with the given variables replaced by their values.
+fn substitute(line: &str, variables: &[(String, String)]) -> String {
+    let mut line = line.to_string();
+    for (name, value) in variables {
+        line = line.replace(name.as_str(), value);
+    }
+    line
+}
+
+/// Returns the name of a mode from the rest of its `mode` line, skipping options and quotes.
+fn mode_name(rest: &str) -> String {
+    let mut rest = rest.trim();
+    while rest.starts_with("--") {
+        rest = split_word(rest).1;
+    }
+    rest.trim_matches('"').to_string()
+}
+
+/// Parses a binding written as `[--options] keys command` into an entry described by its command.
+///
+/// The keys are kept as a single chord like `Super+Shift+q`, with modifiers and key names translated.
+/// Returns `None` if there is no command.
+fn binding(text: &str, keycodes: bool) -> Option<Entry> {
+    let (mut keys, mut command) = split_word(text);
+    while keys.starts_with("--") {
+        (keys, command) = split_word(command);
+    }
+    if keys.is_empty() || command.is_empty() {
+        return None;
+    }
+
+    let mut chord = vec![];
+    let mut parts = keys.split('+').peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_some() {
+            if let Some(modifier) = modifier(part) {
+                chord.push(modifier.to_string());
+            }
+        } else if keycodes {
+            chord.push(format!("Keycode {}", part));
+        } else {
+            chord.push(key(part).to_string());
+        }
+    }
+
+    Some(Entry {
+        name: entry_name(command),
+        content: vec![chord.join("+")],
+        description: command.to_string(),
+        ..Entry::default()
+    })
+}
+
+/// Returns the name recall gives a modifier of X, or `None` for keyboard groups, which are no keys.
+fn modifier(name: &str) -> Option<&str> {
+    let modifier = match name {
+        "Mod1" => "Alt",
+        "Mod2" => "NumLock",
+        "Mod4" => "Super",
+        "Mod5" => "AltGr",
+        "Control" | "Ctrl" => "Ctrl",
+        "Lock" => "CapsLock",
+        name if name.starts_with("Group") => return None,
+        name => name,
+    };
+    Some(modifier)
+}
+
+/// Returns the name recall gives a key symbol of X, e.g. `Enter` for `Return` or `-` for `minus`.
+pub fn key(symbol: &str) -> &str {
+    match symbol {
+        "Return" | "KP_Enter" => "Enter",
+        "space" => "Space",
+        "Escape" => "Esc",
+        "BackSpace" => "Backspace",
+        "Delete" => "Del",
+        "Insert" => "Ins",
+        "Prior" | "Page_Up" => "PageUp",
+        "Next" | "Page_Down" => "PageDown",
+        "minus" => "-",
+        "plus" => "+",
+        "equal" => "=",
+        "comma" => ",",
+        "period" => ".",
+        "slash" => "/",
+        "backslash" => "\\",
+        "semicolon" => ";",
+        "apostrophe" => "'",
+        "grave" => "`",
+        "bracketleft" => "[",
+        "bracketright" => "]",
+        symbol => symbol,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::bindings;
+
+    #[test]
+    fn translates_variables_and_modifiers() {
+        let page = import(
+            "set $mod Mod4\nset $mod_alt Mod1\nbindsym $mod+Shift+q kill\nbindsym $mod_alt+Control+l exec lock\n",
+            I3_PAGE_NAME,
+        )
+        .unwrap();
+
+        assert_eq!(page.name, "i3");
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["Super+Shift+q"], "kill"),
+                (vec!["Alt+Ctrl+l"], "exec lock"),
+            ]
+        );
+        assert_eq!(page.entries[0].name, "kill");
+    }
+
+    #[test]
+    fn translates_key_symbols() {
+        let page = import(
+            "bindsym Mod4+Return exec alacritty\nbindsym Mod4+minus scratchpad show\nbindsym XF86AudioMute exec mute\n",
+            I3_PAGE_NAME,
+        )
+        .unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["Super+Enter"], "exec alacritty"),
+                (vec!["Super+-"], "scratchpad show"),
+                (vec!["XF86AudioMute"], "exec mute"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_modes_on_subpages() {
+        let config = "\
+bindsym Mod4+r mode \"resize\"
+mode \"resize\" {
+    bindsym h resize shrink width 10 px
+    bindsym Escape mode \"default\"
+}
+set $mode_system System (l) lock
+mode --pango_markup \"$mode_system\" {
+    bindsym l exec lock
+}
+";
+        let page = import(config, SWAY_PAGE_NAME).unwrap();
+
+        assert_eq!(bindings(&page), vec![(vec!["Super+r"], "mode \"resize\"")]);
+        assert_eq!(page.entries[0].goto.as_deref(), Some("sway/resize"));
+        assert_eq!(page.subpages.len(), 2);
+        assert_eq!(page.subpages[0].name, "resize");
+        assert_eq!(
+            bindings(&page.subpages[0]),
+            vec![
+                (vec!["h"], "resize shrink width 10 px"),
+                (vec!["Esc"], "mode \"default\""),
+            ]
+        );
+        assert_eq!(page.subpages[1].name, "System (l) lock");
+        assert_eq!(bindings(&page.subpages[1]), vec![(vec!["l"], "exec lock")]);
+    }
+
+    #[test]
+    fn reads_keycodes() {
+        let page = import("bindcode --release Mod4+38 focus left\n", I3_PAGE_NAME).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![(vec!["Super+Keycode 38"], "focus left")]
+        );
+    }
+
+    #[test]
+    fn skips_options_comments_and_bar_bindings() {
+        let config = "\
+# bindsym Mod4+x exec commented
+bindsym --release --to-code Mod4+x \\
+    exec scrot
+bar {
+    bindsym button4 nop
+    colors {
+        background #000000
+    }
+}
+bindsym Mod4+f fullscreen toggle
+";
+        let page = import(config, I3_PAGE_NAME).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["Super+x"], "exec scrot"),
+                (vec!["Super+f"], "fullscreen toggle"),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_binding_blocks() {
+        let config = "\
+set $mod Mod4
+bindsym {
+    $mod+1 workspace number 1
+    $mod+Shift+1 move container to workspace number 1
+}
+";
+        let page = import(config, SWAY_PAGE_NAME).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["Super+1"], "workspace number 1"),
+                (
+                    vec!["Super+Shift+1"],
+                    "move container to workspace number 1"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn renames_taken_entry_names() {
+        let page = import("bindsym Mod4+q kill\nbindsym Mod4+w kill\n", I3_PAGE_NAME).unwrap();
+
+        let names: Vec<&str> = page.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["kill", "kill_2"]);
+    }
+
+    #[test]
+    fn returns_nothing_without_bindings() {
+        assert!(import("set $mod Mod4\nexec_always nm-applet\n", I3_PAGE_NAME).is_none());
+    }
+}
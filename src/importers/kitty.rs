@@ -0,0 +1,314 @@
+//! kitty importer for Recall.
+//!
+//! Reads the `map` lines of a kitty.conf into a page. `kitty_mod` is expanded into the modifiers it is set to,
+//! `ctrl+shift` unless the config sets it otherwise, and key names of kitty are translated into those of recall,
+//! e.g. `page_up` into `PageUp`. Multi-key shortcuts like `kitty_mod+t>n` become a key sequence like `ctrl+shift+t n`.
+//! Keyboard modes defined with `map --new-mode` get a subpage listing the keys mapped with `--mode`.
+
+use log::debug;
+
+use crate::{
+    app::{Entry, Page, PAGE_PATH_SEPARATOR},
+    import::{add_entry, entry_name},
+    importers::split_word,
+};
+
+/// Name of the page imported kitty shortcuts are listed on
+pub const KITTY_PAGE_NAME: &str = "kitty";
+
+/// Modifiers of `kitty_mod` unless the config sets it
+const DEFAULT_KITTY_MOD: &str = "ctrl+shift";
+
+/// Actions that unmap a shortcut instead of doing something
+const UNMAPPING_ACTIONS: [&str; 2] = ["no_op", "discard_event"];
+
+/// A `map` line of kitty.
+struct Mapping {
+    /// Keyboard mode the shortcut is mapped in, set with `--mode`
+    mode: Option<String>,
+
+    /// Keyboard mode the shortcut enters, defined with `--new-mode`
+    new_mode: Option<String>,
+
+    /// Keys of the shortcut as written, e.g. `kitty_mod+t>n`
+    keys: String,
+
+    /// Mapped action, e.g. `new_tab`
+    action: String,
+}
+
+/// Parses the shortcuts of a kitty.conf into a page, or `None` if nothing is mapped.
+///
+/// Shortcuts of keyboard modes are listed on a subpage per mode, shortcuts entering a mode go to its subpage.
+pub fn import(content: &str) -> Option<Page> {
+    let mut kitty_mod = DEFAULT_KITTY_MOD.to_string();
+    let mut mappings = vec![];
+
+    for line in logical_lines(content) {
+        let line = line.trim();
+        match split_word(line) {
+            // kitty resolves kitty_mod after reading the whole config, so its last value applies to all shortcuts
+            ("kitty_mod", value) if !value.is_empty() => kitty_mod = value.to_string(),
+            ("map", rest) => match mapping(rest) {
+                Some(mapping) => mappings.push(mapping),
+                None => debug!("Skipping mapping without an action: {}", line),
+            },
+            _ => {}
+        }
+    }
+
+    // Only modes with mapped keys have a subpage to go to
+    let modes: Vec<String> = mappings
+        .iter()
+        .filter_map(|mapping| mapping.mode.clone())
+        .collect();
+
+    let mut page = Page {
+        name: KITTY_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+    for mapping in mappings {
+        let content = vec![keys(&mapping.keys, &kitty_mod)];
+        let entry = match mapping.new_mode {
+            Some(mode) => {
+                let description = format!("Enter the {} mode", mode);
+                Entry {
+                    name: entry_name(&description),
+                    content,
+                    description,
+                    goto: modes
+                        .contains(&mode)
+                        .then(|| format!("{}{}{}", KITTY_PAGE_NAME, PAGE_PATH_SEPARATOR, mode)),
+                    ..Entry::default()
+                }
+            }
+            None => Entry {
+                name: entry_name(&mapping.action),
+                content,
+                description: mapping.action,
+                ..Entry::default()
+            },
+        };
+
+        let Some(mode) = mapping.mode else {
+            add_entry(&mut page, entry);
+            continue;
+        };
+        let index = match page.subpages.iter().position(|page| page.name == mode) {
+            Some(index) => index,
+            None => {
+                page.subpages.push(Page {
+                    name: mode,
+                    ..Page::default()
+                });
+                page.subpages.len() - 1
+            }
+        };
+        add_entry(&mut page.subpages[index], entry);
+    }
+
+    (!page.entries.is_empty() || !page.subpages.is_empty()).then_some(page)
+}
+
+/// Parses the arguments of `map`, i.e. options like `--mode`, the keys and the action, into a mapping.
+///
+/// Returns `None` if no action follows the keys, unless the keys enter a new mode, or if the action unmaps them.
+fn mapping(arguments: &str) -> Option<Mapping> {
+    let mut mode = None;
+    let mut new_mode = None;
+
+    let mut rest = arguments;
+    loop {
+        let (word, remainder) = split_word(rest);
+        let Some(option) = word.strip_prefix("--") else {
+            break;
+        };
+        // Options take a value, either after an equals sign or as the next word
+        let (option, value, remainder) = match option.split_once('=') {
+            Some((option, value)) => (option, value, remainder),
+            None => {
+                let (value, remainder) = split_word(remainder);
+                (option, value, remainder)
+            }
+        };
+        match option {
+            "mode" => mode = Some(value.to_string()),
+            "new-mode" => new_mode = Some(value.to_string()),
+            _ => debug!("Ignoring option --{} {} of mapping", option, value),
+        }
+        rest = remainder;
+    }
+
+    let (keys, action) = split_word(rest);
+    let is_unmapped = UNMAPPING_ACTIONS.contains(&action);
+    if keys.is_empty() || is_unmapped || action.is_empty() && new_mode.is_none() {
+        return None;
+    }
+
+    Some(Mapping {
+        mode,
+        new_mode,
+        keys: keys.to_string(),
+        action: action.to_string(),
+    })
+}
+
+/// Returns the keys of a shortcut as a sequence of chords separated by spaces, the form recall reads key sequences in.
+///
+/// `kitty_mod` is replaced by the given modifiers and the names of keys are translated, e.g. `kitty_mod+t>n`
+/// into `ctrl+shift+t n`.
+fn keys(keys: &str, kitty_mod: &str) -> String {
+    keys.split('>')
+        .filter(|chord| !chord.is_empty())
+        .map(|chord| {
+            let chord = chord.replace("kitty_mod", kitty_mod);
+            match chord.rsplit_once('+') {
+                // A trailing plus is the key itself, e.g. ctrl++
+                Some((modifiers, "")) => format!("{}+", modifiers),
+                Some((modifiers, key)) => format!("{}+{}", modifiers, key_name(key)),
+                None => key_name(&chord).to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the name recall uses for a key named by kitty, e.g. `PageUp` for `page_up` or `-` for `minus`.
+///
+/// Other names are kept, as recall reads them as they are, e.g. `enter` or `f5`.
+fn key_name(key: &str) -> &str {
+    match key {
+        "page_up" => "PageUp",
+        "page_down" => "PageDown",
+        "plus" => "+",
+        "minus" => "-",
+        "equal" => "=",
+        "comma" => ",",
+        "period" => ".",
+        "slash" => "/",
+        "backslash" => "\\",
+        "semicolon" => ";",
+        "apostrophe" => "'",
+        "grave_accent" => "`",
+        "left_bracket" => "[",
+        "right_bracket" => "]",
+        key => key,
+    }
+}
+
+/// Returns the lines of the config, joining lines starting with a backslash to the line before them.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for physical_line in content.lines() {
+        match (
+            physical_line.trim_start().strip_prefix('\\'),
+            lines.last_mut(),
+        ) {
+            (Some(continuation), Some(line)) => line.push_str(continuation),
+            _ => lines.push(physical_line.to_string()),
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::bindings;
+
+    #[test]
+    fn reads_map_lines() {
+        let config = "\
+# map kitty_mod+c copy_to_clipboard
+font_size 12.0
+map ctrl+shift+enter new_window
+map kitty_mod+t     new_tab
+map f1 launch --cwd=current
+map ctrl+shift+page_up scroll_page_up
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(page.name, "kitty");
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["ctrl+shift+enter"], "new_window"),
+                (vec!["ctrl+shift+t"], "new_tab"),
+                (vec!["f1"], "launch --cwd=current"),
+                (vec!["ctrl+shift+PageUp"], "scroll_page_up"),
+            ]
+        );
+        assert_eq!(page.entries[1].name, "new_tab");
+    }
+
+    #[test]
+    fn expands_kitty_mod() {
+        let config = "\
+map kitty_mod+n new_os_window
+kitty_mod ctrl+alt
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(page.entries[0].content, vec!["ctrl+alt+n"]);
+    }
+
+    #[test]
+    fn splits_multi_key_shortcuts() {
+        let config = "\
+map kitty_mod+t>n new_tab
+map ctrl+f>2 set_font_size 20
+map ctrl+x>ctrl+plus>minus change_font_size all -1
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["ctrl+shift+t n"], "new_tab"),
+                (vec!["ctrl+f 2"], "set_font_size 20"),
+                (vec!["ctrl+x ctrl++ -"], "change_font_size all -1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_modes_on_subpages() {
+        let config = "\
+map --new-mode mw kitty_mod+f7
+map --mode mw left neighboring_window left
+map --mode=mw esc pop_keyboard_mode
+map --new-mode unused ctrl+u
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(page.entries[0].content, vec!["ctrl+shift+f7"]);
+        assert_eq!(page.entries[0].description, "Enter the mw mode");
+        assert_eq!(page.entries[0].goto.as_deref(), Some("kitty/mw"));
+        assert_eq!(page.entries[1].goto, None);
+        assert_eq!(page.subpages[0].name, "mw");
+        assert_eq!(
+            bindings(&page.subpages[0]),
+            vec![
+                (vec!["left"], "neighboring_window left"),
+                (vec!["esc"], "pop_keyboard_mode"),
+            ]
+        );
+    }
+
+    #[test]
+    fn joins_continuation_lines_and_skips_unmapped_keys() {
+        let config = "\
+map ctrl+shift+w
+map ctrl+shift+q no_op
+map ctrl+shift+e launch
+    \\ --type=tab
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![(vec!["ctrl+shift+e"], "launch --type=tab")]
+        );
+        assert!(import("map ctrl+a discard_event\n").is_none());
+    }
+}
@@ -0,0 +1,274 @@
+//! tmux importer for Recall.
+//!
+//! Reads the `bind-key` lines of a tmux config, e.g. `~/.tmux.conf`, into a page.
+
+use log::debug;
+
+use crate::{
+    app::{Entry, Page},
+    import::{add_entry, entry_name},
+};
+
+/// Name of the page imported tmux key bindings are listed on
+const TMUX_PAGE_NAME: &str = "tmux";
+
+/// Key table of the bindings pressed after the prefix key, which is also used if no table is given
+const TMUX_PREFIX_TABLE: &str = "prefix";
+
+/// Key table of the bindings pressed without the prefix key, as bound with `bind-key -n`
+const TMUX_ROOT_TABLE: &str = "root";
+
+/// Prefix key of tmux unless the config sets another one
+const TMUX_DEFAULT_PREFIX: &str = "C-b";
+
+/// Parses the `bind-key` lines of a tmux config into a page, or `None` if nothing is bound.
+///
+/// Bindings of the prefix table start with the prefix key, `C-b` unless the config sets another `prefix`.
+/// Bindings of other tables, like those bound with `-n` or `-T copy-mode-vi`, are listed in a section named after
+/// the table. The note given with `-N` describes a binding, otherwise the bound command does.
+/// Keys are written in the notation of tmux, which is read like that of Emacs, e.g. `C-a` or `M-Left`.
+pub fn import(content: &str) -> Option<Page> {
+    let mut prefix = TMUX_DEFAULT_PREFIX.to_string();
+    let mut bindings = vec![];
+
+    let mut line = String::new();
+    for physical_line in content.lines() {
+        // A backslash at the end continues the line
+        if let Some(continued) = physical_line.strip_suffix('\\') {
+            line.push_str(continued);
+            line.push(' ');
+            continue;
+        }
+        line.push_str(physical_line);
+
+        let (words, uncommented) = tmux_words(&line);
+        match words.first().map(|(word, _)| word.as_str()) {
+            Some("bind-key" | "bind") => match tmux_binding(uncommented, &words[1..]) {
+                Some(binding) => bindings.push(binding),
+                None => debug!("Skipping tmux binding without a key: {}", line.trim()),
+            },
+            Some("set-option" | "set") => {
+                let mut options = words[1..]
+                    .iter()
+                    .map(|(word, _)| word.as_str())
+                    .skip_while(|word| word.starts_with('-'));
+                if let (Some("prefix"), Some(key)) = (options.next(), options.next()) {
+                    prefix = tmux_key(key);
+                }
+            }
+            _ => {}
+        }
+        line.clear();
+    }
+
+    if bindings.is_empty() {
+        return None;
+    }
+
+    let mut page = Page {
+        name: TMUX_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+    for binding in bindings {
+        let (content, section) = match binding.table.as_str() {
+            TMUX_PREFIX_TABLE => (vec![format!("{} {}", prefix, binding.key)], None),
+            _ => (vec![binding.key], Some(binding.table)),
+        };
+        let entry = Entry {
+            name: entry_name(&binding.description),
+            content,
+            description: binding.description,
+            section,
+            ..Entry::default()
+        };
+        add_entry(&mut page, entry);
+    }
+    Some(page)
+}
+
+/// A key binding of tmux.
+struct TmuxBinding {
+    /// Key table the binding belongs to, e.g. `prefix` or `copy-mode-vi`
+    table: String,
+
+    /// Bound key, e.g. `C-a` or `%`
+    key: String,
+
+    /// Note of the binding, or the bound command if it has no note
+    description: String,
+}
+
+/// Parses the arguments of `bind-key`, written as words of the given line, into a binding.
+///
+/// Returns `None` if no key follows the flags.
+fn tmux_binding(line: &str, arguments: &[(String, usize)]) -> Option<TmuxBinding> {
+    let mut table = TMUX_PREFIX_TABLE.to_string();
+    let mut note = None;
+
+    let mut arguments = arguments.iter();
+    let (key, command_start) = loop {
+        let (word, _) = arguments.next()?;
+        // A lone dash is a key, e.g. `bind - split-window`
+        let Some(flags) = word.strip_prefix('-').filter(|flags| !flags.is_empty()) else {
+            break (word, arguments.next().map(|(_, start)| *start));
+        };
+        if flags == "-" {
+            let (key, _) = arguments.next()?;
+            break (key, arguments.next().map(|(_, start)| *start));
+        }
+        for flag in flags.chars() {
+            match flag {
+                'n' => table = TMUX_ROOT_TABLE.to_string(),
+                'T' => table = arguments.next()?.0.clone(),
+                'N' => note = Some(arguments.next()?.0.clone()),
+                _ => {}
+            }
+        }
+    };
+
+    let command = command_start.map_or("", |start| line[start..].trim());
+    Some(TmuxBinding {
+        table,
+        key: tmux_key(key),
+        description: note.unwrap_or_else(|| command.to_string()),
+    })
+}
+
+/// Splits a line of a tmux config into words along with where they start in the line, dropping any comment.
+///
+/// Quotes and backslashes are removed from the words, a `#` starting a word begins a comment.
+/// Returns the words and the line without the comment.
+fn tmux_words(line: &str) -> (Vec<(String, usize)>, &str) {
+    let mut end = line.len();
+    let mut words = vec![];
+    let mut word: Option<(String, usize)> = None;
+    let mut quote = None;
+    let mut chars = line.char_indices();
+
+    while let Some((index, char)) = chars.next() {
+        match (char, quote) {
+            (_, Some(open)) if char == open => quote = None,
+            ('\\', Some('\'')) => word.get_or_insert((String::new(), index)).0.push(char),
+            ('\\', _) => {
+                let escaped = chars.next().map_or('\\', |(_, escaped)| escaped);
+                word.get_or_insert((String::new(), index)).0.push(escaped);
+            }
+            (_, Some(_)) => word.get_or_insert((String::new(), index)).0.push(char),
+            ('\'' | '"', None) => {
+                quote = Some(char);
+                word.get_or_insert((String::new(), index));
+            }
+            ('#', None) if word.is_none() => {
+                end = index;
+                break;
+            }
+            (char, None) if char.is_whitespace() => words.extend(word.take()),
+            (char, None) => word.get_or_insert((String::new(), index)).0.push(char),
+        }
+    }
+    words.extend(word);
+    (words, &line[..end])
+}
+
+/// Returns a key of tmux in a notation recall reads, renaming keys only tmux calls like that, e.g. `BSpace`.
+fn tmux_key(key: &str) -> String {
+    let (modifiers, name) = match key.rfind('-').filter(|&index| index + 1 < key.len()) {
+        Some(index) => key.split_at(index + 1),
+        None => ("", key),
+    };
+    let name = match name {
+        "BSpace" => "Backspace",
+        "PPage" | "PgUp" => "PageUp",
+        "NPage" | "PgDn" => "PageDown",
+        "DC" => "Del",
+        "IC" => "Ins",
+        "BTab" => return format!("{}S-Tab", modifiers),
+        name => name,
+    };
+    format!("{}{}", modifiers, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::sectioned_bindings;
+
+    #[test]
+    fn starts_bindings_with_the_prefix() {
+        let page = import("bind | split-window -h\n").unwrap();
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![(vec!["C-b |"], "split-window -h", None)]
+        );
+
+        let page = import("bind | split-window -h\nset -g prefix C-a\n").unwrap();
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![(vec!["C-a |"], "split-window -h", None)]
+        );
+    }
+
+    #[test]
+    fn lists_other_tables_in_sections() {
+        let config = "\
+bind-key -n M-Left select-pane -L
+bind-key -T copy-mode-vi v send-keys -X begin-selection
+bind -r H resize-pane -L 5
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![
+                (vec!["M-Left"], "select-pane -L", Some("root")),
+                (
+                    vec!["v"],
+                    "send-keys -X begin-selection",
+                    Some("copy-mode-vi")
+                ),
+                (vec!["C-b H"], "resize-pane -L 5", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn describes_bindings_by_their_note() {
+        let page =
+            import("bind -N \"Reload config\" r source-file ~/.tmux.conf \\; display Reloaded\n")
+                .unwrap();
+
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![(vec!["C-b r"], "Reload config", None)]
+        );
+        assert_eq!(page.entries[0].name, "reload_config");
+    }
+
+    #[test]
+    fn reads_quoted_keys_comments_and_continued_lines() {
+        let config = "\
+# bind x kill-pane
+bind '\"' choose-window
+bind BSpace kill-pane # close the pane
+bind \\; last-pane
+bind - \\
+    split-window -v
+";
+        let page = import(config).unwrap();
+
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![
+                (vec!["C-b \""], "choose-window", None),
+                (vec!["C-b Backspace"], "kill-pane", None),
+                (vec!["C-b ;"], "last-pane", None),
+                (vec!["C-b -"], "split-window -v", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn returns_nothing_without_bindings() {
+        assert!(import("set -g mouse on\n").is_none());
+    }
+}
@@ -0,0 +1,403 @@
+//! Vim importer for Recall.
+//!
+//! Reads the mappings of a vimrc or init.vim, written with commands like `nnoremap` or `imap`, into a page with a
+//! subpage per mode. The listing Vim and Neovim print for `:map`, e.g. with `nvim --headless +map +q`, is read as well.
+//! Left-hand sides become key sequences, e.g. `<C-w>h` becomes `<C-w> h`, with `<Leader>` replaced by the `mapleader`
+//! the vimrc sets. A trailing comment, e.g. `nnoremap <Leader>w :w<CR> " save`, describes a mapping,
+//! otherwise its right-hand side does. Mappings of `<Plug>` names are internal to plugins and skipped.
+
+use log::debug;
+
+use crate::{
+    app::{Entry, Page},
+    import::{add_entry, entry_name},
+    importers::split_word,
+};
+
+/// Name of the page imported Vim mappings are listed on
+pub const VIM_PAGE_NAME: &str = "vim";
+
+/// Mode whose mappings are listed on the page itself, the modes of other mappings name their subpages
+const NORMAL_MODE: &str = "normal";
+
+/// Arguments of a mapping command given before the left-hand side
+const MAP_ARGUMENTS: [&str; 7] = [
+    "<buffer>",
+    "<nowait>",
+    "<silent>",
+    "<special>",
+    "<script>",
+    "<expr>",
+    "<unique>",
+];
+
+/// Parses the mappings of a vimrc, or a listing of mappings as printed by `:map`, into a page.
+///
+/// Returns `None` if nothing is mapped.
+pub fn import(content: &str) -> Option<Page> {
+    // A vimrc maps with commands, whose lines could be mistaken for a listing
+    let is_vimrc = content
+        .lines()
+        .any(|line| map_command(split_word(line).0).is_some());
+
+    let mappings = if is_vimrc {
+        vimrc_mappings(content)
+    } else {
+        listed_mappings(content)
+    };
+    if mappings.is_empty() {
+        return None;
+    }
+
+    let mut page = Page {
+        name: VIM_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+    for (mode, entry) in mappings {
+        if mode == NORMAL_MODE {
+            add_entry(&mut page, entry);
+            continue;
+        }
+
+        let index = match page.subpages.iter().position(|page| page.name == mode) {
+            Some(index) => index,
+            None => {
+                page.subpages.push(Page {
+                    name: mode.to_string(),
+                    ..Page::default()
+                });
+                page.subpages.len() - 1
+            }
+        };
+        add_entry(&mut page.subpages[index], entry);
+    }
+    Some(page)
+}
+
+/// Returns the mappings made by the commands of a vimrc along with their modes.
+fn vimrc_mappings(content: &str) -> Vec<(&'static str, Entry)> {
+    let mut leader = None;
+    let mut mappings = vec![];
+
+    for line in content.lines() {
+        let (command, mut rest) = split_word(line);
+        if command == "let" {
+            if let Some(value) = rest.strip_prefix("mapleader") {
+                let value = value.trim_start().trim_start_matches('=').trim();
+                leader = Some(value.trim_matches(['"', '\'']).to_string());
+            }
+            continue;
+        }
+
+        let Some(mode) = map_command(command) else {
+            continue;
+        };
+        let lhs = loop {
+            let (word, remainder) = split_word(rest);
+            rest = remainder;
+            if !MAP_ARGUMENTS.contains(&word.to_lowercase().as_str()) {
+                break word;
+            }
+        };
+        if lhs.is_empty() || rest.is_empty() {
+            debug!(
+                "Skipping mapping without a right-hand side: {}",
+                line.trim()
+            );
+            continue;
+        }
+
+        let (rhs, comment) = split_comment(rest);
+        if let Some(entry) = mapping(lhs, rhs, comment, leader.as_deref()) {
+            mappings.push((mode, entry));
+        }
+    }
+    mappings
+}
+
+/// Returns the mappings of a listing as printed by `:map`, along with their modes.
+///
+/// Each mapping starts with its modes in the first three columns, followed by its left-hand side, the flags `*`, `&`
+/// and `@` and its right-hand side. Indented lines below a mapping describe it, as Neovim prints the `desc` of
+/// mappings, or tell where it was set, as `:verbose map` prints.
+fn listed_mappings(content: &str) -> Vec<(&'static str, Entry)> {
+    let mut mappings: Vec<(&'static str, Entry)> = vec![];
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.chars().take(4).all(char::is_whitespace) {
+            let text = line.trim();
+            if let (Some((_, entry)), false) = (mappings.last_mut(), text.starts_with("Last set")) {
+                entry.name = entry_name(text);
+                entry.description = text.to_string();
+            }
+            continue;
+        }
+
+        let Some(modes) = line.get(..3) else {
+            continue;
+        };
+        if !modes.chars().all(|c| c == ' ' || "nvxsoilct!".contains(c)) {
+            debug!("Skipping line that lists no mapping: {}", line);
+            continue;
+        }
+
+        let (lhs, rest) = split_word(&line[3..]);
+        let rhs = rest.trim_start_matches(['*', '&', '@']).trim_start();
+        if lhs.is_empty() || rhs.is_empty() {
+            continue;
+        }
+
+        let mode = mode_name(modes.trim_start().chars().next());
+        if let Some(entry) = mapping(lhs, rhs, None, None) {
+            mappings.push((mode, entry));
+        }
+    }
+    mappings
+}
+
+/// Returns the entry of a mapping, described by its comment or else by its right-hand side.
+///
+/// Returns `None` for mappings of `<Plug>` names.
+fn mapping(lhs: &str, rhs: &str, comment: Option<&str>, leader: Option<&str>) -> Option<Entry> {
+    if lhs.to_lowercase().starts_with("<plug>") {
+        debug!("Skipping mapping of plugin name {}", lhs);
+        return None;
+    }
+
+    let description = comment.unwrap_or(rhs);
+    Some(Entry {
+        name: entry_name(description),
+        content: vec![keys(lhs, leader).join(" ")],
+        description: description.to_string(),
+        ..Entry::default()
+    })
+}
+
+/// Returns the mode a mapping command maps in, or `None` if the word is no mapping command.
+///
+/// Commands may be abbreviated like in Vim, e.g. `nn` for `nnoremap`, and `!` maps in insert and command-line mode.
+/// Mappings of several modes are listed under the first of them, e.g. those of `map` under normal mode.
+fn map_command(word: &str) -> Option<&'static str> {
+    if let Some(command) = word.strip_suffix('!') {
+        return matches!(
+            command,
+            "map" | "no" | "nor" | "nore" | "norem" | "norema" | "noremap"
+        )
+        .then_some(mode_name(Some('!')));
+    }
+
+    if word == "map" || word.len() >= 2 && "noremap".starts_with(word) {
+        return Some(mode_name(None));
+    }
+
+    let mut chars = word.chars();
+    let mode = chars.next().filter(|c| "nvxsoilct".contains(*c))?;
+    let command = chars.as_str();
+    // Only a few modes allow `noremap` to be abbreviated to a single letter, e.g. `nn` but not `cn`, which is `:cnext`
+    let shortest_noremap = if "nvxl".contains(mode) { 1 } else { 2 };
+    let is_map = !command.is_empty() && "map".starts_with(command);
+    let is_noremap = command.len() >= shortest_noremap && "noremap".starts_with(command);
+    (is_map || is_noremap).then_some(mode_name(Some(mode)))
+}
+
+/// Returns the name of the mode of the given letter, used as the name of its subpage.
+fn mode_name(letter: Option<char>) -> &'static str {
+    match letter {
+        Some('v' | 'x') => "visual",
+        Some('s') => "select",
+        Some('o') => "operator-pending",
+        Some('i' | '!') => "insert",
+        Some('c') => "command-line",
+        Some('l') => "lang-arg",
+        Some('t') => "terminal",
+        _ => NORMAL_MODE,
+    }
+}
+
+/// Splits the keys of a left-hand side, keeping keys in angle brackets like `<C-w>` together.
+///
+/// `<Leader>` is replaced by the given leader, `<Space>` if it is a space, and `<lt>` and `<Bar>` by what they stand for.
+fn keys(lhs: &str, leader: Option<&str>) -> Vec<String> {
+    let mut keys = vec![];
+    let mut rest = lhs;
+    while let Some(char) = rest.chars().next() {
+        let bracketed = rest
+            .strip_prefix('<')
+            .and_then(|inner| inner.find('>'))
+            .filter(|&end| end > 0)
+            .map(|end| &rest[..end + 2]);
+        let key = bracketed.unwrap_or(&rest[..char.len_utf8()]);
+        rest = &rest[key.len()..];
+
+        keys.push(match key.to_lowercase().as_str() {
+            "<leader>" => match leader {
+                Some(" ") => "<Space>".to_string(),
+                Some(leader) if !leader.is_empty() => leader.to_string(),
+                _ => key.to_string(),
+            },
+            "<lt>" => "<".to_string(),
+            "<bar>" => "|".to_string(),
+            _ => key.to_string(),
+        });
+    }
+    keys
+}
+
+/// Splits a trailing comment off the right-hand side of a mapping, e.g. `:w<CR> " save` or `:w<CR>|" save`.
+///
+/// Vim itself reads such comments as part of the right-hand side, but they are a common way to note what a mapping
+/// does. The last quote is only taken as the start of a comment if the quotes before it are paired, so strings are kept.
+fn split_comment(rhs: &str) -> (&str, Option<&str>) {
+    let Some(start) = rhs.rfind('"') else {
+        return (rhs, None);
+    };
+    let comment = rhs[start + 1..].trim();
+    let before = rhs[..start].trim_end();
+    let is_comment = !comment.is_empty()
+        && (before.ends_with('|') || rhs[..start].ends_with(char::is_whitespace))
+        && before.matches('"').count().is_multiple_of(2);
+    if !is_comment {
+        return (rhs, None);
+    }
+
+    let rhs = before.strip_suffix('|').unwrap_or(before).trim_end();
+    (rhs, Some(comment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::bindings;
+
+    #[test]
+    fn reads_mapping_commands() {
+        let vimrc = "\
+set number
+nnoremap <silent> <C-w>h :wincmd h<CR>
+nmap gcc <Plug>CommentaryLine
+nn Y y$
+noremap <Space> :nohlsearch<CR>
+";
+        let page = import(vimrc).unwrap();
+
+        assert_eq!(page.name, "vim");
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["<C-w> h"], ":wincmd h<CR>"),
+                (vec!["g c c"], "<Plug>CommentaryLine"),
+                (vec!["Y"], "y$"),
+                (vec!["<Space>"], ":nohlsearch<CR>"),
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_mappings_by_mode() {
+        let vimrc = "\
+inoremap jk <Esc>
+vnoremap < <gv
+tnoremap <Esc> <C-\\><C-n>
+cnoremap <C-a> <Home>
+imap! <C-e> <End>
+";
+        let page = import(vimrc).unwrap();
+
+        assert!(page.entries.is_empty());
+        let modes: Vec<&str> = page.subpages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(modes, vec!["insert", "visual", "terminal", "command-line"]);
+        assert_eq!(bindings(&page.subpages[0]), vec![(vec!["j k"], "<Esc>")]);
+        assert_eq!(
+            bindings(&page.subpages[2]),
+            vec![(vec!["<Esc>"], "<C-\\><C-n>")]
+        );
+    }
+
+    #[test]
+    fn replaces_the_leader() {
+        let page = import("nnoremap <Leader>w :w<CR>\n").unwrap();
+        assert_eq!(page.entries[0].content, vec!["<Leader> w"]);
+
+        let page = import("let mapleader = \" \"\nnnoremap <leader>w :w<CR>\n").unwrap();
+        assert_eq!(page.entries[0].content, vec!["<Space> w"]);
+
+        let page = import("let mapleader=','\nnnoremap <leader>w :w<CR>\n").unwrap();
+        assert_eq!(page.entries[0].content, vec![", w"]);
+    }
+
+    #[test]
+    fn describes_mappings_by_trailing_comments() {
+        let vimrc = "\
+nnoremap <Leader>w :w<CR> \" Save the file
+nnoremap <Leader>q :q<CR>|\" Quit
+nnoremap <Leader>e :echo \"hi\"<CR>
+";
+        let page = import(vimrc).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["<Leader> w"], "Save the file"),
+                (vec!["<Leader> q"], "Quit"),
+                (vec!["<Leader> e"], ":echo \"hi\"<CR>"),
+            ]
+        );
+        assert_eq!(page.entries[0].name, "save_the_file");
+    }
+
+    #[test]
+    fn skips_plug_mappings_and_other_commands() {
+        let vimrc = "\
+nmap <Plug>(my-plugin) :call MyPlugin()<CR>
+cnext
+unmap Q
+nnoremap Q
+";
+        assert!(import(vimrc).is_none());
+    }
+
+    #[test]
+    fn reads_map_listings() {
+        let listing = "\
+n  <Space>w    * :w<CR>
+x  #           * y?\\V<C-R>\"<CR>
+   <C-L>       * <Cmd>nohlsearch<CR>
+n  gx          * <Lua 33: vim/_defaults.lua:0>
+                 Opens filepath or URI under cursor with the system handler
+	Last set from ~/.config/nvim/init.lua line 3
+n  <Plug>NetrwBrowseX * :call netrw#BrowseX()<CR>
+i  <C-S>       * <Cmd>lua vim.lsp.buf.signature_help()<CR>
+";
+        let page = import(listing).unwrap();
+
+        assert_eq!(
+            bindings(&page),
+            vec![
+                (vec!["<Space> w"], ":w<CR>"),
+                (vec!["<C-L>"], "<Cmd>nohlsearch<CR>"),
+                (
+                    vec!["g x"],
+                    "Opens filepath or URI under cursor with the system handler"
+                ),
+            ]
+        );
+        let modes: Vec<&str> = page.subpages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(modes, vec!["visual", "insert"]);
+        assert_eq!(
+            bindings(&page.subpages[0]),
+            vec![(vec!["#"], "y?\\V<C-R>\"<CR>")]
+        );
+    }
+
+    #[test]
+    fn splits_keys_in_angle_brackets() {
+        assert_eq!(keys("<C-x><C-s>", None), vec!["<C-x>", "<C-s>"]);
+        assert_eq!(keys("<lt>a<Bar>", None), vec!["<", "a", "|"]);
+        assert_eq!(keys("<>", None), vec!["<", ">"]);
+        assert_eq!(keys("ä<", None), vec!["ä", "<"]);
+    }
+}
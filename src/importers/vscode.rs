@@ -0,0 +1,254 @@
+//! VS Code importer for Recall.
+//!
+//! Reads the `keybindings.json` of VS Code into a page. The file is JSON with comments and trailing commas, which are
+//! removed before parsing. Key bindings are grouped into subpages by the prefix of their command, e.g. `workbench` for
+//! `workbench.action.files.save`, and into sections by their `when` context. Chords like `ctrl+k ctrl+s` are kept as a
+//! key sequence, while bindings removing a default binding, whose command starts with `-`, are skipped.
+
+use anyhow::{Context, Result};
+use log::debug;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    app::{Entry, Page},
+    import::{add_entry, entry_name},
+};
+
+/// Name of the page imported VS Code key bindings are listed on
+pub const VSCODE_PAGE_NAME: &str = "vscode";
+
+/// A key binding of VS Code.
+#[derive(Debug, Deserialize)]
+struct VscodeBinding {
+    /// Keys of the binding, e.g. `ctrl+k ctrl+s`
+    key: String,
+
+    /// Bound command, e.g. `workbench.action.files.save`
+    #[serde(default)]
+    command: String,
+
+    /// Context in which the binding is active, e.g. `editorTextFocus`
+    when: Option<String>,
+
+    /// Arguments passed to the command
+    args: Option<Value>,
+}
+
+/// Parses the key bindings of a `keybindings.json` into a page, or `None` if nothing is bound.
+pub fn import(content: &str) -> Result<Option<Page>> {
+    let bindings: Vec<VscodeBinding> = serde_json::from_str(&strip_jsonc(content))
+        .context("Failed to parse the key bindings of VS Code, expected a list of bindings")?;
+
+    let mut page = Page {
+        name: VSCODE_PAGE_NAME.to_string(),
+        ..Page::default()
+    };
+    for binding in bindings {
+        if binding.command.is_empty() || binding.command.starts_with('-') {
+            debug!("Skipping binding of {} without a command", binding.key);
+            continue;
+        }
+
+        let mut description = binding.command.clone();
+        if let Some(args) = &binding.args {
+            description.push(' ');
+            description.push_str(&args.to_string());
+        }
+        let entry = Entry {
+            name: entry_name(&binding.command),
+            content: vec![keys(&binding.key)],
+            description,
+            section: binding.when.filter(|when| !when.trim().is_empty()),
+            ..Entry::default()
+        };
+
+        // Commands without a prefix, e.g. `undo`, are listed on the page itself
+        let Some((prefix, _)) = binding.command.split_once('.') else {
+            add_entry(&mut page, entry);
+            continue;
+        };
+        let index = match page.subpages.iter().position(|page| page.name == prefix) {
+            Some(index) => index,
+            None => {
+                page.subpages.push(Page {
+                    name: prefix.to_string(),
+                    ..Page::default()
+                });
+                page.subpages.len() - 1
+            }
+        };
+        add_entry(&mut page.subpages[index], entry);
+    }
+
+    Ok((!page.entries.is_empty() || !page.subpages.is_empty()).then_some(page))
+}
+
+/// Returns the keys of a binding as a sequence of chords separated by spaces, the form recall reads key sequences in.
+///
+/// Keys given by their scan code, e.g. `[KeyA]` or `[Digit1]`, are named like other keys.
+fn keys(keys: &str) -> String {
+    keys.split_whitespace()
+        .map(|chord| {
+            chord
+                .split('+')
+                .map(|key| {
+                    let Some(code) = key.strip_prefix('[').and_then(|key| key.strip_suffix(']'))
+                    else {
+                        return key;
+                    };
+                    code.strip_prefix("Key")
+                        .or_else(|| code.strip_prefix("Digit"))
+                        .filter(|key| key.len() == 1)
+                        .unwrap_or(code)
+                })
+                .collect::<Vec<_>>()
+                .join("+")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns JSON with comments without the comments and trailing commas, so it can be parsed as JSON.
+fn strip_jsonc(content: &str) -> String {
+    let mut json = String::new();
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(char) = chars.next() {
+        if in_string {
+            json.push(char);
+            match char {
+                '\\' => json.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (char, chars.peek()) {
+            ('/', Some('/')) => while chars.next_if(|&char| char != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = None;
+                for char in chars.by_ref() {
+                    if previous == Some('*') && char == '/' {
+                        break;
+                    }
+                    previous = Some(char);
+                }
+                // A comment separates what is around it
+                json.push(' ');
+            }
+            (']' | '}', _) => {
+                let kept = json.trim_end().len();
+                if json[..kept].ends_with(',') {
+                    json.truncate(kept - 1);
+                }
+                json.push(char);
+            }
+            _ => {
+                in_string = char == '"';
+                json.push(char);
+            }
+        }
+    }
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::importers::sectioned_bindings;
+
+    #[test]
+    fn groups_bindings_by_command_prefix_and_context() {
+        let config = r#"// Place your key bindings in this file to override the defaults
+[
+    {
+        "key": "ctrl+k ctrl+s",
+        "command": "workbench.action.files.saveAll"
+    },
+    {
+        "key": "ctrl+shift+d",
+        "command": "editor.action.copyLinesDownAction",
+        "when": "editorTextFocus && !editorReadonly"
+    },
+    { "key": "ctrl+alt+q", "command": "workbench.action.closeAllEditors" },
+    { "key": "ctrl+z", "command": "undo" },
+]
+"#;
+        let page = import(config).unwrap().unwrap();
+
+        assert_eq!(page.name, "vscode");
+        assert_eq!(
+            sectioned_bindings(&page),
+            vec![(vec!["ctrl+z"], "undo", None)]
+        );
+        let prefixes: Vec<&str> = page.subpages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(prefixes, vec!["workbench", "editor"]);
+        assert_eq!(
+            sectioned_bindings(&page.subpages[0]),
+            vec![
+                (
+                    vec!["ctrl+k ctrl+s"],
+                    "workbench.action.files.saveAll",
+                    None
+                ),
+                (vec!["ctrl+alt+q"], "workbench.action.closeAllEditors", None),
+            ]
+        );
+        assert_eq!(
+            sectioned_bindings(&page.subpages[1]),
+            vec![(
+                vec!["ctrl+shift+d"],
+                "editor.action.copyLinesDownAction",
+                Some("editorTextFocus && !editorReadonly")
+            )]
+        );
+        assert_eq!(
+            page.subpages[0].entries[0].name,
+            "workbench_action_files_saveall"
+        );
+    }
+
+    #[test]
+    fn describes_arguments_and_skips_removals() {
+        let config = r#"[
+    { "key": "ctrl+shift+t", "command": "workbench.action.terminal.sendSequence", "args": { "text": "ls\n" } },
+    { "key": "ctrl+k ctrl+c", "command": "-editor.action.addCommentLine" },
+    { "key": "ctrl+[Minus]", "command": "workbench.action.zoomOut" },
+    { "key": "alt+[KeyA]", "command": "" }
+]"#;
+        let page = import(config).unwrap().unwrap();
+
+        assert_eq!(
+            sectioned_bindings(&page.subpages[0]),
+            vec![
+                (
+                    vec!["ctrl+shift+t"],
+                    "workbench.action.terminal.sendSequence {\"text\":\"ls\\n\"}",
+                    None
+                ),
+                (vec!["ctrl+Minus"], "workbench.action.zoomOut", None),
+            ]
+        );
+        assert_eq!(page.subpages.len(), 1);
+        assert_eq!(keys("alt+[KeyA] [Digit1]"), "alt+A 1");
+    }
+
+    #[test]
+    fn strips_comments_and_trailing_commas() {
+        let jsonc = r#"[ /* block
+comment */ {"key": "ctrl+/", "command": "a//b", /* inline */ "when": "x /* y */",}, // line
+]"#;
+        let json = strip_jsonc(jsonc);
+        let value: Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value[0]["key"], "ctrl+/");
+        assert_eq!(value[0]["command"], "a//b");
+        assert_eq!(value[0]["when"], "x /* y */");
+        assert!(import("[]").unwrap().is_none());
+        assert!(import("{\"key\": 1}").is_err());
+    }
+}
@@ -0,0 +1,643 @@
+//! Keymap module for Recall.
+//!
+//! The keys of recall's own actions while browsing the pages, like changing pages or quitting, can be remapped
+//! in the config with `[recall.keybinds]`, e.g. `next_page = "l"`. Keys are written like the shortcuts of entries,
+//! e.g. `Ctrl+P`, `C-p` or `Enter`, several keys of an action are given as a list.
+//! A remapped action no longer reacts to its default keys, which are free for other actions.
+//! `?` lists the actions with their current keys, see [`Keymap::bindings`].
+//!
+//! `keymap_preset = "vim"` adds the keys of Vim below the remapped ones: `h` and `l` change the page, `j` and `k`
+//! the selected row, `gg` and `G` go to the first and last page. Counts typed before them repeat the move,
+//! e.g. `3l` goes three pages ahead and `3G` to the third page.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Result};
+use indexmap::IndexMap;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Serialize, Serializer};
+
+use crate::keys::{parse_chord, Modifier};
+
+/// Actions of recall that keys are bound to while browsing the pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Shows the previous page
+    PreviousPage,
+
+    /// Shows the next page
+    NextPage,
+
+    /// Shows the first page, or the page with the typed count as its number
+    FirstPage,
+
+    /// Shows the last page, or the page with the typed count as its number
+    LastPage,
+
+    /// Selects the previous row
+    PreviousEntry,
+
+    /// Selects the next row
+    NextEntry,
+
+    /// Activates the selected row
+    Activate,
+
+    /// Drills out of the subpage
+    LeaveSubpage,
+
+    /// Moves the selected entry up
+    MoveEntryUp,
+
+    /// Moves the selected entry down
+    MoveEntryDown,
+
+    /// Deletes the selected entry
+    DeleteEntry,
+
+    /// Starts the input of a new entry
+    AddEntry,
+
+    /// Starts changing the selected entry
+    EditEntry,
+
+    /// Undoes the last edit
+    Undo,
+
+    /// Redoes the last undone edit
+    Redo,
+
+    /// Starts the input of a new page
+    NewPage,
+
+    /// Moves the selected entry to another page
+    MoveEntry,
+
+    /// Stars or unstars the selected entry
+    ToggleFavorite,
+
+    /// Collapses or expands the selected section
+    ToggleSection,
+
+    /// Cycles the sort order of the page
+    CycleSort,
+
+    /// Shows or hides hidden entries
+    ToggleHidden,
+
+    /// Reveals the selected secret entry
+    RevealSecret,
+
+    /// Wraps or truncates descriptions
+    ToggleWrap,
+
+    /// Shows or hides the keyboard diagram
+    ToggleKeyboard,
+
+    /// Opens the all-pages view
+    AllPages,
+
+    /// Opens the page index
+    PageIndex,
+
+    /// Opens the details of the selected entry
+    Details,
+
+    /// Copies the content of the selected entry
+    Copy,
+
+    /// Copies the description of the selected entry
+    CopyDescription,
+
+    /// Opens the web page of the selected entry
+    OpenUrl,
+
+    /// Opens the stats view
+    Stats,
+
+    /// Opens the settings
+    Settings,
+
+    /// Opens or closes the split view
+    ToggleSplit,
+
+    /// Switches the focused pane
+    SwitchPane,
+
+    /// Opens the workspace switcher
+    Workspaces,
+
+    /// Starts filtering the page
+    Filter,
+
+    /// Starts searching all pages
+    Search,
+
+    /// Opens the page switcher
+    PageSwitcher,
+
+    /// Opens the command line jumping to a page by name
+    CommandLine,
+
+    /// Clears the filter or the search
+    Clear,
+
+    /// Reads the config again
+    Reload,
+
+    /// Lists the keys of all actions
+    Help,
+
+    /// Quits recall
+    Quit,
+}
+
+/// A key together with the modifiers held while pressing it.
+///
+/// Shift is part of the character of character keys, so `K` is Shift+K.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    /// The pressed key
+    code: KeyCode,
+
+    /// Modifiers held while pressing the key
+    modifiers: KeyModifiers,
+}
+
+/// Ready-made sets of keys added to the defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// Keys moving around like in Vim, along with counts
+    Vim,
+}
+
+/// The keys of the actions, built from the defaults, the preset and the remapped keys in the config.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    /// Action of each bound sequence of keys, most are a single key
+    bindings: HashMap<Vec<Key>, Action>,
+
+    /// Preset whose keys were added to the defaults, if any
+    preset: Option<Preset>,
+
+    /// Keys of the remapped actions by the names of the actions, as written in the config
+    overrides: IndexMap<String, Vec<String>>,
+}
+
+/// Keys typed so far of a sequence bound to an action, along with the count typed before them.
+#[derive(Debug, Default)]
+pub struct PendingKeys {
+    /// Number typed before the keys, if any
+    count: Option<usize>,
+
+    /// Keys typed so far, which start at least one bound sequence
+    keys: Vec<Key>,
+}
+
+/// Names of all actions as written in the config, along with their default keys.
+const ACTIONS: [(Action, &str, &[Key]); 43] = [
+    (Action::PreviousPage, "previous_page", &[key(KeyCode::Left)]),
+    (Action::NextPage, "next_page", &[key(KeyCode::Right)]),
+    (Action::FirstPage, "first_page", &[key(KeyCode::Home)]),
+    (Action::LastPage, "last_page", &[key(KeyCode::End)]),
+    (Action::PreviousEntry, "previous_entry", &[key(KeyCode::Up)]),
+    (Action::NextEntry, "next_entry", &[key(KeyCode::Down)]),
+    (Action::Activate, "activate", &[key(KeyCode::Enter)]),
+    (
+        Action::LeaveSubpage,
+        "leave_subpage",
+        &[key(KeyCode::Backspace)],
+    ),
+    (Action::MoveEntryUp, "move_entry_up", &[typed('K')]),
+    (Action::MoveEntryDown, "move_entry_down", &[typed('J')]),
+    (Action::DeleteEntry, "delete_entry", &[typed('d')]),
+    (Action::AddEntry, "add_entry", &[typed('A')]),
+    (Action::EditEntry, "edit_entry", &[typed('e')]),
+    (Action::Undo, "undo", &[typed('u')]),
+    (Action::Redo, "redo", &[ctrl('r')]),
+    (Action::NewPage, "new_page", &[typed('n')]),
+    (Action::MoveEntry, "move_entry", &[typed('m')]),
+    (Action::ToggleFavorite, "toggle_favorite", &[typed('*')]),
+    (Action::ToggleSection, "toggle_section", &[typed(' ')]),
+    (Action::CycleSort, "cycle_sort", &[typed('s')]),
+    (Action::ToggleHidden, "toggle_hidden", &[typed('.')]),
+    (Action::RevealSecret, "reveal_secret", &[typed('r')]),
+    (Action::ToggleWrap, "toggle_wrap", &[typed('w')]),
+    (Action::ToggleKeyboard, "toggle_keyboard", &[typed('k')]),
+    (Action::AllPages, "all_pages", &[typed('a')]),
+    (Action::PageIndex, "page_index", &[typed('p')]),
+    (Action::Details, "details", &[typed('i')]),
+    (Action::Copy, "copy", &[typed('y')]),
+    (Action::CopyDescription, "copy_description", &[typed('Y')]),
+    (Action::OpenUrl, "open_url", &[typed('o')]),
+    (Action::Stats, "stats", &[typed('S')]),
+    (Action::Settings, "settings", &[typed('t')]),
+    (Action::ToggleSplit, "toggle_split", &[typed('v')]),
+    (Action::SwitchPane, "switch_pane", &[key(KeyCode::Tab)]),
+    (Action::Workspaces, "workspaces", &[typed('W')]),
+    (Action::Filter, "filter", &[typed('/')]),
+    (Action::Search, "search", &[typed('f')]),
+    (Action::PageSwitcher, "page_switcher", &[ctrl('p')]),
+    (Action::CommandLine, "command_line", &[typed(':')]),
+    (Action::Clear, "clear", &[key(KeyCode::Esc)]),
+    (Action::Reload, "reload", &[typed('R')]),
+    (Action::Help, "help", &[typed('?')]),
+    (Action::Quit, "quit", &[typed('q')]),
+];
+
+/// Sequences of keys of the Vim preset, see [`Preset::Vim`].
+const VIM_KEYS: [(Action, &[Key]); 6] = [
+    (Action::PreviousPage, &[typed('h')]),
+    (Action::NextPage, &[typed('l')]),
+    (Action::PreviousEntry, &[typed('k')]),
+    (Action::NextEntry, &[typed('j')]),
+    (Action::FirstPage, &[typed('g'), typed('g')]),
+    (Action::LastPage, &[typed('G')]),
+];
+
+/// Returns the key without modifiers.
+const fn key(code: KeyCode) -> Key {
+    Key {
+        code,
+        modifiers: KeyModifiers::NONE,
+    }
+}
+
+/// Returns the key typing the given character.
+const fn typed(character: char) -> Key {
+    key(KeyCode::Char(character))
+}
+
+/// Returns the key typing the given character with Ctrl held.
+const fn ctrl(character: char) -> Key {
+    Key {
+        code: KeyCode::Char(character),
+        modifiers: KeyModifiers::CONTROL,
+    }
+}
+
+impl Keymap {
+    /// Builds the keymap from the default keys, the keys of the preset and the keys of remapped actions by the names of the actions.
+    ///
+    /// Fails for unknown actions, keys that can't be read and keys remapped to several actions.
+    pub fn new(preset: Option<Preset>, overrides: IndexMap<String, Vec<String>>) -> Result<Keymap> {
+        let mut remapped: Vec<(Action, Vec<Vec<Key>>)> = vec![];
+        for (name, keys) in &overrides {
+            let action = Action::from_name(name).ok_or(anyhow!(
+                "Unknown action {} in the keybinds, expected one of {}",
+                name,
+                ACTIONS.map(|(_, name, _)| name).join(", ")
+            ))?;
+            if keys.is_empty() {
+                bail!("Action {} in the keybinds has no keys", name);
+            }
+            let keys = keys
+                .iter()
+                .map(|text| {
+                    parse_keys(text).ok_or(anyhow!(
+                        "Unknown key {} of action {} in the keybinds",
+                        text,
+                        name
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            remapped.push((action, keys));
+        }
+        let is_remapped = |action: Action| remapped.iter().any(|(remapped, _)| *remapped == action);
+
+        let mut bindings = HashMap::new();
+        for (action, _, keys) in ACTIONS {
+            if !is_remapped(action) {
+                bindings.extend(keys.iter().map(|key| (vec![*key], action)));
+            }
+        }
+
+        // Keys of the preset take the place of default keys of other actions
+        if preset == Some(Preset::Vim) {
+            for (action, keys) in VIM_KEYS {
+                if !is_remapped(action) {
+                    bindings.insert(keys.to_vec(), action);
+                }
+            }
+        }
+
+        // Remapped keys take the place of other keys as well, but not of each other
+        let mut taken: HashMap<Vec<Key>, &str> = HashMap::new();
+        for ((action, keys), (name, texts)) in remapped.into_iter().zip(&overrides) {
+            for (keys, text) in keys.into_iter().zip(texts) {
+                if let Some(other) = taken.insert(keys.clone(), name) {
+                    if other != name {
+                        bail!(
+                            "Key {} is bound to both {} and {} in the keybinds",
+                            text,
+                            other,
+                            name
+                        );
+                    }
+                }
+                bindings.insert(keys, action);
+            }
+        }
+
+        Ok(Keymap {
+            bindings,
+            preset,
+            overrides,
+        })
+    }
+
+    /// Returns the action completed by the pressed key along with the count typed before it, if any.
+    ///
+    /// Keys starting a longer sequence or typing a count are kept as pending until the action is complete.
+    pub fn resolve(
+        &self,
+        pending: &mut PendingKeys,
+        event: &KeyEvent,
+    ) -> Option<(Action, Option<usize>)> {
+        let key = Key::pressed(event);
+
+        // Counts are typed before any other key, 0 only continues a count
+        if let (Some(Preset::Vim), KeyCode::Char(digit), true) =
+            (self.preset, key.code, pending.keys.is_empty())
+        {
+            let bound = self.bindings.contains_key(&vec![key]);
+            if let Some(digit) = digit
+                .to_digit(10)
+                .filter(|_| key.modifiers.is_empty() && !bound)
+            {
+                if digit != 0 || pending.count.is_some() {
+                    let count = pending.count.unwrap_or(0).saturating_mul(10);
+                    pending.count = Some(count.saturating_add(digit as usize));
+                    return None;
+                }
+            }
+        }
+
+        pending.keys.push(key);
+        if let Some(action) = self.bindings.get(&pending.keys) {
+            pending.keys.clear();
+            return Some((*action, pending.count.take()));
+        }
+        if self
+            .bindings
+            .keys()
+            .any(|keys| keys.starts_with(&pending.keys))
+        {
+            return None;
+        }
+
+        // The typed keys lead nowhere, so the last key starts over on its own
+        let started_over = pending.keys.len() > 1;
+        *pending = PendingKeys::default();
+        if started_over {
+            return self.resolve(pending, event);
+        }
+        None
+    }
+
+    /// Returns the action bound to the pressed key on its own, if any.
+    ///
+    /// Used by popups, which take neither sequences of keys nor counts, so they close and quit with the keys of the
+    /// actions opening them and quitting.
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        self.bindings.get(&vec![Key::pressed(event)]).copied()
+    }
+
+    /// Returns the keys bound to the action as written in the help, e.g. `Ctrl+R` or `g g`.
+    ///
+    /// Remapped keys come first, followed by the default keys and the keys of the preset that are still bound to the action.
+    pub fn keys(&self, action: Action) -> Vec<String> {
+        let remapped = ACTIONS
+            .iter()
+            .find(|(other, _, _)| *other == action)
+            .and_then(|(_, name, _)| self.overrides.get(*name))
+            .into_iter()
+            .flatten()
+            .filter_map(|text| parse_keys(text));
+        let defaults = ACTIONS
+            .iter()
+            .filter(|(other, _, _)| *other == action)
+            .flat_map(|(_, _, keys)| keys.iter().map(|key| vec![*key]));
+        let preset = VIM_KEYS
+            .iter()
+            .filter(|(other, _)| *other == action)
+            .map(|(_, keys)| keys.to_vec());
+
+        let mut bound: Vec<Vec<Key>> = vec![];
+        for keys in remapped.chain(defaults).chain(preset) {
+            if self.bindings.get(&keys) == Some(&action) && !bound.contains(&keys) {
+                bound.push(keys);
+            }
+        }
+        bound.iter().map(|keys| keys_text(keys)).collect()
+    }
+
+    /// Returns the names of all actions with keys bound to them, along with their keys, see [`Keymap::keys`].
+    pub fn bindings(&self) -> Vec<(&'static str, Vec<String>)> {
+        ACTIONS
+            .iter()
+            .map(|(action, name, _)| (*name, self.keys(*action)))
+            .filter(|(_, keys)| !keys.is_empty())
+            .collect()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap {
+            bindings: ACTIONS
+                .iter()
+                .flat_map(|(action, _, keys)| keys.iter().map(|key| (vec![*key], *action)))
+                .collect(),
+            preset: None,
+            overrides: IndexMap::new(),
+        }
+    }
+}
+
+impl Serialize for Keymap {
+    /// Serializes the keymap as the keys of each action by the name of the action, as in `[recall.keybinds]`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_map(self.bindings())
+    }
+}
+
+impl Key {
+    /// Returns the key of a key event, ignoring Shift for characters, which are typed with Shift already applied
+    fn pressed(event: &KeyEvent) -> Key {
+        let mut modifiers =
+            event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER);
+        if !matches!(event.code, KeyCode::Char(_)) {
+            modifiers |= event.modifiers & KeyModifiers::SHIFT;
+        }
+
+        Key {
+            code: event.code,
+            modifiers,
+        }
+    }
+
+    /// Returns the key as written in the config, e.g. `q`, `Ctrl+R` or `Shift+Tab`
+    fn text(&self) -> String {
+        let mut text = String::new();
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+            (KeyModifiers::SUPER, "Super"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                text.push_str(name);
+                text.push('+');
+            }
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => text.push_str("Space"),
+            // Letters of chords are written in upper case, see `parse_key`
+            KeyCode::Char(character) if !text.is_empty() => {
+                text.push(character.to_ascii_uppercase())
+            }
+            KeyCode::Char(character) => text.push(character),
+            KeyCode::BackTab => text.push_str("Tab"),
+            code => text.push_str(&key_name(code)),
+        }
+        text
+    }
+}
+
+impl Preset {
+    /// Returns the preset with the given name, if there is one
+    pub fn from_name(name: &str) -> Option<Preset> {
+        match name {
+            "vim" => Some(Preset::Vim),
+            _ => None,
+        }
+    }
+}
+
+impl Action {
+    /// Returns the action with the given name as written in the config, if there is one
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTIONS
+            .iter()
+            .find(|(_, action_name, _)| *action_name == name)
+            .map(|(action, _, _)| *action)
+    }
+}
+
+/// Returns a sequence of keys as written in the config, separated by spaces.
+fn keys_text(keys: &[Key]) -> String {
+    keys.iter().map(Key::text).collect::<Vec<_>>().join(" ")
+}
+
+/// Reads a key or a sequence of keys separated by spaces, e.g. `g g` or `C-x C-s`.
+///
+/// A word of letters and digits that isn't the name of a key is typed letter by letter, so `gg` is `g g`.
+fn parse_keys(text: &str) -> Option<Vec<Key>> {
+    if let Some(key) = parse_key(text) {
+        return Some(vec![key]);
+    }
+
+    let mut keys = vec![];
+    for part in text.split_whitespace() {
+        match parse_key(part) {
+            Some(key) => keys.push(key),
+            None if part.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                keys.extend(part.chars().map(typed))
+            }
+            None => return None,
+        }
+    }
+    (!keys.is_empty()).then_some(keys)
+}
+
+/// Reads a key written like the shortcut of an entry, e.g. `l`, `Ctrl+P`, `C-p`, `<Left>` or `PageDown`.
+///
+/// Single characters are taken as they are, so `K` is Shift+K.
+fn parse_key(text: &str) -> Option<Key> {
+    let mut characters = text.chars();
+    if let (Some(character), None) = (characters.next(), characters.next()) {
+        return Some(typed(character));
+    }
+
+    // Named keys without modifiers are only recognized within angle brackets
+    let chord = parse_chord(text).or_else(|| parse_chord(&format!("<{}>", text)))?;
+
+    let mut modifiers = KeyModifiers::NONE;
+    let mut shift = false;
+    for modifier in &chord.modifiers {
+        match modifier {
+            Modifier::Primary | Modifier::Ctrl => modifiers |= KeyModifiers::CONTROL,
+            Modifier::Alt => modifiers |= KeyModifiers::ALT,
+            Modifier::Super => modifiers |= KeyModifiers::SUPER,
+            Modifier::Shift => shift = true,
+        }
+    }
+
+    let mut characters = chord.key.chars();
+    let code = match (characters.next(), characters.next()) {
+        // Letters of chords are written in upper case, which is only typed with Shift
+        (Some(character), None) if shift => KeyCode::Char(character.to_ascii_uppercase()),
+        (Some(character), None) => KeyCode::Char(character.to_ascii_lowercase()),
+        // Terminals report Shift+Tab as a key of its own
+        _ if shift && chord.key == "Tab" => {
+            modifiers |= KeyModifiers::SHIFT;
+            KeyCode::BackTab
+        }
+        _ => {
+            if shift {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            named_key(&chord.key)?
+        }
+    };
+
+    Some(Key { code, modifiers })
+}
+
+/// Returns the canonical name of the key, the reverse of [`named_key`].
+fn key_name(code: KeyCode) -> String {
+    let name = match code {
+        KeyCode::Enter => "Enter",
+        KeyCode::Esc => "Esc",
+        KeyCode::Tab => "Tab",
+        KeyCode::Backspace => "Backspace",
+        KeyCode::Delete => "Del",
+        KeyCode::Insert => "Ins",
+        KeyCode::Up => "Up",
+        KeyCode::Down => "Down",
+        KeyCode::Left => "Left",
+        KeyCode::Right => "Right",
+        KeyCode::Home => "Home",
+        KeyCode::End => "End",
+        KeyCode::PageUp => "PageUp",
+        KeyCode::PageDown => "PageDown",
+        KeyCode::F(number) => return format!("F{}", number),
+        code => return code.to_string(),
+    };
+    name.to_string()
+}
+
+/// Returns the key with the given canonical name, see [`crate::keys`].
+fn named_key(name: &str) -> Option<KeyCode> {
+    let code = match name {
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" => KeyCode::Tab,
+        "Backspace" => KeyCode::Backspace,
+        "Del" => KeyCode::Delete,
+        "Ins" => KeyCode::Insert,
+        "Space" => KeyCode::Char(' '),
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        _ => KeyCode::F(name.strip_prefix('F')?.parse().ok()?),
+    };
+    Some(code)
+}
@@ -0,0 +1,416 @@
+//! Key notation module for Recall.
+//!
+//! Shortcuts are written in many notations, e.g. `Ctrl+X`, `C-x` (Emacs), `<C-x>` (Vim) or `^X` (caret notation).
+//! This module recognizes these notations in the content of entries and converts them into a canonical form like `Ctrl+X`,
+//! which is used for displaying entries and comparing shortcuts.
+//! Content that isn't recognized as a key notation, e.g. a command, is kept as written.
+//!
+//! The abstract `Primary` modifier is substituted depending on the keyboard, Ctrl on PCs and Cmd on Macs,
+//! so a single configuration fits both.
+
+use serde::Serialize;
+
+/// Modifier keys in the order they are written in the canonical form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Modifier {
+    /// The modifier used for most shortcuts of the keyboard, Ctrl or Cmd
+    Primary,
+
+    /// The control key
+    Ctrl,
+
+    /// The alt key, also called meta or option
+    Alt,
+
+    /// The shift key
+    Shift,
+
+    /// The super key, also called windows or command key
+    Super,
+}
+
+/// Keyboards that differ in their modifier keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Keyboard {
+    /// Keyboards of Linux and Windows computers, using Ctrl as primary modifier
+    #[default]
+    Pc,
+
+    /// Apple keyboards, using Cmd as primary modifier
+    Mac,
+}
+
+/// A key pressed together with any number of modifiers, e.g. `Ctrl+Shift+T`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+    /// Modifiers of the chord, sorted and without duplicates
+    pub modifiers: Vec<Modifier>,
+
+    /// Canonical name of the key, e.g. `X`, `Enter` or `F5`
+    pub key: String,
+}
+
+impl Modifier {
+    /// Returns the canonical name of the modifier on the given keyboard
+    pub fn name(&self, keyboard: Keyboard) -> &'static str {
+        match (self.resolve(keyboard), keyboard) {
+            (Modifier::Super, Keyboard::Mac) => "Cmd",
+            (Modifier::Super, Keyboard::Pc) => "Super",
+            (Modifier::Ctrl, _) => "Ctrl",
+            (Modifier::Alt, _) => "Alt",
+            (Modifier::Shift, _) => "Shift",
+            (Modifier::Primary, _) => unreachable!("Primary modifier is always resolved"),
+        }
+    }
+
+    /// Returns the concrete modifier on the given keyboard, substituting the primary modifier
+    fn resolve(self, keyboard: Keyboard) -> Modifier {
+        match (self, keyboard) {
+            (Modifier::Primary, Keyboard::Pc) => Modifier::Ctrl,
+            (Modifier::Primary, Keyboard::Mac) => Modifier::Super,
+            (modifier, _) => modifier,
+        }
+    }
+
+    /// Returns the modifier with the given name, ignoring case and accepting common synonyms
+    fn from_name(name: &str) -> Option<Modifier> {
+        let modifier = match name.to_lowercase().as_str() {
+            "primary" => Modifier::Primary,
+            "ctrl" | "control" | "ctl" => Modifier::Ctrl,
+            "alt" | "meta" | "option" | "opt" => Modifier::Alt,
+            "shift" => Modifier::Shift,
+            "super" | "win" | "cmd" | "command" => Modifier::Super,
+            _ => return None,
+        };
+        Some(modifier)
+    }
+
+    /// Returns the modifier with the given single-letter prefix, e.g. `C` in `C-x`
+    ///
+    /// Emacs distinguishes `S` (shift) from `s` (super), while Vim ignores case within angle brackets and uses `D` for super.
+    fn from_prefix(prefix: &str, bracketed: bool) -> Option<Modifier> {
+        let modifier = match (prefix, bracketed) {
+            ("C", _) | ("c", true) => Modifier::Ctrl,
+            ("M" | "A", _) | ("m" | "a", true) => Modifier::Alt,
+            ("S", _) | ("s", true) => Modifier::Shift,
+            ("s", false) | ("D" | "d", true) => Modifier::Super,
+            _ => return None,
+        };
+        Some(modifier)
+    }
+}
+
+impl Chord {
+    /// Creates a chord from modifiers and a key name, canonicalizing both.
+    fn new(mut modifiers: Vec<Modifier>, key: &str) -> Option<Chord> {
+        if key.is_empty() {
+            return None;
+        }
+
+        modifiers.sort();
+        modifiers.dedup();
+
+        // Named keys may keep their angle brackets after the modifiers, e.g. M-S-<F1>
+        let key = key
+            .strip_prefix('<')
+            .and_then(|k| k.strip_suffix('>'))
+            .filter(|name| is_key_name(name))
+            .unwrap_or(key);
+        let key = canonical_key(key, !modifiers.is_empty());
+        Some(Chord { modifiers, key })
+    }
+
+    /// Returns the chord in canonical form as written on the given keyboard, e.g. `Ctrl+Shift+T`
+    pub fn text(&self, keyboard: Keyboard) -> String {
+        let mut modifiers: Vec<Modifier> = self
+            .modifiers
+            .iter()
+            .map(|modifier| modifier.resolve(keyboard))
+            .collect();
+        modifiers.sort();
+        modifiers.dedup();
+
+        let mut text = String::new();
+        for modifier in modifiers {
+            text.push_str(modifier.name(keyboard));
+            text.push('+');
+        }
+        text.push_str(&self.key);
+        text
+    }
+}
+
+impl Keyboard {
+    /// Returns the keyboard of the operating system recall is running on
+    pub fn current() -> Keyboard {
+        if cfg!(target_os = "macos") {
+            Keyboard::Mac
+        } else {
+            Keyboard::Pc
+        }
+    }
+
+    /// Returns the name of the keyboard as used in the configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            Keyboard::Pc => "pc",
+            Keyboard::Mac => "mac",
+        }
+    }
+
+    /// Returns the keyboard with the given name, if there is one
+    pub fn from_name(name: &str) -> Option<Keyboard> {
+        [Keyboard::Pc, Keyboard::Mac]
+            .into_iter()
+            .find(|keyboard| keyboard.name() == name)
+    }
+}
+
+/// Parses a single chord in any of the supported notations.
+///
+/// Returns `None` if the text isn't written in a key notation, so plain keys like `q` or words are not recognized.
+pub fn parse_chord(text: &str) -> Option<Chord> {
+    if text.chars().any(char::is_whitespace) {
+        return None;
+    }
+
+    // Vim notation like <C-x> or <CR>, named keys are recognized within angle brackets as well
+    if let Some(inner) = text.strip_prefix('<').and_then(|t| t.strip_suffix('>')) {
+        return parse_prefixed(inner, true).or_else(|| Chord::new(vec![], inner));
+    }
+
+    // Caret notation like ^X
+    if let Some(key) = text.strip_prefix('^') {
+        if key.chars().count() == 1 {
+            return Chord::new(vec![Modifier::Ctrl], key);
+        }
+    }
+
+    parse_prefixed(text, false).or_else(|| parse_plus_separated(text))
+}
+
+/// Parses a sequence of chords separated by spaces like `C-x C-s`.
+///
+/// Keys pressed on their own, a single character or a named key like `Enter`, may be part of the sequence, like `%` in
+/// the tmux binding `C-b %`, as long as another part is written in a key notation, so `ls -l` isn't taken for keys.
+fn parse_sequence(component: &str) -> Option<Vec<Chord>> {
+    let mut has_notation = false;
+    let chords = component
+        .split_whitespace()
+        .map(|text| match parse_chord(text) {
+            Some(chord) => {
+                has_notation = true;
+                Some(chord)
+            }
+            None if text.chars().count() == 1 || is_key_name(text) => Chord::new(vec![], text),
+            None => None,
+        })
+        .collect::<Option<Vec<Chord>>>()?;
+
+    (has_notation && !chords.is_empty()).then_some(chords)
+}
+
+/// Parses the Emacs notation like `C-M-x`, where each modifier is a single letter followed by a dash.
+///
+/// Vim uses the same notation within angle brackets, which is indicated by `bracketed`.
+fn parse_prefixed(text: &str, bracketed: bool) -> Option<Chord> {
+    let mut modifiers = vec![];
+    let mut rest = text;
+
+    while let Some((prefix, remainder)) = rest.split_once('-') {
+        // A trailing dash is the key itself, e.g. C--
+        if remainder.is_empty() {
+            break;
+        }
+        modifiers.push(Modifier::from_prefix(prefix, bracketed)?);
+        rest = remainder;
+    }
+
+    if modifiers.is_empty() {
+        return None;
+    }
+    Chord::new(modifiers, rest)
+}
+
+/// Parses notations like `Ctrl+Shift+T`, where full modifier names are joined with plus signs.
+fn parse_plus_separated(text: &str) -> Option<Chord> {
+    let mut modifiers = vec![];
+    let mut rest = text;
+
+    while let Some((name, remainder)) = rest.split_once('+') {
+        // A trailing plus is the key itself, e.g. Ctrl++
+        if remainder.is_empty() {
+            break;
+        }
+        modifiers.push(Modifier::from_name(name)?);
+        rest = remainder;
+    }
+
+    if modifiers.is_empty() {
+        return None;
+    }
+    Chord::new(modifiers, rest)
+}
+
+/// Returns the canonical name of a key, which is pressed together with modifiers if `modified` is set.
+fn canonical_key(key: &str, modified: bool) -> String {
+    let key = canonical_key_name(key);
+    // Letters are case-insensitive together with modifiers, e.g. `C-x` and `Ctrl+X` are the same chord
+    if modified && key.chars().count() == 1 {
+        key.to_uppercase()
+    } else {
+        key
+    }
+}
+
+/// Returns `true` if the text names a key like `Enter` or `F5`, which [`canonical_key_name`] knows.
+fn is_key_name(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    canonical_key_name(&lower) != lower
+}
+
+/// Returns the canonical name of a key, e.g. `Enter` for `CR` or `return`.
+///
+/// Unknown names are kept as written.
+fn canonical_key_name(key: &str) -> String {
+    let name = match key.to_lowercase().as_str() {
+        "cr" | "enter" | "return" | "ret" => "Enter",
+        "esc" | "escape" => "Esc",
+        "tab" => "Tab",
+        "bs" | "backspace" => "Backspace",
+        "del" | "delete" => "Del",
+        "ins" | "insert" => "Ins",
+        "space" | "spc" => "Space",
+        "up" => "Up",
+        "down" => "Down",
+        "left" => "Left",
+        "right" => "Right",
+        "home" => "Home",
+        "end" => "End",
+        "pageup" | "pgup" => "PageUp",
+        "pagedown" | "pgdn" => "PageDown",
+        lower => {
+            // Function keys like f5 or F12
+            if let Some(number) = lower.strip_prefix('f') {
+                if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+                    return format!("F{}", number);
+                }
+            }
+            return key.to_string();
+        }
+    };
+    name.to_string()
+}
+
+/// Returns a single component of an entry's content in canonical form for the given keyboard.
+///
+/// Components are either a chord, a sequence of chords separated by spaces like `C-x C-s`,
+/// a lone modifier like `control` or anything else, which is kept as written.
+pub fn normalize(component: &str, keyboard: Keyboard) -> String {
+    if let Some(modifier) = Modifier::from_name(component) {
+        return modifier.name(keyboard).to_string();
+    }
+
+    match parse_sequence(component) {
+        Some(chords) => chords
+            .iter()
+            .map(|chord| chord.text(keyboard))
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => component.to_string(),
+    }
+}
+
+/// Returns `true` if a single component of an entry's content is written in a key notation,
+/// i.e. it is a lone modifier or a sequence of chords.
+pub fn is_key_notation(component: &str) -> bool {
+    if Modifier::from_name(component).is_some() {
+        return true;
+    }
+
+    parse_sequence(component).is_some()
+}
+
+/// Returns the canonical names of the keys pressed for the content of an entry on the given keyboard,
+/// modifiers included, e.g. `Ctrl` and `X` for `C-x`.
+///
+/// Components without whitespace that aren't written in a key notation are pressed as single keys, like `q`.
+/// Other content, like a command, presses no keys.
+pub fn pressed_keys(content: &[String], keyboard: Keyboard) -> Vec<String> {
+    let mut keys = vec![];
+    for component in content {
+        if let Some(modifier) = Modifier::from_name(component) {
+            keys.push(modifier.name(keyboard).to_string());
+            continue;
+        }
+
+        match parse_sequence(component) {
+            Some(chords) => {
+                for chord in chords {
+                    keys.extend(
+                        chord
+                            .modifiers
+                            .iter()
+                            .map(|modifier| modifier.name(keyboard).to_string()),
+                    );
+                    keys.push(chord.key);
+                }
+            }
+            _ if !component.is_empty() && !component.contains(char::is_whitespace) => {
+                keys.push(canonical_key(component, true));
+            }
+            _ => {}
+        }
+    }
+
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// Returns the content of an entry with all recognized key notations in canonical form for the given keyboard.
+///
+/// Keys written as separate components after a modifier, like `["control", "c"]`, are canonicalized like in a chord.
+pub fn normalize_content(content: &[String], keyboard: Keyboard) -> Vec<String> {
+    let mut normalized = Vec::with_capacity(content.len());
+    let mut after_modifier = false;
+
+    for component in content {
+        if after_modifier && Modifier::from_name(component).is_none() {
+            normalized.push(canonical_key(component, true));
+        } else {
+            normalized.push(normalize(component, keyboard));
+        }
+        after_modifier = Modifier::from_name(component).is_some();
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_key_notations() {
+        for (component, expected) in [
+            ("C-x", "Ctrl+X"),
+            ("<C-x>", "Ctrl+X"),
+            ("^X", "Ctrl+X"),
+            ("ctrl+x", "Ctrl+X"),
+            ("<CR>", "Enter"),
+            ("C-x C-s", "Ctrl+X Ctrl+S"),
+            ("M-S-<F1>", "Alt+Shift+F1"),
+            ("Ctrl+<Enter>", "Ctrl+Enter"),
+            ("ls -l", "ls -l"),
+        ] {
+            assert_eq!(
+                normalize(component, Keyboard::Pc),
+                expected,
+                "{}",
+                component
+            );
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! List module for Recall.
+//!
+//! Prints the paths of all pages, or the entries of a single page, for scripts to read instead of the interface.
+//! The text output has one line per page or entry with its fields separated by tabs, the JSON output is an array
+//! of objects. Like in `recall show`, hidden entries aren't listed and the content of secret and encrypted entries is masked.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::{
+    app::{Entry, Page, PAGE_PATH_SEPARATOR, SECRET_MASK},
+    config::count_entries,
+    keys::{normalize_content, Keyboard},
+};
+
+/// A page as listed in JSON.
+#[derive(Serialize)]
+struct PageListing<'a> {
+    /// Path of the page, the names of nested pages separated by [`PAGE_PATH_SEPARATOR`]
+    path: String,
+
+    /// Name of the page
+    name: &'a str,
+
+    /// Group of the page, if it is a top-level page belonging to one
+    group: Option<&'a str>,
+
+    /// Description of the page, if it has one
+    description: Option<&'a str>,
+
+    /// Number of entries of the page and its subpages, as counted in the page index
+    entries: usize,
+}
+
+/// An entry as listed in JSON.
+#[derive(Serialize)]
+struct EntryListing<'a> {
+    /// Name of the entry
+    name: &'a str,
+
+    /// Keys or command of the entry in canonical form, masked for secret and encrypted entries
+    content: Vec<String>,
+
+    /// Description of the entry
+    description: &'a str,
+
+    /// Section of the entry within its page, if any
+    section: Option<&'a str>,
+
+    /// Path of the page opened by the entry, if any
+    goto: Option<&'a str>,
+
+    /// Shell command run by the entry, if any
+    exec: Option<&'a str>,
+
+    /// Web page about the entry, if any
+    url: Option<&'a str>,
+
+    /// Whether the content of the entry is masked
+    secret: bool,
+}
+
+/// Returns the paths of the pages and their subpages, one per line, or as JSON with their group, description and number of entries.
+pub fn list_pages(pages: &[Page], json: bool) -> Result<String> {
+    let mut listings = vec![];
+    for page in pages {
+        collect_listings(page, page.name.clone(), &mut listings);
+    }
+
+    if json {
+        return to_json(&listings);
+    }
+    Ok(listings
+        .iter()
+        .map(|listing| format!("{}\n", listing.path))
+        .collect())
+}
+
+/// Returns the entries of the page without those of its subpages, one per line with name, content and description
+/// separated by tabs, or as JSON with all their fields.
+pub fn list_entries(page: &Page, keyboard: Keyboard, json: bool) -> Result<String> {
+    let listings: Vec<EntryListing> = page
+        .entries
+        .iter()
+        .filter(|entry| !entry.hidden)
+        .map(|entry| entry_listing(entry, keyboard))
+        .collect();
+
+    if json {
+        return to_json(&listings);
+    }
+    Ok(listings
+        .iter()
+        .map(|listing| {
+            format!(
+                "{}\t{}\t{}\n",
+                listing.name,
+                listing.content.join("+"),
+                listing.description.replace(['\n', '\r', '\t'], " ")
+            )
+        })
+        .collect())
+}
+
+/// Collects the listing of the page at the given path, followed by those of its subpages.
+fn collect_listings<'a>(page: &'a Page, path: String, listings: &mut Vec<PageListing<'a>>) {
+    listings.push(PageListing {
+        path: path.clone(),
+        name: &page.name,
+        group: page.group.as_deref(),
+        description: page.description.as_deref(),
+        entries: count_entries(std::slice::from_ref(page)),
+    });
+
+    for subpage in &page.subpages {
+        let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+        collect_listings(subpage, subpage_path, listings);
+    }
+}
+
+/// Returns the listing of an entry, with its content written for the given keyboard.
+fn entry_listing(entry: &Entry, keyboard: Keyboard) -> EntryListing<'_> {
+    EntryListing {
+        name: &entry.name,
+        // Encrypted entries are listed without decrypting them, so scripts never have age ask for a passphrase
+        content: match entry.secret || entry.encrypted.is_some() {
+            true => vec![SECRET_MASK.to_string()],
+            false => normalize_content(&entry.content, keyboard),
+        },
+        description: &entry.description,
+        section: entry.section.as_deref(),
+        goto: entry.goto.as_deref(),
+        exec: entry.exec.as_deref(),
+        url: entry.url.as_deref(),
+        secret: entry.secret || entry.encrypted.is_some(),
+    }
+}
+
+/// Returns the listings as a pretty-printed JSON array, ending with a newline.
+fn to_json<T: Serialize>(listings: &[T]) -> Result<String> {
+    let json = serde_json::to_string_pretty(listings).context("Failed to write JSON")?;
+    Ok(json + "\n")
+}
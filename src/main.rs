@@ -8,27 +8,108 @@
 //! - Keyboard event handling
 //! - Sets up UI rendering via ratatui
 
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    io::{self, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process, slice,
+    time::{Duration, Instant},
+};
 
-use anyhow::{Ok, Result};
-use clap::Parser;
+use anyhow::{anyhow, bail, Context, Ok, Result};
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use cli::Commands;
-use log::{info, trace};
+use log::{info, trace, warn};
 use ratatui::{
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    buffer::Buffer,
+    crossterm::{
+        event::{
+            self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+        },
+        style::Stylize,
+        terminal::{self, EnterAlternateScreen},
+        ExecutableCommand,
+    },
+    layout::{Position, Rect},
     prelude::Backend,
+    style::Color,
     Terminal,
 };
+use regex::Regex;
 
 mod app;
+mod backup;
 mod cli;
+mod clipboard;
+mod completion;
 mod config;
+mod diagram;
+mod editor;
+mod encryption;
+mod export;
+mod fuzzy;
+mod graphics;
+mod history;
+mod html;
+mod hyperlink;
+mod i18n;
+mod import;
+mod importers;
+mod keymap;
+mod keys;
+mod list;
+mod mouse;
+mod pager;
+mod plugin;
+mod rewrite;
+mod show;
+mod snippet;
+mod state;
+mod stats;
+mod template;
+mod theme;
+mod title;
 mod ui;
+mod wasm;
+mod watch;
+mod workspace;
 
-use app::{App, AppState, QuitReason};
+use app::{
+    match_entry, match_page, page_at, App, AppState, Config, ConflictResolution, Entry, Mode, Page,
+    QuitReason, PAGE_PATH_SEPARATOR,
+};
 use cli::Cli;
-use config::{default_config_path, init_config, read_from_config};
-use ui::ui;
+use clipboard::Clipboard;
+use config::{
+    collect_entries, config_fingerprint, count_entries, default_config_path,
+    find_shortcut_conflicts, init_config, read_from_config, run_page_source, serialize_pages,
+    Entries,
+};
+use export::export;
+use graphics::Graphics;
+use history::{default_history_path, read_history, suggest};
+use i18n::{Localizer, DEFAULT_LANGUAGE};
+use import::{add_entry, entry_name, import, import_help, import_source, merge_pages, read_import};
+use keymap::Action;
+use keys::{is_key_notation, Keyboard};
+use plugin::{plugin_dir, Plugins};
+use rewrite::{diff, ConfigDocument};
+use show::Colors;
+use state::{config_state_path, read_state, write_state, EntryKey};
+use template::{find_template, Template, TEMPLATES};
+use theme::{find_theme, ColorOverrides, Theme, THEMES};
+use ui::{ui, Drawn};
+use wasm::converter_path;
+use watch::Watcher;
+use workspace::{workspace_names, Workspace};
+
+/// Name of the page accepted suggestions from the shell history are added to
+const SUGGESTIONS_PAGE_NAME: &str = "Frequently retyped";
+
+/// How long to wait for input before timers are checked again, unless the config sets a tick rate
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
 
 /// Entry point for recall.
 ///
@@ -39,65 +120,511 @@ fn main() -> Result<()> {
 
     // TODO: Use color_eyre
 
+    // Answers completion requests of the shell and exits, which are set up as described in the README
+    CompleteEnv::with_factory(Cli::command).complete();
+
+    // Copies of recall started by `recall copy` only hold the copied text in the clipboard
+    #[cfg(all(unix, not(target_os = "macos")))]
+    if env::var_os(clipboard::HOLDER_VARIABLE).is_some() {
+        return clipboard::hold();
+    }
+
     trace!("Parsing CLI arguments");
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    cli.page = cli.page.or(cli.page_option.take());
 
     // TODO: What if path contains illegal unicode symbols?
     //       -> Dangerous unwrap
-    let config_path = match cli.config {
-        Some(path) => {
+    let config_paths = if cli.config.is_empty() {
+        let path = default_config_path()?;
+        info!("Using default config path: {}", path.to_str().unwrap());
+        vec![path]
+    } else {
+        for path in &cli.config {
             info!("Using custom config path: {}", path.to_str().unwrap());
-            path
-        }
-        None => {
-            let path = default_config_path()?;
-            info!("Using default config path: {}", path.to_str().unwrap());
-            path
         }
+        cli.config
     };
+    let config_path = config_paths[0].clone();
+
+    if config_paths.len() > 1 && cli.command.is_some() {
+        bail!(
+            "Subcommands work on a single config, but {} were given",
+            config_paths.len()
+        );
+    }
+
+    if let (Some(page), Some(_)) = (cli.page.as_ref().or(cli.split.as_ref()), &cli.command) {
+        bail!("Page {} can only be opened without a subcommand", page);
+    }
+
+    // See https://no-color.org, an empty value doesn't disable colors
+    let no_color = cli.no_color || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
 
     // This log might be the job of the handle_subcommands function
     trace!("Parsing CLI subcommands");
-    let state_after_subcommands = handle_subcommands(cli.command, config_path.clone())?;
+    let overrides = ColorOverrides {
+        theme: cli.theme.as_deref().and_then(find_theme),
+        primary_color: cli.primary_color.map(Color::Indexed),
+        highlight_color: cli.highlight_color.map(Color::Indexed),
+    };
+    let state_after_subcommands = handle_subcommands(
+        cli.command,
+        config_path.clone(),
+        cli.no_pager,
+        no_color,
+        overrides,
+    )?;
 
     if let AppState::Quitting(reason) = state_after_subcommands {
-        info!("Quitting due to: {}", reason.text());
+        // The config isn't read before subcommands, so their messages use the default language
+        let localizer = Localizer::new(DEFAULT_LANGUAGE)?;
+        info!("Quitting due to: {}", reason.text(&localizer));
         return Ok(());
     }
 
-    // TODO: Handle non-existent config without throwing an error
-    let config = read_from_config(config_path)?;
+    let names = workspace_names(&config_paths);
+    let mut workspaces = vec![];
+    for (index, config_path) in config_paths.into_iter().enumerate() {
+        // TODO: Handle non-existent config without throwing an error
+        let (config, plugins) = read_with_plugins(&config_path, Entries::Decrypted)?;
+
+        // Resolved before the terminal is taken over, so an unknown page is reported like other errors
+        // Pages given on the command line are opened in the first workspace
+        let (initial_page, split_page) = if index == 0 {
+            (
+                cli.page
+                    .as_ref()
+                    .map(|query| match_page(&config.pages, query))
+                    .transpose()?,
+                cli.split
+                    .as_ref()
+                    .map(|query| match_page(&config.pages, query))
+                    .transpose()?,
+            )
+        } else {
+            (None, None)
+        };
+
+        let state_path = config_state_path(&config_path)?;
+        let user_state = read_state(&state_path);
+
+        let localizer = Localizer::new(&config.language)?;
+        let graphics = config.graphics.unwrap_or_else(Graphics::detect);
+
+        let mut app = App::new(config, user_state, localizer);
+        app.set_graphics(graphics);
+        if names.len() > 1 {
+            app.set_workspaces(names.clone(), index);
+        }
+        if let Some(path) = initial_page {
+            info!("Opening page {}", path);
+            app.open_page(&path);
+        }
+        if let Some(path) = split_page {
+            info!("Opening page {} next to the current page", path);
+            app.open_split(&path);
+        }
+        if cli.plain {
+            app.enable_plain_mode();
+        }
+        if no_color {
+            app.disable_colors();
+        }
+        if !overrides.is_empty() {
+            let (primary_color, highlight_color) =
+                overrides.apply(app.config().primary_color, app.config().highlight_color);
+            app.override_colors(primary_color, highlight_color);
+        }
 
-    let mut app = App::new(config);
+        workspaces.push(Workspace {
+            app,
+            fingerprint: config_fingerprint(&config_path),
+            watcher: Watcher::new(&config_path),
+            config_path,
+            state_path,
+            plugins,
+        });
+    }
 
     trace!("Creating terminal backend");
     let mut terminal = ratatui::init();
+    title::save()?;
 
     trace!("Starting main loop");
-    run(&mut terminal, &mut app)?;
+    let cycle_interval = cli.cycle.map(Duration::from_secs);
+    let result = run(&mut terminal, &mut workspaces, cycle_interval);
 
     trace!("Restoring terminal");
+    mouse::capture(false)?;
+    title::restore()?;
     ratatui::restore();
+    result
+}
+
+/// Runs a command in the shell with the terminal attached, failing if the command does.
+fn run_command(command: &str) -> Result<()> {
+    info!("Running {}", command);
+    let status = if cfg!(windows) {
+        process::Command::new("cmd").args(["/C", command]).status()
+    } else {
+        process::Command::new("sh").args(["-c", command]).status()
+    }
+    .context(format!("Failed to run {}", command))?;
+
+    if !status.success() {
+        bail!("Command {} failed with {}", command, status);
+    }
     Ok(())
 }
 
+/// Runs the command of an executable entry with the terminal handed over to it, and takes the terminal back afterwards.
+///
+/// The output stays visible until Enter is pressed. Returns `false` if the command failed.
+fn run_suspended<B: Backend>(terminal: &mut Terminal<B>, command: &str) -> Result<bool> {
+    mouse::capture(false)?;
+    ratatui::restore();
+
+    let succeeded = match run_command(command) {
+        Result::Ok(()) => true,
+        Err(error) => {
+            warn!("{:#}", error);
+            eprintln!("{:#}", error);
+            false
+        }
+    };
+    print!("Press Enter to return to recall");
+    io::stdout()
+        .flush()
+        .context("Failed to write to the terminal")?;
+    io::stdin()
+        .read_line(&mut String::new())
+        .context("Failed to read from the terminal")?;
+
+    terminal::enable_raw_mode().context("Failed to take back the terminal")?;
+    io::stdout()
+        .execute(EnterAlternateScreen)
+        .context("Failed to take back the terminal")?;
+    terminal
+        .clear()
+        .context("Failed to take back the terminal")?;
+    Ok(succeeded)
+}
+
+/// Reads the config with its entries prepared as far as asked for, see [`Entries`], and loads the plugins, which
+/// transform it and, unless the entries are read as written, generate the entries of their pages.
+fn read_with_plugins(config_path: &Path, entries: Entries) -> Result<(Config, Plugins)> {
+    let mut config = read_from_config(config_path.to_path_buf(), entries)?;
+
+    let plugins = Plugins::load(&plugin_dir(config_path))?;
+    plugins.on_load(&mut config.pages)?;
+    if entries >= Entries::Generated {
+        plugins.generate_entries(&mut config.pages);
+    }
+
+    Ok((config, plugins))
+}
+
+/// Writes the pages and colors edited in the interface into the config at the given path.
+///
+/// Only what differs from the config as written is changed, so its comments and formatting stay as they are.
+/// The config isn't backed up, which is done once before the edits of a session instead.
+fn save_edits(config_path: &Path, config: &Config) -> Result<()> {
+    let mut document = ConfigDocument::read(config_path)?;
+    document.update_pages(&config.pages)?;
+    document.set_colors(
+        config.theme.as_deref(),
+        config.primary_color,
+        config.highlight_color,
+    )?;
+    document.write_without_backup()
+}
+
 /// Runs the main application loop
 ///
 /// Repeatedly draws the UI loop and handles keyboard events until the applications state changes to 'Quitting'
-fn run<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
-    while app.is_active() {
-        terminal.draw(|f| ui(f, app))?;
+/// Edits made within the application are written back to the config file after each key event,
+/// changes to the user state (e.g. favorites) to the state file, unless a plugin transforms the config.
+/// The config is backed up before the first edit of a session, and again only after another program changed it.
+/// If another program changed the config in the meantime, the user decides whether to reload, merge or overwrite it.
+/// Without edits made here, changes to the config and plugins are reloaded as soon as they are written.
+/// Lookups of pages and entries are counted and written along with other changes to the user state or on exit.
+/// Entries activated without a built-in action are handed to the plugins.
+/// If a cycle interval is given, the pages are advanced whenever no key was pressed for that long.
+/// After the configured idle timeout without input, recall quits or blanks the screen.
+/// The window title names the current page, links of entries are made clickable and their images shown.
+/// Only the workspace switched to is shown and checked for changes, the others keep their state until switched back to.
+fn run<B: Backend>(
+    terminal: &mut Terminal<B>,
+    workspaces: &mut [Workspace],
+    cycle_interval: Option<Duration>,
+) -> Result<()> {
+    let mut active = 0;
+    let mut last_cycle = Instant::now();
+    let mut last_input = Instant::now();
+    let mut window_title = String::new();
+    let mut linked_frame: Option<Buffer> = None;
+    let mut shown_image: Option<(Rect, String)> = None;
+    let mut mouse_captured = false;
+    // Fingerprints of the configs as last written here, so a config is backed up before the first edit of a session
+    // and after other programs changed it, rather than before every edit rotating the older backups out
+    let mut written: HashMap<PathBuf, Option<u64>> = HashMap::new();
+    // Opened on the first copy and kept open, so the copied text stays in the clipboard of X11 while recall runs
+    let mut clipboard: Option<Clipboard> = None;
+
+    while workspaces[active].app.is_active() {
+        let Workspace {
+            app,
+            config_path,
+            state_path,
+            plugins,
+            fingerprint,
+            watcher,
+        } = &mut workspaces[active];
+        let config_dir = config_path.parent().unwrap_or(Path::new("."));
+        let idle_timeout = app
+            .config()
+            .idle_timeout
+            .map(|minutes| Duration::from_secs(minutes * 60));
+        let tick_rate = app
+            .config()
+            .tick_rate
+            .map_or(POLL_INTERVAL, Duration::from_millis);
+
+        app.track_usage();
+
+        // Workspaces and reloaded configs can turn the mouse on or off
+        if app.config().mouse != mouse_captured {
+            mouse::capture(app.config().mouse)?;
+            mouse_captured = app.config().mouse;
+        }
+
+        let page = app.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+        if page != window_title {
+            trace!("Setting window title to page {}", page);
+            title::set(&page)?;
+            window_title = page;
+        }
 
-        // TODO: Non-blocking with poll
-        if let Event::Key(key) = event::read()? {
-            trace!("Handling key event");
-            handle_key_event(key, app)
+        let mut drawn = Drawn::default();
+        let frame = terminal.draw(|f| drawn = ui(f, app))?;
+        if let Some(offset) = drawn.scroll_offset {
+            app.set_scroll_offset(offset);
+        }
+        let image = drawn
+            .image_area
+            .zip(app.current_image())
+            .map(|(area, path)| (area, path.to_string()));
+
+        let frame =
+            if image != shown_image && shown_image.is_some() && app.graphics() == Graphics::Sixel {
+                // Sixel images stay until their cells are written again, which ratatui only does when redrawing everything
+                terminal.clear()?;
+                linked_frame = None;
+                terminal.draw(|f| {
+                    ui(f, app);
+                })?
+            } else {
+                frame
+            };
+
+        // Screen readers follow the cursor in plain mode, which writing links would move
+        if !app.is_plain() && linked_frame.as_ref() != Some(frame.buffer) {
+            hyperlink::write_links(frame.buffer, &app.link_urls())?;
+            linked_frame = Some(frame.buffer.clone());
+        }
+
+        if image != shown_image {
+            app.graphics().clear()?;
+            if let Some((area, path)) = &image {
+                trace!("Showing image {}", path);
+                if let Err(error) = app.graphics().show(&config_dir.join(path), *area) {
+                    warn!("{:#}", error);
+                }
+            }
+            shown_image = image;
+        }
+
+        // Waiting for events only briefly keeps timers running without input
+        if event::poll(tick_rate)? {
+            match event::read()? {
+                Event::Key(key) => {
+                    trace!("Handling key event");
+                    handle_key_event(key, app);
+                    last_cycle = Instant::now();
+                    last_input = Instant::now();
+                }
+                // Moving the mouse is reported as well, which isn't input
+                Event::Mouse(mouse) if mouse.kind != MouseEventKind::Moved => {
+                    trace!("Handling mouse event");
+                    handle_mouse_event(mouse, app, &drawn.tabs);
+                    last_cycle = Instant::now();
+                    last_input = Instant::now();
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(timeout) = idle_timeout {
+            if !app.is_blanked() && last_input.elapsed() >= timeout {
+                info!("Idle for {} seconds", timeout.as_secs());
+                app.idle();
+            }
+        }
+
+        if let Some(interval) = cycle_interval {
+            if last_cycle.elapsed() >= interval {
+                trace!("Cycling to next page");
+                app.cycle_page();
+                last_cycle = Instant::now();
+            }
+        }
+
+        for (path, source) in app.due_page_sources() {
+            trace!("Refreshing page {}", path);
+            let entries = run_page_source(&source, &path)
+                .inspect_err(|error| warn!("{:#}", error))
+                .ok();
+            app.update_generated_entries(&path, entries);
+        }
+
+        if let Some((page, entry)) = app.take_selection() {
+            if let Some(text) = plugins.on_select(&page, &entry) {
+                app.copy_to_clipboard(text);
+            }
+        }
+
+        if let Some(text) = app.take_clipboard() {
+            clipboard.get_or_insert_with(Clipboard::open).copy(&text)?;
+        }
+
+        if let Some(url) = app.take_browser_url() {
+            info!("Opening {} in the browser", url);
+            let opened = open::that_detached(&url);
+            if let Err(error) = &opened {
+                warn!("Failed to open {}: {}", url, error);
+            }
+            app.report_opened_url(opened.is_ok());
+        }
+
+        if let Some(command) = app.take_execution() {
+            if !run_suspended(terminal, &command)? {
+                app.report_failed_execution();
+            }
+            // Everything is drawn anew, including the mouse, window title, links and images
+            mouse_captured = false;
+            window_title.clear();
+            linked_frame = None;
+            shown_image = None;
+        }
+
+        // Edits made here are saved below, if another program changed the config as well the user decides
+        if watcher.poll() && !app.has_unsaved_changes() {
+            match read_with_plugins(config_path, Entries::Decrypted) {
+                Result::Ok((config, reloaded_plugins)) => {
+                    info!("Reloading changed config");
+                    app.reload_pages(config.pages);
+                    *plugins = reloaded_plugins;
+                    *fingerprint = config_fingerprint(config_path);
+                }
+                Err(error) => {
+                    warn!("{:#}", error);
+                    app.report_failed_reload();
+                }
+            }
+        }
+
+        if app.take_reload_request() {
+            match read_with_plugins(config_path, Entries::Decrypted).and_then(
+                |(config, reloaded_plugins)| {
+                    let localizer = Localizer::new(&config.language)?;
+                    Ok((config, reloaded_plugins, localizer))
+                },
+            ) {
+                Result::Ok((config, reloaded_plugins, localizer)) => {
+                    info!("Reading config again");
+                    app.set_graphics(config.graphics.unwrap_or_else(Graphics::detect));
+                    app.reload_config(config, localizer);
+                    *plugins = reloaded_plugins;
+                    *fingerprint = config_fingerprint(config_path);
+                    watcher.acknowledge();
+                }
+                Err(error) => {
+                    warn!("{:#}", error);
+                    app.report_config_error(format!("{:#}", error));
+                }
+            }
+        }
+
+        if let Some(resolution) = app.take_conflict_resolution() {
+            if resolution == ConflictResolution::Overwrite {
+                info!("Overwriting config changed by another program");
+                *fingerprint = config_fingerprint(config_path);
+            } else {
+                match read_with_plugins(config_path, Entries::Decrypted) {
+                    Result::Ok((config, _)) => {
+                        info!("Reading config changed by another program");
+                        if resolution == ConflictResolution::Reload {
+                            app.reload_pages(config.pages);
+                        } else {
+                            app.merge_pages(config.pages);
+                        }
+                        *fingerprint = config_fingerprint(config_path);
+                        watcher.acknowledge();
+                    }
+                    Err(error) => {
+                        warn!("{:#}", error);
+                        app.report_unreadable_config();
+                    }
+                }
+            }
+        }
+
+        // Writing transformed pages would replace the config with the output of the plugins
+        if app.has_unsaved_changes() && plugins.transforms_config() {
+            warn!("Not saving edits, a plugin transforms the config");
+            app.mark_saved();
+        } else if app.has_unsaved_changes() && config_fingerprint(config_path) != *fingerprint {
+            // Edits would silently replace what another program, e.g. an editor, wrote
+            trace!("Config was changed by another program, not saving edits");
+            app.open_config_conflict();
+        } else if app.has_unsaved_changes() {
+            trace!("Saving edited config");
+            if written.get(config_path.as_path()) != Some(&config_fingerprint(config_path)) {
+                backup::back_up(config_path)?;
+            }
+            save_edits(config_path, app.config())?;
+            *fingerprint = config_fingerprint(config_path);
+            written.insert(config_path.clone(), *fingerprint);
+            watcher.acknowledge();
+            app.mark_saved();
+        }
+
+        if app.has_unsaved_state() {
+            trace!("Saving user state");
+            write_state(state_path, app.user_state())?;
+            app.mark_state_saved();
+        }
+
+        if let Some(index) = app.take_workspace_switch() {
+            info!("Switching to workspace {}", index + 1);
+            active = index;
+            last_cycle = Instant::now();
+        }
+    }
+
+    for workspace in workspaces.iter_mut() {
+        if workspace.app.has_unsaved_usage() {
+            trace!("Saving lookups");
+            write_state(&workspace.state_path, workspace.app.user_state())?;
+            workspace.app.mark_state_saved();
         }
     }
 
     // This should always be the case if the app is not active anymore
+    let app = &workspaces[active].app;
     if let AppState::Quitting(reason) = &app.state {
-        info!("Quitting due to: {}", reason.text());
+        info!("Quitting due to: {}", reason.text(app.localizer()));
     }
     Ok(())
 }
@@ -108,49 +635,1484 @@ fn handle_key_event(key: KeyEvent, app: &mut App) {
     // Actually, seems like this is already the case.
 
     // Is this the correct way to handle SIGINTs and SIGKILLs?
-    if key.modifiers == KeyModifiers::CONTROL {
-        if let KeyCode::Char('c') = key.code {
-            // TODO: Reformulate Quitting messages
-            info!("Quitting due to received SIGINT Signal");
-            app.quit(app::QuitReason::Sigint);
+    if key.modifiers == KeyModifiers::CONTROL && key.code == KeyCode::Char('c') {
+        // TODO: Reformulate Quitting messages
+        info!("Quitting due to received SIGINT Signal");
+        app.quit(app::QuitReason::Sigint);
+        return;
+    }
+
+    app.clear_notice();
+    // Secrets are only revealed while no other key is pressed
+    app.hide_secrets();
+
+    // The key that wakes up a blanked screen has no other effect
+    if app.is_blanked() {
+        trace!("Waking up from idle");
+        app.wake();
+        return;
+    }
+
+    match app.mode {
+        Mode::Normal => handle_normal_key_event(key, app),
+        Mode::PageNameInput(_) => handle_page_name_key_event(key, app),
+        Mode::MoveEntry(_) => handle_move_key_event(key, app),
+        Mode::FilterInput => handle_filter_key_event(key, app),
+        Mode::SearchInput => handle_search_key_event(key, app),
+        Mode::PageSwitcher(_) => handle_page_switcher_key_event(key, app),
+        Mode::CommandLine(_) => handle_command_line_key_event(key, app),
+        Mode::Settings(_) => handle_settings_key_event(key, app),
+        Mode::AllPages(_) => handle_all_pages_key_event(key, app),
+        Mode::PlaceholderInput(_) => handle_placeholder_key_event(key, app),
+        Mode::EntryInput(_) => handle_entry_key_event(key, app),
+        Mode::Stats(_) => handle_stats_key_event(key, app),
+        Mode::Details => handle_details_key_event(key, app),
+        Mode::ConfigConflict => handle_conflict_key_event(key, app),
+        Mode::Workspaces(_) => handle_workspaces_key_event(key, app),
+        Mode::PageIndex(_) => handle_page_index_key_event(key, app),
+        Mode::Help(_) => handle_help_key_event(key, app),
+        Mode::ConfigError(_) => handle_config_error_key_event(key, app),
+    }
+}
+
+/// Handles a mouse event, the wheel scrolls through the entries while navigating the pages.
+///
+/// Scrolling sideways or with Shift held changes the page, as does clicking a tab of the tab bar or the label of a
+/// group, which opens the first page of the group. Other mouse events and modes are ignored.
+fn handle_mouse_event(mouse: MouseEvent, app: &mut App, tabs: &[(Rect, usize)]) {
+    // The mouse wakes up a blanked screen like any key
+    if app.is_blanked() {
+        trace!("Waking up from idle");
+        app.wake();
+        return;
+    }
+
+    if !matches!(app.mode, Mode::Normal) {
+        return;
+    }
+
+    let shift = mouse.modifiers.contains(KeyModifiers::SHIFT);
+    let position = Position::new(mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            match tabs.iter().find(|(area, _)| area.contains(position)) {
+                Some((_, number)) => {
+                    trace!("Going to clicked page {}", number);
+                    app.go_to_page(*number)
+                }
+                None => trace!("Clicked outside of the tab bar"),
+            }
         }
-    } else {
-        match key.code {
-            KeyCode::Left => {
-                trace!("Decremting page number");
+        MouseEventKind::ScrollUp if shift => app.decrement_page(),
+        MouseEventKind::ScrollDown if shift => app.increment_page(),
+        MouseEventKind::ScrollLeft => app.decrement_page(),
+        MouseEventKind::ScrollRight => app.increment_page(),
+        MouseEventKind::ScrollUp => app.select_previous_entry(),
+        MouseEventKind::ScrollDown => app.select_next_entry(),
+        _ => {
+            trace!("Unused mouse event: {:?}", mouse.kind);
+        }
+    }
+}
+
+/// Handles a key event while navigating and editing pages.
+///
+/// Keys are looked up in the keymap, so they follow the keybinds of the config.
+/// A count typed before a key moving between pages or rows repeats the move.
+fn handle_normal_key_event(key: KeyEvent, app: &mut App) {
+    let Some((action, count)) = app.resolve_key(&key) else {
+        trace!(
+            "Key(s) pressed without completing an action: {}+{}",
+            key.modifiers,
+            key.code
+        );
+        return;
+    };
+    let repeat = count.unwrap_or(1);
+
+    match action {
+        Action::PreviousPage => {
+            trace!("Decremting page number");
+            for _ in 0..repeat {
                 app.decrement_page()
             }
-            KeyCode::Right => {
-                trace!("Incrementing page number");
+        }
+        Action::NextPage => {
+            trace!("Incrementing page number");
+            for _ in 0..repeat {
                 app.increment_page()
             }
-            KeyCode::Char('q') => {
-                info!("Quitting due to pressed 'quit' button");
-                app.quit(app::QuitReason::CloseKeyPressed);
+        }
+        Action::FirstPage => {
+            trace!("Going to first page or counted page");
+            app.go_to_page(count.map_or(0, |number| number.saturating_sub(1)))
+        }
+        Action::LastPage => {
+            trace!("Going to last page or counted page");
+            let last = app.number_of_pages().saturating_sub(1);
+            app.go_to_page(count.map_or(last, |number| number.saturating_sub(1)))
+        }
+        Action::PreviousEntry => {
+            trace!("Selecting previous entry");
+            for _ in 0..repeat {
+                app.select_previous_entry()
             }
-            _ => {
-                trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+        Action::NextEntry => {
+            trace!("Selecting next entry");
+            for _ in 0..repeat {
+                app.select_next_entry()
             }
         }
+        Action::Activate => {
+            trace!("Activating selected row");
+            app.activate_selection()
+        }
+        Action::LeaveSubpage => {
+            trace!("Drilling out of subpage");
+            app.leave_subpage()
+        }
+        Action::MoveEntryUp => {
+            trace!("Moving selected entry up");
+            app.move_selected_entry_up()
+        }
+        Action::MoveEntryDown => {
+            trace!("Moving selected entry down");
+            app.move_selected_entry_down()
+        }
+        Action::DeleteEntry => {
+            trace!("Deleting selected entry");
+            app.delete_selected_entry()
+        }
+        Action::AddEntry => {
+            trace!("Starting input of new entry");
+            app.open_add_entry_form()
+        }
+        Action::EditEntry => {
+            trace!("Starting to change selected entry");
+            app.open_edit_entry_form()
+        }
+        Action::Undo => {
+            trace!("Undoing last edit");
+            app.undo()
+        }
+        Action::Redo => {
+            trace!("Redoing last undone edit");
+            app.redo()
+        }
+        Action::NewPage => {
+            trace!("Starting input of new page name");
+            app.mode = Mode::PageNameInput(String::new())
+        }
+        Action::MoveEntry => {
+            trace!("Starting input of page to move selected entry to");
+            app.open_move_prompt()
+        }
+        Action::ToggleFavorite => {
+            trace!("Toggling star of selected entry");
+            app.toggle_favorite()
+        }
+        Action::ToggleSection => {
+            trace!("Toggling selected section");
+            app.toggle_selected_section();
+        }
+        Action::CycleSort => {
+            trace!("Cycling sort order of current page");
+            app.cycle_sort_order()
+        }
+        Action::ToggleHidden => {
+            trace!("Toggling hidden entries");
+            app.toggle_hidden_entries()
+        }
+        Action::RevealSecret => {
+            trace!("Revealing selected secret entry");
+            app.reveal_selected_secret()
+        }
+        Action::ToggleWrap => {
+            trace!("Toggling wrapping of descriptions");
+            app.toggle_wrap_descriptions()
+        }
+        Action::ToggleKeyboard => {
+            trace!("Toggling keyboard diagram");
+            app.toggle_keyboard()
+        }
+        Action::AllPages => {
+            trace!("Opening all-pages view");
+            app.open_all_pages_view()
+        }
+        Action::PageIndex => {
+            trace!("Opening page index");
+            app.open_page_index()
+        }
+        Action::Reload => {
+            trace!("Reading config again");
+            app.request_reload()
+        }
+        Action::Help => {
+            trace!("Opening help");
+            app.open_help()
+        }
+        Action::Details => {
+            trace!("Opening details of selected entry");
+            app.open_details()
+        }
+        Action::Copy => {
+            trace!("Copying content of selected entry");
+            app.copy_selected_entry(false)
+        }
+        Action::CopyDescription => {
+            trace!("Copying description of selected entry");
+            app.copy_selected_entry(true)
+        }
+        Action::OpenUrl => {
+            trace!("Opening web page of selected entry");
+            app.open_selected_url()
+        }
+        Action::Stats => {
+            trace!("Opening stats view");
+            app.open_stats_view()
+        }
+        Action::Settings => {
+            trace!("Opening settings");
+            app.open_settings()
+        }
+        Action::ToggleSplit => {
+            trace!("Toggling split view");
+            app.toggle_split()
+        }
+        Action::SwitchPane => {
+            trace!("Switching focused pane");
+            app.switch_pane()
+        }
+        Action::Workspaces => {
+            trace!("Opening workspace switcher");
+            app.open_workspaces()
+        }
+        Action::Filter => {
+            trace!("Starting input of filter");
+            app.open_filter()
+        }
+        Action::Search => {
+            trace!("Starting input of search across all pages");
+            app.open_search()
+        }
+        Action::PageSwitcher => {
+            trace!("Opening page switcher");
+            app.open_page_switcher()
+        }
+        Action::CommandLine => {
+            trace!("Opening command line");
+            app.open_command_line()
+        }
+        Action::Clear => {
+            if app.clear_filter() {
+                trace!("Cleared filter of current page");
+            } else if app.clear_search() {
+                trace!("Cleared search across all pages");
+            }
+        }
+        Action::Quit => {
+            info!("Quitting due to pressed 'quit' button");
+            app.quit(app::QuitReason::CloseKeyPressed);
+        }
+    }
+}
+
+/// Handles a key event while typing the name of a new page.
+///
+/// Enter creates the page if the name is valid, Esc cancels the input.
+fn handle_page_name_key_event(key: KeyEvent, app: &mut App) {
+    let Mode::PageNameInput(name) = &mut app.mode else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Char(c) => name.push(c),
+        KeyCode::Backspace => {
+            name.pop();
+        }
+        KeyCode::Esc => {
+            trace!("Cancelling input of new page name");
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Enter => {
+            let name = name.clone();
+            if app.page_name_error(&name).is_none() {
+                info!("Creating new page {}", name);
+                app.create_page(name);
+                app.mode = Mode::Normal;
+            }
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while typing the filter of the current page.
+///
+/// Enter keeps the filter applied, Esc clears it.
+fn handle_filter_key_event(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Char(c) => app.push_filter_char(c),
+        KeyCode::Backspace => app.pop_filter_char(),
+        KeyCode::Up => app.select_previous_entry(),
+        KeyCode::Down => app.select_next_entry(),
+        KeyCode::Esc => {
+            trace!("Clearing filter");
+            app.clear_filter();
+        }
+        KeyCode::Enter => {
+            trace!("Applying filter");
+            app.confirm_filter();
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while typing into the command line.
+///
+/// Tab and Shift+Tab complete the names of pages, Enter jumps to the page and Esc cancels.
+fn handle_command_line_key_event(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Char(c) => app.push_command_line_char(c),
+        KeyCode::Backspace => app.pop_command_line_char(),
+        KeyCode::Tab => app.complete_command_line(true),
+        KeyCode::BackTab => app.complete_command_line(false),
+        KeyCode::Esc => {
+            trace!("Closing command line");
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Enter => {
+            trace!("Jumping to page of command line");
+            app.confirm_command_line();
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while typing the search across all pages.
+///
+/// Enter keeps the results page, Esc clears the search.
+fn handle_search_key_event(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Char(c) => app.push_search_char(c),
+        KeyCode::Backspace => app.pop_search_char(),
+        KeyCode::Up => app.select_previous_entry(),
+        KeyCode::Down => app.select_next_entry(),
+        KeyCode::Esc => {
+            trace!("Clearing search");
+            app.clear_search();
+        }
+        KeyCode::Enter => {
+            trace!("Keeping search results");
+            app.confirm_search();
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while the page switcher is open.
+///
+/// Typing narrows the pages, Up and Down select one, Enter opens it and Esc or the keys of the page switcher action
+/// close the switcher.
+fn handle_page_switcher_key_event(key: KeyEvent, app: &mut App) {
+    // Keys typing a character go into the query, even if they open the switcher
+    let typed = matches!(key.code, KeyCode::Char(_))
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+    match key.code {
+        _ if !typed && app.key_action(&key) == Some(Action::PageSwitcher) => {
+            trace!("Closing page switcher");
+            app.mode = Mode::Normal
+        }
+        KeyCode::Char(c) => app.push_page_switcher_char(c),
+        KeyCode::Backspace => app.pop_page_switcher_char(),
+        KeyCode::Up => app.move_page_switcher_selection(false),
+        KeyCode::Down => app.move_page_switcher_selection(true),
+        KeyCode::Enter => {
+            trace!("Opening selected page");
+            app.open_selected_switcher_page()
+        }
+        KeyCode::Esc => {
+            trace!("Closing page switcher");
+            app.mode = Mode::Normal
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while asking how to resolve edits to a config that another program changed.
+///
+/// `r` reloads the config, `m` merges it with the edits and `o` overwrites it.
+fn handle_conflict_key_event(key: KeyEvent, app: &mut App) {
+    let resolution = match key.code {
+        KeyCode::Char('r') => ConflictResolution::Reload,
+        KeyCode::Char('m') => ConflictResolution::Merge,
+        KeyCode::Char('o') => ConflictResolution::Overwrite,
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+            return;
+        }
+    };
+
+    info!("Resolving changed config by {:?}", resolution);
+    app.resolve_config_conflict(resolution);
+}
+
+/// Handles a key event while typing the page to move the selected entry to.
+///
+/// Enter moves the entry if a single page matches, Esc cancels the input.
+fn handle_move_key_event(key: KeyEvent, app: &mut App) {
+    let Mode::MoveEntry(query) = &mut app.mode else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Char(c) => query.push(c),
+        KeyCode::Backspace => {
+            query.pop();
+        }
+        KeyCode::Esc => {
+            trace!("Cancelling move of selected entry");
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Enter => {
+            let query = query.clone();
+            if app.move_target(&query).is_ok() {
+                info!("Moving selected entry to {}", query);
+                app.move_selected_entry_to(&query);
+                app.mode = Mode::Normal;
+            }
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while filling in the placeholders of a command.
+///
+/// Tab and Shift+Tab move between the placeholders, Enter copies the filled in command and Esc cancels.
+fn handle_placeholder_key_event(key: KeyEvent, app: &mut App) {
+    let Mode::PlaceholderInput(form) = &mut app.mode else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Char(c) => form.value_mut().push(c),
+        KeyCode::Backspace => {
+            form.value_mut().pop();
+        }
+        KeyCode::Tab | KeyCode::Down => form.next_field(),
+        KeyCode::BackTab | KeyCode::Up => form.previous_field(),
+        KeyCode::Esc => {
+            trace!("Cancelling input of placeholders");
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Enter => {
+            info!("Copying command with filled in placeholders");
+            app.confirm_placeholders();
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while typing into the form adding or changing an entry.
+///
+/// Tab and Shift+Tab move between the fields, Enter saves the entry if it is valid and Esc cancels.
+fn handle_entry_key_event(key: KeyEvent, app: &mut App) {
+    let Mode::EntryInput(form) = &mut app.mode else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Char(c) => form.value_mut().push(c),
+        KeyCode::Backspace => {
+            form.value_mut().pop();
+        }
+        KeyCode::Tab | KeyCode::Down => form.next_field(),
+        KeyCode::BackTab | KeyCode::Up => form.previous_field(),
+        KeyCode::Esc => {
+            trace!("Cancelling input of entry");
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Enter => {
+            info!("Saving entry {}", form.name());
+            app.confirm_entry_form();
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while the settings popup is open.
+///
+/// Up and Down select a setting, Left and Right change it, Enter saves the settings and Esc discards them.
+fn handle_settings_key_event(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Up => app.select_previous_setting(),
+        KeyCode::Down => app.select_next_setting(),
+        KeyCode::Left => app.adjust_setting(false),
+        KeyCode::Right => app.adjust_setting(true),
+        KeyCode::Enter => {
+            info!("Saving settings");
+            app.confirm_settings()
+        }
+        KeyCode::Esc => {
+            trace!("Discarding settings");
+            app.cancel_settings()
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while all pages are shown in a single table.
+///
+/// Up and Down scroll through the rows, Enter opens the page of the selected row and Esc or the keys of the all-pages
+/// action return to the current page.
+fn handle_all_pages_key_event(key: KeyEvent, app: &mut App) {
+    let action = app.key_action(&key);
+    match key.code {
+        KeyCode::Up => app.move_all_pages_selection(false),
+        KeyCode::Down => app.move_all_pages_selection(true),
+        KeyCode::Enter => {
+            trace!("Opening page of selected row");
+            app.open_selected_section()
+        }
+        _ if key.code == KeyCode::Esc || action == Some(Action::AllPages) => {
+            trace!("Closing all-pages view");
+            app.mode = Mode::Normal
+        }
+        _ if action == Some(Action::Quit) => {
+            info!("Quitting due to pressed 'quit' button");
+            app.quit(app::QuitReason::CloseKeyPressed);
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event in the page index.
+///
+/// Enter opens the selected page, Enter or Space collapse and expand a selected group, Esc or the keys of the page index
+/// action go back.
+fn handle_page_index_key_event(key: KeyEvent, app: &mut App) {
+    let action = app.key_action(&key);
+    match key.code {
+        KeyCode::Up => app.move_page_index_selection(false),
+        KeyCode::Down => app.move_page_index_selection(true),
+        KeyCode::Enter | KeyCode::Char(' ') => {
+            trace!("Activating selected row of page index");
+            app.activate_page_index_row()
+        }
+        _ if key.code == KeyCode::Esc || action == Some(Action::PageIndex) => {
+            trace!("Closing page index");
+            app.mode = Mode::Normal
+        }
+        _ if action == Some(Action::Quit) => {
+            info!("Quitting due to pressed 'quit' button");
+            app.quit(app::QuitReason::CloseKeyPressed);
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while the help is shown.
+///
+/// Up and Down scroll through the actions, Esc, Enter or the keys of the help action close the popup.
+fn handle_help_key_event(key: KeyEvent, app: &mut App) {
+    let action = app.key_action(&key);
+    match key.code {
+        KeyCode::Up => app.move_help_selection(false),
+        KeyCode::Down => app.move_help_selection(true),
+        _ if matches!(key.code, KeyCode::Esc | KeyCode::Enter) || action == Some(Action::Help) => {
+            trace!("Closing help");
+            app.mode = Mode::Normal
+        }
+        _ if action == Some(Action::Quit) => {
+            info!("Quitting due to pressed 'quit' button");
+            app.quit(app::QuitReason::CloseKeyPressed);
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while the popup tells why the config couldn't be read again.
+///
+/// Esc or Enter close the popup, the keys of the reload action try reading the config again.
+fn handle_config_error_key_event(key: KeyEvent, app: &mut App) {
+    let action = app.key_action(&key);
+    match key.code {
+        KeyCode::Esc | KeyCode::Enter => {
+            trace!("Closing config error");
+            app.mode = Mode::Normal
+        }
+        _ if action == Some(Action::Reload) => {
+            trace!("Reading config again");
+            app.mode = Mode::Normal;
+            app.request_reload()
+        }
+        _ if action == Some(Action::Quit) => {
+            info!("Quitting due to pressed 'quit' button");
+            app.quit(app::QuitReason::CloseKeyPressed);
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while the details of the selected entry are shown.
+///
+/// Esc, Enter or the keys of the details action close the popup, those of revealing secrets reveal the entry.
+fn handle_details_key_event(key: KeyEvent, app: &mut App) {
+    let action = app.key_action(&key);
+    match key.code {
+        _ if matches!(key.code, KeyCode::Esc | KeyCode::Enter)
+            || action == Some(Action::Details) =>
+        {
+            trace!("Closing details");
+            app.mode = Mode::Normal
+        }
+        _ if action == Some(Action::RevealSecret) => {
+            trace!("Revealing secret entry");
+            app.reveal_selected_secret()
+        }
+        _ if action == Some(Action::Quit) => {
+            info!("Quitting due to pressed 'quit' button");
+            app.quit(app::QuitReason::CloseKeyPressed);
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while the workspace switcher is open.
+///
+/// Up and Down select a workspace, Enter or the number of a workspace switch to it and Esc or the keys of the
+/// workspaces action return to the current page.
+fn handle_workspaces_key_event(key: KeyEvent, app: &mut App) {
+    let action = app.key_action(&key);
+    match key.code {
+        KeyCode::Up => app.move_workspace_selection(false),
+        KeyCode::Down => app.move_workspace_selection(true),
+        KeyCode::Enter => {
+            if let Mode::Workspaces(selected) = app.mode {
+                trace!("Switching to selected workspace");
+                app.switch_workspace(selected)
+            }
+        }
+        KeyCode::Char(c @ '1'..='9') => {
+            trace!("Switching to workspace {}", c);
+            app.switch_workspace(c as usize - '1' as usize)
+        }
+        _ if key.code == KeyCode::Esc || action == Some(Action::Workspaces) => {
+            trace!("Closing workspace switcher");
+            app.mode = Mode::Normal
+        }
+        _ if action == Some(Action::Quit) => {
+            info!("Quitting due to pressed 'quit' button");
+            app.quit(app::QuitReason::CloseKeyPressed);
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
+    }
+}
+
+/// Handles a key event while the most looked up entries are ranked.
+///
+/// Up and Down scroll through the rows, Enter opens the selected entry and Esc or the keys of the stats action return
+/// to the current page.
+fn handle_stats_key_event(key: KeyEvent, app: &mut App) {
+    let action = app.key_action(&key);
+    match key.code {
+        KeyCode::Up => app.move_stats_selection(false),
+        KeyCode::Down => app.move_stats_selection(true),
+        KeyCode::Enter => {
+            trace!("Opening selected entry");
+            app.open_selected_usage()
+        }
+        _ if key.code == KeyCode::Esc || action == Some(Action::Stats) => {
+            trace!("Closing stats view");
+            app.mode = Mode::Normal
+        }
+        _ if action == Some(Action::Quit) => {
+            info!("Quitting due to pressed 'quit' button");
+            app.quit(app::QuitReason::CloseKeyPressed);
+        }
+        _ => {
+            trace!("Unused key(s) pressed: {}+{}", key.modifiers, key.code);
+        }
     }
 }
 
 /// Processes CLI subcommands before launching the main application.
-fn handle_subcommands(command: Option<Commands>, config_path: PathBuf) -> Result<AppState> {
-    // TODO: When more subcommands are added, do `match` instead of `if let`
-
-    if let Some(Commands::Init) = command {
-        // This log might be the job of the init_config function
-        // TODO: What if path contains illegal unicode symbols?
-        //       -> Dangerous unwrap
-        info!(
-            "Creating initial config in {}",
-            config_path.to_str().unwrap()
-        );
+fn handle_subcommands(
+    command: Option<Commands>,
+    config_path: PathBuf,
+    no_pager: bool,
+    no_color: bool,
+    overrides: ColorOverrides,
+) -> Result<AppState> {
+    match command {
+        Some(Commands::Init { example }) => {
+            let (config_path, theme, templates) = if example || !io::stdin().is_terminal() {
+                (config_path, None, vec![])
+            } else {
+                init_wizard(config_path, no_color)?
+            };
+
+            // This log might be the job of the init_config function
+            // TODO: What if path contains illegal unicode symbols?
+            //       -> Dangerous unwrap
+            info!(
+                "Creating initial config in {}",
+                config_path.to_str().unwrap()
+            );
+
+            println!("{}", init_config(config_path.clone(), theme, &templates)?);
+            if default_config_path().ok() != Some(config_path.clone()) {
+                println!("Open it with `recall --config {}`", config_path.display());
+            }
+
+            Ok(AppState::Quitting(QuitReason::InitSubcommandCompleted))
+        }
+        Some(Commands::Check { across_pages }) => {
+            let config = read_from_config(config_path, Entries::Written)?;
 
-        let _ = init_config(config_path)?;
+            let keyboard = config.keyboard.unwrap_or_else(Keyboard::current);
+            let conflicts = find_shortcut_conflicts(&config.pages, across_pages, keyboard);
+            for conflict in &conflicts {
+                println!("{}", conflict);
+            }
+            if !conflicts.is_empty() {
+                bail!("Found {} shortcut conflict(s)", conflicts.len());
+            }
+            println!("No shortcut conflicts found");
+
+            Ok(AppState::Quitting(QuitReason::CheckSubcommandCompleted))
+        }
+        Some(Commands::Edit) => {
+            // The editor writes the config itself, so it is backed up first to be restored with `recall restore`
+            backup::back_up(&config_path)?;
+            loop {
+                editor::edit(&config_path)?;
 
-        return Ok(AppState::Quitting(QuitReason::InitSubcommandCompleted));
+                let Err(error) = read_from_config(config_path.clone(), Entries::Written) else {
+                    println!("Config {} is valid", config_path.display());
+                    break;
+                };
+                eprintln!("{:#}", error);
+                // Without a terminal to answer on, the errors are reported like those of other subcommands
+                if !io::stdin().is_terminal() {
+                    bail!("Config {} has errors", config_path.display());
+                }
+                let answer = prompt("\nEdit the config again? [y]es, [n]o: ")?;
+                if !matches!(answer.to_lowercase().as_str(), "y" | "yes") {
+                    bail!("Config {} has errors", config_path.display());
+                }
+            }
+
+            Ok(AppState::Quitting(QuitReason::EditSubcommandCompleted))
+        }
+        Some(Commands::Export {
+            format_argument,
+            format,
+            page,
+            plugin,
+            output,
+        }) => {
+            let mut config = read_from_config(config_path.clone(), Entries::Generated)?;
+            (config.primary_color, config.highlight_color) =
+                overrides.apply(config.primary_color, config.highlight_color);
+
+            // The page keeps its path, so it is exported like among all pages
+            let (pages, parents) = match page {
+                Some(query) => {
+                    let path = match_page(&config.pages, &query)?;
+                    let page = page_at(&config.pages, &path)
+                        .ok_or(anyhow!("Can not get page {} from config", path))?;
+                    let mut parents: Vec<String> = path
+                        .split(PAGE_PATH_SEPARATOR)
+                        .map(str::to_string)
+                        .collect();
+                    parents.pop();
+                    (slice::from_ref(page), parents)
+                }
+                None => (config.pages.as_slice(), vec![]),
+            };
+            let parents: Vec<&str> = parents.iter().map(String::as_str).collect();
+
+            let keyboard = config.keyboard.unwrap_or_else(Keyboard::current);
+            let exported = match (format.or(format_argument), plugin) {
+                (_, Some(name)) => wasm::export(&converter_path(&config_path, &name), pages)?,
+                (Some(format), None) => export(&config, pages, &parents, format, keyboard)?,
+                (None, None) => unreachable!("Either a format or a plugin is required"),
+            };
+            match output {
+                Some(path) => {
+                    info!("Writing export to {}", path.display());
+                    fs::write(&path, exported)
+                        .context(format!("Failed to write export to {}", path.display()))?;
+                }
+                None => pager::print(&exported, no_pager)?,
+            }
+
+            Ok(AppState::Quitting(QuitReason::ExportSubcommandCompleted))
+        }
+        Some(Commands::Import {
+            format,
+            plugin,
+            source,
+            file,
+            dry_run,
+            print,
+        }) => {
+            let (format, file) = import_source(format, source, file)?;
+            let pages = match (format, plugin) {
+                (_, Some(name)) => {
+                    wasm::import(&converter_path(&config_path, &name), &read_import(&file)?)?
+                }
+                (Some(format), None) => import(&file, format)?,
+                (None, None) => bail!(
+                    "Can not tell the format of {} from its extension, choose one with --format",
+                    file.display()
+                ),
+            };
+
+            // Printed pages can be reviewed or kept in a config of their own, which doesn't need to exist
+            if print {
+                print!("{}", serialize_pages(&pages));
+                return Ok(AppState::Quitting(QuitReason::ImportSubcommandCompleted));
+            }
+
+            // Pages are merged as written, so neither commands are run nor entries decrypted
+            let mut document = ConfigDocument::read(&config_path)?;
+            let mut merged = document.pages()?;
+            let existing = count_entries(&merged);
+            merge_pages(&mut merged, pages);
+            let imported = count_entries(&merged) - existing;
+            document.update_pages(&merged)?;
+
+            if dry_run {
+                let colors = output_colors(document.colors(), overrides, no_color);
+                print!("{}", document.diff(colors));
+                return Ok(AppState::Quitting(QuitReason::ImportSubcommandCompleted));
+            }
+
+            document.write()?;
+            println!(
+                "Imported {} entries from {} into {}",
+                imported,
+                file.display(),
+                config_path.display()
+            );
+
+            Ok(AppState::Quitting(QuitReason::ImportSubcommandCompleted))
+        }
+        Some(Commands::Scrape { command }) => {
+            let command = command.join(" ");
+            let help = read_help(&command)?;
+
+            let page = import_help(&command, &help);
+            if page.entries.is_empty() {
+                bail!("Found no options in the help of {}", command);
+            }
+
+            println!(
+                "# Drafted from `{} --help`, review before adding it to the config",
+                command
+            );
+            print!("{}", serialize_pages(&[page]));
+
+            Ok(AppState::Quitting(QuitReason::ScrapeSubcommandCompleted))
+        }
+        Some(Commands::Suggest {
+            history,
+            min_length,
+            min_count,
+            limit,
+        }) => {
+            let config = read_from_config(config_path.clone(), Entries::Written)?;
+            // Read before prompting, so changes made to the config meanwhile aren't overwritten
+            let mut document = ConfigDocument::read(&config_path)?;
+
+            let history = match history {
+                Some(path) => path,
+                None => default_history_path()
+                    .context("Found no shell history, specify one with --history")?,
+            };
+            let commands = read_history(&history)?;
+
+            let mut known = HashSet::new();
+            for page in &config.pages {
+                let mut entries = vec![];
+                collect_entries(page, page.name.clone(), &mut entries);
+                known.extend(
+                    entries
+                        .iter()
+                        .map(|(_, entry)| snippet::command(&entry.content)),
+                );
+            }
+
+            let suggestions = suggest(&commands, min_length, min_count, &known);
+            if suggestions.is_empty() {
+                println!(
+                    "No frequently retyped commands found in {}",
+                    history.display()
+                );
+                return Ok(AppState::Quitting(QuitReason::SuggestSubcommandCompleted));
+            }
+
+            let mut page = Page {
+                name: SUGGESTIONS_PAGE_NAME.to_string(),
+                ..Page::default()
+            };
+            for suggestion in suggestions.into_iter().take(limit) {
+                println!("\n{}", suggestion.command);
+                let answer = prompt(&format!(
+                    "Typed {} times, add as entry? [y]es, [n]o, [q]uit: ",
+                    suggestion.count
+                ))?;
+                match answer.to_lowercase().as_str() {
+                    "y" | "yes" => {
+                        let description = prompt("Description: ")?;
+                        let name = if description.is_empty() {
+                            entry_name(&suggestion.command)
+                        } else {
+                            entry_name(&description)
+                        };
+                        let entry = Entry {
+                            name,
+                            content: vec![suggestion.command],
+                            description,
+                            ..Entry::default()
+                        };
+                        add_entry(&mut page, entry);
+                    }
+                    "q" | "quit" => break,
+                    _ => continue,
+                }
+            }
+
+            if page.entries.is_empty() {
+                println!("\nNo suggestions were added");
+                return Ok(AppState::Quitting(QuitReason::SuggestSubcommandCompleted));
+            }
+
+            let added = page.entries.len();
+            let mut pages = document.pages()?;
+            merge_pages(&mut pages, vec![page]);
+            document.update_pages(&pages)?;
+            document.write()?;
+            println!(
+                "\nAdded {} entries to page {} in {}",
+                added,
+                SUGGESTIONS_PAGE_NAME,
+                config_path.display()
+            );
+
+            Ok(AppState::Quitting(QuitReason::SuggestSubcommandCompleted))
+        }
+        Some(Commands::Show { page, all }) => {
+            let (config, _) = read_with_plugins(&config_path, Entries::Decrypted)?;
+
+            let keyboard = config.keyboard.unwrap_or_else(Keyboard::current);
+            let colors = output_colors(
+                (config.primary_color, config.highlight_color),
+                overrides,
+                no_color,
+            );
+            let shown = match page {
+                Some(query) if !all => {
+                    let path = match_page(&config.pages, &query)?;
+                    let page = page_at(&config.pages, &path)
+                        .ok_or(anyhow!("Can not get page {} from config", path))?;
+                    let mut parents: Vec<&str> = path.split(PAGE_PATH_SEPARATOR).collect();
+                    parents.pop();
+                    show::show(
+                        std::slice::from_ref(page),
+                        &parents,
+                        config.show_names,
+                        keyboard,
+                        colors,
+                    )
+                }
+                _ => show::show(&config.pages, &[], config.show_names, keyboard, colors),
+            };
+            pager::print(&shown, no_pager)?;
+
+            Ok(AppState::Quitting(QuitReason::ShowSubcommandCompleted))
+        }
+        Some(Commands::List { page, json }) => {
+            let (config, _) = read_with_plugins(&config_path, Entries::Generated)?;
+
+            let listed = match page {
+                Some(query) => {
+                    let path = match_page(&config.pages, &query)?;
+                    let page = page_at(&config.pages, &path)
+                        .ok_or(anyhow!("Can not get page {} from config", path))?;
+                    let keyboard = config.keyboard.unwrap_or_else(Keyboard::current);
+                    list::list_entries(page, keyboard, json)?
+                }
+                None => list::list_pages(&config.pages, json)?,
+            };
+            print!("{}", listed);
+
+            Ok(AppState::Quitting(QuitReason::ListSubcommandCompleted))
+        }
+        Some(Commands::Stats { usage }) => {
+            let (config, _) = read_with_plugins(&config_path, Entries::Written)?;
+            let user_state = read_state(&config_state_path(&config_path)?);
+
+            if usage {
+                let colors = output_colors(
+                    (config.primary_color, config.highlight_color),
+                    overrides,
+                    no_color,
+                );
+                pager::print(&stats::usage(&user_state, colors), no_pager)?;
+            } else {
+                println!("{}", stats::summary(&config.pages, &user_state));
+            }
+
+            Ok(AppState::Quitting(QuitReason::StatsSubcommandCompleted))
+        }
+        Some(Commands::Add {
+            page,
+            name,
+            keys,
+            description,
+            dry_run,
+        }) => {
+            let mut document = ConfigDocument::read(&config_path)?;
+            let (path, created) = document.add_entry(&page, &name, &keys, &description)?;
+            if dry_run {
+                let colors = output_colors(document.colors(), overrides, no_color);
+                print!("{}", document.diff(colors));
+                return Ok(AppState::Quitting(QuitReason::AddSubcommandCompleted));
+            }
+            document.write()?;
+            if created {
+                println!("Added {} to the new page {}", name, path);
+            } else {
+                println!("Added {} to {}", name, path);
+            }
+
+            Ok(AppState::Quitting(QuitReason::AddSubcommandCompleted))
+        }
+        Some(Commands::Rm {
+            page,
+            entry,
+            yes,
+            dry_run,
+        }) => {
+            let mut document = ConfigDocument::read(&config_path)?;
+            let pages = document.pages()?;
+            let path = match_page(&pages, &page)?;
+            let page =
+                page_at(&pages, &path).ok_or(anyhow!("Can not get page {} from config", path))?;
+
+            let removed = match entry {
+                Some(entry) => {
+                    let entry = match_entry(page, &entry)?;
+                    document.remove_entry(&path, &entry.name)?;
+                    format!("{} from {}", entry.name, path)
+                }
+                None => {
+                    document.remove_page(&path)?;
+                    format!("page {}", path)
+                }
+            };
+
+            let colors = output_colors(document.colors(), overrides, no_color);
+            print!("{}", document.diff(colors));
+            if dry_run {
+                return Ok(AppState::Quitting(QuitReason::RemoveSubcommandCompleted));
+            }
+
+            if !yes {
+                let answer = prompt(&format!("\nRemove {}? [y]es, [n]o: ", removed))?;
+                if !matches!(answer.to_lowercase().as_str(), "y" | "yes") {
+                    println!("Left the config as it is");
+                    return Ok(AppState::Quitting(QuitReason::RemoveSubcommandCompleted));
+                }
+            }
+
+            document.write()?;
+            println!("Removed {}", removed);
+
+            Ok(AppState::Quitting(QuitReason::RemoveSubcommandCompleted))
+        }
+        Some(Commands::Rename {
+            page,
+            name,
+            new_name,
+            dry_run,
+        }) => {
+            let mut document = ConfigDocument::read(&config_path)?;
+            let pages = document.pages()?;
+            let path = match_page(&pages, &page)?;
+
+            let state_path = config_state_path(&config_path)?;
+            let mut user_state = read_state(&state_path);
+            let renamed = match new_name {
+                Some(new_name) => {
+                    let page = page_at(&pages, &path)
+                        .ok_or(anyhow!("Can not get page {} from config", path))?;
+                    let entry = match_entry(page, &name)?;
+                    document.rename_entry(&path, &entry.name, &new_name)?;
+                    user_state.rename_entry(&path, &entry.name, &new_name);
+                    format!("Renamed {} on page {} to {}", entry.name, path, new_name)
+                }
+                None => {
+                    let new_path = document.rename_page(&path, &name)?;
+                    user_state.rename_page(&path, &new_path);
+                    format!("Renamed page {} to {}", path, new_path)
+                }
+            };
+
+            if dry_run {
+                let colors = output_colors(document.colors(), overrides, no_color);
+                print!("{}", document.diff(colors));
+                return Ok(AppState::Quitting(QuitReason::RenameSubcommandCompleted));
+            }
+
+            document.write()?;
+            write_state(&state_path, &user_state)?;
+            println!("{}", renamed);
+
+            Ok(AppState::Quitting(QuitReason::RenameSubcommandCompleted))
+        }
+        Some(Commands::Mv {
+            page,
+            entry,
+            target,
+            dry_run,
+        }) => {
+            let mut document = ConfigDocument::read(&config_path)?;
+            let pages = document.pages()?;
+            let path = match_page(&pages, &page)?;
+            let page =
+                page_at(&pages, &path).ok_or(anyhow!("Can not get page {} from config", path))?;
+            let entry = match_entry(page, &entry)?;
+            let target = match_page(&pages, &target)?;
+            if target == path {
+                bail!("Entry {} is already on page {}", entry.name, path);
+            }
+
+            document.move_entry(&path, &entry.name, &target)?;
+            if dry_run {
+                let colors = output_colors(document.colors(), overrides, no_color);
+                print!("{}", document.diff(colors));
+                return Ok(AppState::Quitting(QuitReason::MoveSubcommandCompleted));
+            }
+            document.write()?;
+
+            let state_path = config_state_path(&config_path)?;
+            let mut user_state = read_state(&state_path);
+            user_state.move_entry(&path, &entry.name, &target);
+            write_state(&state_path, &user_state)?;
+            println!("Moved {} from {} to {}", entry.name, path, target);
+
+            Ok(AppState::Quitting(QuitReason::MoveSubcommandCompleted))
+        }
+        Some(Commands::Sed {
+            pattern,
+            replacement,
+            page,
+            field,
+            yes,
+            dry_run,
+        }) => {
+            let pattern = Regex::new(&pattern).context(format!("Invalid pattern {}", pattern))?;
+            let mut document = ConfigDocument::read(&config_path)?;
+            let path = match page {
+                Some(page) => Some(match_page(&document.pages()?, &page)?),
+                None => None,
+            };
+            let replacements = document.replace(&pattern, &replacement, field, path.as_deref())?;
+            if replacements.is_empty() {
+                println!("Nothing matches {}", pattern);
+                return Ok(AppState::Quitting(QuitReason::SedSubcommandCompleted));
+            }
+
+            let colors = output_colors(document.colors(), overrides, no_color);
+            print!("{}", diff(&replacements, colors));
+            if dry_run {
+                return Ok(AppState::Quitting(QuitReason::SedSubcommandCompleted));
+            }
+
+            if !yes {
+                let answer = prompt(&format!(
+                    "\nWrite {}? [y]es, [n]o: ",
+                    stats::count(replacements.len() as u64, "change")
+                ))?;
+                if !matches!(answer.to_lowercase().as_str(), "y" | "yes") {
+                    println!("Left the config as it is");
+                    return Ok(AppState::Quitting(QuitReason::SedSubcommandCompleted));
+                }
+            }
+
+            document.write()?;
+            println!(
+                "Wrote {}",
+                stats::count(replacements.len() as u64, "change")
+            );
+
+            Ok(AppState::Quitting(QuitReason::SedSubcommandCompleted))
+        }
+        Some(Commands::Restore { list }) => {
+            if list {
+                let backups = backup::list_backups(&config_path)?;
+                if backups.is_empty() {
+                    println!("No backups of {}", config_path.display());
+                }
+                for backup in backups {
+                    println!("{}", backup.display());
+                }
+            } else {
+                let backup = backup::restore(&config_path)?;
+                println!(
+                    "Restored {} from {}",
+                    config_path.display(),
+                    backup.display()
+                );
+            }
+
+            Ok(AppState::Quitting(QuitReason::RestoreSubcommandCompleted))
+        }
+        Some(Commands::Copy { page, entry, print }) => {
+            let (config, _) = read_with_plugins(&config_path, Entries::Decrypted)?;
+
+            let path = match_page(&config.pages, &page)?;
+            let page = page_at(&config.pages, &path)
+                .ok_or(anyhow!("Can not get page {} from config", path))?;
+            let entry = match_entry(page, &entry)?;
+
+            let content = snippet::command(&entry.content);
+            if print {
+                println!("{}", content);
+            } else {
+                Clipboard::open().copy_before_exit(&content)?;
+                println!("Copied {} from {} to the clipboard", entry.name, path);
+            }
+
+            let state_path = config_state_path(&config_path)?;
+            let mut user_state = read_state(&state_path);
+            user_state.record_usage(&path, Some(&entry.name), true);
+            user_state.remember_recent(EntryKey {
+                page: path.clone(),
+                entry: entry.name.clone(),
+            });
+            write_state(&state_path, &user_state)?;
+
+            Ok(AppState::Quitting(QuitReason::CopySubcommandCompleted))
+        }
+        Some(Commands::Exec {
+            page,
+            entry,
+            values,
+            dry_run,
+        }) => {
+            let (config, _) = read_with_plugins(&config_path, Entries::Decrypted)?;
+
+            let path = match_page(&config.pages, &page)?;
+            let page = page_at(&config.pages, &path)
+                .ok_or(anyhow!("Can not get page {} from config", path))?;
+            let entry = match_entry(page, &entry)?;
+            // Entries run their `exec` command when activated, like in the interface, otherwise their content
+            let content = match &entry.exec {
+                Some(command) => slice::from_ref(command),
+                None if entry.content.iter().any(|part| is_key_notation(part)) => bail!(
+                    "Entry {} of page {} is a shortcut, not a command",
+                    entry.name,
+                    path
+                ),
+                None => entry.content.as_slice(),
+            };
+
+            let placeholders = snippet::placeholders(content);
+            if values.len() != placeholders.len() {
+                bail!(
+                    "Entry {} expects one value for each of its placeholders [{}], but got {}",
+                    entry.name,
+                    placeholders.join(", "),
+                    values.len()
+                );
+            }
+            let values: Vec<(String, String)> = placeholders.into_iter().zip(values).collect();
+            let command = snippet::fill(&snippet::command(content), &values);
+
+            if dry_run {
+                println!("{}", command);
+            } else {
+                run_command(&command)?;
+            }
+
+            Ok(AppState::Quitting(QuitReason::ExecSubcommandCompleted))
+        }
+        None => Ok(AppState::Running),
     }
-    Ok(AppState::Running)
+}
+
+/// Runs the given command with `--help` and returns what it printed.
+///
+/// Some programs print their help to stderr, which is used if nothing was printed to stdout.
+fn read_help(command: &str) -> Result<String> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().context("No command to scrape")?;
+
+    info!("Running {} --help", command);
+    let output = process::Command::new(program)
+        .args(parts)
+        .arg("--help")
+        .output()
+        .context(format!("Failed to run {} --help", command))?;
+
+    let help = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+    if help.is_empty() {
+        bail!("{} --help printed nothing", command);
+    }
+
+    Ok(String::from_utf8_lossy(&help).into_owned())
+}
+
+/// Asks where to write a new config, which templates to include in it and which theme to use.
+///
+/// Each question is asked again until it is answered with something valid, an empty answer takes the suggestion.
+fn init_wizard(
+    config_path: PathBuf,
+    no_color: bool,
+) -> Result<(PathBuf, Option<&'static Theme>, Vec<&'static Template>)> {
+    println!("Setting up a new config, press Enter to take the suggestion in brackets");
+
+    let path = loop {
+        let answer = prompt(&format!(
+            "\nWrite the config to [{}]: ",
+            config_path.display()
+        ))?;
+        let path = if answer.is_empty() {
+            config_path.clone()
+        } else {
+            PathBuf::from(answer)
+        };
+        if !path.exists() {
+            break path;
+        }
+        println!("{} already exists, choose another path", path.display());
+    };
+
+    println!("\nTemplates with pages of common tools:");
+    for (i, template) in TEMPLATES.iter().enumerate() {
+        println!("  {}. {:8} {}", i + 1, template.name, template.description);
+    }
+    let templates = loop {
+        let answer = prompt(
+            "Include which templates? Numbers or names separated by spaces, none for an example page [all]: ",
+        )?;
+        match answer.to_lowercase().as_str() {
+            "" | "all" => break TEMPLATES.iter().collect(),
+            "none" => break vec![],
+            answer => {
+                let chosen: Vec<Option<&str>> = answer
+                    .split([' ', ','])
+                    .filter(|choice| !choice.is_empty())
+                    .map(|choice| match choice.parse::<usize>() {
+                        Result::Ok(number) => number.checked_sub(1).and_then(|i| TEMPLATES.get(i)),
+                        Err(_) => find_template(choice),
+                    })
+                    .map(|template| template.map(|template| template.name))
+                    .collect();
+                if chosen.iter().all(Option::is_some) {
+                    // Templates are included in the order they are offered, each once
+                    break TEMPLATES
+                        .iter()
+                        .filter(|template| chosen.contains(&Some(template.name)))
+                        .collect();
+                }
+                println!("Choose templates by their number or name, e.g. 1 3 or git tmux");
+            }
+        }
+    };
+
+    // Colors are left out when the output is redirected, as in other subcommands
+    let colors = !no_color && io::stdout().is_terminal();
+    println!("\nThemes:");
+    for (i, theme) in THEMES.iter().enumerate() {
+        let sample = if colors {
+            format!(
+                "{} {}",
+                "Text".with(Color::Indexed(theme.primary_color).into()),
+                "Highlight"
+                    .with(Color::Indexed(theme.highlight_color).into())
+                    .bold()
+            )
+        } else {
+            String::new()
+        };
+        println!("  {}. {:12} {}", i + 1, theme.name, sample);
+    }
+    let theme = loop {
+        let answer = prompt(&format!("Use which theme? [{}]: ", THEMES[0].name))?;
+        let theme = match answer.parse::<usize>() {
+            _ if answer.is_empty() => THEMES.first(),
+            Result::Ok(number) => number.checked_sub(1).and_then(|i| THEMES.get(i)),
+            Err(_) => find_theme(&answer.to_lowercase()),
+        };
+        if theme.is_some() {
+            break theme;
+        }
+        println!("Choose a theme by its number or name, e.g. 2 or light");
+    };
+    println!();
+
+    Ok((path, theme, templates))
+}
+
+/// Returns the colors to print with, the given ones with the overrides applied.
+///
+/// Colors are left out when the output is redirected, e.g. into a file.
+fn output_colors(colors: Colors, overrides: ColorOverrides, no_color: bool) -> Option<Colors> {
+    let (primary_color, highlight_color) = colors;
+    (!no_color && io::stdout().is_terminal())
+        .then_some(overrides.apply(primary_color, highlight_color))
+}
+
+/// Asks a question on the terminal and returns the trimmed answer.
+///
+/// Returns an empty answer once the input is closed.
+fn prompt(question: &str) -> Result<String> {
+    print!("{}", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
 }
@@ -0,0 +1,21 @@
+//! Mouse module for Recall.
+//!
+//! While recall is running, the terminal reports the mouse wheel, so it scrolls through the entries and pages.
+//! Terminals don't select text while they report the mouse, which is why `mouse = false` in the config turns this off.
+
+use std::io;
+
+use anyhow::{Context, Result};
+use ratatui::crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+};
+
+/// Makes the terminal report the mouse to recall, or stops it from doing so.
+pub fn capture(enabled: bool) -> Result<()> {
+    if enabled {
+        execute!(io::stdout(), EnableMouseCapture).context("Failed to enable the mouse")
+    } else {
+        execute!(io::stdout(), DisableMouseCapture).context("Failed to disable the mouse")
+    }
+}
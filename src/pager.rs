@@ -0,0 +1,81 @@
+//! Pager module for Recall.
+//!
+//! Printed output taller than the terminal is piped through the pager in `$PAGER` like git does,
+//! falling back to `less`. Output is printed directly when stdout isn't a terminal, when it fits
+//! or when `--no-pager` is given.
+
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context, Result};
+use log::{debug, warn};
+use ratatui::crossterm::terminal;
+
+/// Environment variable naming the pager
+const PAGER_VARIABLE: &str = "PAGER";
+
+/// Pager used if `$PAGER` isn't set
+const DEFAULT_PAGER: &str = if cfg!(windows) { "more" } else { "less" };
+
+/// Environment variable with the options of `less`
+const LESS_VARIABLE: &str = "LESS";
+
+/// Options of `less` if `$LESS` isn't set: quit if the output fits, keep ANSI colors and don't clear the screen
+const DEFAULT_LESS_OPTIONS: &str = "FRX";
+
+/// Prints the text, through the pager if stdout is a terminal the text doesn't fit on.
+pub fn print(text: &str, no_pager: bool) -> Result<()> {
+    if no_pager || !io::stdout().is_terminal() || fits_terminal(text) {
+        return write_stdout(text);
+    }
+
+    let pager = env::var(PAGER_VARIABLE).unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    // An empty pager disables paging, as in git
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        return write_stdout(text);
+    };
+
+    debug!("Piping output through pager {}", pager);
+    let mut command = Command::new(program);
+    command.args(parts).stdin(Stdio::piped());
+    if env::var_os(LESS_VARIABLE).is_none() {
+        command.env(LESS_VARIABLE, DEFAULT_LESS_OPTIONS);
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(error) => {
+            warn!("Failed to start pager {}: {}", pager, error);
+            return write_stdout(text);
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager closing early, e.g. when quit before reading everything, is not an error
+        if let Err(error) = stdin.write_all(text.as_bytes()) {
+            debug!("Pager stopped reading: {}", error);
+        }
+    }
+    child
+        .wait()
+        .context(format!("Failed to wait for pager {}", pager))?;
+
+    Ok(())
+}
+
+/// Returns `true` if the text has no more lines than the terminal, or if its size is unknown.
+fn fits_terminal(text: &str) -> bool {
+    terminal::size().map_or(true, |(_, height)| text.lines().count() <= height as usize)
+}
+
+/// Prints the text to stdout as is.
+fn write_stdout(text: &str) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(text.as_bytes())
+        .and_then(|_| stdout.flush())
+        .context("Failed to write to stdout")
+}
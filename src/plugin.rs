@@ -0,0 +1,411 @@
+//! Plugin module for Recall.
+//!
+//! Plugins are [Rhai](https://rhai.rs) scripts in the `plugins` directory next to the config file,
+//! loaded in order of their file names. Each script may define any of these functions:
+//!
+//! - `on_load(pages)` receives the pages of the config and returns them transformed
+//! - `on_select(page, entry)` is called when an entry without a built-in action is activated,
+//!   a returned string is copied to the clipboard
+//! - Any other function can be named by the `generator` of a page, it returns the entries of that page
+//!
+//! Pages and entries are passed as object maps with the same fields as in the config.
+//! Scripts can run shell commands with `run(command)`, which returns what the command printed.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info, warn};
+use rhai::{
+    serde::{from_dynamic, to_dynamic},
+    Dynamic, Engine, EvalAltResult, Scope, AST,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{Entry, Page, PAGE_PATH_SEPARATOR},
+    import::{add_entry, entry_name},
+};
+
+/// Name of the directory next to the config file plugins are loaded from
+const PLUGIN_DIRECTORY: &str = "plugins";
+
+/// File extension of plugin scripts
+const PLUGIN_EXTENSION: &str = "rhai";
+
+/// Name of the function transforming the pages of the config
+const ON_LOAD_HOOK: &str = "on_load";
+
+/// Name of the function acting on activated entries
+const ON_SELECT_HOOK: &str = "on_select";
+
+/// Maximum nesting of expressions at the top level of a script
+const MAX_EXPRESSION_DEPTH: usize = 64;
+
+/// Maximum nesting of expressions within functions of a script
+const MAX_FUNCTION_EXPRESSION_DEPTH: usize = 32;
+
+/// The scripting engine along with all loaded plugins.
+pub struct Plugins {
+    /// Engine running the scripts
+    engine: Engine,
+
+    /// Compiled scripts along with their file names, in the order they are called in
+    scripts: Vec<(String, AST)>,
+}
+
+/// A page as seen by plugins.
+#[derive(Debug, Serialize, Deserialize)]
+struct PluginPage {
+    /// Name of the page
+    name: String,
+
+    /// Entries of the page
+    #[serde(default)]
+    entries: Vec<PluginEntry>,
+
+    /// Nested pages
+    #[serde(default)]
+    subpages: Vec<PluginPage>,
+}
+
+/// An entry as seen by plugins.
+#[derive(Debug, Serialize, Deserialize)]
+struct PluginEntry {
+    /// Name of the entry, derived from the description or content if left empty
+    #[serde(default)]
+    name: String,
+
+    /// Keys or command of the entry
+    content: PluginContent,
+
+    /// Description of the entry
+    #[serde(default)]
+    description: String,
+
+    /// Whether the entry is hidden
+    #[serde(default)]
+    hidden: bool,
+
+    /// Whether the content of the entry is masked
+    #[serde(default)]
+    secret: bool,
+
+    /// Path of the page the entry links to
+    goto: Option<String>,
+
+    /// Shell command run when the entry is activated
+    exec: Option<String>,
+
+    /// Web page about the entry
+    url: Option<String>,
+
+    /// Section the entry is listed under
+    section: Option<String>,
+
+    /// Path of the image illustrating the entry
+    image: Option<String>,
+
+    /// Note of the entry, written as comments above it in the config
+    note: Option<String>,
+}
+
+/// Content of an entry, which plugins may also return as a single string.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum PluginContent {
+    /// A single key or command
+    Single(String),
+
+    /// Any number of keys or commands
+    Multiple(Vec<String>),
+}
+
+/// Returns the directory plugins are loaded from for the config at the given path.
+pub fn plugin_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(PLUGIN_DIRECTORY)
+}
+
+impl Plugins {
+    /// Loads and compiles all scripts within the given directory.
+    ///
+    /// A missing directory means there are no plugins, a script that doesn't compile is an error.
+    pub fn load(dir: &Path) -> Result<Plugins> {
+        let mut engine = Engine::new();
+        // Printing would garble the terminal user interface, so scripts print to the log instead
+        engine.on_print(|text| info!("{}", text));
+        engine.on_debug(|text, _, position| debug!("{} at {}", text, position));
+        engine.register_fn("run", run_command);
+        // Debug builds of Rhai use much lower limits, which ordinary scripts already exceed
+        engine.set_max_expr_depths(MAX_EXPRESSION_DEPTH, MAX_FUNCTION_EXPRESSION_DEPTH);
+
+        let mut plugins = Plugins {
+            engine,
+            scripts: vec![],
+        };
+        if !dir.is_dir() {
+            debug!("No plugin directory at {}", dir.display());
+            return Ok(plugins);
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .context(format!("Failed to read plugin directory {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == PLUGIN_EXTENSION))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            info!("Loading plugin {}", path.display());
+            let ast = plugins
+                .engine
+                .compile_file(path.clone())
+                .context(format!("Failed to compile plugin {}", path.display()))?;
+            let name = path
+                .file_name()
+                .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+            plugins.scripts.push((name, ast));
+        }
+
+        Ok(plugins)
+    }
+
+    /// Returns `true` if any plugin transforms the config when it is loaded
+    pub fn transforms_config(&self) -> bool {
+        self.scripts
+            .iter()
+            .any(|(_, ast)| defines_function(ast, ON_LOAD_HOOK))
+    }
+
+    /// Passes the pages through the `on_load` hook of each plugin.
+    ///
+    /// Settings like the group or sort order are kept for pages whose name is unchanged.
+    pub fn on_load(&self, pages: &mut Vec<Page>) -> Result<()> {
+        for (name, ast) in &self.scripts {
+            if !defines_function(ast, ON_LOAD_HOOK) {
+                continue;
+            }
+
+            debug!("Calling {} of plugin {}", ON_LOAD_HOOK, name);
+            let argument = to_dynamic(pages.iter().map(plugin_page).collect::<Vec<_>>())?;
+            let result: Dynamic = self
+                .engine
+                .call_fn(&mut Scope::new(), ast, ON_LOAD_HOOK, (argument,))
+                .context(format!("Plugin {} failed to transform the config", name))?;
+            let transformed: Vec<PluginPage> = from_dynamic(&result).context(format!(
+                "Plugin {} returned invalid pages from {}",
+                name, ON_LOAD_HOOK
+            ))?;
+
+            *pages = transformed
+                .into_iter()
+                .map(|transformed| {
+                    let original = pages.iter().find(|page| page.name == transformed.name);
+                    build_page(transformed, original)
+                })
+                .collect();
+        }
+
+        Ok(())
+    }
+
+    /// Fills the pages and subpages that have a generator with the entries returned by its plugin function.
+    ///
+    /// A failing generator leaves its page empty instead of keeping recall from starting.
+    pub fn generate_entries(&self, pages: &mut [Page]) {
+        for page in pages {
+            let path = page.name.clone();
+            self.generate_page_entries(page, &path);
+        }
+    }
+
+    /// Calls the `on_select` hook of each plugin with the activated entry until one returns a string,
+    /// which is then returned to be copied to the clipboard.
+    pub fn on_select(&self, page: &str, entry: &Entry) -> Option<String> {
+        for (name, ast) in &self.scripts {
+            if !defines_function(ast, ON_SELECT_HOOK) {
+                continue;
+            }
+
+            debug!("Calling {} of plugin {}", ON_SELECT_HOOK, name);
+            let result = to_dynamic(plugin_entry(entry)).and_then(|argument| {
+                self.engine.call_fn::<Dynamic>(
+                    &mut Scope::new(),
+                    ast,
+                    ON_SELECT_HOOK,
+                    (page.to_string(), argument),
+                )
+            });
+            match result {
+                Result::Ok(result) if result.is_string() => return result.into_string().ok(),
+                Result::Ok(_) => continue,
+                Err(error) => warn!("Plugin {} failed to handle selection: {}", name, error),
+            }
+        }
+
+        None
+    }
+
+    /// Generates the entries of a single page and its subpages, the path of the page is used for messages.
+    fn generate_page_entries(&self, page: &mut Page, path: &str) {
+        for subpage in &mut page.subpages {
+            let subpage_path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, subpage.name);
+            self.generate_page_entries(subpage, &subpage_path);
+        }
+
+        let Some(generator) = &page.generator else {
+            return;
+        };
+
+        match self.call_generator(generator) {
+            Result::Ok(entries) => {
+                let mut generated = Page::default();
+                for entry in entries {
+                    add_entry(&mut generated, build_entry(entry));
+                }
+                page.entries = generated.entries;
+                page.sort.sort(&mut page.entries);
+            }
+            Err(error) => warn!("Failed to generate entries of page {}: {:#}", path, error),
+        }
+    }
+
+    /// Calls the generator function of the first plugin defining it and returns the entries it generated.
+    fn call_generator(&self, generator: &str) -> Result<Vec<PluginEntry>> {
+        let Some((name, ast)) = self
+            .scripts
+            .iter()
+            .find(|(_, ast)| defines_function(ast, generator))
+        else {
+            bail!("No plugin defines the generator {}", generator);
+        };
+
+        debug!("Calling {} of plugin {}", generator, name);
+        let result: Dynamic = self
+            .engine
+            .call_fn(&mut Scope::new(), ast, generator, ())
+            .context(format!("Plugin {} failed to run {}", name, generator))?;
+        from_dynamic(&result).context(format!(
+            "Plugin {} returned invalid entries from {}",
+            name, generator
+        ))
+    }
+}
+
+/// Returns `true` if the script defines a function with the given name
+fn defines_function(ast: &AST, name: &str) -> bool {
+    ast.iter_functions().any(|function| function.name == name)
+}
+
+/// Runs a shell command for a script and returns what it printed.
+fn run_command(command: &str) -> Result<String, Box<EvalAltResult>> {
+    debug!("Running command for plugin: {}", command);
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", command]).output()
+    } else {
+        Command::new("sh").args(["-c", command]).output()
+    }
+    .map_err(|error| format!("Failed to run {}: {}", command, error))?;
+
+    if !output.status.success() {
+        return Err(format!("Command {} failed with {}", command, output.status).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Converts a page and its subpages into the form passed to plugins.
+fn plugin_page(page: &Page) -> PluginPage {
+    PluginPage {
+        name: page.name.clone(),
+        entries: page.entries.iter().map(plugin_entry).collect(),
+        subpages: page.subpages.iter().map(plugin_page).collect(),
+    }
+}
+
+/// Converts an entry into the form passed to plugins.
+fn plugin_entry(entry: &Entry) -> PluginEntry {
+    PluginEntry {
+        name: entry.name.clone(),
+        content: PluginContent::Multiple(entry.content.clone()),
+        description: entry.description.clone(),
+        hidden: entry.hidden,
+        secret: entry.secret,
+        goto: entry.goto.clone(),
+        exec: entry.exec.clone(),
+        url: entry.url.clone(),
+        section: entry.section.clone(),
+        image: entry.image.clone(),
+        note: entry.note.clone(),
+    }
+}
+
+/// Converts a page returned by a plugin back into a `Page`, taking the settings from the original page if there is one.
+fn build_page(page: PluginPage, original: Option<&Page>) -> Page {
+    let mut built = Page {
+        name: page.name,
+        ..original.cloned().unwrap_or_default()
+    };
+
+    built.entries.clear();
+    for entry in page.entries {
+        let mut entry = build_entry(entry);
+        // Content that was encrypted in the config stays encrypted when it is written back, unless the plugin changed it
+        entry.encrypted = original
+            .and_then(|page| {
+                page.entries
+                    .iter()
+                    .find(|e| e.name == entry.name && e.content == entry.content)
+            })
+            .and_then(|original| original.encrypted.clone());
+        add_entry(&mut built, entry);
+    }
+
+    built.subpages = page
+        .subpages
+        .into_iter()
+        .map(|subpage| {
+            let original =
+                original.and_then(|page| page.subpages.iter().find(|s| s.name == subpage.name));
+            build_page(subpage, original)
+        })
+        .collect();
+
+    built
+}
+
+/// Converts an entry returned by a plugin into an `Entry`, naming it after its description or content if unnamed.
+fn build_entry(entry: PluginEntry) -> Entry {
+    let content = match entry.content {
+        PluginContent::Single(content) => vec![content],
+        PluginContent::Multiple(content) => content,
+    };
+
+    let name = if !entry.name.is_empty() {
+        entry.name
+    } else if !entry.description.is_empty() {
+        entry_name(&entry.description)
+    } else {
+        entry_name(&content.join(" "))
+    };
+
+    Entry {
+        name,
+        content,
+        description: entry.description,
+        hidden: entry.hidden,
+        secret: entry.secret,
+        encrypted: None,
+        goto: entry.goto,
+        exec: entry.exec,
+        url: entry.url,
+        section: entry.section,
+        image: entry.image,
+        note: entry.note,
+    }
+}
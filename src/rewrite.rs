@@ -0,0 +1,942 @@
+//! Rewrite module for Recall.
+//!
+//! Edits made in the interface and subcommands reorganizing the config change the TOML document in place,
+//! so comments, formatting and the order of everything they don't touch stay as they were written.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    slice,
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
+use log::{debug, info};
+use ratatui::{crossterm::style::Stylize, style::Color};
+use regex::Regex;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Key, Table, TableLike, Value};
+
+use crate::{
+    app::{
+        rename_path, Entry, Page, SortOrder, DEFAULT_PRIMARY_COLOR, DEFAULT_SECONDARY_COLOR,
+        PAGE_PATH_SEPARATOR,
+    },
+    backup::back_up,
+    config::{
+        parse_pages, serialize_pages, write_locked, ENCRYPTED_CONTENT_KEY, RECALL_TABLE_NAME,
+    },
+    show::{Colors, PATH_SEPARATOR},
+    theme::{find_theme, Theme},
+};
+
+/// Key of the content of entries, which tells entries apart from subpages
+const CONTENT_KEY: &str = "content";
+
+/// Key of the page an entry links to
+const GOTO_KEY: &str = "goto";
+
+/// Key of the description of entries
+const DESCRIPTION_KEY: &str = "description";
+
+/// Key of the theme in the settings
+const THEME_KEY: &str = "theme";
+
+/// Key of the primary color in the settings
+const PRIMARY_COLOR_KEY: &str = "primary_color";
+
+/// Key of the highlight color in the settings
+const HIGHLIGHT_COLOR_KEY: &str = "highlight_color";
+
+/// Number of unchanged lines shown around changed ones in a diff of the config
+const CONTEXT_LINES: usize = 2;
+
+/// Fields of entries that replacements can be applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EntryField {
+    /// Each of the keys, commands or lines of the content
+    Content,
+
+    /// The description
+    Description,
+}
+
+/// A value of an entry that a replacement changed.
+#[derive(Debug)]
+pub struct Replacement {
+    /// Path of the page of the entry
+    pub page: String,
+
+    /// Name of the entry
+    pub entry: String,
+
+    /// The value before the replacement
+    pub old: String,
+
+    /// The value after the replacement
+    pub new: String,
+}
+
+/// The config file as a TOML document, keeping comments and formatting.
+pub struct ConfigDocument {
+    /// Path the config was read from and is written to
+    path: PathBuf,
+
+    /// The config as it was read, to notice changes made by other programs before writing
+    original: String,
+
+    /// The parsed config
+    document: DocumentMut,
+}
+
+impl ConfigDocument {
+    /// Reads the config at the given path.
+    pub fn read(path: &Path) -> Result<ConfigDocument> {
+        let content = fs::read_to_string(path)
+            .context(format!("Failed to read config from {}", path.display()))?;
+        let document = content.parse().context("Failed to read toml")?;
+
+        Ok(ConfigDocument {
+            path: path.to_path_buf(),
+            original: content,
+            document,
+        })
+    }
+
+    /// Writes the config back to where it was read from, after checking that it is still a valid config
+    /// that no other program changed in the meantime, and backing it up.
+    pub fn write(&self) -> Result<()> {
+        self.write_checked(true)
+    }
+
+    /// Writes the config back like [`ConfigDocument::write`] without backing it up, e.g. as it was already backed up
+    /// before a session of edits.
+    pub fn write_without_backup(&self) -> Result<()> {
+        self.write_checked(false)
+    }
+
+    /// Writes the config back to where it was read from if it is still valid and unchanged by other programs.
+    fn write_checked(&self, backup: bool) -> Result<()> {
+        let content = self.document.to_string();
+        parse_pages(&content).context("Changed config would be invalid, leaving it as it is")?;
+        if fs::read_to_string(&self.path).ok().as_ref() != Some(&self.original) {
+            bail!(
+                "Config {} was changed by another program since it was read, leaving it as it is",
+                self.path.display()
+            );
+        }
+        if backup {
+            back_up(&self.path)?;
+        }
+
+        info!("Writing config to {}", self.path.display());
+        write_locked(&self.path, &content)
+    }
+
+    /// Returns the changes made to the config since it was read as a diff, see [`diff_lines`].
+    pub fn diff(&self, colors: Option<Colors>) -> String {
+        diff_lines(&self.original, &self.document.to_string(), colors)
+    }
+
+    /// Renames the page at the given path, returning its new path.
+    ///
+    /// Entries linking to the page or its subpages are changed to link to the new path.
+    pub fn rename_page(&mut self, path: &str, name: &str) -> Result<String> {
+        if name.trim().is_empty() || name.contains(PAGE_PATH_SEPARATOR) {
+            bail!(
+                "Page name {} must not be empty or contain {}",
+                name,
+                PAGE_PATH_SEPARATOR
+            );
+        }
+
+        let (parent, old_name) = match path.rsplit_once(PAGE_PATH_SEPARATOR) {
+            Some((parent, old_name)) => (Some(parent), old_name),
+            None => (None, path),
+        };
+        if parent.is_none() && name == RECALL_TABLE_NAME {
+            bail!("Page name {} is reserved for settings", name);
+        }
+
+        let table = match parent {
+            Some(parent) => self.page_table(parent)?,
+            None => self.document.as_table_mut(),
+        };
+        rename_key(table, old_name, name)?;
+
+        let new_path = match parent {
+            Some(parent) => format!("{}{}{}", parent, PAGE_PATH_SEPARATOR, name),
+            None => name.to_string(),
+        };
+        retarget_links(self.document.as_table_mut(), path, &new_path);
+        Ok(new_path)
+    }
+
+    /// Renames the entry with the given name on the page at the given path.
+    pub fn rename_entry(&mut self, path: &str, entry: &str, name: &str) -> Result<()> {
+        if name.trim().is_empty() {
+            bail!("Entry name must not be empty");
+        }
+
+        let table = self.page_table(path)?;
+        if !is_entry(table.get(entry)) {
+            bail!(
+                "Entry {} is not written on page {} in the config, it may be generated",
+                entry,
+                path
+            );
+        }
+        rename_key(table, entry, name)
+    }
+
+    /// Adds an entry to the end of the page at the given path, returning the path as written in the config
+    /// and whether the page or one of its parents was created for it.
+    ///
+    /// Pages are looked up by their exact names first and ignoring case otherwise, missing ones are created as tables.
+    pub fn add_entry(
+        &mut self,
+        path: &str,
+        name: &str,
+        content: &[String],
+        description: &str,
+    ) -> Result<(String, bool)> {
+        if name.trim().is_empty() {
+            bail!("Entry name must not be empty");
+        }
+        if content.is_empty() {
+            bail!("Entry {} needs at least one key or command", name);
+        }
+
+        let mut names = vec![];
+        let mut created = false;
+        let mut table = self.document.as_table_mut();
+        let mut pages = path.split(PAGE_PATH_SEPARATOR).peekable();
+        while let Some(page) = pages.next() {
+            if page.trim().is_empty() {
+                bail!("Page path {} must not contain empty names", path);
+            }
+            if names.is_empty() && page == RECALL_TABLE_NAME {
+                bail!("Page name {} is reserved for settings", page);
+            }
+
+            let existing = table
+                .iter()
+                .map(|(key, _)| key)
+                .find(|key| *key == page)
+                .or_else(|| {
+                    table
+                        .iter()
+                        .map(|(key, _)| key)
+                        .find(|key| key.to_lowercase() == page.to_lowercase())
+                })
+                .map(str::to_string);
+            let page = match existing {
+                Some(existing) => {
+                    if is_entry(table.get(&existing)) {
+                        bail!("{} on page {} is an entry, not a page", existing, path);
+                    }
+                    existing
+                }
+                None => {
+                    debug!("Creating page {} for the entry", page);
+                    // Parents created along with a subpage aren't written as headers of their own
+                    let mut new_table = Table::new();
+                    new_table.set_implicit(pages.peek().is_some());
+                    table.insert(page, Item::Table(new_table));
+                    created = true;
+                    page.to_string()
+                }
+            };
+
+            table = table
+                .get_mut(&page)
+                .and_then(Item::as_table_mut)
+                .ok_or(anyhow!(
+                    "Page {} is not written as a table in the config",
+                    path
+                ))?;
+            names.push(page);
+        }
+
+        let path = names.join(PAGE_PATH_SEPARATOR);
+        if table.contains_key(name) {
+            bail!("Page {} already has an entry named {}", path, name);
+        }
+
+        let mut entry = InlineTable::new();
+        entry.insert(CONTENT_KEY, Value::Array(content.iter().collect::<Array>()));
+        entry.insert(DESCRIPTION_KEY, description.into());
+        table.insert(name, Item::Value(Value::InlineTable(entry)));
+        Ok((path, created))
+    }
+
+    /// Moves the entry with the given name from the page at the given path to the end of the target page.
+    ///
+    /// The comments above the entry move along with it.
+    pub fn move_entry(&mut self, path: &str, entry: &str, target: &str) -> Result<()> {
+        if self.page_table(target)?.contains_key(entry) {
+            bail!("Page {} already has an entry named {}", target, entry);
+        }
+
+        let table = self.page_table(path)?;
+        if !is_entry(table.get(entry)) {
+            bail!(
+                "Entry {} is not written on page {} in the config, it may be generated",
+                entry,
+                path
+            );
+        }
+        let Some((key, item)) = table.remove_entry(entry) else {
+            bail!("Found no {} in the config", entry);
+        };
+
+        self.page_table(target)?.insert_formatted(&key, item);
+        Ok(())
+    }
+
+    /// Removes the page at the given path along with its entries and subpages.
+    pub fn remove_page(&mut self, path: &str) -> Result<()> {
+        let (table, name) = match path.rsplit_once(PAGE_PATH_SEPARATOR) {
+            Some((parent, name)) => (self.page_table(parent)?, name),
+            None => (self.document.as_table_mut(), path),
+        };
+        if name == RECALL_TABLE_NAME && !path.contains(PAGE_PATH_SEPARATOR) {
+            bail!("{} holds the settings, not a page", name);
+        }
+        if is_entry(table.get(name)) || table.remove(name).is_none() {
+            bail!(
+                "Page {} is not written in the config, it may be generated",
+                path
+            );
+        }
+        Ok(())
+    }
+
+    /// Removes the entry with the given name from the page at the given path, along with the comments above it.
+    pub fn remove_entry(&mut self, path: &str, entry: &str) -> Result<()> {
+        let table = self.page_table(path)?;
+        if !is_entry(table.get(entry)) {
+            bail!(
+                "Entry {} is not written on page {} in the config, it may be generated",
+                entry,
+                path
+            );
+        }
+        table.remove(entry);
+        Ok(())
+    }
+
+    /// Replaces all matches of the pattern in the given field of the entries, returning the changed values.
+    ///
+    /// Only entries on the page at the given path and its subpages are changed if a path is given,
+    /// otherwise those of all pages. The replacement can refer to groups of the pattern, e.g. `$1`.
+    pub fn replace(
+        &mut self,
+        pattern: &Regex,
+        replacement: &str,
+        field: EntryField,
+        path: Option<&str>,
+    ) -> Result<Vec<Replacement>> {
+        let mut replacements = vec![];
+        match path {
+            Some(path) => {
+                let table = self.page_table(path)?;
+                replace_in(table, path, pattern, replacement, field, &mut replacements);
+            }
+            None => {
+                for (name, item) in self.document.as_table_mut().iter_mut() {
+                    if name == RECALL_TABLE_NAME {
+                        continue;
+                    }
+                    if let Some(table) = item.as_table_like_mut() {
+                        let path = name.to_string();
+                        replace_in(table, &path, pattern, replacement, field, &mut replacements);
+                    }
+                }
+            }
+        }
+        Ok(replacements)
+    }
+
+    /// Changes the pages written in the config into the given ones, e.g. after they were edited in the interface.
+    ///
+    /// Only pages, entries and settings of pages that differ from those written are changed, and entries are reordered
+    /// only if the order of a manually sorted page differs. Entries generated by a command or plugin aren't written.
+    pub fn update_pages(&mut self, pages: &[Page]) -> Result<()> {
+        let written = self.pages()?;
+        update_subpages(self.document.as_table_mut(), "", &written, pages)
+    }
+
+    /// Sets the theme and colors chosen in the interface, keeping the settings as written if they already match.
+    ///
+    /// Colors matching the theme are left out like when the config is serialized, so they keep following it.
+    pub fn set_colors(
+        &mut self,
+        theme: Option<&str>,
+        primary_color: Color,
+        highlight_color: Color,
+    ) -> Result<()> {
+        let theme = theme.and_then(find_theme);
+        let mut changes: Vec<(&str, Option<Value>)> = vec![];
+        if theme.map(|theme| theme.name) != self.theme().map(|theme| theme.name) {
+            changes.push((THEME_KEY, theme.map(|theme| theme.name.into())));
+        }
+
+        let colors = [
+            (
+                PRIMARY_COLOR_KEY,
+                primary_color,
+                theme.map(|theme| theme.primary_color),
+                DEFAULT_PRIMARY_COLOR,
+            ),
+            (
+                HIGHLIGHT_COLOR_KEY,
+                highlight_color,
+                theme.map(|theme| theme.highlight_color),
+                DEFAULT_SECONDARY_COLOR,
+            ),
+        ];
+        for (key, color, theme_color, default) in colors {
+            // The color in effect once the theme is set, whether written or taken from the theme
+            if self.color(key, theme_color, default) == color {
+                continue;
+            }
+            let value = match color {
+                Color::Indexed(color) if Some(color) != theme_color => {
+                    Some(i64::from(color).into())
+                }
+                _ => None,
+            };
+            changes.push((key, value));
+        }
+
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let settings = self
+            .document
+            .entry(RECALL_TABLE_NAME)
+            .or_insert_with(|| {
+                // Settings are written first, like in a new config
+                let mut settings = Table::new();
+                settings.set_position(0);
+                Item::Table(settings)
+            })
+            .as_table_like_mut()
+            .ok_or(anyhow!(
+                "Settings in {} are not written as a table",
+                RECALL_TABLE_NAME
+            ))?;
+        for (key, value) in changes {
+            debug!("Setting {} to {:?}", key, value);
+            match (value, settings.get_mut(key).and_then(Item::as_value_mut)) {
+                (Some(value), Some(written)) => set_value(written, value),
+                (Some(value), None) => {
+                    settings.insert(key, Item::Value(value));
+                }
+                (None, _) => {
+                    settings.remove(key);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the primary and highlight colors set in the config, taken from its theme or the defaults if not set.
+    pub fn colors(&self) -> (Color, Color) {
+        let theme = self.theme();
+        (
+            self.color(
+                PRIMARY_COLOR_KEY,
+                theme.map(|theme| theme.primary_color),
+                DEFAULT_PRIMARY_COLOR,
+            ),
+            self.color(
+                HIGHLIGHT_COLOR_KEY,
+                theme.map(|theme| theme.highlight_color),
+                DEFAULT_SECONDARY_COLOR,
+            ),
+        )
+    }
+
+    /// Returns the value of a setting written in the config, if any.
+    fn setting(&self, key: &str) -> Option<&Value> {
+        self.document
+            .get(RECALL_TABLE_NAME)?
+            .as_table_like()?
+            .get(key)?
+            .as_value()
+    }
+
+    /// Returns the theme set in the config, if it is set to a known theme.
+    fn theme(&self) -> Option<&'static Theme> {
+        self.setting(THEME_KEY)
+            .and_then(Value::as_str)
+            .and_then(find_theme)
+    }
+
+    /// Returns the color set in the config under the given key, or else the color of the theme or the default.
+    fn color(&self, key: &str, theme_color: Option<u8>, default: Color) -> Color {
+        self.setting(key)
+            .and_then(Value::as_integer)
+            .and_then(|color| u8::try_from(color).ok())
+            .or(theme_color)
+            .map_or(default, Color::Indexed)
+    }
+
+    /// Returns the pages written in the config, without running the commands generating entries or decrypting them.
+    pub fn pages(&self) -> Result<Vec<Page>> {
+        parse_pages(&self.document.to_string())
+    }
+
+    /// Returns the table of the page at the given path.
+    fn page_table(&mut self, path: &str) -> Result<&mut Table> {
+        let mut table = self.document.as_table_mut();
+        for name in path.split(PAGE_PATH_SEPARATOR) {
+            table = table
+                .get_mut(name)
+                .and_then(Item::as_table_mut)
+                .ok_or(anyhow!(
+                    "Page {} is not written as a table in the config",
+                    path
+                ))?;
+        }
+        Ok(table)
+    }
+}
+
+/// Returns the replaced values as a diff, grouped by entry and colored with ANSI escape codes if colors are given.
+pub fn diff(replacements: &[Replacement], colors: Option<Colors>) -> String {
+    let mut str = String::new();
+    let mut previous: Option<(&str, &str)> = None;
+    for replacement in replacements {
+        let entry = (replacement.page.as_str(), replacement.entry.as_str());
+        if previous != Some(entry) {
+            if previous.is_some() {
+                str.push('\n');
+            }
+            let heading = replacement
+                .page
+                .split(PAGE_PATH_SEPARATOR)
+                .chain([replacement.entry.as_str()])
+                .collect::<Vec<_>>()
+                .join(PATH_SEPARATOR);
+            str.push_str(&match colors {
+                Some((_, highlight)) => heading.with(highlight.into()).bold().to_string(),
+                None => heading,
+            });
+            str.push('\n');
+            previous = Some(entry);
+        }
+
+        let (old, new) = (
+            format!("- {}", replacement.old),
+            format!("+ {}", replacement.new),
+        );
+        match colors {
+            Some(_) => str.push_str(&format!(
+                "{}\n{}\n",
+                old.with(Color::Red.into()),
+                new.with(Color::Green.into())
+            )),
+            None => str.push_str(&format!("{}\n{}\n", old, new)),
+        }
+    }
+    str
+}
+
+/// Returns the changed lines between two versions of the config as a diff, colored with ANSI escape codes if colors are given.
+///
+/// Changes are grouped into hunks headed by their line in the old version, each with [`CONTEXT_LINES`] unchanged lines around it.
+pub fn diff_lines(old: &str, new: &str, colors: Option<Colors>) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Length of the longest common subsequence of the lines from each pair of positions on
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    // Each line with its line number in the old version and whether it was removed, added or kept
+    let mut lines: Vec<(usize, char, &str)> = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((i, ' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push((i, '-', old[i]));
+            i += 1;
+        } else {
+            lines.push((i, '+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&index| lines[index].1 != ' ')
+        .collect();
+    let mut str = String::new();
+    let mut index = 0;
+    while index < changed.len() {
+        // Changes closer than twice the context are shown in the same hunk
+        let start = changed[index].saturating_sub(CONTEXT_LINES);
+        let mut end = changed[index];
+        while index < changed.len() && changed[index] <= end + 2 * CONTEXT_LINES + 1 {
+            end = changed[index];
+            index += 1;
+        }
+        let end = (end + CONTEXT_LINES + 1).min(lines.len());
+
+        if !str.is_empty() {
+            str.push('\n');
+        }
+        let heading = format!("Line {}", lines[start].0 + 1);
+        str.push_str(&match colors {
+            Some((_, highlight)) => heading.with(highlight.into()).bold().to_string(),
+            None => heading,
+        });
+        str.push('\n');
+
+        for &(_, change, line) in &lines[start..end] {
+            let line = format!("{} {}", change, line).trim_end().to_string();
+            let line = match (colors, change) {
+                (Some(_), '-') => line.with(Color::Red.into()).to_string(),
+                (Some(_), '+') => line.with(Color::Green.into()).to_string(),
+                _ => line,
+            };
+            str.push_str(&line);
+            str.push('\n');
+        }
+    }
+    str
+}
+
+/// Returns `true` if the item is an entry, a table with content or encrypted content.
+fn is_entry(item: Option<&Item>) -> bool {
+    item.and_then(Item::as_table_like).is_some_and(|table| {
+        table.contains_key(CONTENT_KEY) || table.contains_key(ENCRYPTED_CONTENT_KEY)
+    })
+}
+
+/// Renames a key of the table in place, keeping its position and the comments around it.
+fn rename_key(table: &mut Table, old: &str, new: &str) -> Result<()> {
+    if !table.contains_key(old) {
+        bail!("Found no {} in the config", old);
+    }
+    if old != new && table.contains_key(new) {
+        bail!("Name {} is already taken", new);
+    }
+
+    // Keys can't be renamed, so all keys are inserted again in their order
+    let names: Vec<String> = table.iter().map(|(name, _)| name.to_string()).collect();
+    for name in names {
+        let Some((key, item)) = table.remove_entry(&name) else {
+            continue;
+        };
+        let key = if name == old {
+            Key::new(new).with_leaf_decor(key.leaf_decor().clone())
+        } else {
+            key
+        };
+        table.insert_formatted(&key, item);
+    }
+    Ok(())
+}
+
+/// Applies the replacement to the field of the entries of the page table at the given path and its subpages,
+/// collecting the changed values.
+fn replace_in(
+    table: &mut dyn TableLike,
+    path: &str,
+    pattern: &Regex,
+    replacement: &str,
+    field: EntryField,
+    replacements: &mut Vec<Replacement>,
+) {
+    for (key, item) in table.iter_mut() {
+        if !is_entry(Some(item)) {
+            if let Some(subtable) = item.as_table_like_mut() {
+                let path = format!("{}{}{}", path, PAGE_PATH_SEPARATOR, key.get());
+                replace_in(subtable, &path, pattern, replacement, field, replacements);
+            }
+            continue;
+        }
+        let Some(entry) = item.as_table_like_mut() else {
+            continue;
+        };
+
+        let values: Vec<&mut Value> = match field {
+            EntryField::Content => entry
+                .get_mut(CONTENT_KEY)
+                .and_then(Item::as_array_mut)
+                .map(|content| content.iter_mut().collect())
+                .unwrap_or_default(),
+            EntryField::Description => entry
+                .get_mut(DESCRIPTION_KEY)
+                .and_then(Item::as_value_mut)
+                .into_iter()
+                .collect(),
+        };
+        for value in values {
+            let Some(old) = value.as_str() else {
+                continue;
+            };
+            let new = pattern.replace_all(old, replacement);
+            if new == old {
+                continue;
+            }
+
+            let (old, new) = (old.to_string(), new.into_owned());
+            debug!(
+                "Replacing {} by {} in {} on page {}",
+                old,
+                new,
+                key.get(),
+                path
+            );
+            set_str(value, &new);
+            replacements.push(Replacement {
+                page: path.to_string(),
+                entry: key.get().to_string(),
+                old,
+                new,
+            });
+        }
+    }
+}
+
+/// Changes the string of a value, keeping the whitespace and comments around it.
+fn set_str(value: &mut Value, str: &str) {
+    set_value(value, str.into());
+}
+
+/// Replaces a value, keeping the whitespace and comments around it.
+fn set_value(value: &mut Value, new: Value) {
+    let decor = value.decor().clone();
+    *value = new;
+    *value.decor_mut() = decor;
+}
+
+/// Changes the subpages written in the table of the page at the given path, or the pages if the path is empty,
+/// from the written ones into the given ones.
+fn update_subpages(table: &mut Table, path: &str, written: &[Page], pages: &[Page]) -> Result<()> {
+    let path_of = |name: &str| match path {
+        "" => name.to_string(),
+        path => format!("{}{}{}", path, PAGE_PATH_SEPARATOR, name),
+    };
+
+    for page in written {
+        if !pages.iter().any(|other| other.name == page.name) {
+            debug!("Removing page {}", path_of(&page.name));
+            table.remove(&page.name);
+        }
+    }
+
+    for page in pages {
+        let page_path = path_of(&page.name);
+        let Some(written) = written.iter().find(|other| other.name == page.name) else {
+            debug!("Adding page {}", page_path);
+            table.insert(&page.name, Item::Table(written_page(page)?));
+            continue;
+        };
+
+        let page_table = table
+            .get_mut(&page.name)
+            .and_then(Item::as_table_mut)
+            .ok_or(anyhow!(
+                "Page {} is not written as a table in the config",
+                page_path
+            ))?;
+        update_page(page_table, &page_path, written, page)?;
+    }
+    Ok(())
+}
+
+/// Changes the settings, entries and subpages written in the table of a page from the written page into the given one.
+fn update_page(table: &mut Table, path: &str, written: &Page, page: &Page) -> Result<()> {
+    let settings = |page: &Page| Page {
+        entries: vec![],
+        subpages: vec![],
+        ..page.clone()
+    };
+    let (written_settings, page_settings) = (settings(written), settings(page));
+    if serialize_pages(slice::from_ref(&written_settings))
+        != serialize_pages(slice::from_ref(&page_settings))
+    {
+        debug!("Changing settings of page {}", path);
+        let new = written_page(&page_settings)?;
+        let old: Vec<String> = table
+            .iter()
+            .filter(|(key, item)| {
+                item.is_value() && !is_entry(Some(item)) && !new.contains_key(key)
+            })
+            .map(|(key, _)| key.to_string())
+            .collect();
+        for key in old {
+            table.remove(&key);
+        }
+        for (key, item) in new.iter() {
+            let Some(value) = item.as_value() else {
+                continue;
+            };
+            match table.get_mut(key).and_then(Item::as_value_mut) {
+                Some(written) => set_value(written, value.clone()),
+                None => {
+                    table.insert(key, item.clone());
+                }
+            }
+        }
+    }
+
+    if !page.is_generated() {
+        update_entries(table, path, written, page)?;
+    }
+    update_subpages(table, path, &written.subpages, &page.subpages)
+}
+
+/// Changes the entries written in the table of a page from those of the written page into those of the given one.
+///
+/// Entries are compared as they would be serialized, so unchanged entries keep their formatting.
+fn update_entries(table: &mut Table, path: &str, written: &Page, page: &Page) -> Result<()> {
+    for entry in &written.entries {
+        if !page.entries.iter().any(|other| other.name == entry.name) {
+            debug!("Removing entry {} from page {}", entry.name, path);
+            table.remove(&entry.name);
+        }
+    }
+
+    for entry in &page.entries {
+        let written = written
+            .entries
+            .iter()
+            .find(|other| other.name == entry.name);
+        if written.is_some_and(|written| written_entry(written) == written_entry(entry)) {
+            continue;
+        }
+
+        let page = written_page(&Page {
+            name: path.to_string(),
+            entries: vec![entry.clone()],
+            ..Page::default()
+        })?;
+        let Some((key, item)) = page.get_key_value(&entry.name) else {
+            bail!("Failed to write entry {} of page {}", entry.name, path);
+        };
+        match table.get_mut(&entry.name) {
+            Some(old) => {
+                debug!("Changing entry {} on page {}", entry.name, path);
+                let mut item = item.clone();
+                if let (Some(old), Some(value)) = (old.as_value(), item.as_value_mut()) {
+                    *value.decor_mut() = old.decor().clone();
+                }
+                *old = item;
+            }
+            None => {
+                debug!("Adding entry {} to page {}", entry.name, path);
+                table.insert_formatted(key, item.clone());
+            }
+        }
+    }
+
+    if page.sort == SortOrder::Manual {
+        let names: Vec<&str> = page
+            .entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        reorder_entries(table, &names);
+    }
+    Ok(())
+}
+
+/// Reorders the entries in the table of a page by the given names, keeping the other keys where they are.
+fn reorder_entries(table: &mut Table, names: &[&str]) {
+    let keys: Vec<String> = table.iter().map(|(key, _)| key.to_string()).collect();
+    let entries: Vec<&str> = keys
+        .iter()
+        .map(String::as_str)
+        .filter(|key| is_entry(table.get(key)))
+        .collect();
+    let mut ordered = names.iter().filter(|name| entries.contains(name));
+    if entries.iter().eq(ordered.clone()) {
+        return;
+    }
+
+    // Keys can't be moved, so all keys are inserted again with the entries in their new order
+    let order: Vec<String> = keys
+        .iter()
+        .map(|key| match entries.contains(&key.as_str()) {
+            true => ordered.next().map_or(key.clone(), |name| name.to_string()),
+            false => key.clone(),
+        })
+        .collect();
+    for name in order {
+        if let Some((key, item)) = table.remove_entry(&name) {
+            table.insert_formatted(&key, item);
+        }
+    }
+}
+
+/// Returns an entry as it is written by [`serialize_pages`], to tell whether it was changed.
+fn written_entry(entry: &Entry) -> String {
+    serialize_pages(&[Page {
+        entries: vec![entry.clone()],
+        ..Page::default()
+    }])
+}
+
+/// Returns the table of a page as it is written by [`serialize_pages`], to be inserted into the config.
+fn written_page(page: &Page) -> Result<Table> {
+    let document: DocumentMut = serialize_pages(slice::from_ref(page))
+        .parse()
+        .context(format!("Failed to write page {}", page.name))?;
+    let table = document
+        .get(&page.name)
+        .and_then(Item::as_table)
+        .ok_or(anyhow!("Failed to write page {}", page.name))?;
+    Ok(unpositioned(table))
+}
+
+/// Returns a copy of the table and its subtables without their positions in the document they were parsed from,
+/// so they are written where they are inserted.
+fn unpositioned(table: &Table) -> Table {
+    let mut copy = Table::new();
+    for (name, item) in table.iter() {
+        let Some(key) = table.key(name) else {
+            continue;
+        };
+        let item = match item {
+            Item::Table(subtable) => Item::Table(unpositioned(subtable)),
+            item => item.clone(),
+        };
+        copy.insert_formatted(key, item);
+    }
+    copy
+}
+
+/// Changes the links of all entries in the table and its subtables from the old page path to the new one.
+fn retarget_links(table: &mut dyn TableLike, old: &str, new: &str) {
+    for (key, item) in table.iter_mut() {
+        if key.get() == GOTO_KEY {
+            let Some(value) = item.as_value_mut() else {
+                continue;
+            };
+            let Some(target) = value.as_str().and_then(|goto| rename_path(goto, old, new)) else {
+                continue;
+            };
+
+            debug!("Changing link to {}", target);
+            set_str(value, &target);
+        } else if let Some(subtable) = item.as_table_like_mut() {
+            retarget_links(subtable, old, new);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Show module for Recall.
+//!
+//! Prints pages as text, so they can be grepped, paged or redirected to a file without opening the interface.
+//! Each page is headed by its path and description and lists its entries like the interface does, entries without a section first,
+//! followed by its subpages. Hidden entries are left out, names of entries are shown as configured.
+
+use ratatui::{
+    crossterm::style::{Attribute, Stylize},
+    style::Color,
+};
+
+use crate::{
+    app::{Entry, Page, SECRET_MASK},
+    hyperlink,
+    keys::{normalize_content, Keyboard},
+};
+
+/// Separator between the names of a page path in headers, as in the all-pages view
+pub const PATH_SEPARATOR: &str = " › ";
+
+/// Number of spaces entries are indented by
+const INDENT: usize = 2;
+
+/// Number of spaces between the shortcut and the description of an entry
+const COLUMN_SPACING: usize = 2;
+
+/// Colors of the printed text, the primary and the highlight color.
+pub type Colors = (Color, Color);
+
+/// Returns the given pages and their subpages as text, colored with ANSI escape codes if colors are given.
+///
+/// The pages are headed by their path below the given parent pages.
+/// Names of entries are shown on pages that don't decide this for themselves if `show_names` is set.
+pub fn show(
+    pages: &[Page],
+    parents: &[&str],
+    show_names: bool,
+    keyboard: Keyboard,
+    colors: Option<Colors>,
+) -> String {
+    let mut str = String::new();
+    for page in pages {
+        show_page(&mut str, parents, page, show_names, keyboard, colors);
+    }
+    str
+}
+
+/// Writes a page into the given string, followed by its subpages.
+fn show_page(
+    str: &mut String,
+    parents: &[&str],
+    page: &Page,
+    show_names: bool,
+    keyboard: Keyboard,
+    colors: Option<Colors>,
+) {
+    let path: Vec<&str> = parents
+        .iter()
+        .copied()
+        .chain([page.name.as_str()])
+        .collect();
+
+    let entries: Vec<&Entry> = page.entries.iter().filter(|entry| !entry.hidden).collect();
+    if !entries.is_empty() {
+        if !str.is_empty() {
+            str.push('\n');
+        }
+        str.push_str(&highlight(&path.join(PATH_SEPARATOR), colors, true));
+        str.push('\n');
+        if let Some(description) = &page.description {
+            let description = description.replace(['\n', '\r'], " ");
+            str.push_str(&match colors {
+                Some((primary, _)) => description.with(primary.into()).dim().to_string(),
+                None => description,
+            });
+            str.push('\n');
+        }
+
+        let shortcuts: Vec<Vec<String>> = entries
+            .iter()
+            .map(|entry| match entry.secret {
+                true => vec![SECRET_MASK.to_string()],
+                false => normalize_content(&entry.content, keyboard),
+            })
+            .collect();
+        let width = shortcuts
+            .iter()
+            .map(|shortcut| shortcut_width(shortcut))
+            .max()
+            .unwrap_or_default();
+        let name_width = if page.show_names.unwrap_or(show_names) {
+            entries.iter().map(|entry| entry.name.chars().count()).max()
+        } else {
+            None
+        };
+
+        let mut sections: Vec<Option<&str>> = vec![None];
+        for entry in &entries {
+            if !sections.contains(&entry.section.as_deref()) {
+                sections.push(entry.section.as_deref());
+            }
+        }
+
+        for section in sections {
+            if let Some(name) = section {
+                str.push('\n');
+                str.push_str(&" ".repeat(INDENT));
+                str.push_str(&highlight(name, colors, false));
+                str.push('\n');
+            }
+
+            for (entry, shortcut) in entries.iter().zip(&shortcuts) {
+                if entry.section.as_deref() != section {
+                    continue;
+                }
+                show_entry(str, entry, name_width, shortcut, width, colors);
+            }
+        }
+    }
+
+    for subpage in &page.subpages {
+        show_page(str, &path, subpage, show_names, keyboard, colors);
+    }
+}
+
+/// Writes an entry as a line with its shortcut padded to the given width, followed by its description.
+/// If a width for names is given, the line starts with the name of the entry padded to it.
+///
+/// The keys of the shortcut are joined by `+`, which is shown in the primary color between keys in the highlight color.
+/// The content of entries linking to a URL is a link instead, unless the entry is a secret.
+fn show_entry(
+    str: &mut String,
+    entry: &Entry,
+    name_width: Option<usize>,
+    shortcut: &[String],
+    width: usize,
+    colors: Option<Colors>,
+) {
+    let padding = width - shortcut_width(shortcut) + COLUMN_SPACING;
+
+    str.push_str(&" ".repeat(INDENT));
+    if let Some(name_width) = name_width {
+        str.push_str(&format!(
+            "{:width$}",
+            entry.name,
+            width = name_width + COLUMN_SPACING
+        ));
+    }
+    // Secrets are masked, so they aren't linked either, which would show them when hovering the link
+    let url = entry.link_url().filter(|_| !entry.secret);
+    str.push_str(&match (colors, url) {
+        // Colors tell that the output goes to a terminal, which can open links
+        (Some((_, highlight)), Some(url)) => hyperlink::link(
+            &shortcut
+                .join("+")
+                .with(highlight.into())
+                .underlined()
+                .to_string(),
+            url,
+        ),
+        (Some((primary, highlight)), None) => shortcut
+            .iter()
+            .map(|key| key.as_str().with(highlight.into()).bold().to_string())
+            .collect::<Vec<_>>()
+            .join(&"+".with(primary.into()).to_string()),
+        (None, _) => shortcut.join("+"),
+    });
+    str.push_str(&" ".repeat(padding));
+    str.push_str(&entry.description.replace(['\n', '\r'], " "));
+    if let Some(target) = &entry.goto {
+        str.push_str(" → ");
+        str.push_str(&match colors {
+            Some((_, highlight)) => target
+                .as_str()
+                .with(highlight.into())
+                .underlined()
+                .to_string(),
+            None => target.clone(),
+        });
+    }
+
+    // Trailing spaces of entries without a description would only get in the way of grep
+    let trimmed = str.trim_end_matches(' ').len();
+    str.truncate(trimmed);
+    str.push('\n');
+}
+
+/// Returns the number of characters of a shortcut with its keys joined by `+`.
+fn shortcut_width(shortcut: &[String]) -> usize {
+    let keys: usize = shortcut.iter().map(|key| key.chars().count()).sum();
+    keys + shortcut.len().saturating_sub(1)
+}
+
+/// Returns a header in the highlight color, bold and optionally underlined, or as is without colors.
+fn highlight(text: &str, colors: Option<Colors>, underlined: bool) -> String {
+    match colors {
+        Some((_, highlight)) => {
+            let styled = text.with(highlight.into()).bold();
+            if underlined {
+                styled.attribute(Attribute::Underlined).to_string()
+            } else {
+                styled.to_string()
+            }
+        }
+        None => text.to_string(),
+    }
+}
@@ -0,0 +1,77 @@
+//! Snippet module for Recall.
+//!
+//! Commands of entries may contain placeholders like `<branch>` in `git checkout <branch>`,
+//! which are filled in before the command is copied.
+//! Key notations like `<C-x>` or `<leader>` look alike, so placeholders are only looked for in content that isn't a key notation.
+
+use crate::keys::is_key_notation;
+
+/// Returns the command of an entry's content, with its parts joined by spaces.
+pub fn command(content: &[String]) -> String {
+    content.join(" ")
+}
+
+/// Returns the names of all placeholders within an entry's content, without duplicates and in order of appearance.
+pub fn placeholders(content: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+
+    for component in content {
+        if is_key_notation(component) {
+            continue;
+        }
+        for (_, name) in find_placeholders(component) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+
+    names
+}
+
+/// Returns the command with each placeholder replaced by the value given for its name.
+///
+/// Placeholders without a value are kept as written.
+pub fn fill(command: &str, values: &[(String, String)]) -> String {
+    let mut filled = String::new();
+    let mut rest = command;
+
+    for (start, name) in find_placeholders(command) {
+        let offset = command.len() - rest.len();
+        let end = start + name.len() + 2;
+        filled.push_str(&command[offset..start]);
+
+        match values.iter().find(|(n, _)| n == name) {
+            Some((_, value)) => filled.push_str(value),
+            None => filled.push_str(&command[start..end]),
+        }
+        rest = &command[end..];
+    }
+
+    filled.push_str(rest);
+    filled
+}
+
+/// Returns the start of each placeholder within the text along with its name.
+///
+/// Names consist of letters, digits, underscores and dashes, so redirections like `< file` aren't mistaken for placeholders.
+fn find_placeholders(text: &str) -> Vec<(usize, &str)> {
+    let mut placeholders = vec![];
+    let mut search_from = 0;
+
+    while let Some(start) = text[search_from..].find('<').map(|i| search_from + i) {
+        let after = &text[start + 1..];
+        let length = after
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(after.len());
+
+        if length > 0 && after[length..].starts_with('>') {
+            placeholders.push((start, &after[..length]));
+            search_from = start + length + 2;
+        } else {
+            search_from = start + 1;
+        }
+    }
+
+    placeholders
+}
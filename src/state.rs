@@ -0,0 +1,229 @@
+//! Persistent state module for Recall.
+//!
+//! This module provides reading and writing of the state file, which keeps data that is collected while using recall (e.g. favorites) separate from the user-written configuration.
+//! The state file is stored as TOML in the platform specific data directory.
+
+use anyhow::{anyhow, Context, Result};
+use directories::ProjectDirs;
+use log::{info, trace, warn};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, path::PathBuf};
+
+use crate::{app::rename_path, config::default_config_path};
+
+/// Data that recall keeps between sessions.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UserState {
+    /// Entries that were starred, in the order they were starred.
+    #[serde(default)]
+    pub favorites: Vec<EntryKey>,
+
+    /// Entries that were recently activated or copied, the most recent first.
+    #[serde(default)]
+    pub recent: Vec<EntryKey>,
+
+    /// How often entries and pages were looked up, in the order they were first looked up.
+    #[serde(default)]
+    pub usage: Vec<Usage>,
+}
+
+/// Maximum number of recent entries that are remembered
+const MAX_RECENT_ENTRIES: usize = 20;
+
+/// How often an entry, or a page if no entry is named, was looked up.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    /// Path of the page
+    pub page: String,
+
+    /// Name of the entry within its page, or `None` for the page itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+
+    /// Number of times the entry was selected or the page opened
+    #[serde(default)]
+    pub views: u64,
+
+    /// Number of times the entry was copied
+    #[serde(default)]
+    pub copies: u64,
+}
+
+/// Identifies an entry across sessions by the name of its page and its own name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryKey {
+    /// Name of the page containing the entry
+    pub page: String,
+
+    /// Name of the entry within its page
+    pub entry: String,
+}
+
+impl UserState {
+    /// Moves the entry to the front of the recent entries, forgetting the oldest one if there are too many.
+    pub fn remember_recent(&mut self, key: EntryKey) {
+        self.recent.retain(|recent| *recent != key);
+        self.recent.insert(0, key);
+        self.recent.truncate(MAX_RECENT_ENTRIES);
+    }
+
+    /// Counts a view or a copy of the entry with the given name, or of the page if no entry is given.
+    pub fn record_usage(&mut self, page: &str, entry: Option<&str>, copied: bool) {
+        let position = self
+            .usage
+            .iter()
+            .position(|usage| usage.page == page && usage.entry.as_deref() == entry);
+        let usage = match position {
+            Some(position) => &mut self.usage[position],
+            None => {
+                self.usage.push(Usage {
+                    page: page.to_string(),
+                    entry: entry.map(str::to_string),
+                    ..Usage::default()
+                });
+                self.usage.last_mut().unwrap()
+            }
+        };
+
+        if copied {
+            usage.copies += 1;
+        } else {
+            usage.views += 1;
+        }
+    }
+
+    /// Moves everything kept about the page at the old path and its subpages to the new path.
+    pub fn rename_page(&mut self, old: &str, new: &str) {
+        let keys = self.favorites.iter_mut().chain(self.recent.iter_mut());
+        let pages = keys
+            .map(|key| &mut key.page)
+            .chain(self.usage.iter_mut().map(|usage| &mut usage.page));
+        for page in pages {
+            if let Some(path) = rename_path(page, old, new) {
+                *page = path;
+            }
+        }
+    }
+
+    /// Moves everything kept about the entry on the page at the given path to its new name.
+    pub fn rename_entry(&mut self, page: &str, old: &str, new: &str) {
+        for key in self.favorites.iter_mut().chain(self.recent.iter_mut()) {
+            if key.page == page && key.entry == old {
+                key.entry = new.to_string();
+            }
+        }
+        for usage in &mut self.usage {
+            if usage.page == page && usage.entry.as_deref() == Some(old) {
+                usage.entry = Some(new.to_string());
+            }
+        }
+    }
+
+    /// Moves everything kept about the entry with the given name from the page at the given path to the target page.
+    pub fn move_entry(&mut self, page: &str, entry: &str, target: &str) {
+        for key in self.favorites.iter_mut().chain(self.recent.iter_mut()) {
+            if key.page == page && key.entry == entry {
+                key.page = target.to_string();
+            }
+        }
+        for usage in &mut self.usage {
+            if usage.page == page && usage.entry.as_deref() == Some(entry) {
+                usage.page = target.to_string();
+            }
+        }
+    }
+
+    /// Returns the usage of entries, or of pages if `pages` is set, the most looked up first.
+    pub fn most_used(&self, pages: bool) -> Vec<&Usage> {
+        let mut usage: Vec<&Usage> = self
+            .usage
+            .iter()
+            .filter(|usage| usage.entry.is_none() == pages)
+            .collect();
+        usage.sort_by_key(|usage| std::cmp::Reverse(usage.total()));
+        usage
+    }
+}
+
+impl Usage {
+    /// Returns how often the entry or page was looked up, counting copies and views alike
+    pub fn total(&self) -> u64 {
+        self.views + self.copies
+    }
+}
+
+/// Returns the default state file path
+///
+/// Uses the standard OS-specific data directory via the `directories` crate.
+/// Returns an error if the platform-specific data directory cannot be determined.
+pub fn default_state_path() -> Result<PathBuf> {
+    let state_path = ProjectDirs::from("", "", "recall")
+        .ok_or(anyhow!("No valid data directory found"))?
+        .data_dir()
+        .join("state.toml");
+
+    trace!("Default state path is {}", state_path.display());
+
+    Ok(state_path)
+}
+
+/// Returns the path of the state file kept for the config at the given path.
+///
+/// The default config keeps the default state file. Other configs get one named after the config and a hash of its
+/// canonical path, so each config keeps its own state however it is opened, even if another has the same file name.
+pub fn config_state_path(config_path: &Path) -> Result<PathBuf> {
+    let state_path = default_state_path()?;
+
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let config_path = canonical(config_path);
+    if default_config_path().is_ok_and(|default| canonical(&default) == config_path) {
+        return Ok(state_path);
+    }
+
+    let stem = config_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or("config".to_string());
+    let hash = path_hash(&config_path);
+    Ok(state_path.with_file_name(format!("state-{}-{:016x}.toml", stem, hash)))
+}
+
+/// Returns the FNV-1a hash of the path, which unlike the hasher of the standard library stays the same across versions.
+fn path_hash(path: &Path) -> u64 {
+    path.to_string_lossy()
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+/// Reads the state file, falling back to an empty state if it doesn't exist or can't be parsed.
+///
+/// Losing the state is preferable to not being able to start recall at all.
+pub fn read_state(path: &Path) -> UserState {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            info!("No state read from {}: {}", path.display(), e);
+            return UserState::default();
+        }
+    };
+
+    toml::from_str(&content).unwrap_or_else(|e| {
+        warn!("Ignoring invalid state file {}: {}", path.display(), e);
+        UserState::default()
+    })
+}
+
+/// Writes the state file, creating its parent directories if necessary.
+pub fn write_state(path: &Path, state: &UserState) -> Result<()> {
+    trace!("Writing state to {}", path.display());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .context(format!("Failed to create directory {}", parent.display()))?;
+    }
+
+    let content = toml::to_string(state).context("Failed to serialize state")?;
+    fs::write(path, content).context(format!("Failed to write state to {}", path.display()))
+}
@@ -0,0 +1,159 @@
+//! Stats module for Recall.
+//!
+//! Recall counts in the user state how often entries are selected or copied and pages opened.
+//! These lookups are shown as a heatmap, a bar per entry or page scaled to the most looked up one,
+//! so the shortcuts that are consulted again and again stand out, e.g. to learn them or star them.
+
+use ratatui::crossterm::style::Stylize;
+
+use crate::{
+    app::{Page, PAGE_PATH_SEPARATOR},
+    config::count_entries,
+    show::{Colors, PATH_SEPARATOR},
+    state::{Usage, UserState},
+};
+
+/// Number of characters of the bar of the most looked up entry or page
+pub const BAR_WIDTH: usize = 20;
+
+/// Character bars are drawn with
+const BAR_CHARACTER: char = '█';
+
+/// Number of spaces lines are indented by
+const INDENT: usize = 2;
+
+/// How often an entry or page was looked up compared to the most looked up one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heat {
+    /// At least two thirds of the lookups of the most looked up one
+    Hot,
+
+    /// At least a third of the lookups of the most looked up one
+    Warm,
+
+    /// Less than a third of the lookups of the most looked up one
+    Cold,
+}
+
+/// Returns the heat of a number of lookups compared to the most lookups.
+pub fn heat(total: u64, max: u64) -> Heat {
+    if total * 3 >= max * 2 {
+        Heat::Hot
+    } else if total * 3 >= max {
+        Heat::Warm
+    } else {
+        Heat::Cold
+    }
+}
+
+/// Returns the bar of a number of lookups, scaled to the most lookups and padded to [`BAR_WIDTH`].
+///
+/// Anything looked up at all gets at least a sliver of a bar.
+pub fn bar(total: u64, max: u64) -> String {
+    let length = if max == 0 {
+        0
+    } else {
+        (total * BAR_WIDTH as u64).div_ceil(max) as usize
+    };
+    format!(
+        "{:width$}",
+        BAR_CHARACTER.to_string().repeat(length),
+        width = BAR_WIDTH
+    )
+}
+
+/// Returns the path of the looked up page, followed by the name of the entry if an entry was looked up.
+pub fn usage_path(usage: &Usage) -> String {
+    usage
+        .page
+        .split(PAGE_PATH_SEPARATOR)
+        .chain(usage.entry.as_deref())
+        .collect::<Vec<_>>()
+        .join(PATH_SEPARATOR)
+}
+
+/// Returns the number of pages, entries, favorites and lookups in a single line.
+pub fn summary(pages: &[Page], state: &UserState) -> String {
+    let lookups: u64 = state.usage.iter().map(Usage::total).sum();
+    format!(
+        "{} pages, {} entries, {} favorites, {} lookups",
+        count_pages(pages),
+        count_entries(pages),
+        state.favorites.len(),
+        lookups
+    )
+}
+
+/// Returns the looked up entries and pages as a heatmap, colored with ANSI escape codes if colors are given.
+pub fn usage(state: &UserState, colors: Option<Colors>) -> String {
+    let mut str = String::new();
+    for (heading, usage) in [
+        ("Entries", state.most_used(false)),
+        ("Pages", state.most_used(true)),
+    ] {
+        if usage.is_empty() {
+            continue;
+        }
+        if !str.is_empty() {
+            str.push('\n');
+        }
+        str.push_str(&match colors {
+            Some((_, highlight)) => heading.with(highlight.into()).bold().to_string(),
+            None => heading.to_string(),
+        });
+        str.push('\n');
+
+        let max = usage.first().map_or(0, |usage| usage.total());
+        let digits = max.to_string().len();
+        let paths: Vec<String> = usage.iter().map(|usage| usage_path(usage)).collect();
+        let path_width = paths
+            .iter()
+            .map(|path| path.chars().count())
+            .max()
+            .unwrap_or_default();
+        for (usage, path) in usage.iter().zip(paths) {
+            let bar = bar(usage.total(), max);
+            let bar = match colors {
+                Some((primary, highlight)) => match heat(usage.total(), max) {
+                    Heat::Hot => bar.with(highlight.into()).bold().to_string(),
+                    Heat::Warm => bar.with(highlight.into()).to_string(),
+                    Heat::Cold => bar.with(primary.into()).dim().to_string(),
+                },
+                None => bar,
+            };
+            str.push_str(&format!(
+                "{}{}  {:>digits$}  {:path_width$}  ({}, {})\n",
+                " ".repeat(INDENT),
+                bar,
+                usage.total(),
+                path,
+                count(usage.views, "view"),
+                count(usage.copies, "copy"),
+                digits = digits,
+                path_width = path_width
+            ));
+        }
+    }
+
+    if str.is_empty() {
+        str.push_str("Nothing was looked up yet\n");
+    }
+    str
+}
+
+/// Returns a number followed by the noun, in plural unless the number is one.
+pub fn count(number: u64, noun: &str) -> String {
+    match (number, noun.strip_suffix('y')) {
+        (1, _) => format!("1 {}", noun),
+        (_, Some(stem)) => format!("{} {}ies", number, stem),
+        (_, None) => format!("{} {}s", number, noun),
+    }
+}
+
+/// Returns the number of the given pages and their subpages.
+fn count_pages(pages: &[Page]) -> usize {
+    pages
+        .iter()
+        .map(|page| 1 + count_pages(&page.subpages))
+        .sum()
+}
@@ -0,0 +1,110 @@
+//! Templates for Recall.
+//!
+//! A template is a ready-made page of a common tool that `recall init` offers to include in a new config,
+//! so the first run starts with shortcuts worth looking up instead of an example to replace.
+//! Templates are written like a config, so they read the same as the pages they become.
+
+/// A named set of pages to start a config with.
+#[derive(Debug)]
+pub struct Template {
+    /// Name used to select the template
+    pub name: &'static str,
+
+    /// What the pages of the template are about
+    pub description: &'static str,
+
+    /// Pages of the template, written like a config
+    pub pages: &'static str,
+}
+
+/// All built-in templates, in the order they are offered.
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        name: "git",
+        description: "Everyday git commands",
+        pages: r#"
+[git]
+description = "Everyday git commands, copied to the clipboard on Enter"
+aliases = ["g"]
+status = { content = ["git status"], description = "Show the state of the working tree" }
+add = { content = ["git add -p"], description = "Stage changes hunk by hunk" }
+commit = { content = ["git commit -m \"<message>\""], description = "Commit the staged changes" }
+amend = { content = ["git commit --amend --no-edit"], description = "Add the staged changes to the last commit" }
+switch = { content = ["git switch <branch>"], description = "Switch to another branch" }
+branch = { content = ["git switch -c <branch>"], description = "Create a branch and switch to it" }
+pull = { content = ["git pull --rebase"], description = "Rebase onto the remote branch" }
+push = { content = ["git push"], description = "Push the current branch" }
+log = { content = ["git log --oneline --graph"], description = "Show the history as a graph" }
+stash = { content = ["git stash"], description = "Put the changes aside" }
+unstash = { content = ["git stash pop"], description = "Bring the changes back" }
+"#,
+    },
+    Template {
+        name: "vim",
+        description: "Moving around and editing in Vim",
+        pages: r#"
+[vim]
+description = "Normal mode unless noted otherwise"
+aliases = ["vi", "nvim"]
+save = { content = [":w"], description = "Save the file", section = "Files" }
+quit = { content = [":q"], description = "Quit, :q! discards the changes", section = "Files" }
+word = { content = ["w"], description = "Next word", section = "Moving" }
+back = { content = ["b"], description = "Previous word", section = "Moving" }
+top = { content = ["gg"], description = "First line", section = "Moving" }
+bottom = { content = ["G"], description = "Last line", section = "Moving" }
+match = { content = ["%"], description = "Matching bracket", section = "Moving" }
+insert = { content = ["i"], description = "Insert before the cursor", section = "Editing" }
+append = { content = ["A"], description = "Append to the line", section = "Editing" }
+change = { content = ["ciw"], description = "Change the word under the cursor", section = "Editing" }
+delete = { content = ["dd"], description = "Delete the line", section = "Editing" }
+undo = { content = ["u"], description = "Undo", section = "Editing" }
+redo = { content = ["Ctrl", "R"], description = "Redo", section = "Editing" }
+search = { content = ["/"], description = "Search forward, n and N jump between matches", section = "Searching" }
+replace = { content = [":%s/<old>/<new>/g"], description = "Replace in the whole file", section = "Searching" }
+"#,
+    },
+    Template {
+        name: "tmux",
+        description: "Windows and panes of tmux with the default prefix",
+        pages: r#"
+[tmux]
+description = "Press the prefix Ctrl+B before each of these"
+new-window = { content = ["c"], description = "Create a window" }
+next-window = { content = ["n"], description = "Next window" }
+previous-window = { content = ["p"], description = "Previous window" }
+rename-window = { content = [","], description = "Rename the window" }
+split-horizontal = { content = ["%"], description = "Split into panes side by side" }
+split-vertical = { content = ["\""], description = "Split into panes above each other" }
+next-pane = { content = ["o"], description = "Next pane" }
+zoom = { content = ["z"], description = "Zoom the pane in and out" }
+close-pane = { content = ["x"], description = "Close the pane" }
+detach = { content = ["d"], description = "Detach from the session" }
+scroll = { content = ["["], description = "Scroll back, q to leave" }
+"#,
+    },
+    Template {
+        name: "shell",
+        description: "Line editing in bash, zsh and other readline shells",
+        pages: r#"
+[shell]
+description = "Bindings of readline and the terminal"
+aliases = ["bash", "zsh", "readline"]
+start = { content = ["C-a"], description = "Beginning of the line" }
+end = { content = ["C-e"], description = "End of the line" }
+word-back = { content = ["M-b"], description = "Previous word" }
+word-forward = { content = ["M-f"], description = "Next word" }
+kill-word = { content = ["C-w"], description = "Delete the word before the cursor" }
+kill-line = { content = ["C-k"], description = "Delete to the end of the line" }
+kill-start = { content = ["C-u"], description = "Delete to the beginning of the line" }
+yank = { content = ["C-y"], description = "Paste the last deleted text" }
+history = { content = ["C-r"], description = "Search the history backwards" }
+clear = { content = ["C-l"], description = "Clear the screen" }
+last-argument = { content = ["M-."], description = "Insert the last argument of the previous command" }
+"#,
+    },
+];
+
+/// Returns the built-in template with the given name, if there is one.
+pub fn find_template(name: &str) -> Option<&'static Template> {
+    TEMPLATES.iter().find(|template| template.name == name)
+}
@@ -0,0 +1,137 @@
+//! Built-in color themes for Recall.
+//!
+//! A theme is a named pair of ANSI colors for the interface and highlights.
+//! It can be selected with the `theme` key in the `[recall]` table, explicitly set colors take precedence over the theme.
+//! A theme and colors given on the command line, e.g. with `--theme`, take precedence over the configured colors
+//! for that invocation only, again with explicitly given colors taking precedence over the theme.
+//! Highlights never rely on their color alone, they are also bold or underlined, so themes only need to keep enough contrast.
+
+use ratatui::style::Color;
+
+/// A named pair of ANSI-encoded colors.
+#[derive(Debug)]
+pub struct Theme {
+    /// Name used to select the theme in the configuration
+    pub name: &'static str,
+
+    /// ANSI color code used for the interface and text
+    pub primary_color: u8,
+
+    /// ANSI color code used for highlighting
+    pub highlight_color: u8,
+}
+
+/// All built-in themes, the first one matches the default colors.
+pub const THEMES: &[Theme] = &[
+    Theme {
+        name: "default",
+        primary_color: 15,
+        highlight_color: 6,
+    },
+    Theme {
+        name: "light",
+        primary_color: 0,
+        highlight_color: 4,
+    },
+    Theme {
+        name: "gruvbox",
+        primary_color: 223,
+        highlight_color: 214,
+    },
+    Theme {
+        name: "nord",
+        primary_color: 254,
+        highlight_color: 110,
+    },
+    Theme {
+        name: "dracula",
+        primary_color: 231,
+        highlight_color: 212,
+    },
+    // The following themes contrast on the blue-yellow axis, which red-green color blindness leaves intact
+    Theme {
+        name: "deuteranopia",
+        primary_color: 15,
+        highlight_color: 33,
+    },
+    Theme {
+        name: "protanopia",
+        primary_color: 15,
+        highlight_color: 220,
+    },
+];
+
+/// Returns the built-in theme with the given name, if there is one.
+pub fn find_theme(name: &str) -> Option<&'static Theme> {
+    THEMES.iter().find(|theme| theme.name == name)
+}
+
+/// Colors chosen on the command line for a single invocation, taking precedence over the configured ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ColorOverrides {
+    /// Theme replacing both colors
+    pub theme: Option<&'static Theme>,
+
+    /// Color replacing the primary color, including the one of the theme
+    pub primary_color: Option<Color>,
+
+    /// Color replacing the highlight color, including the one of the theme
+    pub highlight_color: Option<Color>,
+}
+
+impl ColorOverrides {
+    /// Returns `true` if no colors are overridden
+    pub fn is_empty(&self) -> bool {
+        self.theme.is_none() && self.primary_color.is_none() && self.highlight_color.is_none()
+    }
+
+    /// Returns the given primary and highlight colors with the overrides applied.
+    pub fn apply(&self, primary_color: Color, highlight_color: Color) -> (Color, Color) {
+        let (primary_color, highlight_color) = match self.theme {
+            Some(theme) => (
+                Color::Indexed(theme.primary_color),
+                Color::Indexed(theme.highlight_color),
+            ),
+            None => (primary_color, highlight_color),
+        };
+        (
+            self.primary_color.unwrap_or(primary_color),
+            self.highlight_color.unwrap_or(highlight_color),
+        )
+    }
+}
+
+/// Returns the names of all built-in themes, separated by commas.
+pub fn theme_names() -> String {
+    THEMES
+        .iter()
+        .map(|theme| theme.name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns the ANSI color code of a color, or `None` if it has no such code (e.g. RGB colors).
+pub fn ansi_index(color: Color) -> Option<u8> {
+    let index = match color {
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(i) => i,
+        Color::Reset | Color::Rgb(..) => return None,
+    };
+
+    Some(index)
+}
@@ -0,0 +1,44 @@
+//! Window title module for Recall.
+//!
+//! While recall is running, the title of the terminal window names the current page, so the window can be found
+//! in taskbars and tmux window lists. The previous title is saved on the title stack of the terminal when recall
+//! starts and restored from it on exit, terminals without such a stack keep the last title.
+
+use std::io::{self, Write};
+
+use anyhow::{Context, Result};
+use ratatui::crossterm::{execute, terminal::SetTitle};
+
+/// Escape sequence saving the window and icon title on the title stack of the terminal
+const SAVE_TITLE: &str = "\x1b[22;0t";
+
+/// Escape sequence restoring the window and icon title from the title stack of the terminal
+const RESTORE_TITLE: &str = "\x1b[23;0t";
+
+/// Prefix of the title, followed by the current page
+const TITLE_PREFIX: &str = "recall — ";
+
+/// Saves the current window title, so it can be restored when recall quits.
+pub fn save() -> Result<()> {
+    write_sequence(SAVE_TITLE)
+}
+
+/// Sets the window title to name the given page.
+pub fn set(page: &str) -> Result<()> {
+    execute!(io::stdout(), SetTitle(format!("{}{}", TITLE_PREFIX, page)))
+        .context("Failed to set the window title")
+}
+
+/// Restores the window title saved when recall started.
+pub fn restore() -> Result<()> {
+    write_sequence(RESTORE_TITLE)
+}
+
+/// Writes an escape sequence to the terminal.
+fn write_sequence(sequence: &str) -> Result<()> {
+    let mut stdout = io::stdout();
+    stdout
+        .write_all(sequence.as_bytes())
+        .and_then(|_| stdout.flush())
+        .context("Failed to write the window title")
+}
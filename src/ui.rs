@@ -3,101 +3,2525 @@
 //! This module provides the main `ui` function for drawing the interface using 'ratatui'
 //! and helper functions for building stylized tables and shortcut lines.
 
-use std::cmp::max;
+use std::{cmp::max, time::Duration};
 
 use ratatui::{
-    layout::Constraint,
-    style::{Color, Stylize},
-    text::Line,
-    widgets::{Block, Padding, Row, Table},
+    layout::{Constraint, Flex, Layout, Position, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{
+        Block, BorderType, Clear, Padding, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Table, TableState, Tabs,
+    },
     Frame,
 };
 
-use crate::app::{App, Entry};
+use crate::{
+    app::{
+        App, CommandLine, Entry, EntryForm, IndexRow, Mode, Page, PageRow, PageSwitcher, PaneView,
+        PlaceholderForm, Settings, SettingsField, SortOrder, ENTRY_FORM_FIELDS,
+        PAGE_PATH_SEPARATOR, SECRET_MASK,
+    },
+    diagram,
+    i18n::Localizer,
+    keymap::Action,
+    keys::{normalize_content, pressed_keys, Keyboard},
+    stats::{bar, heat, usage_path, Heat, BAR_WIDTH},
+    theme::ansi_index,
+};
+
+/// Number of spaces between the columns of a table
+const TABLE_COLUMN_SPACING: u16 = 2;
+
+/// Width of the column of the page index counting the entries of each page
+const PAGE_INDEX_COUNT_WIDTH: u16 = 12;
+
+/// Width of the arrows on both sides of the tab bar that tell about pages left out
+const TAB_ARROW_WIDTH: u16 = 2;
+
+/// What was drawn that is kept track of outside of the UI.
+#[derive(Debug, Default)]
+pub struct Drawn {
+    /// Area left blank for the image of the selected entry or page, if the terminal can show it
+    pub image_area: Option<Rect>,
+
+    /// Number of rows scrolled past at the top of the current page, if it was drawn
+    pub scroll_offset: Option<usize>,
+
+    /// Areas of the tab bar opening a page when clicked, along with the number of the page
+    pub tabs: Vec<(Rect, usize)>,
+}
+
+/// Number of matching pages the page switcher shows at once, scrolling through the rest
+const PAGE_SWITCHER_ROWS: u16 = 10;
 
 /// Renders the main user interface for the application within the given frame.
 ///
 /// This function constructs a stylized table of entries on the current page,
 /// along with a title, legend, and page counter.
-pub fn ui(frame: &mut Frame, app: &App) {
-    let curr_page = app
-        .get_current_page()
-        // we may want to rewrite this, we could have a config that just has no pages
-        .expect("Expected page number to reference an existing page");
+/// Returns the area left blank for the image of the selected entry or page and how far the page is scrolled.
+pub fn ui(frame: &mut Frame, app: &App) -> Drawn {
+    // Nothing is drawn, so the frame stays cleared
+    if app.is_blanked() {
+        return Drawn::default();
+    }
+
+    if app.is_plain() {
+        render_plain(frame, app);
+        return Drawn::default();
+    }
+
+    if let Mode::AllPages(selected) = app.mode {
+        render_all_pages(frame, app, selected);
+        return Drawn::default();
+    }
+
+    if let Mode::Stats(selected) = app.mode {
+        render_stats(frame, app, selected);
+        return Drawn::default();
+    }
+
+    if let Mode::PageIndex(selected) = app.mode {
+        render_page_index(frame, app, selected);
+        return Drawn::default();
+    }
+
+    let title = page_title(app, app.current_group(), &app.breadcrumbs());
+
+    let localizer = app.localizer();
+
+    let page_counter = page_counter(localizer, app.current_page_number(), app.number_of_pages());
+
+    let mut legend = Line::default();
+    let previous_page = localizer.text("legend-previous-page");
+    push_legend_key(&mut legend, app, Action::PreviousPage, previous_page);
+    let next_page = localizer.text("legend-next-page");
+    push_legend_key(&mut legend, app, Action::NextPage, next_page);
+    push_legend_key(
+        &mut legend,
+        app,
+        Action::Quit,
+        localizer.text("legend-close"),
+    );
+    push_legend_key(
+        &mut legend,
+        app,
+        Action::Help,
+        localizer.text("legend-help"),
+    );
+    if app.is_split() {
+        let switch_pane = localizer.text("legend-switch-pane");
+        push_legend_key(&mut legend, app, Action::SwitchPane, switch_pane);
+    }
+    if let Some(workspace) = app.workspace_name() {
+        push_legend_key(&mut legend, app, Action::Workspaces, workspace.to_string());
+    }
+    legend.push_span(page_counter.fg(app.highlight_color()).bold());
+    if app.shows_hidden_entries() {
+        legend.push_span(
+            format!("[{}] ", localizer.text("showing-hidden"))
+                .fg(app.highlight_color())
+                .bold(),
+        );
+    }
+    if app.wraps_descriptions() {
+        legend.push_span(
+            format!("[{}] ", localizer.text("wrapping-descriptions"))
+                .fg(app.highlight_color())
+                .bold(),
+        );
+    }
+    // While typing, the prompt shows the filter instead
+    if let (Some(filter), false) = (app.current_filter(), matches!(app.mode, Mode::FilterInput)) {
+        legend.push_span(
+            format!(
+                "[{}] ",
+                localizer.text_with("filtered-by", &[("filter", filter.into())])
+            )
+            .fg(app.highlight_color())
+            .bold(),
+        );
+    }
+    if let Some(id) = sort_order_message(app.current_sort_order()) {
+        legend.push_span(
+            format!("[{}] ", localizer.text(id))
+                .fg(app.highlight_color())
+                .bold(),
+        );
+    }
+    if let Some(age) = app.current_page_age() {
+        legend.push_span(
+            format!("[{}] ", updated_text(localizer, age))
+                .fg(app.highlight_color())
+                .bold(),
+        );
+    }
+    if let Some(id) = app.notice() {
+        legend.push_span(
+            format!("[{}] ", localizer.text(id))
+                .fg(app.highlight_color())
+                .bold(),
+        );
+    }
+
+    let mut block = Block::bordered()
+        .title(title.centered())
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1));
+    if app.is_split() {
+        // The focused pane stands out from the other one with a thick border in the highlight color
+        block = block
+            .border_type(BorderType::Thick)
+            .border_style(Style::new().fg(app.highlight_color()));
+    }
+
+    let area = match &app.mode {
+        Mode::Normal
+        | Mode::Settings(_)
+        | Mode::AllPages(_)
+        | Mode::PlaceholderInput(_)
+        | Mode::EntryInput(_)
+        | Mode::Stats(_)
+        | Mode::PageIndex(_)
+        | Mode::Details
+        | Mode::Help(_)
+        | Mode::Workspaces(_)
+        | Mode::PageSwitcher(_)
+        | Mode::ConfigConflict
+        | Mode::ConfigError(_) => frame.area(),
+        Mode::PageNameInput(name) => {
+            let [main_area, prompt_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            render_page_name_prompt(frame, app, name, prompt_area);
+            main_area
+        }
+        Mode::MoveEntry(query) => {
+            let [main_area, prompt_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            render_move_prompt(frame, app, query, prompt_area);
+            main_area
+        }
+        Mode::FilterInput => {
+            let [main_area, prompt_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            render_filter_prompt(frame, app, prompt_area);
+            main_area
+        }
+        Mode::SearchInput => {
+            let [main_area, prompt_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            render_search_prompt(frame, app, prompt_area);
+            main_area
+        }
+        Mode::CommandLine(line) => {
+            let [main_area, prompt_area] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            render_command_line(frame, app, line, prompt_area);
+            main_area
+        }
+    };
+    let [tabs_area, area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+    let tabs = render_tabs(
+        frame,
+        app,
+        content_area(app, tabs_area, if app.is_split() { 2 } else { 1 }),
+    );
+    let area = content_area(app, area, if app.is_split() { 2 } else { 1 });
+
+    let (area, image_area) = match app.current_image() {
+        Some(path) => {
+            let [main_area, image_area] =
+                Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .areas(area);
+            (main_area, render_image_panel(frame, app, path, image_area))
+        }
+        None => (area, None),
+    };
+
+    let area = if app.shows_keyboard() {
+        let [main_area, keyboard_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(diagram::HEIGHT + 2)])
+                .areas(area);
+        render_keyboard(frame, app, keyboard_area);
+        main_area
+    } else {
+        area
+    };
+
+    let area = match app.unfocused_pane() {
+        Some(pane) => {
+            let [left, right] =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .areas(area);
+            let (focused_area, unfocused_area) = if app.is_left_pane_focused() {
+                (left, right)
+            } else {
+                (right, left)
+            };
+            render_unfocused_pane(frame, app, pane, unfocused_area);
+            focused_area
+        }
+        None => area,
+    };
+
+    let rows = table_rows(app.rows(), |index| app.is_favorite(index));
+    let table = build_table(&rows, app, app.shows_names(), block.inner(area).width);
+    let mut table_state = TableState::new()
+        .with_offset(app.scroll_offset())
+        .with_selected(app.selected_entry());
+
+    let table_area = render_page(
+        frame,
+        app,
+        block,
+        table,
+        &mut table_state,
+        app.current_description(),
+        area,
+    );
+    render_scrollbar(
+        frame,
+        app,
+        area,
+        table_area,
+        rows.len(),
+        table_state.offset(),
+    );
+
+    if let Mode::Settings(settings) = &app.mode {
+        render_settings(frame, app, settings);
+    }
+    if let Mode::PlaceholderInput(form) = &app.mode {
+        render_placeholder_form(frame, app, form);
+    }
+    if let Mode::EntryInput(form) = &app.mode {
+        render_entry_form(frame, app, form);
+    }
+    if let (Mode::Details, Some(entry)) = (&app.mode, app.selected_page_entry()) {
+        render_details(frame, app, entry);
+    }
+    if let Mode::Help(selected) = app.mode {
+        render_help(frame, app, selected);
+    }
+    if let Mode::ConfigConflict = app.mode {
+        render_config_conflict(frame, app);
+    }
+    if let Mode::ConfigError(error) = &app.mode {
+        render_config_error(frame, app, error);
+    }
+    if let Mode::Workspaces(selected) = app.mode {
+        render_workspaces(frame, app, selected);
+    }
+    if let Mode::PageSwitcher(switcher) = &app.mode {
+        render_page_switcher(frame, app, switcher);
+    }
+
+    Drawn {
+        image_area,
+        scroll_offset: Some(table_state.offset()),
+        tabs,
+    }
+}
+
+/// Renders the pane of the split view that isn't focused, scrolled to its own selection without highlighting it.
+fn render_unfocused_pane(frame: &mut Frame, app: &App, pane: PaneView, area: Rect) {
+    let localizer = app.localizer();
+
+    let title = page_title(app, pane.group, &pane.breadcrumbs);
+    let page_counter = page_counter(localizer, pane.page_number, app.number_of_pages());
+
+    let block = Block::bordered()
+        .title(title.centered())
+        .title_bottom(Line::from(page_counter.fg(app.primary_color())).centered())
+        .border_style(Style::new().fg(app.primary_color()).dim())
+        .padding(Padding::horizontal(1));
+
+    let rows = table_rows(pane.rows, |index| pane.starred.contains(&index));
+
+    let table = build_table(&rows, app, pane.show_names, block.inner(area).width)
+        .row_highlight_style(Style::new());
+    let mut table_state = TableState::new()
+        .with_offset(pane.scroll_offset)
+        .with_selected(pane.selected);
+
+    let table_area = render_page(
+        frame,
+        app,
+        block,
+        table,
+        &mut table_state,
+        pane.description,
+        area,
+    );
+    render_scrollbar(
+        frame,
+        app,
+        area,
+        table_area,
+        rows.len(),
+        table_state.offset(),
+    );
+}
+
+/// Renders the table of a page inside its block, below the description of the page if it has one.
+///
+/// Returns the area of the table.
+fn render_page(
+    frame: &mut Frame,
+    app: &App,
+    block: Block,
+    table: Table,
+    table_state: &mut TableState,
+    description: Option<&str>,
+    area: Rect,
+) -> Rect {
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let area = match description {
+        Some(description) => {
+            // Like the descriptions of entries, it is either wrapped or cut off after the last word that fits
+            let line = Line::from(description.fg(app.primary_color()).dim()).centered();
+            let mut lines = wrap_line(line, inner.width as usize);
+            if !app.wraps_descriptions() {
+                lines.truncate(1);
+            }
+
+            // The description is set apart from the table by an empty line
+            let [description_area, _, table_area] = Layout::vertical([
+                Constraint::Length(lines.len() as u16),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .areas(inner);
+            frame.render_widget(Text::from(lines), description_area);
+            table_area
+        }
+        None => inner,
+    };
+
+    frame.render_stateful_widget(table, area, table_state);
+    area
+}
+
+/// Renders a scrollbar on the right border of a page next to its table, if the page has more rows than fit.
+///
+/// Rows of wrapped descriptions take several lines, so a page that is scrolled has a scrollbar as well.
+fn render_scrollbar(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    table_area: Rect,
+    rows: usize,
+    offset: usize,
+) {
+    if rows <= table_area.height as usize && offset == 0 {
+        return;
+    }
+
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .thumb_style(Style::new().fg(app.highlight_color()))
+        .track_style(Style::new().fg(app.primary_color()).dim());
+    let mut state = ScrollbarState::new(rows.saturating_sub(table_area.height as usize) + 1)
+        .position(offset)
+        .viewport_content_length(table_area.height as usize);
+
+    // The scrollbar replaces the right border next to the table
+    let scrollbar_area = Rect::new(area.x, table_area.y, area.width, table_area.height);
+    frame.render_stateful_widget(scrollbar, scrollbar_area, &mut state);
+}
+
+/// Renders the panel of an image, returning the area left blank for it if the terminal can show it.
+///
+/// Otherwise, the panel names the path of the image.
+fn render_image_panel(frame: &mut Frame, app: &App, path: &str, area: Rect) -> Option<Rect> {
+    let block = Block::bordered()
+        .border_style(Style::new().fg(app.primary_color()))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    if app.graphics().can_show(path) {
+        return Some(inner);
+    }
+
+    let text = app
+        .localizer()
+        .text_with("image-fallback", &[("path", path.into())]);
+    frame.render_widget(
+        Line::from(text.fg(app.primary_color()).dim()).centered(),
+        inner,
+    );
+    None
+}
+
+/// Renders the keyboard diagram with the keys of the selected entry and those of the other entries on the page.
+fn render_keyboard(frame: &mut Frame, app: &App, area: Rect) {
+    let keyboard = app.keyboard();
+
+    let pressed = app
+        .selected_page_entry()
+        .filter(|entry| app.shows_content(entry))
+        .map(|entry| pressed_keys(&entry.content, keyboard))
+        .unwrap_or_default();
+    let mut bound: Vec<String> = app
+        .visible_entries()
+        .into_iter()
+        .filter(|(_, entry)| app.shows_content(entry))
+        .flat_map(|(_, entry)| pressed_keys(&entry.content, keyboard))
+        .collect();
+    bound.sort();
+    bound.dedup();
+
+    let title = Line::from(format!("[ {} ]", app.localizer().text("keyboard-title")))
+        .fg(app.highlight_color())
+        .bold();
+    let block = Block::bordered().title(title.centered());
+
+    let lines = diagram::diagram(
+        &pressed,
+        &bound,
+        keyboard,
+        app.primary_color(),
+        app.highlight_color(),
+    );
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // The rows are centered together, so the keys stay in their columns
+    let width = lines.iter().map(Line::width).max().unwrap_or_default();
+    let [diagram_area] = Layout::horizontal([Constraint::Length(width as u16)])
+        .flex(Flex::Center)
+        .areas(inner);
+    frame.render_widget(Paragraph::new(lines), diagram_area);
+}
+
+/// Builds the title of a page from its group and the names of the pages leading to it.
+fn page_title(app: &App, group: Option<&str>, breadcrumbs: &[&str]) -> Line<'static> {
+    let mut title = Line::default();
+    if let Some(group) = group {
+        title.push_span(format!("{} · ", group).fg(app.primary_color()));
+    }
+    title.push_span(format!("[ {} ]", breadcrumbs.join(" › ")));
+    title.fg(app.highlight_color()).bold()
+}
+
+/// Returns the text showing the position of a page among all pages.
+fn page_counter(localizer: &Localizer, page_number: usize, total: usize) -> String {
+    format!(
+        " [{}] ",
+        localizer.text_with(
+            "page-counter",
+            &[
+                ("current", (page_number + 1).into()),
+                ("total", total.into()),
+            ],
+        )
+    )
+}
+
+/// Converts the rows of a page into rows of a table, marking the entries whose index is starred.
+fn table_rows<'a>(rows: Vec<PageRow<'a>>, starred: impl Fn(usize) -> bool) -> Vec<TableRow<'a>> {
+    rows.into_iter()
+        .map(|row| match row {
+            PageRow::Subpage(subpage) => TableRow::Subpage(subpage),
+            PageRow::Section {
+                name,
+                collapsed,
+                entries,
+            } => TableRow::Section(name, collapsed, entries),
+            PageRow::Entry(index, entry) => TableRow::Entry(entry, starred(index)),
+        })
+        .collect()
+}
+
+/// Renders every page as a section of a single table, each headed by the path of its page.
+fn render_all_pages(frame: &mut Frame, app: &App, selected: usize) {
+    let localizer = app.localizer();
+
+    let title = Line::from(format!("[ {} ]", localizer.text("all-pages-title")))
+        .fg(app.highlight_color())
+        .bold();
+
+    let mut legend = Line::from(vec![
+        " <Enter> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-open-page").fg(app.primary_color()),
+    ]);
+    push_legend_key(
+        &mut legend,
+        app,
+        Action::AllPages,
+        localizer.text("legend-back"),
+    );
+    let close = format!("{} ", localizer.text("legend-close"));
+    push_legend_key(&mut legend, app, Action::Quit, close);
+
+    let block = Block::bordered()
+        .title(title.centered())
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1));
+
+    let mut rows = vec![];
+    for section in app.all_pages_sections() {
+        rows.push(TableRow::Header(
+            section.path.join(" › "),
+            section.description,
+        ));
+        rows.extend(
+            section
+                .entries
+                .into_iter()
+                .map(|(entry, starred)| TableRow::Entry(entry, starred)),
+        );
+    }
+
+    let area = content_area(app, frame.area(), 1);
+    let table =
+        build_table(&rows, app, app.config().show_names, block.inner(area).width).block(block);
+    let mut table_state = TableState::new().with_selected(selected);
+
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
 
-    let title = Line::from(format!("[ {} ]", curr_page.name))
+/// Renders the most looked up entries as a heatmap, a bar per entry scaled to the most looked up one.
+fn render_stats(frame: &mut Frame, app: &App, selected: usize) {
+    let localizer = app.localizer();
+
+    let title = Line::from(format!("[ {} ]", localizer.text("stats-title")))
         .fg(app.highlight_color())
         .bold();
 
-    let page_counter = format!(
-        " [Page {} of {}] ",
-        app.current_page_number() + 1,
-        app.number_of_pages()
+    let mut legend = Line::from(vec![
+        " <Enter> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-open-page").fg(app.primary_color()),
+    ]);
+    push_legend_key(
+        &mut legend,
+        app,
+        Action::Stats,
+        localizer.text("legend-back"),
+    );
+    let close = format!("{} ", localizer.text("legend-close"));
+    push_legend_key(&mut legend, app, Action::Quit, close);
+
+    let block = Block::bordered()
+        .title(title.centered())
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1));
+
+    let area = content_area(app, frame.area(), 1);
+    let usage = app.entry_usage();
+    if usage.is_empty() {
+        let empty = Paragraph::new(localizer.text("stats-empty").fg(app.primary_color()).dim())
+            .centered()
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let max = usage.first().map_or(0, |usage| usage.total());
+    let rows = usage.iter().map(|usage| {
+        let bar = bar(usage.total(), max);
+        let bar = match heat(usage.total(), max) {
+            Heat::Hot => bar.fg(app.highlight_color()).bold(),
+            Heat::Warm => bar.fg(app.highlight_color()),
+            Heat::Cold => bar.fg(app.primary_color()).dim(),
+        };
+        Row::new([
+            Line::from(bar),
+            Line::from(usage.total().to_string().fg(app.highlight_color()).bold()).right_aligned(),
+            Line::from(usage_path(usage).fg(app.primary_color())),
+            Line::from(
+                localizer
+                    .text_with(
+                        "stats-lookups",
+                        &[
+                            ("views", usage.views.into()),
+                            ("copies", usage.copies.into()),
+                        ],
+                    )
+                    .fg(app.primary_color())
+                    .dim(),
+            ),
+        ])
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(BAR_WIDTH as u16),
+            Constraint::Length(max.to_string().len() as u16),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+        ],
+    )
+    .column_spacing(TABLE_COLUMN_SPACING)
+    .row_highlight_style(Modifier::REVERSED)
+    .block(block);
+    let mut table_state = TableState::new().with_selected(selected);
+
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
+
+/// Renders the settings popup, showing the selected theme and colors along with color swatches.
+fn render_settings(frame: &mut Frame, app: &App, settings: &Settings) {
+    let localizer = app.localizer();
+
+    let rows = settings_fields(app)
+        .into_iter()
+        .map(|(field, label, value, swatch)| {
+            let mut value_line = Line::from(vec![
+                "< ".fg(app.primary_color()),
+                value.fg(app.highlight_color()).bold(),
+                " > ".fg(app.primary_color()),
+            ]);
+            if let Some(color) = swatch {
+                value_line.push_span("    ".bg(color));
+            }
+
+            let row = Row::new([Line::from(label.fg(app.primary_color())), value_line]);
+            if field == settings.field {
+                row.reversed()
+            } else {
+                row
+            }
+        });
+
+    let legend = Line::from(vec![
+        " <Left/Right> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-change").fg(app.primary_color()),
+        " <Enter> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-save").fg(app.primary_color()),
+        " <Esc> ".fg(app.highlight_color()).bold(),
+        format!("{} ", localizer.text("legend-cancel")).fg(app.primary_color()),
+    ]);
+
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", localizer.text("settings-title")))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let table = Table::new(rows, [Constraint::Length(16), Constraint::Min(0)]).block(block);
+
+    let area = popup_area(frame.area(), 60, 5);
+    frame.render_widget(Clear, area);
+    frame.render_widget(table, area);
+}
+
+/// Renders the form for filling in placeholders as a popup, previewing the filled in command above the fields.
+fn render_placeholder_form(frame: &mut Frame, app: &App, form: &PlaceholderForm) {
+    let localizer = app.localizer();
+
+    let legend = Line::from(vec![
+        " <Tab> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-next-field").fg(app.primary_color()),
+        " <Enter> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-copy").fg(app.primary_color()),
+        " <Esc> ".fg(app.highlight_color()).bold(),
+        format!("{} ", localizer.text("legend-cancel")).fg(app.primary_color()),
+    ]);
+
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", localizer.text("placeholder-title")))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let area = popup_area(frame.area(), 60, form.fields.len() as u16 + 3);
+    let inner = block.inner(area);
+    let [preview_area, fields_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+
+    let rows = form
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, (name, value))| {
+            let row = Row::new([
+                Line::from(format!("<{}>", name).fg(app.highlight_color()).bold()),
+                Line::from(value.as_str().fg(app.primary_color())),
+            ]);
+            if index == form.field {
+                row.reversed()
+            } else {
+                row
+            }
+        });
+    let label_width = 16;
+    let table = Table::new(rows, [Constraint::Length(label_width), Constraint::Min(0)]);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(
+        Line::from(form.preview().fg(app.primary_color()).dim()),
+        preview_area,
+    );
+    frame.render_widget(table, fields_area);
+
+    // The table separates its columns by a single space
+    let value = &form.fields[form.field].1;
+    let cursor_x = fields_area.x + label_width + 1 + Line::from(value.as_str()).width() as u16;
+    let cursor_y = fields_area.y + form.field as u16;
+    if cursor_y < fields_area.bottom() {
+        frame.set_cursor_position(Position::new(cursor_x, cursor_y));
+    }
+}
+
+/// Renders the form adding or changing an entry as a popup, with the reason why it can't be saved yet below the fields.
+fn render_entry_form(frame: &mut Frame, app: &App, form: &EntryForm) {
+    let localizer = app.localizer();
+
+    let legend = Line::from(vec![
+        " <Tab> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-next-field").fg(app.primary_color()),
+        " <Enter> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-save").fg(app.primary_color()),
+        " <Esc> ".fg(app.highlight_color()).bold(),
+        format!("{} ", localizer.text("legend-cancel")).fg(app.primary_color()),
+    ]);
+
+    let title = match form.original {
+        Some(_) => "entry-form-edit-title",
+        None => "entry-form-add-title",
+    };
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", localizer.text(title)))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let area = popup_area(frame.area(), 60, ENTRY_FORM_FIELDS.len() as u16 + 3);
+    let inner = block.inner(area);
+    let [fields_area, error_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+    let values = entry_form_values(form);
+    let rows = ENTRY_FORM_FIELDS
+        .iter()
+        .zip(&values)
+        .enumerate()
+        .map(|(index, (label, value))| {
+            let row = Row::new([
+                Line::from(localizer.text(label).fg(app.highlight_color()).bold()),
+                Line::from(value.as_str().fg(app.primary_color())),
+            ]);
+            if index == form.field {
+                row.reversed()
+            } else {
+                row
+            }
+        });
+    let label_width = 16;
+    let table = Table::new(rows, [Constraint::Length(label_width), Constraint::Min(0)]);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(table, fields_area);
+    if let Some(error) = app.entry_form_error(form) {
+        frame.render_widget(
+            Line::from(localizer.text(error).fg(app.primary_color()).dim()),
+            error_area,
+        );
+    }
+
+    // The table separates its columns by a single space
+    let value = &values[form.field];
+    let cursor_x = fields_area.x + label_width + 1 + Line::from(value.as_str()).width() as u16;
+    let cursor_y = fields_area.y + form.field as u16;
+    if cursor_y < fields_area.bottom() {
+        frame.set_cursor_position(Position::new(cursor_x, cursor_y));
+    }
+}
+
+/// Returns the values typed into the entry form as they are shown, with the keys masked if they belong to a secret.
+fn entry_form_values(form: &EntryForm) -> Vec<String> {
+    form.values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| match index {
+            1 if form.secret => SECRET_MASK.to_string(),
+            _ => value.clone(),
+        })
+        .collect()
+}
+
+/// Renders the selected entry in full as a popup: its shortcut, its wrapped description and its note.
+fn render_details(frame: &mut Frame, app: &App, entry: &Entry) {
+    let localizer = app.localizer();
+
+    let legend = Line::from(vec![
+        " <Esc> ".fg(app.highlight_color()).bold(),
+        format!("{} ", localizer.text("legend-back")).fg(app.primary_color()),
+    ]);
+
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", entry.name))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let width = 60.min(frame.area().width);
+    let text_width = block.inner(Rect::new(0, 0, width, 3)).width as usize;
+
+    let mut lines = vec![entry_shortcut(app, entry)];
+    lines.extend(wrap_line(
+        Line::from(entry.description.as_str().fg(app.primary_color())),
+        text_width,
+    ));
+    lines.push(Line::default());
+    let details = entry_details(app, entry);
+    if !details.is_empty() {
+        for detail in details {
+            lines.extend(wrap_line(
+                Line::from(detail.fg(app.primary_color()).dim()),
+                text_width,
+            ));
+        }
+        lines.push(Line::default());
+    }
+    match &entry.note {
+        Some(note) => {
+            for line in note.lines() {
+                lines.extend(wrap_line(
+                    Line::from(line.fg(app.primary_color()).italic()),
+                    text_width,
+                ));
+            }
+        }
+        None => lines.push(Line::from(
+            localizer
+                .text("details-no-note")
+                .fg(app.primary_color())
+                .dim(),
+        )),
+    }
+
+    let area = popup_area(frame.area(), width, lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Renders the keys of all actions as a popup, generated from the keymap so remapped keys are listed as well.
+fn render_help(frame: &mut Frame, app: &App, selected: usize) {
+    let localizer = app.localizer();
+    let bindings = app.config().keybinds.bindings();
+
+    let mut legend = Line::from(vec![
+        " <Esc> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-back").fg(app.primary_color()),
+    ]);
+    let close = format!("{} ", localizer.text("legend-close"));
+    push_legend_key(&mut legend, app, Action::Quit, close);
+
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", localizer.text("help-title")))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let key_width = bindings
+        .iter()
+        .map(|(_, keys)| keys.join(", ").chars().count())
+        .max()
+        .unwrap_or_default();
+    let rows = bindings.iter().map(|(name, keys)| {
+        Row::new([
+            Line::from(keys.join(", ").fg(app.highlight_color()).bold()),
+            Line::from(action_description(app, name).fg(app.primary_color())),
+            Line::from(name.fg(app.primary_color()).dim()),
+        ])
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(key_width as u16),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+        ],
+    )
+    .column_spacing(TABLE_COLUMN_SPACING)
+    .row_highlight_style(Modifier::REVERSED)
+    .block(block);
+    let mut table_state = TableState::new().with_selected(selected);
+
+    let area = popup_area(frame.area(), 80, bindings.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(table, area, &mut table_state);
+}
+
+/// Returns what the action with the given name does, as listed in the help.
+fn action_description(app: &App, name: &str) -> String {
+    app.localizer()
+        .text(&format!("help-{}", name.replace('_', "-")))
+}
+
+/// Returns what is set for the entry besides its content and description, one line each.
+fn entry_details(app: &App, entry: &Entry) -> Vec<String> {
+    let localizer = app.localizer();
+
+    let mut details = Vec::new();
+    if let Some(section) = &entry.section {
+        details
+            .push(localizer.text_with("details-section", &[("section", section.as_str().into())]));
+    }
+    if let Some(goto) = &entry.goto {
+        details.push(localizer.text_with("details-goto", &[("page", goto.as_str().into())]));
+    }
+    if let Some(exec) = &entry.exec {
+        details.push(localizer.text_with("details-exec", &[("command", exec.as_str().into())]));
+    }
+    if let Some(url) = &entry.url {
+        details.push(localizer.text_with("details-url", &[("url", url.as_str().into())]));
+    }
+    if let Some(image) = &entry.image {
+        details.push(localizer.text_with("details-image", &[("path", image.as_str().into())]));
+    }
+    if entry.hidden {
+        details.push(localizer.text("details-hidden"));
+    }
+    if entry.secret {
+        details.push(localizer.text("details-secret"));
+    }
+    if entry.encrypted.is_some() {
+        details.push(localizer.text("details-encrypted"));
+    }
+    if app.is_selected_favorite() {
+        details.push(localizer.text("details-favorite"));
+    }
+    details
+}
+
+/// Keys resolving a conflict with a config changed by another program, along with the message ids explaining them
+const CONFLICT_OPTIONS: [(&str, &str); 3] = [
+    ("r", "conflict-reload"),
+    ("m", "conflict-merge"),
+    ("o", "conflict-overwrite"),
+];
+
+/// Renders a popup asking how to resolve unsaved edits after another program changed the config.
+fn render_config_conflict(frame: &mut Frame, app: &App) {
+    let localizer = app.localizer();
+
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", localizer.text("conflict-title")))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let width = 60.min(frame.area().width);
+    let text_width = block.inner(Rect::new(0, 0, width, 3)).width as usize;
+
+    let mut lines = wrap_line(
+        Line::from(localizer.text("conflict-message").fg(app.primary_color())),
+        text_width,
     );
+    lines.push(Line::default());
+    for (key, message) in CONFLICT_OPTIONS {
+        lines.extend(wrap_line(
+            Line::from(vec![
+                format!("<{}> ", key).fg(app.highlight_color()).bold(),
+                localizer.text(message).fg(app.primary_color()),
+            ]),
+            text_width,
+        ));
+    }
+
+    let area = popup_area(frame.area(), width, lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Renders why the config couldn't be read again as a popup, keeping the pages as they were behind it.
+fn render_config_error(frame: &mut Frame, app: &App, error: &str) {
+    let localizer = app.localizer();
+
+    let mut legend = Line::default();
+    push_legend_key(
+        &mut legend,
+        app,
+        Action::Reload,
+        localizer.text("legend-retry"),
+    );
+    legend.push_span(" <Esc> ".fg(app.highlight_color()).bold());
+    legend.push_span(format!("{} ", localizer.text("legend-back")).fg(app.primary_color()));
+
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", localizer.text("config-error-title")))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let width = 80.min(frame.area().width);
+    let text_width = block.inner(Rect::new(0, 0, width, 3)).width as usize;
+
+    let mut lines = wrap_line(
+        Line::from(
+            localizer
+                .text("config-error-message")
+                .fg(app.primary_color()),
+        ),
+        text_width,
+    );
+    lines.push(Line::default());
+    for line in error.lines() {
+        lines.extend(wrap_line(
+            Line::from(line.fg(app.highlight_color())),
+            text_width,
+        ));
+    }
+
+    let area = popup_area(frame.area(), width, lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Renders the workspace switcher as a popup, numbering the workspaces and marking the current one.
+fn render_workspaces(frame: &mut Frame, app: &App, selected: usize) {
+    let localizer = app.localizer();
+
+    let rows = app.workspaces().iter().enumerate().map(|(index, name)| {
+        let mut line = Line::from(vec![
+            format!("{} ", index + 1).fg(app.highlight_color()).bold(),
+            name.as_str().fg(app.primary_color()),
+        ]);
+        if Some(name.as_str()) == app.workspace_name() {
+            line.push_span(format!(" ({})", localizer.text("workspace-current")).dim());
+        }
+        let row = Row::new([line]);
+        if index == selected {
+            row.reversed()
+        } else {
+            row
+        }
+    });
 
     let legend = Line::from(vec![
-        " <Left> ".fg(app.highlight_color()),
-        "Previous Page".fg(app.primary_color()),
-        " <Right>".fg(app.highlight_color()),
-        "Next Page".fg(app.primary_color()),
-        " <q> ".fg(app.highlight_color()),
-        "Close".fg(app.primary_color()),
-        page_counter.fg(app.highlight_color()),
+        " <Enter> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-switch").fg(app.primary_color()),
+        " <Esc> ".fg(app.highlight_color()).bold(),
+        format!("{} ", localizer.text("legend-back")).fg(app.primary_color()),
     ]);
 
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", localizer.text("workspaces-title")))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let table = Table::new(rows, [Constraint::Min(0)]).block(block);
+
+    let area = popup_area(frame.area(), 40, app.workspaces().len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(table, area);
+}
+
+/// Renders the page switcher as a popup, with what was typed above the matching pages.
+fn render_page_switcher(frame: &mut Frame, app: &App, switcher: &PageSwitcher) {
+    let localizer = app.localizer();
+    let matches = app.page_switcher_matches(&switcher.query);
+
+    let legend = Line::from(vec![
+        " <Enter> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-open-page").fg(app.primary_color()),
+        " <Esc> ".fg(app.highlight_color()).bold(),
+        format!("{} ", localizer.text("legend-back")).fg(app.primary_color()),
+    ]);
+
+    let block = Block::bordered()
+        .title(
+            Line::from(format!("[ {} ]", localizer.text("page-switcher-title")))
+                .fg(app.highlight_color())
+                .bold()
+                .centered(),
+        )
+        .title_bottom(legend.centered())
+        .padding(Padding::horizontal(1))
+        .fg(app.primary_color());
+
+    let rows = (matches.len() as u16).clamp(1, PAGE_SWITCHER_ROWS);
+    let area = popup_area(frame.area(), 60, rows + 3);
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+
+    let [prompt_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+
+    let prompt = Line::from(vec![
+        "> ".fg(app.highlight_color()).bold(),
+        switcher.query.as_str().fg(app.primary_color()),
+    ]);
+    frame.render_widget(prompt, prompt_area);
+    frame.set_cursor_position(Position::new(
+        prompt_area.x + 2 + Line::from(switcher.query.as_str()).width() as u16,
+        prompt_area.y,
+    ));
+
+    if matches.is_empty() {
+        let empty = Line::from(localizer.text("page-switcher-empty"))
+            .fg(app.primary_color())
+            .dim();
+        frame.render_widget(empty, list_area);
+        return;
+    }
+
+    let rows = matches.iter().map(|path| {
+        Row::new([Line::from(
+            path.split(PAGE_PATH_SEPARATOR)
+                .collect::<Vec<_>>()
+                .join(" › "),
+        )])
+    });
+    let table = Table::new(rows, [Constraint::Min(0)]).row_highlight_style(Modifier::REVERSED);
+    let mut table_state = TableState::new().with_selected(switcher.selected);
+    frame.render_stateful_widget(table, list_area, &mut table_state);
+}
+
+/// Returns each field of the settings with its label, its current value and the color to preview, if any.
+///
+/// Colors are not previewed while they are disabled.
+fn settings_fields(app: &App) -> [(SettingsField, String, String, Option<Color>); 3] {
+    let config = app.config();
+    let localizer = app.localizer();
+    let swatch = |color| Some(color).filter(|_| app.has_colors());
+
+    let color_value = |color| match ansi_index(color) {
+        Some(index) => index.to_string(),
+        None => localizer.text("settings-default-color"),
+    };
+
+    [
+        (
+            SettingsField::Theme,
+            localizer.text("settings-theme"),
+            config
+                .theme
+                .clone()
+                .unwrap_or_else(|| localizer.text("settings-no-theme")),
+            None,
+        ),
+        (
+            SettingsField::PrimaryColor,
+            localizer.text("settings-primary-color"),
+            color_value(config.primary_color),
+            swatch(config.primary_color),
+        ),
+        (
+            SettingsField::HighlightColor,
+            localizer.text("settings-highlight-color"),
+            color_value(config.highlight_color),
+            swatch(config.highlight_color),
+        ),
+    ]
+}
+
+/// Returns the message id describing a sort order chosen for the session, or `None` for the order of the config.
+fn sort_order_message(order: SortOrder) -> Option<&'static str> {
+    match order {
+        SortOrder::Manual => None,
+        SortOrder::Alphabetical => Some("sorted-by-name"),
+        SortOrder::Shortcut => Some("sorted-by-shortcut"),
+    }
+}
+
+/// Returns the text telling how long ago the entries of a generated page were updated, in the largest whole unit.
+fn updated_text(localizer: &Localizer, age: Duration) -> String {
+    let seconds = age.as_secs();
+    let age = match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        _ => format!("{}h", seconds / 3600),
+    };
+    localizer.text_with("updated-ago", &[("age", age.into())])
+}
+
+/// Returns the part of the area tables are drawn in, centered horizontally if the maximum width is exceeded.
+///
+/// Each of the given number of side-by-side tables may be as wide as the maximum width.
+fn content_area(app: &App, area: Rect, tables: u16) -> Rect {
+    let Some(max_width) = app.config().max_width else {
+        return area;
+    };
+
+    let width = max_width.saturating_mul(tables).min(area.width);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y,
+        width,
+        area.height,
+    )
+}
+
+/// Renders the page index, listing all top-level pages with their number of entries under the headers of their groups.
+fn render_page_index(frame: &mut Frame, app: &App, selected: usize) {
+    let localizer = app.localizer();
+
+    let title = Line::from(format!("[ {} ]", localizer.text("page-index-title")))
+        .fg(app.highlight_color())
+        .bold();
+
+    let mut legend = Line::from(vec![
+        " <Enter> ".fg(app.highlight_color()).bold(),
+        localizer.text("legend-open-page").fg(app.primary_color()),
+    ]);
+    push_legend_key(
+        &mut legend,
+        app,
+        Action::PageIndex,
+        localizer.text("legend-back"),
+    );
+    let close = format!("{} ", localizer.text("legend-close"));
+    push_legend_key(&mut legend, app, Action::Quit, close);
+
     let block = Block::bordered()
         .title(title.centered())
         .title_bottom(legend.centered())
         .padding(Padding::horizontal(1));
 
-    let table = build_table(
-        &curr_page.entries,
-        app.primary_color(),
-        app.highlight_color(),
+    let current = app.current_page_number();
+    let rows = app.page_index_rows().into_iter().map(|row| match row {
+        IndexRow::Group {
+            name,
+            collapsed,
+            pages,
+        } => {
+            let marker = if collapsed { "[+] " } else { "[-] " };
+            Row::new([
+                Line::from(vec![
+                    marker.fg(app.highlight_color()),
+                    name.fg(app.highlight_color()).bold().underlined(),
+                ]),
+                Line::from(
+                    localizer
+                        .text_with("page-index-pages", &[("pages", pages.into())])
+                        .fg(app.primary_color())
+                        .dim(),
+                ),
+                Line::default(),
+            ])
+        }
+        IndexRow::Page {
+            number,
+            page,
+            entries,
+        } => {
+            let marker = if number == current { "▸ " } else { "  " };
+            Row::new([
+                Line::from(vec![
+                    marker.fg(app.highlight_color()).bold(),
+                    page.name.as_str().fg(app.primary_color()),
+                ]),
+                Line::from(
+                    localizer
+                        .text_with("page-index-entries", &[("entries", entries.into())])
+                        .fg(app.highlight_color()),
+                ),
+                Line::from(
+                    page.description
+                        .as_deref()
+                        .unwrap_or_default()
+                        .fg(app.primary_color())
+                        .dim(),
+                ),
+            ])
+        }
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(1),
+            Constraint::Length(PAGE_INDEX_COUNT_WIDTH),
+            Constraint::Fill(2),
+        ],
     )
+    .column_spacing(TABLE_COLUMN_SPACING)
+    .row_highlight_style(Modifier::REVERSED)
     .block(block);
+    let mut table_state = TableState::new().with_selected(selected);
+
+    frame.render_stateful_widget(table, content_area(app, frame.area(), 1), &mut table_state);
+}
+
+/// Renders a bar with a tab for each top-level page, highlighting the current page.
+///
+/// Pages that don't fit are left out, keeping the current page in view, and arrows tell on which side more pages are.
+/// The first page of each group is labeled with the group.
+/// Returns the areas of the tabs along with the numbers of their pages, where the label of a group stands for the
+/// first page of the group.
+fn render_tabs(frame: &mut Frame, app: &App, area: Rect) -> Vec<(Rect, usize)> {
+    let pages = app.top_level_pages();
+    let current = app.current_page_number();
+
+    let mut titles: Vec<Line> = pages
+        .iter()
+        .enumerate()
+        .map(|(index, page)| tab_title(page, index == 0 || pages[index - 1].group != page.group))
+        .collect();
+
+    // Each tab is padded by a space on both sides and divided from the next one by a line
+    let mut widths: Vec<usize> = titles.iter().map(|title| title.width() + 3).collect();
+    let available = area.width.saturating_sub(2 * TAB_ARROW_WIDTH) as usize + 1;
+    let mut start = 0;
+    while start < current && widths[start..=current].iter().sum::<usize>() > available {
+        start += 1;
+    }
+    // The group of the first tab shown is labeled even if it started on a page left out
+    titles[start] = tab_title(pages[start], true);
+    widths[start] = titles[start].width() + 3;
+    let mut end = current + 1;
+    while end < titles.len() && widths[start..=end].iter().sum::<usize>() <= available {
+        end += 1;
+    }
+
+    let [left_area, tabs_area, right_area] = Layout::horizontal([
+        Constraint::Length(TAB_ARROW_WIDTH),
+        Constraint::Min(0),
+        Constraint::Length(TAB_ARROW_WIDTH),
+    ])
+    .areas(area);
+    if start > 0 {
+        frame.render_widget(Line::from("‹").fg(app.highlight_color()).bold(), left_area);
+    }
+    if end < titles.len() {
+        frame.render_widget(
+            Line::from("›")
+                .fg(app.highlight_color())
+                .bold()
+                .right_aligned(),
+            right_area,
+        );
+    }
+
+    // Labels come before the tabs they are part of, so they are found first when looking up a click
+    let mut clickable = vec![];
+    let mut x = tabs_area.x;
+    for number in start..end {
+        let page = pages[number];
+        let labeled = titles[number].spans.len() > 1;
+        if labeled {
+            let first = pages
+                .iter()
+                .position(|other| other.group == page.group)
+                .unwrap_or(number);
+            let label_width = titles[number].spans[0].width() as u16;
+            let label = Rect::new(x.saturating_add(1), tabs_area.y, label_width, 1);
+            clickable.push((label.intersection(tabs_area), first));
+        }
+        let width = widths[number] as u16 - 1;
+        let tab = Rect::new(x, tabs_area.y, width, 1);
+        clickable.push((tab.intersection(tabs_area), number));
+        x = x.saturating_add(width + 1);
+    }
+
+    let tabs = Tabs::new(titles.drain(start..end))
+        .select(current - start)
+        .style(Style::new().fg(app.primary_color()))
+        .highlight_style(Style::new().fg(app.highlight_color()).bold())
+        .divider("│");
+    frame.render_widget(tabs, tabs_area);
+
+    clickable
+}
 
-    frame.render_widget(table, frame.area());
+/// Adds the first key bound to the action to the legend, followed by the label telling what it does.
+///
+/// Nothing is added if no key is bound to the action, e.g. because it was remapped to another action.
+fn push_legend_key(legend: &mut Line, app: &App, action: Action, label: String) {
+    if let Some(key) = app.config().keybinds.keys(action).first() {
+        legend.push_span(format!(" <{}> ", key).fg(app.highlight_color()).bold());
+        legend.push_span(label.fg(app.primary_color()));
+    }
+}
+
+/// Returns the title of the tab of a page, labeled with the group of the page if asked for and it has one.
+fn tab_title(page: &Page, label_group: bool) -> Line<'_> {
+    let mut title = Line::default();
+    if let Some(group) = page.group.as_deref().filter(|_| label_group) {
+        title.push_span(format!("{} › ", group).dim());
+    }
+    title.push_span(page.name.as_str());
+    title
+}
+
+/// Returns an area of the given size centered within another area, shrunk to fit if necessary.
+fn popup_area(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// Renders a single-line prompt for the name of a new page, including the reason if the name is invalid.
+fn render_page_name_prompt(frame: &mut Frame, app: &App, name: &str, area: Rect) {
+    let label = format!(" {} ", app.localizer().text("new-page-prompt"));
+
+    let mut prompt = Line::from(vec![
+        label.as_str().fg(app.highlight_color()).bold(),
+        name.fg(app.primary_color()),
+    ]);
+
+    if let Some(error) = app.page_name_error(name) {
+        let error = app.localizer().text(error);
+        prompt.push_span(format!("  ({})", error).fg(app.primary_color()).dim());
+    }
+
+    frame.render_widget(prompt, area);
+
+    let cursor_x =
+        area.x + Line::from(label.as_str()).width() as u16 + Line::from(name).width() as u16;
+    frame.set_cursor_position(Position::new(cursor_x, area.y));
+}
+
+/// Renders a single-line prompt for the filter of the current page, along with the number of matching entries.
+fn render_filter_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    let label = format!(" {} ", app.localizer().text("filter-prompt"));
+    let filter = app.current_filter().unwrap_or_default();
+
+    let matches = app.localizer().text_with(
+        "filter-matches",
+        &[("entries", app.visible_entries().len().into())],
+    );
+    let prompt = Line::from(vec![
+        label.as_str().fg(app.highlight_color()).bold(),
+        filter.fg(app.primary_color()),
+        format!("  ({})", matches).fg(app.primary_color()).dim(),
+    ]);
+    frame.render_widget(prompt, area);
+
+    let cursor_x =
+        area.x + Line::from(label.as_str()).width() as u16 + Line::from(filter).width() as u16;
+    frame.set_cursor_position(Position::new(cursor_x, area.y));
+}
+
+/// Renders a single-line prompt for the search across all pages, along with the number of results.
+fn render_search_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    let label = format!(" {} ", app.localizer().text("search-prompt"));
+    let search = app.current_search().unwrap_or_default();
+
+    let results = app.localizer().text_with(
+        "search-results",
+        &[("results", app.number_of_search_results().into())],
+    );
+    let prompt = Line::from(vec![
+        label.as_str().fg(app.highlight_color()).bold(),
+        search.fg(app.primary_color()),
+        format!("  ({})", results).fg(app.primary_color()).dim(),
+    ]);
+    frame.render_widget(prompt, area);
+
+    let cursor_x =
+        area.x + Line::from(label.as_str()).width() as u16 + Line::from(search).width() as u16;
+    frame.set_cursor_position(Position::new(cursor_x, area.y));
+}
+
+/// Renders the prompt for the page the selected entry is moved to, along with the matching page or why it can't be moved.
+fn render_move_prompt(frame: &mut Frame, app: &App, query: &str, area: Rect) {
+    let label = format!(" {} ", app.localizer().text("move-prompt"));
+
+    let mut prompt = Line::from(vec![
+        label.as_str().fg(app.highlight_color()).bold(),
+        query.fg(app.primary_color()),
+    ]);
+
+    let hint = match app.move_target(query) {
+        Ok(target) => format!("  (→ {})", target),
+        Err(error) => format!("  ({})", app.localizer().text(error)),
+    };
+    prompt.push_span(hint.fg(app.primary_color()).dim());
+
+    frame.render_widget(prompt, area);
+
+    let cursor_x =
+        area.x + Line::from(label.as_str()).width() as u16 + Line::from(query).width() as u16;
+    frame.set_cursor_position(Position::new(cursor_x, area.y));
+}
+
+/// Renders the command line, hinting at the page it jumps to or else at the pages starting with what was typed.
+fn render_command_line(frame: &mut Frame, app: &App, line: &CommandLine, area: Rect) {
+    let localizer = app.localizer();
+    let label = ":";
+
+    let mut prompt = Line::from(vec![
+        label.fg(app.highlight_color()).bold(),
+        line.input.as_str().fg(app.primary_color()),
+    ]);
+
+    let hint = if line.input.trim().is_empty() {
+        localizer.text("command-line-hint")
+    } else if let Some(target) = app.command_line_target(&line.input) {
+        format!("→ {}", target)
+    } else {
+        let completions = app.command_line_completions(&line.input);
+        if completions.is_empty() {
+            localizer.text("command-line-unknown")
+        } else {
+            completions.join(", ")
+        }
+    };
+    prompt.push_span(format!("  ({})", hint).fg(app.primary_color()).dim());
+
+    frame.render_widget(prompt, area);
+
+    let cursor_x =
+        area.x + Line::from(label).width() as u16 + Line::from(line.input.as_str()).width() as u16;
+    frame.set_cursor_position(Position::new(cursor_x, area.y));
 }
 
-/// Builds a stylized table widget from a list of entries.
+/// Linear text shown in plain mode, read from top to bottom.
+struct PlainView {
+    /// Lines describing what is shown, e.g. the page and its group
+    heading: Vec<String>,
+
+    /// One line per selectable row
+    rows: Vec<String>,
+
+    /// Index of the selected row, if any
+    selected: Option<usize>,
+
+    /// Repeats the selected row along with its position
+    status: String,
+
+    /// Explains the available keys
+    keys: String,
+}
+
+/// Renders the interface as linear, labeled text without borders, symbols or color-only cues.
 ///
+/// This is meant for screen readers and braille displays:
+/// the terminal cursor follows the selected row and a status line repeats the selection along with its position.
+fn render_plain(frame: &mut Frame, app: &App) {
+    let view = match &app.mode {
+        Mode::AllPages(selected) => plain_all_pages_view(app, *selected),
+        Mode::Settings(settings) => plain_settings_view(app, settings),
+        Mode::PlaceholderInput(form) => plain_placeholder_view(app, form),
+        Mode::EntryInput(form) => plain_entry_form_view(app, form),
+        Mode::Stats(selected) => plain_stats_view(app, *selected),
+        Mode::PageIndex(selected) => plain_page_index_view(app, *selected),
+        Mode::Details => plain_details_view(app),
+        Mode::Help(selected) => plain_help_view(app, *selected),
+        Mode::ConfigConflict => plain_conflict_view(app),
+        Mode::ConfigError(error) => plain_config_error_view(app, error),
+        Mode::Workspaces(selected) => plain_workspaces_view(app, *selected),
+        Mode::PageSwitcher(switcher) => plain_page_switcher_view(app, switcher),
+        Mode::Normal
+        | Mode::PageNameInput(_)
+        | Mode::MoveEntry(_)
+        | Mode::FilterInput
+        | Mode::SearchInput
+        | Mode::CommandLine(_) => plain_page_view(app),
+    };
+
+    let prompt_height = if matches!(
+        app.mode,
+        Mode::PageNameInput(_)
+            | Mode::MoveEntry(_)
+            | Mode::FilterInput
+            | Mode::SearchInput
+            | Mode::CommandLine(_)
+    ) {
+        1
+    } else {
+        0
+    };
+
+    let [heading_area, rows_area, status_area, prompt_area] = Layout::vertical([
+        Constraint::Length(view.heading.len() as u16 + 1),
+        Constraint::Min(0),
+        Constraint::Length(2),
+        Constraint::Length(prompt_height),
+    ])
+    .areas(frame.area());
+
+    let heading: Vec<Line> = view.heading.into_iter().map(Line::from).collect();
+    frame.render_widget(Paragraph::new(heading), heading_area);
+
+    // Only scroll as far as needed to keep the selected row visible
+    let offset = view.selected.map_or(0, |selected| {
+        (selected + 1).saturating_sub(rows_area.height as usize)
+    });
+
+    let rows: Vec<Line> = view
+        .rows
+        .into_iter()
+        .enumerate()
+        .skip(offset)
+        .map(|(index, row)| {
+            if Some(index) == view.selected {
+                Line::from(format!("> {}", row)).reversed()
+            } else {
+                Line::from(format!("  {}", row))
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(rows), rows_area);
+
+    // Confirmations replace the position until the next key, so screen readers announce them
+    let status = match app.notice() {
+        Some(id) => app.localizer().text(id),
+        None => view.status,
+    };
+    let status = vec![Line::from(status), Line::from(view.keys)];
+    frame.render_widget(Paragraph::new(status), status_area);
+
+    if let Mode::PageNameInput(name) = &app.mode {
+        render_page_name_prompt(frame, app, name, prompt_area);
+    } else if let Mode::MoveEntry(query) = &app.mode {
+        render_move_prompt(frame, app, query, prompt_area);
+    } else if let Mode::FilterInput = app.mode {
+        render_filter_prompt(frame, app, prompt_area);
+    } else if let Mode::SearchInput = app.mode {
+        render_search_prompt(frame, app, prompt_area);
+    } else if let Mode::CommandLine(line) = &app.mode {
+        render_command_line(frame, app, line, prompt_area);
+    } else if let Some(selected) = view.selected {
+        // Screen readers follow the cursor, so it is placed on the selected row
+        let y = rows_area.y + (selected - offset) as u16;
+        if y < rows_area.bottom() {
+            frame.set_cursor_position(Position::new(rows_area.x, y));
+        }
+    }
+}
+
+/// Builds the plain text of the current page, listing its subpages and visible entries.
+fn plain_page_view(app: &App) -> PlainView {
+    let localizer = app.localizer();
+    let page = app.breadcrumbs().join(PAGE_PATH_SEPARATOR);
+
+    let mut heading = vec![localizer.text_with(
+        "plain-page",
+        &[
+            ("page", page.as_str().into()),
+            ("current", (app.current_page_number() + 1).into()),
+            ("total", app.number_of_pages().into()),
+        ],
+    )];
+    if let Some(workspace) = app.workspace_name() {
+        heading.push(localizer.text_with("plain-workspace", &[("workspace", workspace.into())]));
+    }
+    if let Some(group) = app.current_group() {
+        heading.push(localizer.text_with("plain-group", &[("group", group.into())]));
+    }
+    if let Some(description) = app.current_description() {
+        heading.push(description.to_string());
+    }
+    if app.is_split() {
+        heading.push(localizer.text(if app.is_left_pane_focused() {
+            "plain-split-left"
+        } else {
+            "plain-split-right"
+        }));
+    }
+    if app.shows_hidden_entries() {
+        heading.push(localizer.text("plain-hidden-shown"));
+    }
+    if let Some(filter) = app.current_filter() {
+        heading.push(localizer.text_with("filtered-by", &[("filter", filter.into())]));
+    }
+    if let Some(id) = sort_order_message(app.current_sort_order()) {
+        heading.push(localizer.text(id));
+    }
+    if let Some(age) = app.current_page_age() {
+        heading.push(updated_text(localizer, age));
+    }
+
+    let rows: Vec<String> = app
+        .rows()
+        .into_iter()
+        .map(|row| match row {
+            PageRow::Subpage(subpage) => plain_subpage_text(subpage, localizer),
+            PageRow::Section {
+                name,
+                collapsed,
+                entries,
+            } => plain_section_text(name, collapsed, entries, localizer),
+            PageRow::Entry(index, entry) => plain_entry_text(
+                entry,
+                app.is_favorite(index),
+                app.shows_names(),
+                app.shows_content(entry),
+                app.keyboard(),
+                localizer,
+            ),
+        })
+        .collect();
+
+    let status = match app
+        .selected_entry()
+        .and_then(|index| Some((index, rows.get(index)?)))
+    {
+        Some((index, row)) => localizer.text_with(
+            "plain-selection",
+            &[
+                ("page", page.as_str().into()),
+                ("current", (index + 1).into()),
+                ("total", rows.len().into()),
+                ("text", row.as_str().into()),
+            ],
+        ),
+        None => localizer.text("plain-empty"),
+    };
+
+    PlainView {
+        heading,
+        selected: app.selected_entry(),
+        rows,
+        status,
+        keys: localizer.text("plain-keys-page"),
+    }
+}
+
+/// Builds the plain text of the all-pages view, introducing the entries of each page with its path.
+fn plain_all_pages_view(app: &App, selected: usize) -> PlainView {
+    let localizer = app.localizer();
+
+    let mut rows = vec![];
+    for section in app.all_pages_sections() {
+        let page = section.path.join(PAGE_PATH_SEPARATOR);
+        rows.push(match section.description {
+            Some(description) => localizer.text_with(
+                "plain-described-section",
+                &[("page", page.into()), ("description", description.into())],
+            ),
+            None => localizer.text_with("plain-section", &[("page", page.into())]),
+        });
+        rows.extend(section.entries.into_iter().map(|(entry, starred)| {
+            plain_entry_text(
+                entry,
+                starred,
+                app.config().show_names,
+                app.shows_content(entry),
+                app.keyboard(),
+                localizer,
+            )
+        }));
+    }
+
+    PlainView {
+        heading: vec![localizer.text("all-pages-title")],
+        status: plain_position(localizer, &rows, selected),
+        selected: Some(selected),
+        rows,
+        keys: localizer.text("plain-keys-all-pages"),
+    }
+}
+
+/// Builds the plain text of the details of the selected entry, one line per line of its note.
+fn plain_details_view(app: &App) -> PlainView {
+    let localizer = app.localizer();
+    let Some(entry) = app.selected_page_entry() else {
+        return plain_page_view(app);
+    };
+
+    let rows: Vec<String> = entry
+        .note
+        .iter()
+        .flat_map(|note| note.lines().map(str::to_string))
+        .collect();
+    let status = if entry.note.is_some() {
+        localizer.text_with("plain-note", &[("lines", rows.len().into())])
+    } else {
+        localizer.text("details-no-note")
+    };
+
+    let mut heading = vec![
+        entry.name.clone(),
+        plain_entry_text(
+            entry,
+            false,
+            false,
+            app.shows_content(entry),
+            app.keyboard(),
+            localizer,
+        ),
+    ];
+    heading.extend(entry_details(app, entry));
+
+    PlainView {
+        heading,
+        status,
+        selected: None,
+        rows,
+        keys: localizer.text("plain-keys-details"),
+    }
+}
+
+/// Builds the plain text of the page index, one line per group and page.
+fn plain_page_index_view(app: &App, selected: usize) -> PlainView {
+    let localizer = app.localizer();
+
+    let rows: Vec<String> = app
+        .page_index_rows()
+        .into_iter()
+        .map(|row| match row {
+            IndexRow::Group {
+                name,
+                collapsed,
+                pages,
+            } => localizer.text_with(
+                if collapsed {
+                    "plain-index-group-collapsed"
+                } else {
+                    "plain-index-group-expanded"
+                },
+                &[("name", name.into()), ("pages", pages.into())],
+            ),
+            IndexRow::Page { page, entries, .. } => localizer.text_with(
+                "plain-index-page",
+                &[
+                    ("name", page.name.as_str().into()),
+                    ("entries", entries.into()),
+                ],
+            ),
+        })
+        .collect();
+
+    PlainView {
+        heading: vec![localizer.text("page-index-title")],
+        status: plain_position(localizer, &rows, selected),
+        selected: (!rows.is_empty()).then_some(selected),
+        rows,
+        keys: localizer.text("plain-keys-page-index"),
+    }
+}
+
+/// Builds the plain text of the help, one line per action with its keys.
+fn plain_help_view(app: &App, selected: usize) -> PlainView {
+    let localizer = app.localizer();
+
+    let rows: Vec<String> = app
+        .config()
+        .keybinds
+        .bindings()
+        .into_iter()
+        .map(|(name, keys)| format!("{}: {}", keys.join(", "), action_description(app, name)))
+        .collect();
+
+    PlainView {
+        heading: vec![localizer.text("help-title")],
+        status: plain_position(localizer, &rows, selected),
+        selected: Some(selected),
+        rows,
+        keys: localizer.text("plain-keys-help"),
+    }
+}
+
+/// Builds the plain text asking how to resolve unsaved edits after another program changed the config, one line per option.
+fn plain_conflict_view(app: &App) -> PlainView {
+    let localizer = app.localizer();
+
+    PlainView {
+        heading: vec![
+            localizer.text("conflict-title"),
+            localizer.text("conflict-message"),
+        ],
+        rows: CONFLICT_OPTIONS
+            .iter()
+            .map(|(key, message)| format!("{}: {}", key, localizer.text(message)))
+            .collect(),
+        selected: None,
+        status: String::new(),
+        keys: localizer.text("plain-keys-conflict"),
+    }
+}
+
+/// Builds the plain text telling why the config couldn't be read again, one line per line of the error.
+fn plain_config_error_view(app: &App, error: &str) -> PlainView {
+    let localizer = app.localizer();
+
+    PlainView {
+        heading: vec![
+            localizer.text("config-error-title"),
+            localizer.text("config-error-message"),
+        ],
+        rows: error.lines().map(str::to_string).collect(),
+        selected: None,
+        status: String::new(),
+        keys: localizer.text("plain-keys-config-error"),
+    }
+}
+
+/// Builds the plain text of the page switcher, one line per matching page.
+fn plain_page_switcher_view(app: &App, switcher: &PageSwitcher) -> PlainView {
+    let localizer = app.localizer();
+    let rows = app.page_switcher_matches(&switcher.query);
+
+    PlainView {
+        heading: vec![
+            localizer.text("page-switcher-title"),
+            localizer.text_with(
+                "plain-page-switcher-query",
+                &[("query", switcher.query.as_str().into())],
+            ),
+        ],
+        status: plain_position(localizer, &rows, switcher.selected),
+        selected: (!rows.is_empty()).then_some(switcher.selected),
+        rows,
+        keys: localizer.text("plain-keys-page-switcher"),
+    }
+}
+
+/// Builds the plain text of the workspace switcher, one line per workspace.
+fn plain_workspaces_view(app: &App, selected: usize) -> PlainView {
+    let localizer = app.localizer();
+
+    let rows: Vec<String> = app
+        .workspaces()
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            if Some(name.as_str()) == app.workspace_name() {
+                format!(
+                    "{} {}, {}",
+                    index + 1,
+                    name,
+                    localizer.text("workspace-current")
+                )
+            } else {
+                format!("{} {}", index + 1, name)
+            }
+        })
+        .collect();
+
+    PlainView {
+        heading: vec![localizer.text("workspaces-title")],
+        status: plain_position(localizer, &rows, selected),
+        selected: Some(selected),
+        rows,
+        keys: localizer.text("plain-keys-workspaces"),
+    }
+}
+
+/// Builds the plain text of the stats view, one line per entry with how often it was looked up.
+fn plain_stats_view(app: &App, selected: usize) -> PlainView {
+    let localizer = app.localizer();
+
+    let rows: Vec<String> = app
+        .entry_usage()
+        .into_iter()
+        .map(|usage| {
+            localizer.text_with(
+                "plain-usage",
+                &[
+                    ("path", usage_path(usage).into()),
+                    ("views", usage.views.into()),
+                    ("copies", usage.copies.into()),
+                ],
+            )
+        })
+        .collect();
+
+    PlainView {
+        heading: vec![localizer.text("stats-title")],
+        status: plain_position(localizer, &rows, selected),
+        selected: (!rows.is_empty()).then_some(selected),
+        rows,
+        keys: localizer.text("plain-keys-stats"),
+    }
+}
+
+/// Builds the plain text of the settings, one line per setting.
+fn plain_settings_view(app: &App, settings: &Settings) -> PlainView {
+    let localizer = app.localizer();
+
+    let fields = settings_fields(app);
+    let selected = fields
+        .iter()
+        .position(|(field, ..)| *field == settings.field)
+        .unwrap_or(0);
+    let rows: Vec<String> = fields
+        .into_iter()
+        .map(|(_, label, value, _)| {
+            localizer.text_with(
+                "plain-setting",
+                &[("label", label.into()), ("value", value.into())],
+            )
+        })
+        .collect();
+
+    PlainView {
+        heading: vec![localizer.text("settings-title")],
+        status: plain_position(localizer, &rows, selected),
+        selected: Some(selected),
+        rows,
+        keys: localizer.text("plain-keys-settings"),
+    }
+}
+
+/// Builds the plain text of the placeholder form, listing each placeholder with its value below the filled in command.
+fn plain_placeholder_view(app: &App, form: &PlaceholderForm) -> PlainView {
+    let localizer = app.localizer();
+
+    let rows: Vec<String> = form
+        .fields
+        .iter()
+        .map(|(name, value)| {
+            localizer.text_with(
+                "plain-placeholder",
+                &[
+                    ("name", name.as_str().into()),
+                    ("value", value.as_str().into()),
+                ],
+            )
+        })
+        .collect();
+
+    PlainView {
+        heading: vec![
+            localizer.text("placeholder-title"),
+            localizer.text_with("plain-command", &[("command", form.preview().into())]),
+        ],
+        status: plain_position(localizer, &rows, form.field),
+        selected: Some(form.field),
+        rows,
+        keys: localizer.text("plain-keys-placeholders"),
+    }
+}
+
+/// Builds the plain text of the entry form, listing each field with its value and the reason the entry can't be saved.
+fn plain_entry_form_view(app: &App, form: &EntryForm) -> PlainView {
+    let localizer = app.localizer();
+    let title = match form.original {
+        Some(_) => "entry-form-edit-title",
+        None => "entry-form-add-title",
+    };
+
+    let rows: Vec<String> = ENTRY_FORM_FIELDS
+        .iter()
+        .zip(entry_form_values(form))
+        .map(|(label, value)| {
+            localizer.text_with(
+                "plain-entry-field",
+                &[
+                    ("field", localizer.text(label).into()),
+                    ("value", value.into()),
+                ],
+            )
+        })
+        .collect();
+
+    let mut heading = vec![localizer.text(title)];
+    heading.extend(
+        app.entry_form_error(form)
+            .map(|error| localizer.text(error)),
+    );
+    PlainView {
+        heading,
+        status: plain_position(localizer, &rows, form.field),
+        selected: Some(form.field),
+        rows,
+        keys: localizer.text("plain-keys-entry-form"),
+    }
+}
+
+/// Describes the selected row along with its position among all rows.
+fn plain_position(localizer: &Localizer, rows: &[String], selected: usize) -> String {
+    match rows.get(selected) {
+        Some(row) => localizer.text_with(
+            "plain-position",
+            &[
+                ("current", (selected + 1).into()),
+                ("total", rows.len().into()),
+                ("text", row.as_str().into()),
+            ],
+        ),
+        None => localizer.text("plain-empty"),
+    }
+}
+
+/// Describes a subpage and how much it contains in a single line.
+fn plain_subpage_text(subpage: &Page, localizer: &Localizer) -> String {
+    let summary = localizer.text_with(
+        "subpage-summary",
+        &[
+            ("entries", subpage.entries.len().into()),
+            ("subpages", subpage.subpages.len().into()),
+        ],
+    );
+
+    localizer.text_with(
+        "plain-subpage",
+        &[
+            ("name", subpage.name.as_str().into()),
+            ("summary", summary.into()),
+        ],
+    )
+}
+
+/// Describes the header of a section and whether it is collapsed in a single line.
+fn plain_section_text(
+    name: &str,
+    collapsed: bool,
+    entries: usize,
+    localizer: &Localizer,
+) -> String {
+    let id = if collapsed {
+        "plain-section-collapsed"
+    } else {
+        "plain-section-expanded"
+    };
+    localizer.text_with(id, &[("name", name.into()), ("entries", entries.into())])
+}
+
+/// Describes an entry in a single line, spelling out what the table view shows with symbols and colors.
+///
+/// The name of the entry comes first if names are shown, the content is named as a secret unless it is shown.
+fn plain_entry_text(
+    entry: &Entry,
+    starred: bool,
+    show_name: bool,
+    show_content: bool,
+    keyboard: Keyboard,
+    localizer: &Localizer,
+) -> String {
+    let mut text = if entry.content.is_empty() {
+        entry.description.clone()
+    } else {
+        let shortcut = if show_content {
+            normalize_content(&entry.content, keyboard).join(" + ")
+        } else {
+            localizer.text("plain-secret")
+        };
+        localizer.text_with(
+            "plain-entry",
+            &[
+                ("shortcut", shortcut.into()),
+                ("description", entry.description.as_str().into()),
+            ],
+        )
+    };
+    if show_name {
+        text = localizer.text_with(
+            "plain-named-entry",
+            &[("name", entry.name.as_str().into()), ("entry", text.into())],
+        );
+    }
+
+    let mut labels = vec![];
+    if starred {
+        labels.push(localizer.text("plain-starred"));
+    }
+    if entry.hidden {
+        labels.push(localizer.text("plain-hidden"));
+    }
+    if let Some(target) = &entry.goto {
+        labels.push(localizer.text_with("plain-goto", &[("target", target.as_str().into())]));
+    }
+    if let Some(command) = &entry.exec {
+        labels.push(localizer.text_with("plain-exec", &[("command", command.as_str().into())]));
+    }
+
+    for label in labels {
+        text.push_str(", ");
+        text.push_str(&label);
+    }
+
+    text
+}
+
+/// A single row of a table of entries.
+enum TableRow<'a> {
+    /// A header naming the page the following entries belong to and its description
+    Header(String, Option<&'a str>),
+
+    /// A subpage that can be drilled into
+    Subpage(&'a Page),
+
+    /// The header of a section, whether it is collapsed and its number of entries
+    Section(&'a str, bool, usize),
+
+    /// An entry and whether it is starred
+    Entry(&'a Entry, bool),
+}
+
+/// Builds a stylized table widget from a list of headers, subpages and entries.
+///
+/// Subpages are shown with their number of entries.
 /// Each entry includes a keyboard shortcut (as a sequence of keys)
-/// and a textual description.
+/// and a textual description, preceded by the name of the entry if names are shown.
 /// The resulting table is formatted with aligned columns and spacing.
-/// To do this, we need to measure the maximum width of such a shortcut.
-fn build_table(entries: &Vec<Entry>, primary_color: Color, highlight_color: Color) -> Table {
+/// To do this, we need to measure the maximum width of such a shortcut and name.
+/// Hidden entries that are revealed are dimmed, starred entries are marked with a star.
+/// Each column of entries is aligned as configured, headers always start on the left.
+/// Descriptions longer than the last column are wrapped onto further lines if wrapping is toggled on,
+/// which needs the width of the table, and truncated otherwise.
+fn build_table<'a>(
+    table_rows: &[TableRow<'a>],
+    app: &App,
+    show_names: bool,
+    width: u16,
+) -> Table<'a> {
+    let localizer = app.localizer();
+    let primary_color = app.primary_color();
+    let highlight_color = app.highlight_color();
+    let alignment = app.config().alignment;
+
+    let mut maximum_name_length = 0;
     let mut maximum_shortcut_length = 0;
 
-    let mut rows = Vec::new();
+    // The last column can only be wrapped once the width of the others is known
+    let mut cells: Vec<(Vec<Line<'a>>, Line<'a>, bool)> = Vec::new();
+
+    for table_row in table_rows {
+        // Headers, subpages and sections have a label in the first column and a summary in the last
+        let (label, summary) = match table_row {
+            TableRow::Header(name, description) => {
+                let header = Line::from(name.clone())
+                    .fg(highlight_color)
+                    .bold()
+                    .underlined();
+                let summary = description.unwrap_or_default().fg(primary_color).dim();
+                (header, Line::from(summary))
+            }
+            TableRow::Subpage(subpage) => {
+                let name = Line::from(vec![
+                    "▸ ".fg(highlight_color),
+                    subpage.name.as_str().fg(highlight_color).bold(),
+                ]);
+
+                let summary = localizer.text_with(
+                    "subpage-summary",
+                    &[
+                        ("entries", subpage.entries.len().into()),
+                        ("subpages", subpage.subpages.len().into()),
+                    ],
+                );
+
+                (name, Line::from(summary.fg(primary_color).dim()))
+            }
+            TableRow::Section(name, collapsed, entries) => {
+                let marker = if *collapsed { "[+] " } else { "[-] " };
+                let header = Line::from(vec![
+                    marker.fg(highlight_color),
+                    name.to_string().fg(highlight_color).bold().underlined(),
+                ]);
 
-    for entry in entries {
-        let shortcut = build_shortcut(&entry.content, primary_color, highlight_color);
+                let summary = if *collapsed {
+                    localizer.text_with("section-collapsed", &[("entries", (*entries).into())])
+                } else {
+                    String::new()
+                };
 
-        // In order to measure the correct column width, we need to track the maximum length of such a shortcut
-        maximum_shortcut_length = max(maximum_shortcut_length, shortcut.width());
+                (header, Line::from(summary.fg(primary_color).dim()))
+            }
+            TableRow::Entry(entry, starred) => {
+                let mut shortcut = entry_shortcut(app, entry);
 
-        let description = entry.description.as_str().into();
+                let marker = if *starred { "★ " } else { "  " };
 
-        let row = Row::new([shortcut, description]);
+                let mut description = Line::from(entry.description.as_str());
+                if let Some(target) = &entry.goto {
+                    description
+                        .push_span(format!(" → {}", target).fg(highlight_color).underlined());
+                }
+                if let Some(command) = &entry.exec {
+                    description.push_span(format!(" $ {}", command).fg(highlight_color).italic());
+                }
 
-        rows.push(row);
+                // In order to measure the correct column width, we need to track the maximum length of such a shortcut
+                let description = description.alignment(alignment.description);
+                let leading = if show_names {
+                    let name = Line::from(vec![
+                        marker.fg(highlight_color),
+                        entry.name.as_str().fg(primary_color),
+                    ]);
+                    maximum_name_length = max(maximum_name_length, name.width());
+                    maximum_shortcut_length = max(maximum_shortcut_length, shortcut.width());
+                    vec![
+                        name.alignment(alignment.name),
+                        shortcut.alignment(alignment.shortcut),
+                    ]
+                } else {
+                    shortcut.spans.insert(0, marker.fg(highlight_color));
+                    maximum_shortcut_length = max(maximum_shortcut_length, shortcut.width());
+                    vec![shortcut.alignment(alignment.shortcut)]
+                };
+
+                cells.push((leading, description, entry.hidden));
+                continue;
+            }
+        };
+
+        if show_names {
+            maximum_name_length = max(maximum_name_length, label.width());
+            cells.push((vec![label, Line::default()], summary, false));
+        } else {
+            maximum_shortcut_length = max(maximum_shortcut_length, label.width());
+            cells.push((vec![label], summary, false));
+        }
     }
 
-    let widths = [
-        Constraint::Min(maximum_shortcut_length as u16),
-        Constraint::Percentage(75),
-    ];
+    let mut widths = vec![];
+    let rows: Vec<Row> = if app.wraps_descriptions() {
+        if show_names {
+            widths.push(Constraint::Length(maximum_name_length as u16));
+        }
+        widths.push(Constraint::Length(maximum_shortcut_length as u16));
+        widths.push(Constraint::Fill(1));
 
-    let table = Table::new(rows, widths).column_spacing(2);
+        let spacing = (widths.len() - 1) * TABLE_COLUMN_SPACING as usize;
+        let leading_width = maximum_name_length + maximum_shortcut_length + spacing;
+        let last_width = (width as usize).saturating_sub(leading_width).max(1);
+
+        cells
+            .into_iter()
+            .map(|(leading, last, hidden)| {
+                let lines = wrap_line(last, last_width);
+                let height = lines.len() as u16;
+                let row = Row::new(
+                    leading
+                        .into_iter()
+                        .map(Text::from)
+                        .chain([Text::from(lines)]),
+                )
+                .height(height);
+                if hidden {
+                    row.dim()
+                } else {
+                    row
+                }
+            })
+            .collect()
+    } else {
+        if show_names {
+            widths.push(Constraint::Min(maximum_name_length as u16));
+        }
+        widths.push(Constraint::Min(maximum_shortcut_length as u16));
+        widths.push(Constraint::Percentage(75));
+
+        cells
+            .into_iter()
+            .map(|(leading, last, hidden)| {
+                let row = Row::new(leading.into_iter().chain([last]));
+                if hidden {
+                    row.dim()
+                } else {
+                    row
+                }
+            })
+            .collect()
+    };
+
+    let table = Table::new(rows, widths)
+        .column_spacing(TABLE_COLUMN_SPACING)
+        .row_highlight_style(Modifier::REVERSED);
 
     table
 }
 
+/// Wraps a line at spaces onto as many lines of at most the given width as needed, keeping the style of its spans.
+///
+/// Words longer than the width are put on a line of their own, where they are cut off.
+fn wrap_line(line: Line<'_>, width: usize) -> Vec<Line<'static>> {
+    let new_line = || {
+        let wrapped = Line::default().style(line.style);
+        match line.alignment {
+            Some(alignment) => wrapped.alignment(alignment),
+            None => wrapped,
+        }
+    };
+
+    let mut lines = vec![];
+    let mut current = new_line();
+    for span in &line.spans {
+        for word in span.content.split_inclusive(' ') {
+            let word = Span::styled(word.to_string(), span.style);
+            // Spaces at the end of a line don't need to fit, and are left out so centered lines stay centered
+            let trimmed = word.content.trim_end_matches(' ');
+            let visible = word.width() - (word.content.len() - trimmed.len());
+            if current.width() > 0 && current.width() + visible > width {
+                lines.push(trim_line_end(std::mem::replace(&mut current, new_line())));
+            }
+            current.push_span(word);
+        }
+    }
+    lines.push(trim_line_end(current));
+
+    lines
+}
+
+/// Removes the spaces at the end of the last span of a line.
+fn trim_line_end(mut line: Line<'static>) -> Line<'static> {
+    if let Some(last) = line.spans.last_mut() {
+        last.content = last.content.trim_end_matches(' ').to_string().into();
+    }
+    line
+}
+
+/// Builds the shortcut of an entry like [`build_shortcut`], or a mask if it is a secret that isn't revealed.
+fn entry_shortcut(app: &App, entry: &Entry) -> Line<'static> {
+    if app.shows_content(entry) {
+        build_shortcut(
+            &entry.content,
+            app.keyboard(),
+            app.primary_color(),
+            app.highlight_color(),
+        )
+    } else {
+        Line::from(SECRET_MASK.fg(app.primary_color()))
+    }
+}
+
 /// Builds a stylized span from a list of keys or other textual content
 ///
 /// The resulting span is an alternating sequence of the given content and a connecting element, in this case the character '+'.
-fn build_shortcut(content: &[String], primary_color: Color, highlight_color: Color) -> Line {
+/// Key notations are shown in their canonical form for the given keyboard, e.g. `C-x` as `Ctrl+X`.
+fn build_shortcut(
+    content: &[String],
+    keyboard: Keyboard,
+    primary_color: Color,
+    highlight_color: Color,
+) -> Line<'static> {
+    let content = normalize_content(content, keyboard);
     let mut shortcut = Line::default();
 
     if content.is_empty() {
@@ -106,11 +2530,11 @@ fn build_shortcut(content: &[String], primary_color: Color, highlight_color: Col
 
     // Do not precompose a '+' before the first actual text-component.
     // first always exists, since content is non-empty
-    shortcut.push_span(content.first().unwrap().as_str().fg(highlight_color).bold());
+    shortcut.push_span(content.first().unwrap().clone().fg(highlight_color).bold());
 
     for component in content.iter().skip(1) {
         shortcut.push_span("+".fg(primary_color));
-        shortcut.push_span(component.as_str().fg(highlight_color).bold());
+        shortcut.push_span(component.clone().fg(highlight_color).bold());
     }
 
     shortcut
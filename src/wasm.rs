@@ -0,0 +1,128 @@
+//! WASM converter module for Recall.
+//!
+//! Converters for formats recall doesn't know are WebAssembly modules in the `plugins` directory next to the config file,
+//! named like `<name>.wasm` and used with `recall import --plugin <name>` or `recall export --plugin <name>`.
+//! They run sandboxed without access to the file system or network, text is exchanged through their memory.
+//!
+//! A converter exports
+//!
+//! - its `memory`
+//! - `alloc(len: i32) -> i32`, reserving `len` bytes for the input and returning their address
+//! - `import(ptr: i32, len: i32) -> i64`, converting a file into pages written in the format of the config
+//! - `export(ptr: i32, len: i32) -> i64`, converting pages written in the format of the config into a file
+//!
+//! Only one of `import` and `export` is needed. Both return the address of their UTF-8 output in the upper
+//! and its length in the lower 32 bits.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use log::{debug, info};
+use wasmtime::{Engine, Instance, Module, Store};
+
+use crate::{
+    app::Page,
+    config::{parse_pages, serialize_pages},
+    plugin::plugin_dir,
+};
+
+/// File extension of converters
+const CONVERTER_EXTENSION: &str = "wasm";
+
+/// Name of the exported memory of a converter
+const MEMORY_EXPORT: &str = "memory";
+
+/// Name of the exported function reserving memory for the input
+const ALLOC_FUNCTION: &str = "alloc";
+
+/// Name of the exported function converting a file into pages
+const IMPORT_FUNCTION: &str = "import";
+
+/// Name of the exported function converting pages into a file
+const EXPORT_FUNCTION: &str = "export";
+
+/// Returns the path of the converter with the given name for the config at the given path.
+pub fn converter_path(config_path: &Path, name: &str) -> PathBuf {
+    plugin_dir(config_path).join(format!("{}.{}", name, CONVERTER_EXTENSION))
+}
+
+/// Converts the content of a file into pages with the converter at the given path.
+pub fn import(converter: &Path, content: &str) -> Result<Vec<Page>> {
+    let pages = convert(converter, IMPORT_FUNCTION, content)?;
+    parse_pages(&pages).context(format!(
+        "Converter {} returned invalid pages",
+        converter.display()
+    ))
+}
+
+/// Converts pages into the content of a file with the converter at the given path.
+pub fn export(converter: &Path, pages: &[Page]) -> Result<String> {
+    convert(converter, EXPORT_FUNCTION, &serialize_pages(pages))
+}
+
+/// Loads the converter at the given path and passes the input through the function with the given name.
+fn convert(converter: &Path, function: &str, input: &str) -> Result<String> {
+    info!("Loading converter {}", converter.display());
+
+    let engine = Engine::default();
+    let module = Module::from_file(&engine, converter)
+        .context(format!("Failed to load converter {}", converter.display()))?;
+    let mut store = Store::new(&engine, ());
+    // Converters get no imports, so they can't reach anything outside of their memory
+    let instance = Instance::new(&mut store, &module, &[]).context(format!(
+        "Failed to start converter {}, converters can't import anything",
+        converter.display()
+    ))?;
+
+    let memory = instance
+        .get_memory(&mut store, MEMORY_EXPORT)
+        .ok_or(anyhow!(
+            "Converter {} doesn't export its {}",
+            converter.display(),
+            MEMORY_EXPORT
+        ))?;
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut store, ALLOC_FUNCTION)
+        .context(format!(
+            "Converter {} doesn't export {}(len: i32) -> i32",
+            converter.display(),
+            ALLOC_FUNCTION
+        ))?;
+    let convert = instance
+        .get_typed_func::<(i32, i32), i64>(&mut store, function)
+        .context(format!(
+            "Converter {} doesn't export {}(ptr: i32, len: i32) -> i64",
+            converter.display(),
+            function
+        ))?;
+
+    let length = i32::try_from(input.len()).context("Input is too large for a converter")?;
+    let address = alloc.call(&mut store, length)?;
+    memory
+        .write(&mut store, address as u32 as usize, input.as_bytes())
+        .context(format!(
+            "Converter {} reserved invalid memory for the input",
+            converter.display()
+        ))?;
+
+    debug!("Calling {} of converter {}", function, converter.display());
+    let output = convert
+        .call(&mut store, (address, length))
+        .context(format!(
+            "Converter {} failed to {}",
+            converter.display(),
+            function
+        ))? as u64;
+
+    let mut bytes = vec![0; (output & 0xffff_ffff) as usize];
+    memory
+        .read(&store, (output >> 32) as usize, &mut bytes)
+        .context(format!(
+            "Converter {} returned output outside of its memory",
+            converter.display()
+        ))?;
+    String::from_utf8(bytes).context(format!(
+        "Converter {} returned invalid UTF-8",
+        converter.display()
+    ))
+}
@@ -0,0 +1,105 @@
+//! Watch module for Recall.
+//!
+//! While recall is open, the files the config is built from are checked for changes, so edits made in an editor
+//! show up without restarting recall. These are the config itself and the scripts and converters in the plugins directory.
+//! Editors often write a file several times in quick succession, e.g. truncating it before writing or replacing it
+//! by a temporary file, so a change is only reported once the files stayed the same for a moment.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use log::debug;
+
+use crate::plugin::plugin_dir;
+
+/// How long the watched files have to stay the same after a change before it is reported
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Path, modification time and size of each watched file, `None` for files that don't exist
+type Snapshot = Vec<(PathBuf, Option<(SystemTime, u64)>)>;
+
+/// Notices changes to the files the config at a path is built from.
+#[derive(Debug)]
+pub struct Watcher {
+    /// Path of the config
+    config_path: PathBuf,
+
+    /// The files as they were last seen, or reported as changed
+    seen: Snapshot,
+
+    /// The files as they were after a change that isn't reported yet, and since when they are like this
+    pending: Option<(Snapshot, Instant)>,
+}
+
+impl Watcher {
+    /// Starts watching the config at the given path and its plugins, taking the files as they are now as unchanged.
+    pub fn new(config_path: &Path) -> Watcher {
+        Watcher {
+            config_path: config_path.to_path_buf(),
+            seen: snapshot(config_path),
+            pending: None,
+        }
+    }
+
+    /// Returns `true` once if any of the watched files changed and then stayed the same for [`DEBOUNCE`].
+    pub fn poll(&mut self) -> bool {
+        let current = snapshot(&self.config_path);
+        if current == self.seen {
+            // Changed files were changed back, e.g. by undoing in the editor
+            self.pending = None;
+            return false;
+        }
+
+        match &self.pending {
+            Some((pending, since)) if *pending == current => {
+                if since.elapsed() < DEBOUNCE {
+                    return false;
+                }
+                debug!("Files of the config changed");
+                self.seen = current;
+                self.pending = None;
+                true
+            }
+            _ => {
+                self.pending = Some((current, Instant::now()));
+                false
+            }
+        }
+    }
+
+    /// Takes the files as they are now as unchanged, e.g. after recall wrote the config itself.
+    pub fn acknowledge(&mut self) {
+        self.seen = snapshot(&self.config_path);
+        self.pending = None;
+    }
+}
+
+/// Returns the watched files of the config at the given path as they are now.
+///
+/// The files of the plugins directory are listed in order of their names, so adding or removing one is a change.
+fn snapshot(config_path: &Path) -> Snapshot {
+    let mut paths = vec![config_path.to_path_buf()];
+
+    let mut plugins: Vec<PathBuf> = fs::read_dir(plugin_dir(config_path))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .collect()
+        })
+        .unwrap_or_default();
+    plugins.sort();
+    paths.extend(plugins);
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let state = fs::metadata(&path)
+                .ok()
+                .and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+            (path, state)
+        })
+        .collect()
+}
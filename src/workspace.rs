@@ -0,0 +1,58 @@
+//! Workspace module for Recall.
+//!
+//! Several configs can be opened at once with `recall -c work.toml -c personal.toml`, each as a workspace of its own.
+//! A workspace keeps its pages, colors, plugins and user state apart from the others, so switching between them
+//! with `W` shows each config as if it was opened alone instead of merging all pages into one list.
+
+use std::path::{Path, PathBuf};
+
+use crate::{app::App, plugin::Plugins, watch::Watcher};
+
+/// A config opened alongside others, with everything recall keeps about it while running.
+pub struct Workspace {
+    /// The app showing the pages of the config
+    pub app: App,
+
+    /// Path of the config
+    pub config_path: PathBuf,
+
+    /// Path of the state file, which is only shared with sessions opening the same config
+    pub state_path: PathBuf,
+
+    /// Plugins in the plugins directory next to the config
+    pub plugins: Plugins,
+
+    /// Fingerprint of the config as it was last read or written, see [`crate::config::config_fingerprint`]
+    pub fingerprint: Option<u64>,
+
+    /// Notices changes to the config and its plugins
+    pub watcher: Watcher,
+}
+
+/// Returns the names of the workspaces of the configs at the given paths, the names of the config files.
+///
+/// Configs with the same file name, e.g. in different directories, are numbered to tell them apart.
+pub fn workspace_names(config_paths: &[PathBuf]) -> Vec<String> {
+    let stems: Vec<String> = config_paths.iter().map(|path| config_stem(path)).collect();
+
+    stems
+        .iter()
+        .enumerate()
+        .map(|(index, stem)| {
+            let same = stems.iter().filter(|other| *other == stem).count();
+            if same == 1 {
+                return stem.clone();
+            }
+            let number = stems[..index].iter().filter(|other| *other == stem).count() + 1;
+            format!("{} {}", stem, number)
+        })
+        .collect()
+}
+
+/// Returns the name of the config file at the given path without its extension.
+fn config_stem(config_path: &Path) -> String {
+    config_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or("config".to_string())
+}